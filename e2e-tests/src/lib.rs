@@ -745,6 +745,8 @@ pub async fn setup_funded_channel(
 			channel_config: None,
 			announce_channel: true,
 			disable_counterparty_reserve: false,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
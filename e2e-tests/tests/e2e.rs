@@ -24,14 +24,13 @@ use ldk_node::lightning_invoice::Bolt11Invoice;
 use ldk_server_client::client::EventStream;
 use ldk_server_client::ldk_server_grpc::api::{
 	open_channel_request, Bolt11ReceiveRequest, Bolt12ReceiveRequest, GetBalancesRequest,
-	OnchainReceiveRequest, OpenChannelRequest,
+	OnchainReceiveRequest, OpenChannelRequest, SubscribeEventsRequest,
 };
 use ldk_server_client::ldk_server_grpc::events::event_envelope::Event;
-use ldk_server_client::ldk_server_grpc::events::{
-	ChannelClosureInitiator, ChannelState, ChannelStateChangeReasonKind, EventEnvelope,
-};
+use ldk_server_client::ldk_server_grpc::events::EventEnvelope;
 use ldk_server_client::ldk_server_grpc::types::{
-	bolt11_invoice_description, Bolt11InvoiceDescription,
+	bolt11_invoice_description, Bolt11InvoiceDescription, ChannelClosureInitiator, ChannelState,
+	ChannelStateChangeReasonKind,
 };
 use ldk_server_grpc::types::payment_kind;
 
@@ -159,6 +158,8 @@ async fn test_cli_export_pathfinding_scores() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -169,6 +170,56 @@ async fn test_cli_export_pathfinding_scores() {
 	assert!(output.get("pathfinding_scores").is_some());
 }
 
+#[tokio::test]
+async fn test_cli_query_routes() {
+	let bitcoind = TestBitcoind::new();
+	let server_a = LdkServerHandle::start(&bitcoind).await;
+	let server_b = LdkServerHandle::start(&bitcoind).await;
+	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
+
+	let output = run_cli(&server_a, &["query-routes", server_b.node_id(), "10000sat"]);
+	let paths = output["route"]["paths"].as_array().unwrap();
+	assert_eq!(paths.len(), 1);
+	let hops = paths[0]["hops"].as_array().unwrap();
+	assert_eq!(hops.len(), 1);
+	assert_eq!(hops[0]["node_id"], server_b.node_id());
+	assert_eq!(output["route"]["total_amount_msat"], 10_000_000);
+
+	let invoice_resp = server_b
+		.client()
+		.bolt11_receive(Bolt11ReceiveRequest {
+			amount_msat: Some(10_000_000),
+			description: Some(Bolt11InvoiceDescription {
+				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
+			}),
+			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
+		})
+		.await
+		.unwrap();
+	let output = run_cli(&server_a, &["estimate-route-fee", &invoice_resp.invoice]);
+	// Direct channels don't charge routing fees.
+	assert_eq!(output["fee_msat"], 0);
+	assert_eq!(output["total_amount_msat"], 10_000_000);
+	assert_eq!(output["path_count"], 1);
+}
+
+#[tokio::test]
+async fn test_cli_send_probes() {
+	let bitcoind = TestBitcoind::new();
+	let server_a = LdkServerHandle::start(&bitcoind).await;
+	let server_b = LdkServerHandle::start(&bitcoind).await;
+	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
+
+	// A path over a single channel isn't probed, as the channel's liquidity is known locally.
+	let output = run_cli(&server_a, &["send-probes", server_b.node_id(), "10000sat"]);
+	let results = output["results"].as_array().unwrap();
+	assert_eq!(results.len(), 1);
+	assert_eq!(results[0]["estimated_status"], "PROBE_STATUS_SKIPPED");
+	assert_eq!(output["locked_msat"], 0);
+}
+
 #[tokio::test]
 async fn test_cli_bolt11_receive() {
 	let bitcoind = TestBitcoind::new();
@@ -185,6 +236,26 @@ async fn test_cli_bolt11_receive() {
 	assert_eq!(invoice.payment_secret().0, payment_secret);
 }
 
+#[tokio::test]
+async fn test_cli_list_invoices() {
+	let bitcoind = TestBitcoind::new();
+	let server = LdkServerHandle::start(&bitcoind).await;
+
+	let output = run_cli(&server, &["list-invoices"]);
+	assert!(output["list"].as_array().unwrap().is_empty());
+
+	let output =
+		run_cli(&server, &["bolt11-receive", "50000sat", "-d", "test", "--label", "order-42"]);
+	let payment_hash = output["payment_hash"].as_str().unwrap().to_string();
+
+	let output = run_cli(&server, &["list-invoices"]);
+	let invoices = output["list"].as_array().unwrap();
+	assert_eq!(invoices.len(), 1);
+	assert_eq!(invoices[0]["payment_hash"], payment_hash);
+	assert_eq!(invoices[0]["label"], "order-42");
+	assert_eq!(invoices[0]["amount_msat"], 50_000_000);
+}
+
 #[tokio::test]
 async fn test_cli_decode_invoice() {
 	let bitcoind = TestBitcoind::new();
@@ -398,6 +469,65 @@ async fn test_cli_onchain_send_all() {
 	assert_eq!(balances.total_onchain_balance_sats, 0);
 }
 
+#[tokio::test]
+async fn test_cli_list_onchain_transactions() {
+	let bitcoind = TestBitcoind::new();
+	let server = LdkServerHandle::start(&bitcoind).await;
+
+	let funding_address =
+		server.client().onchain_receive(OnchainReceiveRequest {}).await.unwrap().address;
+	bitcoind.fund_address(&funding_address, 1.0);
+	mine_and_sync(&bitcoind, &[&server], 6).await;
+	wait_for_onchain_balance(server.client(), Duration::from_secs(30)).await;
+
+	let address = bitcoind.bitcoind.client.new_address().unwrap().to_string();
+	let output = run_cli(&server, &["onchain-send", &address, "50000sat"]);
+	let txid = output["txid"].as_str().unwrap().to_string();
+	mine_and_sync(&bitcoind, &[&server], 1).await;
+
+	// The wallet's transactions are recorded by the node monitor once the wallet synced.
+	let timeout = Duration::from_secs(30);
+	let start = std::time::Instant::now();
+	let output = loop {
+		let output = run_cli(&server, &["list-onchain-transactions"]);
+		let transactions = output["list"].as_array().unwrap();
+		let confirmed = transactions
+			.iter()
+			.all(|transaction| transaction["confirmations"].as_u64().unwrap_or(0) > 0);
+		if transactions.len() == 2 && confirmed {
+			break output;
+		}
+		if start.elapsed() > timeout {
+			panic!("Timed out waiting for the on-chain transactions to be listed");
+		}
+		tokio::time::sleep(Duration::from_millis(500)).await;
+	};
+	let transactions = output["list"].as_array().unwrap();
+	assert_eq!(transactions[0]["txid"], txid);
+	assert_eq!(transactions[0]["direction"], "OUTBOUND");
+	assert!(transactions[0]["fee_sats"].as_u64().unwrap() > 0);
+	assert_eq!(transactions[1]["direction"], "INBOUND");
+	assert_eq!(transactions[1]["amount_sats"], 100_000_000);
+	assert!(transactions[1]["confirmations"].as_u64().unwrap() >= 6);
+
+	let output = run_cli(&server, &["list-onchain-transactions", "--direction", "inbound"]);
+	let transactions = output["list"].as_array().unwrap();
+	assert_eq!(transactions.len(), 1);
+	assert_eq!(transactions[0]["direction"], "INBOUND");
+
+	let output = run_cli(&server, &["list-onchain-transactions", "--page-size", "1"]);
+	assert_eq!(output["list"].as_array().unwrap().len(), 1);
+	let page_token = output["next_page_token"].as_str().unwrap();
+	let output = run_cli(
+		&server,
+		&["list-onchain-transactions", "--page-size", "1", "--page-token", page_token],
+	);
+	let transactions = output["list"].as_array().unwrap();
+	assert_eq!(transactions.len(), 1);
+	assert_eq!(transactions[0]["direction"], "INBOUND");
+	assert!(output.get("next_page_token").is_none());
+}
+
 #[tokio::test]
 async fn test_cli_connect_peer() {
 	let bitcoind = TestBitcoind::new();
@@ -488,8 +618,10 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_closed() {
 	wait_for_onchain_balance(server_a.client(), Duration::from_secs(30)).await;
 	wait_for_onchain_balance(server_b.client(), Duration::from_secs(30)).await;
 
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	let open_resp = server_a
 		.client()
@@ -503,6 +635,8 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_closed() {
 			channel_config: None,
 			announce_channel: true,
 			disable_counterparty_reserve: false,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -654,8 +788,10 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_force_close
 	wait_for_onchain_balance(server_a.client(), Duration::from_secs(30)).await;
 	wait_for_onchain_balance(server_b.client(), Duration::from_secs(30)).await;
 
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	let open_resp = server_a
 		.client()
@@ -669,6 +805,8 @@ async fn test_subscribe_events_channel_state_lifecycle_pending_ready_force_close
 			channel_config: None,
 			announce_channel: true,
 			disable_counterparty_reserve: false,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -844,8 +982,10 @@ async fn test_cli_bolt11_send() {
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
 	// Subscribe to events before the payment
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
@@ -858,6 +998,8 @@ async fn test_cli_bolt11_send() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -882,9 +1024,12 @@ async fn test_cli_bolt11_send_underpaying_split_payment() {
 	let server_c = LdkServerHandle::start(&bitcoind).await;
 
 	// Subscribe to events on all three nodes before any payment is sent.
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
-	let mut events_c = server_c.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_c =
+		server_c.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	// Each payer gets its own direct channel into the receiver. The channels are sized well
 	// above the 50,000 sat HTLCs because LDK limits a channel's maximum HTLC size to a fraction
@@ -903,6 +1048,8 @@ async fn test_cli_bolt11_send_underpaying_split_payment() {
 				)),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -942,6 +1089,8 @@ async fn test_cli_pay() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -956,6 +1105,7 @@ async fn test_cli_pay() {
 			amount_msat: None,
 			expiry_secs: None,
 			quantity: None,
+			label: None,
 		})
 		.await
 		.unwrap();
@@ -978,6 +1128,7 @@ async fn test_cli_bolt12_send() {
 			amount_msat: None,
 			expiry_secs: None,
 			quantity: None,
+			label: None,
 		})
 		.await
 		.unwrap();
@@ -993,8 +1144,10 @@ async fn test_cli_spontaneous_send() {
 	let server_a = LdkServerHandle::start(&bitcoind).await;
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
@@ -1009,13 +1162,60 @@ async fn test_cli_spontaneous_send() {
 	assert!(matches!(&event_b.event, Some(Event::PaymentReceived(_))));
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_subscribe_events_resumes_after_sequence() {
+	let bitcoind = TestBitcoind::new();
+	let server_a = LdkServerHandle::start(&bitcoind).await;
+	let server_b = LdkServerHandle::start(&bitcoind).await;
+
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+
+	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
+	run_cli(&server_a, &["spontaneous-send", server_b.node_id(), "10000sat"]);
+	let received = wait_for_event(&mut events_b, |e| matches!(e, Event::PaymentReceived(_))).await;
+
+	// Replaying from the start yields every event up to the payment, in order.
+	let mut replay = server_b
+		.client()
+		.subscribe_events(SubscribeEventsRequest {
+			start_after_sequence: Some(0),
+			..Default::default()
+		})
+		.await
+		.unwrap();
+	for sequence in 1..=received.sequence {
+		let event = tokio::time::timeout(EVENT_TIMEOUT, replay.next_message())
+			.await
+			.expect("Timed out waiting for replayed event")
+			.expect("Event stream ended before replaying all events")
+			.unwrap();
+		assert_eq!(event.sequence, sequence);
+		assert!(event.timestamp > 0);
+	}
+
+	// Resuming after an event starts with the one following it.
+	let request = SubscribeEventsRequest {
+		start_after_sequence: Some(received.sequence - 1),
+		..Default::default()
+	};
+	let mut resumed = server_b.client().subscribe_events(request).await.unwrap();
+	let event = tokio::time::timeout(EVENT_TIMEOUT, resumed.next_message())
+		.await
+		.expect("Timed out waiting for resumed event")
+		.expect("Event stream ended without resuming")
+		.unwrap();
+	assert_eq!(event, received);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
 async fn test_cli_spontaneous_send_with_custom_tlvs() {
 	let bitcoind = TestBitcoind::new();
 	let server_a = LdkServerHandle::start(&bitcoind).await;
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
@@ -1062,6 +1262,8 @@ async fn test_cli_get_payment_details() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -1093,6 +1295,8 @@ async fn test_cli_list_payments() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -1119,6 +1323,19 @@ async fn test_cli_close_channel() {
 
 	let channels_output = run_cli(&server_a, &["list-channels"]);
 	assert!(channels_output["channels"].as_array().unwrap().is_empty());
+
+	let closed_output = run_cli(&server_a, &["list-closed-channels"]);
+	let closed_channels = closed_output["list"].as_array().unwrap();
+	assert_eq!(closed_channels.len(), 1);
+	let closed = &closed_channels[0];
+	assert_eq!(closed["user_channel_id"], user_channel_id.as_str());
+	assert_eq!(closed["counterparty_node_id"], server_b.node_id());
+	assert_eq!(closed["state"], "CHANNEL_STATE_CLOSED");
+	assert_eq!(closed["closure_initiator"], "CHANNEL_CLOSURE_INITIATOR_LOCAL");
+	assert_eq!(closed["channel_value_sats"], 100_000);
+	assert_eq!(closed["is_outbound"], true);
+	assert!(closed["last_local_balance_msat"].as_u64().unwrap() > 0);
+	assert!(closed["funding_txo"].is_object());
 }
 
 #[tokio::test]
@@ -1294,7 +1511,8 @@ async fn test_forwarded_payment_event() {
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
 	// Subscribe to events on B before any payments
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	// Open channel A -> B (1M sats, larger for JIT forwarding)
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 1_000_000).await;
@@ -1379,8 +1597,10 @@ async fn test_hodl_invoice_claim() {
 	let server_a = LdkServerHandle::start(&bitcoind).await;
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
@@ -1446,8 +1666,10 @@ async fn test_hodl_invoice_fail() {
 	let server_a = LdkServerHandle::start(&bitcoind).await;
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
-	let mut events_a = server_a.client().subscribe_events().await.unwrap();
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_a =
+		server_a.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
@@ -1478,12 +1700,22 @@ async fn test_hodl_invoice_fail() {
 	let event_b = wait_for_event(&mut events_b, |e| matches!(e, Event::PaymentClaimable(_))).await;
 	assert!(matches!(&event_b.event, Some(Event::PaymentClaimable(_))));
 
+	// The held payment is listed until it is resolved
+	let claimable = run_cli(&server_b, &["list-claimable-payments"]);
+	let claimable = claimable["list"].as_array().unwrap();
+	assert_eq!(claimable.len(), 1);
+	assert_eq!(claimable[0]["payment_hash"], payment_hash_hex);
+	assert_eq!(claimable[0]["auto_claim"], false);
+
 	// Fail the payment on B using CLI
 	run_cli(&server_b, &["bolt11-fail-for-hash", &payment_hash_hex]);
 
 	// Verify PaymentFailed on A
 	let event_a = wait_for_event(&mut events_a, |e| matches!(e, Event::PaymentFailed(_))).await;
 	assert!(matches!(&event_a.event, Some(Event::PaymentFailed(_))));
+
+	let claimable = run_cli(&server_b, &["list-claimable-payments"]);
+	assert!(claimable["list"].as_array().unwrap().is_empty());
 }
 
 #[tokio::test]
@@ -1552,6 +1784,8 @@ async fn test_metrics_endpoint() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("metrics test".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
@@ -1621,7 +1855,8 @@ async fn test_cli_spontaneous_send_with_preimage() {
 	let server_a = LdkServerHandle::start(&bitcoind).await;
 	let server_b = LdkServerHandle::start(&bitcoind).await;
 
-	let mut events_b = server_b.client().subscribe_events().await.unwrap();
+	let mut events_b =
+		server_b.client().subscribe_events(SubscribeEventsRequest::default()).await.unwrap();
 
 	setup_funded_channel(&bitcoind, &server_a, &server_b, 100_000).await;
 
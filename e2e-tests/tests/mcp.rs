@@ -70,6 +70,8 @@ async fn test_mcp_live_tool_calls() {
 				kind: Some(bolt11_invoice_description::Kind::Direct("mcp decode".to_string())),
 			}),
 			expiry_secs: 3600,
+			label: None,
+			metadata: Default::default(),
 		})
 		.await
 		.unwrap();
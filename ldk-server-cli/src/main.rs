@@ -10,45 +10,58 @@
 use std::fmt::Write;
 use std::path::PathBuf;
 
-use clap::{CommandFactory, Parser, Subcommand};
+use clap::{ArgGroup, CommandFactory, Parser, Subcommand};
 use clap_complete::{generate, Shell};
 use hex_conservative::{DisplayHex, FromHex};
 use ldk_server_client::client::LdkServerClient;
 use ldk_server_client::config::{
-	get_default_config_path, load_config, resolve_api_key, resolve_base_url, resolve_cert_path,
-	DEFAULT_GRPC_SERVICE_ADDRESS,
+	get_default_config_path, is_unix_socket_url, load_config, resolve_api_key, resolve_base_url,
+	resolve_cert_path, DEFAULT_GRPC_SERVICE_ADDRESS,
 };
 use ldk_server_client::error::LdkServerError;
 use ldk_server_client::error::LdkServerErrorCode::{
 	AuthError, InternalError, InternalServerError, InvalidRequestError, LightningError,
+	PermissionDeniedError, SpendLimitExceededError,
 };
 use ldk_server_client::ldk_server_grpc::api::{
-	onchain_send_request, open_channel_request, splice_in_request, AllFunds,
-	Bolt11ClaimForHashRequest, Bolt11ClaimForHashResponse, Bolt11FailForHashRequest,
-	Bolt11FailForHashResponse, Bolt11ReceiveForHashRequest, Bolt11ReceiveForHashResponse,
-	Bolt11ReceiveRequest, Bolt11ReceiveResponse, Bolt11ReceiveVariableAmountViaJitChannelRequest,
+	estimate_route_fee_request, find_payments_request, onchain_send_request, open_channel_request,
+	query_routes_request, send_probes_request, splice_in_request, update_label_request, AllFunds,
+	ApproveActionRequest, ApproveActionResponse, Bolt11ClaimForHashRequest,
+	Bolt11ClaimForHashResponse, Bolt11FailForHashRequest, Bolt11FailForHashResponse,
+	Bolt11ReceiveForHashRequest, Bolt11ReceiveForHashResponse, Bolt11ReceiveRequest,
+	Bolt11ReceiveResponse, Bolt11ReceiveVariableAmountViaJitChannelRequest,
 	Bolt11ReceiveVariableAmountViaJitChannelResponse, Bolt11ReceiveViaJitChannelRequest,
 	Bolt11ReceiveViaJitChannelResponse, Bolt11SendRequest, Bolt11SendResponse,
 	Bolt11SendUnderpayingRequest, Bolt11SendUnderpayingResponse, Bolt12ReceiveRequest,
 	Bolt12ReceiveResponse, Bolt12SendRequest, Bolt12SendResponse, CloseChannelRequest,
-	CloseChannelResponse, ConnectPeerRequest, ConnectPeerResponse, DecodeInvoiceRequest,
-	DecodeInvoiceResponse, DecodeOfferRequest, DecodeOfferResponse, DisconnectPeerRequest,
-	DisconnectPeerResponse, ExportPathfindingScoresRequest, ForceCloseChannelRequest,
+	CloseChannelResponse, ConnectPeerRequest, ConnectPeerResponse, CreateApiKeyRequest,
+	CreateApiKeyResponse, DecodeInvoiceRequest, DecodeInvoiceResponse, DecodeOfferRequest,
+	DecodeOfferResponse, DisconnectPeerRequest, DisconnectPeerResponse, EncryptMnemonicRequest,
+	EncryptMnemonicResponse, EstimateRouteFeeRequest, EstimateRouteFeeResponse,
+	ExportPathfindingScoresRequest, FindPaymentsRequest, ForceCloseChannelRequest,
 	ForceCloseChannelResponse, GetBalancesRequest, GetBalancesResponse, GetNodeInfoRequest,
 	GetNodeInfoResponse, GetPaymentDetailsRequest, GetPaymentDetailsResponse,
 	GraphGetChannelRequest, GraphGetChannelResponse, GraphGetNodeRequest, GraphGetNodeResponse,
 	GraphListChannelsRequest, GraphListChannelsResponse, GraphListNodesRequest,
-	GraphListNodesResponse, ListChannelsRequest, ListChannelsResponse,
-	ListForwardedPaymentsRequest, ListPaymentsRequest, ListPeersRequest, ListPeersResponse,
-	OnchainReceiveRequest, OnchainReceiveResponse, OnchainSendRequest, OnchainSendResponse,
-	OpenChannelRequest, OpenChannelResponse, SignMessageRequest, SignMessageResponse,
-	SpliceInRequest, SpliceInResponse, SpliceOutRequest, SpliceOutResponse, SpontaneousSendRequest,
-	SpontaneousSendResponse, UnifiedSendRequest, UnifiedSendResponse, UpdateChannelConfigRequest,
-	UpdateChannelConfigResponse, VerifySignatureRequest, VerifySignatureResponse,
+	GraphListNodesResponse, ListApiKeysRequest, ListApiKeysResponse, ListAuditLogRequest,
+	ListChannelsRequest, ListChannelsResponse, ListClaimablePaymentsRequest,
+	ListClosedChannelsRequest, ListFailedWebhookDeliveriesRequest, ListForwardedPaymentsRequest,
+	ListInvoicesRequest, ListOffersRequest, ListOnchainTransactionsRequest, ListPaymentsRequest,
+	ListPeersRequest, ListPeersResponse, ListPendingActionsRequest, OnchainReceiveRequest,
+	OnchainReceiveResponse, OnchainSendRequest, OnchainSendResponse, OpenChannelRequest,
+	OpenChannelResponse, QueryRoutesRequest, QueryRoutesResponse, RedeliverWebhookRequest,
+	RedeliverWebhookResponse, RejectActionRequest, RejectActionResponse, RevokeApiKeyRequest,
+	RevokeApiKeyResponse, RotateApiKeyRequest, RotateApiKeyResponse, SendProbesRequest,
+	SendProbesResponse, SignMessageRequest, SignMessageResponse, SpliceInRequest, SpliceInResponse,
+	SpliceOutRequest, SpliceOutResponse, SpontaneousSendRequest, SpontaneousSendResponse,
+	UnifiedSendRequest, UnifiedSendResponse, UnlockRequest, UnlockResponse,
+	UpdateChannelConfigRequest, UpdateChannelConfigResponse, UpdateLabelRequest,
+	UpdateLabelResponse, VerifySignatureRequest, VerifySignatureResponse,
 };
 use ldk_server_client::ldk_server_grpc::types::{
-	bolt11_invoice_description, Bolt11InvoiceDescription, ChannelConfig, CustomTlvRecord,
-	PageToken, RouteParametersConfig,
+	bolt11_invoice_description, ApiKeyScope, Bolt11InvoiceDescription, ChannelConfig,
+	CustomTlvRecord, PageToken, PaymentDirection, PaymentKindType, PaymentStatus,
+	RouteParametersConfig,
 };
 use ldk_server_client::{
 	DEFAULT_EXPIRY_SECS, DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF, DEFAULT_MAX_PATH_COUNT,
@@ -57,7 +70,10 @@ use ldk_server_client::{
 use serde::Serialize;
 use serde_json::{json, Value};
 use types::{
-	Amount, AmountOrAll, CliListForwardedPaymentsResponse, CliListPaymentsResponse,
+	Amount, AmountOrAll, CliListAuditLogResponse, CliListClaimablePaymentsResponse,
+	CliListClosedChannelsResponse, CliListFailedWebhookDeliveriesResponse,
+	CliListForwardedPaymentsResponse, CliListInvoicesResponse, CliListOffersResponse,
+	CliListOnchainTransactionsResponse, CliListPaymentsResponse, CliListPendingActionsResponse,
 	CliPaginatedResponse, Preimage,
 };
 
@@ -85,7 +101,7 @@ struct Cli {
 		short,
 		long,
 		help = format!(
-			"Base URL of the server. Defaults to config file or {DEFAULT_GRPC_SERVICE_ADDRESS}"
+			"Base URL of the server, or unix:///path/to/socket to connect to its Unix socket. Defaults to config file or {DEFAULT_GRPC_SERVICE_ADDRESS}"
 		)
 	)]
 	base_url: Option<String>,
@@ -96,6 +112,20 @@ struct Cli {
 	#[arg(short, long, help = format!("Path to the server's TLS certificate file (PEM format). Defaults to {DEFAULT_DIR}/tls.crt"))]
 	tls_cert: Option<String>,
 
+	#[arg(
+		long,
+		requires = "tls_client_key",
+		help = "Path to a TLS client certificate (PEM format) for servers requiring mutual TLS. Requests are authenticated by the certificate unless --api-key is given"
+	)]
+	tls_client_cert: Option<String>,
+
+	#[arg(
+		long,
+		requires = "tls_client_cert",
+		help = "Path to the private key of the TLS client certificate (PEM format)"
+	)]
+	tls_client_key: Option<String>,
+
 	#[arg(short, long, help = format!("Path to config file. Defaults to {DEFAULT_DIR}/config.toml"))]
 	config: Option<String>,
 
@@ -124,6 +154,40 @@ enum Commands {
 			help = "Fee rate in satoshis per virtual byte. If not set, a reasonable estimate will be used"
 		)]
 		fee_rate_sat_per_vb: Option<u64>,
+		#[arg(
+			long,
+			help = "Label to attach to the payment, returned along with it by list and get commands"
+		)]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to attach to the payment, format: <key>=<value>. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
+	},
+	#[command(about = "List the transactions of the on-chain wallet, most recently seen first")]
+	ListOnchainTransactions {
+		#[arg(short, long)]
+		#[arg(
+			help = "Fetch at least this many transactions by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_transactions: Option<u64>,
+		#[arg(long)]
+		#[arg(help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+		#[arg(
+			long,
+			value_parser = parse_payment_direction,
+			help = "Only list transactions in this direction: inbound or outbound"
+		)]
+		direction: Option<PaymentDirection>,
+		#[arg(
+			long,
+			value_parser = clap::value_parser!(u32).range(1..),
+			help = "Maximum number of transactions per page, up to 1000 (default: 100)"
+		)]
+		page_size: Option<u32>,
 	},
 	#[command(about = "Create a BOLT11 invoice to receive a payment")]
 	Bolt11Receive {
@@ -140,6 +204,17 @@ enum Commands {
 		description_hash: Option<String>,
 		#[arg(short, long, help = "Invoice expiry time in seconds (default: 86400)")]
 		expiry_secs: Option<u32>,
+		#[arg(
+			long,
+			help = "Label to record the invoice with, which is not included in the invoice"
+		)]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to record the invoice with, format: <key>=<value>. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
 	},
 	#[command(
 		about = "Create a BOLT11 hodl invoice for a given payment hash (manual claim required)"
@@ -160,6 +235,16 @@ enum Commands {
 		description_hash: Option<String>,
 		#[arg(short, long, help = "Invoice expiry time in seconds (default: 86400)")]
 		expiry_secs: Option<u32>,
+		#[arg(
+			long,
+			help = "Label to record the invoice with, which is not included in the invoice"
+		)]
+		label: Option<String>,
+		#[arg(
+			long,
+			help = "The hex-encoded 32-byte preimage to claim the payment with if it is neither claimed nor failed before its claim deadline. If unset, the payment is failed back"
+		)]
+		auto_claim_preimage: Option<String>,
 	},
 	#[command(about = "Claim a held payment by providing the preimage")]
 	Bolt11ClaimForHash {
@@ -183,6 +268,17 @@ enum Commands {
 		#[arg(help = "The hex-encoded 32-byte payment hash")]
 		payment_hash: String,
 	},
+	#[command(about = "Return a list of held payments waiting to be claimed or failed")]
+	ListClaimablePayments {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many payments by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_payments: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
 	#[command(about = "Create a fixed-amount BOLT11 invoice to receive via an LSPS2 JIT channel")]
 	Bolt11ReceiveViaJitChannel {
 		#[arg(help = "Amount to request, e.g. 50sat or 50000msat")]
@@ -201,6 +297,11 @@ enum Commands {
 			help = "Maximum total fee an LSP may deduct for opening the JIT channel, e.g. 50sat or 50000msat"
 		)]
 		max_total_lsp_fee_limit: Option<Amount>,
+		#[arg(
+			long,
+			help = "Label to record the invoice with, which is not included in the invoice"
+		)]
+		label: Option<String>,
 	},
 	#[command(
 		about = "Create a variable-amount BOLT11 invoice to receive via an LSPS2 JIT channel"
@@ -217,6 +318,11 @@ enum Commands {
 		expiry_secs: Option<u32>,
 		#[arg(long, help = "Maximum proportional fee the LSP may deduct in ppm-msat")]
 		max_proportional_lsp_fee_limit_ppm_msat: Option<u64>,
+		#[arg(
+			long,
+			help = "Label to record the invoice with, which is not included in the invoice"
+		)]
+		label: Option<String>,
 	},
 	#[command(about = "Pay a BOLT11 invoice")]
 	Bolt11Send {
@@ -243,6 +349,17 @@ enum Commands {
 			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2)"
 		)]
 		max_channel_saturation_power_of_half: Option<u32>,
+		#[arg(
+			long,
+			help = "Label to attach to the payment, returned along with it by list and get commands"
+		)]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to attach to the payment, format: <key>=<value>. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
 	},
 	#[command(
 		about = "Send part of a fixed-amount BOLT11 invoice. Other nodes must send partial payments for the same invoice until the combined amount equals the invoice amount"
@@ -284,6 +401,8 @@ enum Commands {
 		expiry_secs: Option<u32>,
 		#[arg(long, help = "Number of items requested. Can only be set for fixed-amount offers")]
 		quantity: Option<u64>,
+		#[arg(long, help = "Label to record the offer with, which is not included in the offer")]
+		label: Option<String>,
 	},
 	#[command(about = "Send a payment for a BOLT12 offer")]
 	Bolt12Send {
@@ -318,6 +437,17 @@ enum Commands {
 			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2)"
 		)]
 		max_channel_saturation_power_of_half: Option<u32>,
+		#[arg(
+			long,
+			help = "Label to attach to the payment, returned along with it by list and get commands"
+		)]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to attach to the payment, format: <key>=<value>. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
 	},
 	#[command(about = "Send a spontaneous payment (keysend) to a node")]
 	SpontaneousSend {
@@ -443,6 +573,17 @@ enum Commands {
 			help = "The difference in the CLTV value between incoming HTLCs and an outbound HTLC forwarded over the channel. This can be updated by using update-channel-config."
 		)]
 		cltv_expiry_delta: Option<u32>,
+		#[arg(
+			long,
+			help = "Label to attach to the channel, returned along with it by list-channels"
+		)]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to attach to the channel, format: <key>=<value>. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
 	},
 	#[command(
 		about = "Increase the channel balance by the given amount, funds will come from the node's on-chain wallet"
@@ -476,7 +617,18 @@ enum Commands {
 	},
 	#[command(about = "Return a list of known channels")]
 	ListChannels,
-	#[command(about = "Retrieve list of all payments")]
+	#[command(about = "Return a list of channels that were closed, most recently closed first")]
+	ListClosedChannels {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many channels by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_channels: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(about = "Retrieve list of all payments, optionally filtered")]
 	ListPayments {
 		#[arg(short, long)]
 		#[arg(
@@ -486,12 +638,120 @@ enum Commands {
 		#[arg(long)]
 		#[arg(help = "Page token to continue from a previous page (format: token:index)")]
 		page_token: Option<String>,
+		#[arg(
+			long,
+			value_parser = parse_payment_direction,
+			help = "Only list payments in this direction: inbound or outbound"
+		)]
+		direction: Option<PaymentDirection>,
+		#[arg(
+			long,
+			value_parser = parse_payment_status,
+			help = "Only list payments with this status: pending, succeeded or failed"
+		)]
+		status: Option<PaymentStatus>,
+		#[arg(
+			long,
+			value_parser = parse_payment_kind,
+			help = "Only list payments of this kind: onchain, bolt11, bolt12_offer, bolt12_refund or spontaneous"
+		)]
+		kind: Option<PaymentKindType>,
+		#[arg(long, help = "Only list payments of at least this amount, e.g. 50sat or 50000msat")]
+		min_amount: Option<Amount>,
+		#[arg(long, help = "Only list payments of at most this amount, e.g. 50sat or 50000msat")]
+		max_amount: Option<Amount>,
+		#[arg(
+			long,
+			help = "Only list payments first recorded at or after this time, in seconds since the UNIX epoch"
+		)]
+		created_since: Option<u64>,
+		#[arg(
+			long,
+			help = "Only list payments first recorded before this time, in seconds since the UNIX epoch"
+		)]
+		created_before: Option<u64>,
+		#[arg(
+			long,
+			help = "Only list payments last updated at or after this time, in seconds since the UNIX epoch"
+		)]
+		updated_since: Option<u64>,
+		#[arg(
+			long,
+			help = "Only list payments last updated before this time, in seconds since the UNIX epoch"
+		)]
+		updated_before: Option<u64>,
+		#[arg(
+			long,
+			value_parser = clap::value_parser!(u32).range(1..),
+			help = "Maximum number of payments per page, up to 1000 (default: 100)"
+		)]
+		page_size: Option<u32>,
+	},
+	#[command(about = "Find payments by payment hash, preimage or BOLT12 offer ID")]
+	#[command(group(
+		ArgGroup::new("identifier").required(true).args(["payment_hash", "preimage", "offer_id"])
+	))]
+	FindPayments {
+		#[arg(long, help = "The hex-encoded payment hash")]
+		payment_hash: Option<String>,
+		#[arg(long, help = "The hex-encoded payment preimage")]
+		preimage: Option<String>,
+		#[arg(long, help = "The hex-encoded ID of the BOLT12 offer the payments were made for")]
+		offer_id: Option<String>,
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many payments by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_payments: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
 	},
 	#[command(about = "Get details of a specific payment by its payment ID")]
 	GetPaymentDetails {
 		#[arg(help = "The payment ID in hex-encoded form")]
 		payment_id: String,
 	},
+	#[command(about = "List the BOLT11 invoices issued by the server, most recent first")]
+	ListInvoices {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many invoices by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_invoices: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(about = "List the BOLT12 offers issued by the server, most recent first")]
+	ListOffers {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many offers by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_offers: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(about = "Change the label and metadata attached to a payment or channel")]
+	#[command(group(
+		ArgGroup::new("target").required(true).args(["payment_id", "user_channel_id"])
+	))]
+	UpdateLabel {
+		#[arg(long, help = "The hex-encoded ID of the payment to update")]
+		payment_id: Option<String>,
+		#[arg(long, help = "The local user_channel_id of the channel to update")]
+		user_channel_id: Option<String>,
+		#[arg(long, help = "New label, replacing the current one. An empty label removes it")]
+		label: Option<String>,
+		#[arg(
+			long = "metadata",
+			value_parser = parse_metadata_entry,
+			help = "Metadata entry to set, format: <key>=<value>. An empty value removes the entry. Repeatable."
+		)]
+		metadata: Vec<(String, String)>,
+	},
 	#[command(about = "Retrieves list of all forwarded payments")]
 	ListForwardedPayments {
 		#[arg(
@@ -567,6 +827,100 @@ enum Commands {
 	},
 	#[command(about = "Export the pathfinding scores used by the router")]
 	ExportPathfindingScores,
+	#[command(about = "Estimate the routing fees of a payment without sending it")]
+	EstimateRouteFee {
+		#[arg(
+			help = "A BOLT11 invoice, or the hex-encoded public key of a node to send a spontaneous payment to"
+		)]
+		destination: String,
+		#[arg(
+			help = "The amount to pay, e.g. 50sat or 50000msat. Required for a node or a variable-amount invoice"
+		)]
+		amount: Option<Amount>,
+		#[arg(
+			long,
+			help = "Maximum total routing fee, e.g. 50sat or 50000msat. Defaults to 1% of payment + 50 sats"
+		)]
+		max_total_routing_fee: Option<Amount>,
+		#[arg(long, help = "Maximum total CLTV delta we accept for the route (default: 1008)")]
+		max_total_cltv_expiry_delta: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum number of paths that may be used by MPP payments (default: 10)"
+		)]
+		max_path_count: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2)"
+		)]
+		max_channel_saturation_power_of_half: Option<u32>,
+	},
+	#[command(about = "Find the route of a payment without sending it")]
+	QueryRoutes {
+		#[arg(
+			help = "A BOLT11 invoice, or the hex-encoded public key of a node to send a spontaneous payment to"
+		)]
+		destination: String,
+		#[arg(
+			help = "The amount to pay, e.g. 50sat or 50000msat. Required for a node or a variable-amount invoice"
+		)]
+		amount: Option<Amount>,
+		#[arg(
+			long,
+			help = "Maximum total routing fee, e.g. 50sat or 50000msat. Defaults to 1% of payment + 50 sats"
+		)]
+		max_total_routing_fee: Option<Amount>,
+		#[arg(long, help = "Maximum total CLTV delta we accept for the route (default: 1008)")]
+		max_total_cltv_expiry_delta: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum number of paths that may be used by MPP payments (default: 10)"
+		)]
+		max_path_count: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2)"
+		)]
+		max_channel_saturation_power_of_half: Option<u32>,
+	},
+	#[command(
+		about = "Probe the liquidity of the route of a payment without sending it. The outcomes are estimated from the pathfinding scores and returned as estimated_status"
+	)]
+	SendProbes {
+		#[arg(
+			help = "A BOLT11 invoice, or the hex-encoded public key of a node to send a spontaneous payment to"
+		)]
+		destination: String,
+		#[arg(
+			help = "The amount to probe for, e.g. 50sat or 50000msat. Required for a node or a variable-amount invoice"
+		)]
+		amount: Option<Amount>,
+		#[arg(
+			long,
+			help = "Seconds to wait for the estimated outcomes of the probes (default: 60)"
+		)]
+		timeout_secs: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum total routing fee, e.g. 50sat or 50000msat. Defaults to 1% of payment + 50 sats. Only for invoices"
+		)]
+		max_total_routing_fee: Option<Amount>,
+		#[arg(
+			long,
+			help = "Maximum total CLTV delta we accept for the route (default: 1008). Only for invoices"
+		)]
+		max_total_cltv_expiry_delta: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum number of paths that may be used by MPP payments (default: 10). Only for invoices"
+		)]
+		max_path_count: Option<u32>,
+		#[arg(
+			long,
+			help = "Maximum share of a channel's total capacity to send over a channel, as a power of 1/2 (default: 2). Only for invoices"
+		)]
+		max_channel_saturation_power_of_half: Option<u32>,
+	},
 	#[command(about = "List all known short channel IDs in the network graph")]
 	GraphListChannels,
 	#[command(about = "Get channel information from the network graph by short channel ID")]
@@ -581,6 +935,118 @@ enum Commands {
 		#[arg(help = "The hex-encoded node ID to look up")]
 		node_id: String,
 	},
+	#[command(about = "Create a new API key. The key is only shown once, store it securely")]
+	CreateApiKey {
+		#[arg(help = "The unique name of the API key")]
+		name: String,
+		#[arg(
+			value_parser = parse_api_key_scope,
+			help = "The scope of the API key: read_only, invoice_only, send or admin"
+		)]
+		scope: ApiKeyScope,
+		#[arg(
+			long,
+			help = "Maximum amount of a single payment sent with the key, e.g. 50sat or 50000msat. Only valid for the send scope"
+		)]
+		max_send_amount: Option<Amount>,
+	},
+	#[command(about = "List all API keys accepted by the server")]
+	ListApiKeys,
+	#[command(about = "Replace an API key with a newly generated one")]
+	RotateApiKey {
+		#[arg(help = "The name of the API key to rotate")]
+		name: String,
+		#[arg(
+			long,
+			help = "How long the replaced key is still accepted, in seconds. Set to 0 to reject it immediately (default: 3600)"
+		)]
+		grace_period_secs: Option<u64>,
+	},
+	#[command(about = "Revoke an API key, immediately rejecting any requests made with it")]
+	RevokeApiKey {
+		#[arg(help = "The name of the API key to revoke")]
+		name: String,
+	},
+	#[command(
+		about = "List the audit log of state-changing calls, most recent first. Requires an admin API key"
+	)]
+	ListAuditLog {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many entries by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_entries: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(
+		about = "Approve and execute a send that is awaiting approval. Requires an admin API key"
+	)]
+	ApproveAction {
+		#[arg(help = "The identifier of the pending action")]
+		id: String,
+	},
+	#[command(about = "Reject a send that is awaiting approval. Requires an admin API key")]
+	RejectAction {
+		#[arg(help = "The identifier of the pending action")]
+		id: String,
+		#[arg(long, help = "The reason for rejecting the action")]
+		reason: Option<String>,
+	},
+	#[command(
+		about = "List sends that were parked for approval, most recent first. Requires an admin API key"
+	)]
+	ListPendingActions {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many actions by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_actions: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(
+		about = "Unlock the encrypted mnemonic of a locked server and start the node. Requires an admin API key"
+	)]
+	Unlock {
+		#[arg(
+			long,
+			help = "The passphrase the mnemonic was encrypted with. If not provided, it is read from standard input"
+		)]
+		passphrase: Option<String>,
+	},
+	#[command(
+		about = "Encrypt the plaintext mnemonic of the server in place. The server has to be unlocked after its next restart. Requires an admin API key"
+	)]
+	EncryptMnemonic {
+		#[arg(
+			long,
+			help = "The passphrase to encrypt the mnemonic with. If not provided, it is read from standard input"
+		)]
+		passphrase: Option<String>,
+	},
+	#[command(
+		about = "List webhook deliveries that ran out of attempts, most recent first. Requires an admin API key"
+	)]
+	ListFailedWebhookDeliveries {
+		#[arg(
+			short,
+			long,
+			help = "Fetch at least this many deliveries by iterating through multiple pages. Returns combined results with the last page token. If not provided, returns only a single page."
+		)]
+		number_of_deliveries: Option<u64>,
+		#[arg(long, help = "Page token to continue from a previous page (format: token:index)")]
+		page_token: Option<String>,
+	},
+	#[command(
+		about = "Queue a failed webhook delivery to be attempted again. Requires an admin API key"
+	)]
+	RedeliverWebhook {
+		#[arg(help = "The identifier of the failed delivery, e.g. 42-payments")]
+		delivery_id: String,
+	},
 	#[command(about = "Generate shell completions for the CLI")]
 	Completions {
 		#[arg(
@@ -617,25 +1083,62 @@ async fn main() {
 		},
 	};
 
-	let api_key = resolve_api_key(cli.api_key, config.as_ref()).unwrap_or_else(|| {
-		eprintln!("API key not provided. Use --api-key or ensure the api_key file exists at {DEFAULT_DIR}/[network]/api_key");
-		std::process::exit(1);
-	});
-
 	let base_url = resolve_base_url(cli.base_url, config.as_ref());
 
-	let tls_cert_path = resolve_cert_path(cli.tls_cert.map(PathBuf::from), config.as_ref())
-		.unwrap_or_else(|| {
-			eprintln!("TLS cert path not provided. Use --tls-cert or ensure config file exists at {DEFAULT_DIR}/config.toml");
+	let client = if is_unix_socket_url(&base_url)
+		&& cli.tls_cert.is_none()
+		&& cli.tls_client_cert.is_none()
+	{
+		// Access to the Unix socket is governed by its file permissions, so requests are only
+		// signed if an API key was given explicitly, and TLS is only used if a certificate was.
+		LdkServerClient::new_unix_socket(base_url, cli.api_key)
+	} else {
+		let tls_cert_path = resolve_cert_path(cli.tls_cert.map(PathBuf::from), config.as_ref())
+			.unwrap_or_else(|| {
+				eprintln!("TLS cert path not provided. Use --tls-cert or ensure config file exists at {DEFAULT_DIR}/config.toml");
+				std::process::exit(1);
+			});
+
+		let server_cert_pem = std::fs::read(&tls_cert_path).unwrap_or_else(|e| {
+			eprintln!(
+				"Failed to read server certificate file '{}': {}",
+				tls_cert_path.display(),
+				e
+			);
 			std::process::exit(1);
 		});
 
-	let server_cert_pem = std::fs::read(&tls_cert_path).unwrap_or_else(|e| {
-		eprintln!("Failed to read server certificate file '{}': {}", tls_cert_path.display(), e);
-		std::process::exit(1);
-	});
-
-	let client = LdkServerClient::new(base_url, api_key, &server_cert_pem).unwrap_or_else(|e| {
+		match (cli.tls_client_cert, cli.tls_client_key) {
+			(Some(client_cert_path), Some(client_key_path)) => {
+				let client_cert_pem = std::fs::read(&client_cert_path).unwrap_or_else(|e| {
+					eprintln!("Failed to read client certificate file '{client_cert_path}': {e}");
+					std::process::exit(1);
+				});
+				let client_key_pem = std::fs::read(&client_key_path).unwrap_or_else(|e| {
+					eprintln!("Failed to read client key file '{client_key_path}': {e}");
+					std::process::exit(1);
+				});
+				// Only sign requests if an API key was given explicitly, otherwise the
+				// certificate's identity would be overridden by the admin key from the default
+				// location.
+				LdkServerClient::new_with_client_cert(
+					base_url,
+					cli.api_key,
+					&server_cert_pem,
+					&client_cert_pem,
+					&client_key_pem,
+				)
+			},
+			_ => {
+				let api_key = resolve_api_key(cli.api_key, config.as_ref()).unwrap_or_else(|| {
+					eprintln!("API key not provided. Use --api-key or ensure the api_key file exists at {DEFAULT_DIR}/[network]/api_key");
+					std::process::exit(1);
+				});
+				LdkServerClient::new(base_url, api_key, &server_cert_pem)
+			},
+		}
+	}
+	.unwrap_or_else(|e| {
 		eprintln!("Failed to create client: {e}");
 		std::process::exit(1);
 	});
@@ -656,7 +1159,7 @@ async fn main() {
 				client.onchain_receive(OnchainReceiveRequest {}).await,
 			);
 		},
-		Commands::OnchainSend { address, amount, fee_rate_sat_per_vb } => {
+		Commands::OnchainSend { address, amount, fee_rate_sat_per_vb, label, metadata } => {
 			let amount = match amount.to_sat().unwrap_or_else(|e| handle_error_msg(e)) {
 				Some(amount_sats) => onchain_send_request::Amount::AmountSats(amount_sats),
 				None => onchain_send_request::Amount::AllFunds(AllFunds {}),
@@ -667,18 +1170,61 @@ async fn main() {
 						address,
 						fee_rate_sat_per_vb,
 						amount: Some(amount),
+						label,
+						metadata: metadata.into_iter().collect(),
 					})
 					.await,
 			);
 		},
-		Commands::Bolt11Receive { description, description_hash, expiry_secs, amount } => {
+		Commands::ListOnchainTransactions {
+			number_of_transactions,
+			page_token,
+			direction,
+			page_size,
+		} => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+			let request = ListOnchainTransactionsRequest {
+				page_token: None,
+				direction: direction.map(Into::into),
+				page_size,
+			};
+
+			handle_response_result::<_, CliListOnchainTransactionsResponse>(
+				fetch_paginated(
+					number_of_transactions,
+					page_token,
+					|pt| {
+						client.list_onchain_transactions(ListOnchainTransactionsRequest {
+							page_token: pt,
+							..request.clone()
+						})
+					},
+					|r| (r.transactions, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::Bolt11Receive {
+			description,
+			description_hash,
+			expiry_secs,
+			amount,
+			label,
+			metadata,
+		} => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			let invoice_description =
 				parse_bolt11_invoice_description(description, description_hash);
 
 			let expiry_secs = expiry_secs.unwrap_or(DEFAULT_EXPIRY_SECS);
-			let request =
-				Bolt11ReceiveRequest { description: invoice_description, expiry_secs, amount_msat };
+			let request = Bolt11ReceiveRequest {
+				description: invoice_description,
+				expiry_secs,
+				amount_msat,
+				label,
+				metadata: metadata.into_iter().collect(),
+			};
 
 			handle_response_result::<_, Bolt11ReceiveResponse>(
 				client.bolt11_receive(request).await,
@@ -690,6 +1236,8 @@ async fn main() {
 			description,
 			description_hash,
 			expiry_secs,
+			label,
+			auto_claim_preimage,
 		} => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			let invoice_description = match (description, description_hash) {
@@ -714,6 +1262,8 @@ async fn main() {
 				expiry_secs,
 				amount_msat,
 				payment_hash,
+				label,
+				auto_claim_preimage,
 			};
 
 			handle_response_result::<_, Bolt11ReceiveForHashResponse>(
@@ -736,18 +1286,38 @@ async fn main() {
 				client.bolt11_fail_for_hash(Bolt11FailForHashRequest { payment_hash }).await,
 			);
 		},
+		Commands::ListClaimablePayments { number_of_payments, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListClaimablePaymentsResponse>(
+				fetch_paginated(
+					number_of_payments,
+					page_token,
+					|pt| {
+						client.list_claimable_payments(ListClaimablePaymentsRequest {
+							page_token: pt,
+						})
+					},
+					|r| (r.claimable_payments, r.next_page_token),
+				)
+				.await,
+			);
+		},
 		Commands::Bolt11ReceiveViaJitChannel {
 			amount,
 			description,
 			description_hash,
 			expiry_secs,
 			max_total_lsp_fee_limit,
+			label,
 		} => {
 			let request = Bolt11ReceiveViaJitChannelRequest {
 				amount_msat: amount.to_msat(),
 				description: parse_bolt11_invoice_description(description, description_hash),
 				expiry_secs: expiry_secs.unwrap_or(DEFAULT_EXPIRY_SECS),
 				max_total_lsp_fee_limit_msat: max_total_lsp_fee_limit.map(|a| a.to_msat()),
+				label,
 			};
 
 			handle_response_result::<_, Bolt11ReceiveViaJitChannelResponse>(
@@ -759,11 +1329,13 @@ async fn main() {
 			description_hash,
 			expiry_secs,
 			max_proportional_lsp_fee_limit_ppm_msat,
+			label,
 		} => {
 			let request = Bolt11ReceiveVariableAmountViaJitChannelRequest {
 				description: parse_bolt11_invoice_description(description, description_hash),
 				expiry_secs: expiry_secs.unwrap_or(DEFAULT_EXPIRY_SECS),
 				max_proportional_lsp_fee_limit_ppm_msat,
+				label,
 			};
 
 			handle_response_result::<_, Bolt11ReceiveVariableAmountViaJitChannelResponse>(
@@ -777,6 +1349,8 @@ async fn main() {
 			max_total_cltv_expiry_delta,
 			max_path_count,
 			max_channel_saturation_power_of_half,
+			label,
+			metadata,
 		} => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			let max_total_routing_fee_msat = max_total_routing_fee.map(|a| a.to_msat());
@@ -794,6 +1368,8 @@ async fn main() {
 						invoice,
 						amount_msat,
 						route_parameters: Some(route_parameters),
+						label,
+						metadata: metadata.into_iter().collect(),
 					})
 					.await,
 			);
@@ -826,7 +1402,7 @@ async fn main() {
 					.await,
 			);
 		},
-		Commands::Bolt12Receive { description, amount, expiry_secs, quantity } => {
+		Commands::Bolt12Receive { description, amount, expiry_secs, quantity, label } => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			handle_response_result::<_, Bolt12ReceiveResponse>(
 				client
@@ -835,6 +1411,7 @@ async fn main() {
 						amount_msat,
 						expiry_secs,
 						quantity,
+						label,
 					})
 					.await,
 			);
@@ -848,6 +1425,8 @@ async fn main() {
 			max_total_cltv_expiry_delta,
 			max_path_count,
 			max_channel_saturation_power_of_half,
+			label,
+			metadata,
 		} => {
 			let amount_msat = amount.map(|a| a.to_msat());
 			let max_total_routing_fee_msat = max_total_routing_fee.map(|a| a.to_msat());
@@ -868,6 +1447,8 @@ async fn main() {
 						quantity,
 						payer_note,
 						route_parameters: Some(route_parameters),
+						label,
+						metadata: metadata.into_iter().collect(),
 					})
 					.await,
 			);
@@ -980,6 +1561,8 @@ async fn main() {
 			forwarding_fee_proportional_millionths,
 			forwarding_fee_base_msat,
 			cltv_expiry_delta,
+			label,
+			metadata,
 		} => {
 			let amount = match channel_amount.to_sat().unwrap_or_else(|e| handle_error_msg(e)) {
 				Some(amount_sats) => open_channel_request::Amount::ChannelAmountSats(amount_sats),
@@ -1008,6 +1591,8 @@ async fn main() {
 						channel_config,
 						announce_channel,
 						disable_counterparty_reserve,
+						label,
+						metadata: metadata.into_iter().collect(),
 					})
 					.await,
 			);
@@ -1045,15 +1630,92 @@ async fn main() {
 				client.list_channels(ListChannelsRequest {}).await,
 			);
 		},
-		Commands::ListPayments { number_of_payments, page_token } => {
+		Commands::ListClosedChannels { number_of_channels, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListClosedChannelsResponse>(
+				fetch_paginated(
+					number_of_channels,
+					page_token,
+					|pt| client.list_closed_channels(ListClosedChannelsRequest { page_token: pt }),
+					|r| (r.closed_channels, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::ListPayments {
+			number_of_payments,
+			page_token,
+			direction,
+			status,
+			kind,
+			min_amount,
+			max_amount,
+			created_since,
+			created_before,
+			updated_since,
+			updated_before,
+			page_size,
+		} => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+			let request = ListPaymentsRequest {
+				page_token: None,
+				direction: direction.map(Into::into),
+				status: status.map(Into::into),
+				kind: kind.map(Into::into),
+				min_amount_msat: min_amount.map(|a| a.to_msat()),
+				max_amount_msat: max_amount.map(|a| a.to_msat()),
+				created_since,
+				created_before,
+				updated_since,
+				updated_before,
+				page_size,
+			};
+
+			handle_response_result::<_, CliListPaymentsResponse>(
+				fetch_paginated(
+					number_of_payments,
+					page_token,
+					|pt| {
+						client.list_payments(ListPaymentsRequest {
+							page_token: pt,
+							..request.clone()
+						})
+					},
+					|r| (r.payments, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::FindPayments {
+			payment_hash,
+			preimage,
+			offer_id,
+			number_of_payments,
+			page_token,
+		} => {
 			let page_token = page_token
 				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+			// Clap ensures exactly one identifier is given.
+			let identifier = match (payment_hash, preimage, offer_id) {
+				(Some(hash), _, _) => find_payments_request::Identifier::PaymentHash(hash),
+				(_, Some(preimage), _) => find_payments_request::Identifier::Preimage(preimage),
+				(_, _, Some(offer_id)) => find_payments_request::Identifier::OfferId(offer_id),
+				(None, None, None) => unreachable!(),
+			};
 
 			handle_response_result::<_, CliListPaymentsResponse>(
 				fetch_paginated(
 					number_of_payments,
 					page_token,
-					|pt| client.list_payments(ListPaymentsRequest { page_token: pt }),
+					|pt| {
+						client.find_payments(FindPaymentsRequest {
+							identifier: Some(identifier.clone()),
+							page_token: pt,
+						})
+					},
 					|r| (r.payments, r.next_page_token),
 				)
 				.await,
@@ -1064,6 +1726,53 @@ async fn main() {
 				client.get_payment_details(GetPaymentDetailsRequest { payment_id }).await,
 			);
 		},
+		Commands::ListInvoices { number_of_invoices, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListInvoicesResponse>(
+				fetch_paginated(
+					number_of_invoices,
+					page_token,
+					|pt| client.list_invoices(ListInvoicesRequest { page_token: pt }),
+					|r| (r.invoices, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::ListOffers { number_of_offers, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListOffersResponse>(
+				fetch_paginated(
+					number_of_offers,
+					page_token,
+					|pt| client.list_offers(ListOffersRequest { page_token: pt }),
+					|r| (r.offers, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::UpdateLabel { payment_id, user_channel_id, label, metadata } => {
+			// Clap ensures exactly one target is given.
+			let target = match (payment_id, user_channel_id) {
+				(Some(payment_id), _) => update_label_request::Target::PaymentId(payment_id),
+				(None, Some(user_channel_id)) => {
+					update_label_request::Target::UserChannelId(user_channel_id)
+				},
+				(None, None) => unreachable!(),
+			};
+			handle_response_result::<_, UpdateLabelResponse>(
+				client
+					.update_label(UpdateLabelRequest {
+						target: Some(target),
+						label,
+						metadata: metadata.into_iter().collect(),
+					})
+					.await,
+			);
+		},
 		Commands::ListForwardedPayments { number_of_payments, page_token } => {
 			let page_token = page_token
 				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
@@ -1159,6 +1868,109 @@ async fn main() {
 				),
 			);
 		},
+		Commands::EstimateRouteFee {
+			destination,
+			amount,
+			max_total_routing_fee,
+			max_total_cltv_expiry_delta,
+			max_path_count,
+			max_channel_saturation_power_of_half,
+		} => {
+			let destination = if is_node_id(&destination) {
+				estimate_route_fee_request::Destination::NodeId(destination)
+			} else {
+				estimate_route_fee_request::Destination::Invoice(destination)
+			};
+			let max_total_routing_fee_msat = max_total_routing_fee.map(|a| a.to_msat());
+			let route_parameters = RouteParametersConfig {
+				max_total_routing_fee_msat,
+				max_total_cltv_expiry_delta: max_total_cltv_expiry_delta
+					.unwrap_or(DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA),
+				max_path_count: max_path_count.unwrap_or(DEFAULT_MAX_PATH_COUNT),
+				max_channel_saturation_power_of_half: max_channel_saturation_power_of_half
+					.unwrap_or(DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF),
+			};
+			handle_response_result::<_, EstimateRouteFeeResponse>(
+				client
+					.estimate_route_fee(EstimateRouteFeeRequest {
+						destination: Some(destination),
+						amount_msat: amount.map(|a| a.to_msat()),
+						route_parameters: Some(route_parameters),
+					})
+					.await,
+			);
+		},
+		Commands::QueryRoutes {
+			destination,
+			amount,
+			max_total_routing_fee,
+			max_total_cltv_expiry_delta,
+			max_path_count,
+			max_channel_saturation_power_of_half,
+		} => {
+			let destination = if is_node_id(&destination) {
+				query_routes_request::Destination::NodeId(destination)
+			} else {
+				query_routes_request::Destination::Invoice(destination)
+			};
+			let max_total_routing_fee_msat = max_total_routing_fee.map(|a| a.to_msat());
+			let route_parameters = RouteParametersConfig {
+				max_total_routing_fee_msat,
+				max_total_cltv_expiry_delta: max_total_cltv_expiry_delta
+					.unwrap_or(DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA),
+				max_path_count: max_path_count.unwrap_or(DEFAULT_MAX_PATH_COUNT),
+				max_channel_saturation_power_of_half: max_channel_saturation_power_of_half
+					.unwrap_or(DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF),
+			};
+			handle_response_result::<_, QueryRoutesResponse>(
+				client
+					.query_routes(QueryRoutesRequest {
+						destination: Some(destination),
+						amount_msat: amount.map(|a| a.to_msat()),
+						route_parameters: Some(route_parameters),
+					})
+					.await,
+			);
+		},
+		Commands::SendProbes {
+			destination,
+			amount,
+			timeout_secs,
+			max_total_routing_fee,
+			max_total_cltv_expiry_delta,
+			max_path_count,
+			max_channel_saturation_power_of_half,
+		} => {
+			let has_route_parameters = max_total_routing_fee.is_some()
+				|| max_total_cltv_expiry_delta.is_some()
+				|| max_path_count.is_some()
+				|| max_channel_saturation_power_of_half.is_some();
+			// Route parameters are only supported for invoices, so they are only set for a node if
+			// given, to surface the error.
+			let (destination, route_parameters) = if is_node_id(&destination) {
+				(send_probes_request::Destination::NodeId(destination), has_route_parameters)
+			} else {
+				(send_probes_request::Destination::Invoice(destination), true)
+			};
+			let route_parameters = route_parameters.then(|| RouteParametersConfig {
+				max_total_routing_fee_msat: max_total_routing_fee.map(|a| a.to_msat()),
+				max_total_cltv_expiry_delta: max_total_cltv_expiry_delta
+					.unwrap_or(DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA),
+				max_path_count: max_path_count.unwrap_or(DEFAULT_MAX_PATH_COUNT),
+				max_channel_saturation_power_of_half: max_channel_saturation_power_of_half
+					.unwrap_or(DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF),
+			});
+			handle_response_result::<_, SendProbesResponse>(
+				client
+					.send_probes(SendProbesRequest {
+						destination: Some(destination),
+						amount_msat: amount.map(|a| a.to_msat()),
+						route_parameters,
+						timeout_secs,
+					})
+					.await,
+			);
+		},
 		Commands::GraphListChannels => {
 			handle_response_result::<_, GraphListChannelsResponse>(
 				client.graph_list_channels(GraphListChannelsRequest {}).await,
@@ -1179,6 +1991,105 @@ async fn main() {
 				client.graph_get_node(GraphGetNodeRequest { node_id }).await,
 			);
 		},
+		Commands::CreateApiKey { name, scope, max_send_amount } => {
+			handle_response_result::<_, CreateApiKeyResponse>(
+				client
+					.create_api_key(CreateApiKeyRequest {
+						name,
+						scope: scope.into(),
+						max_send_amount_msat: max_send_amount.map(|a| a.to_msat()),
+					})
+					.await,
+			);
+		},
+		Commands::ListApiKeys => {
+			handle_response_result::<_, ListApiKeysResponse>(
+				client.list_api_keys(ListApiKeysRequest {}).await,
+			);
+		},
+		Commands::RotateApiKey { name, grace_period_secs } => {
+			handle_response_result::<_, RotateApiKeyResponse>(
+				client.rotate_api_key(RotateApiKeyRequest { name, grace_period_secs }).await,
+			);
+		},
+		Commands::RevokeApiKey { name } => {
+			handle_response_result::<_, RevokeApiKeyResponse>(
+				client.revoke_api_key(RevokeApiKeyRequest { name }).await,
+			);
+		},
+		Commands::ListAuditLog { number_of_entries, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListAuditLogResponse>(
+				fetch_paginated(
+					number_of_entries,
+					page_token,
+					|pt| client.list_audit_log(ListAuditLogRequest { page_token: pt }),
+					|r| (r.entries, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::ApproveAction { id } => {
+			handle_response_result::<_, ApproveActionResponse>(
+				client.approve_action(ApproveActionRequest { id }).await,
+			);
+		},
+		Commands::RejectAction { id, reason } => {
+			handle_response_result::<_, RejectActionResponse>(
+				client.reject_action(RejectActionRequest { id, reason }).await,
+			);
+		},
+		Commands::ListPendingActions { number_of_actions, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListPendingActionsResponse>(
+				fetch_paginated(
+					number_of_actions,
+					page_token,
+					|pt| client.list_pending_actions(ListPendingActionsRequest { page_token: pt }),
+					|r| (r.actions, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::Unlock { passphrase } => {
+			let passphrase = passphrase.unwrap_or_else(read_passphrase);
+			handle_response_result::<_, UnlockResponse>(
+				client.unlock(UnlockRequest { passphrase }).await,
+			);
+		},
+		Commands::EncryptMnemonic { passphrase } => {
+			let passphrase = passphrase.unwrap_or_else(read_passphrase);
+			handle_response_result::<_, EncryptMnemonicResponse>(
+				client.encrypt_mnemonic(EncryptMnemonicRequest { passphrase }).await,
+			);
+		},
+		Commands::ListFailedWebhookDeliveries { number_of_deliveries, page_token } => {
+			let page_token = page_token
+				.map(|token_str| parse_page_token(&token_str).unwrap_or_else(|e| handle_error(e)));
+
+			handle_response_result::<_, CliListFailedWebhookDeliveriesResponse>(
+				fetch_paginated(
+					number_of_deliveries,
+					page_token,
+					|pt| {
+						client.list_failed_webhook_deliveries(ListFailedWebhookDeliveriesRequest {
+							page_token: pt,
+						})
+					},
+					|r| (r.deliveries, r.next_page_token),
+				)
+				.await,
+			);
+		},
+		Commands::RedeliverWebhook { delivery_id } => {
+			handle_response_result::<_, RedeliverWebhookResponse>(
+				client.redeliver_webhook(RedeliverWebhookRequest { delivery_id }).await,
+			);
+		},
 		Commands::Completions { .. } => unreachable!("Handled above"),
 	}
 }
@@ -1298,6 +2209,11 @@ where
 	}
 }
 
+/// Whether `destination` is a hex-encoded public key rather than a BOLT11 invoice.
+fn is_node_id(destination: &str) -> bool {
+	<[u8; 33]>::from_hex(destination).is_ok()
+}
+
 fn parse_bolt11_invoice_description(
 	description: Option<String>, description_hash: Option<String>,
 ) -> Option<Bolt11InvoiceDescription> {
@@ -1345,6 +2261,51 @@ fn parse_custom_tlv(s: &str) -> Result<(u64, Vec<u8>), String> {
 	Ok((type_num, value))
 }
 
+fn parse_metadata_entry(s: &str) -> Result<(String, String), String> {
+	let (key, value) =
+		s.split_once('=').ok_or_else(|| format!("expected <key>=<value>, got '{s}'"))?;
+	if key.is_empty() {
+		return Err(format!("metadata key must not be empty, got '{s}'"));
+	}
+	Ok((key.to_string(), value.to_string()))
+}
+
+fn parse_api_key_scope(s: &str) -> Result<ApiKeyScope, String> {
+	ApiKeyScope::from_str_name(&s.replace('-', "_").to_ascii_uppercase()).ok_or_else(|| {
+		format!("unknown scope '{s}', expected one of read_only, invoice_only, send or admin")
+	})
+}
+
+fn parse_payment_direction(s: &str) -> Result<PaymentDirection, String> {
+	PaymentDirection::from_str_name(&s.to_ascii_uppercase())
+		.ok_or_else(|| format!("unknown direction '{s}', expected inbound or outbound"))
+}
+
+fn parse_payment_status(s: &str) -> Result<PaymentStatus, String> {
+	PaymentStatus::from_str_name(&s.to_ascii_uppercase())
+		.ok_or_else(|| format!("unknown status '{s}', expected pending, succeeded or failed"))
+}
+
+fn parse_payment_kind(s: &str) -> Result<PaymentKindType, String> {
+	let name = format!("PAYMENT_KIND_TYPE_{}", s.replace('-', "_").to_ascii_uppercase());
+	PaymentKindType::from_str_name(&name).ok_or_else(|| {
+		format!(
+			"unknown kind '{s}', expected one of onchain, bolt11, bolt12_offer, bolt12_refund or spontaneous"
+		)
+	})
+}
+
+/// Reads a passphrase from the first line of standard input, so it doesn't end up in the shell
+/// history.
+fn read_passphrase() -> String {
+	eprint!("Passphrase: ");
+	let mut passphrase = String::new();
+	if let Err(e) = std::io::stdin().read_line(&mut passphrase) {
+		handle_error_msg(format!("Failed to read passphrase: {e}"));
+	}
+	passphrase.trim_end_matches(['\r', '\n']).to_string()
+}
+
 fn handle_error_msg(msg: String) -> ! {
 	eprintln!("Error: {}", sanitize_for_terminal(msg));
 	std::process::exit(1);
@@ -1356,6 +2317,8 @@ fn handle_error(e: LdkServerError) -> ! {
 		AuthError => "Authentication Error",
 		LightningError => "Lightning Error",
 		InternalServerError => "Internal Server Error",
+		PermissionDeniedError => "Permission Denied",
+		SpendLimitExceededError => "Spend Limit Exceeded",
 		InternalError => "Internal Error",
 	};
 	eprintln!("Error ({}): {}", error_type, e.message);
@@ -1390,4 +2353,32 @@ mod tests {
 		let err = parse_custom_tlv("65537:not-hex").unwrap_err();
 		assert!(err.contains("invalid hex value"));
 	}
+
+	#[test]
+	fn parse_metadata_entry_splits_at_first_separator() {
+		let (key, value) = parse_metadata_entry("order=42=a").unwrap();
+		assert_eq!(key, "order");
+		assert_eq!(value, "42=a");
+		assert_eq!(parse_metadata_entry("order=").unwrap(), ("order".to_string(), String::new()));
+		assert!(parse_metadata_entry("order").is_err());
+		assert!(parse_metadata_entry("=42").is_err());
+	}
+
+	#[test]
+	fn parse_api_key_scope_accepts_known_scopes() {
+		assert_eq!(parse_api_key_scope("read_only").unwrap(), ApiKeyScope::ReadOnly);
+		assert_eq!(parse_api_key_scope("invoice-only").unwrap(), ApiKeyScope::InvoiceOnly);
+		assert_eq!(parse_api_key_scope("SEND").unwrap(), ApiKeyScope::Send);
+		assert!(parse_api_key_scope("everything").is_err());
+	}
+
+	#[test]
+	fn parse_payment_filters_accepts_known_values() {
+		assert_eq!(parse_payment_direction("inbound").unwrap(), PaymentDirection::Inbound);
+		assert_eq!(parse_payment_status("Succeeded").unwrap(), PaymentStatus::Succeeded);
+		assert_eq!(parse_payment_kind("bolt12-offer").unwrap(), PaymentKindType::Bolt12Offer);
+		assert_eq!(parse_payment_kind("onchain").unwrap(), PaymentKindType::Onchain);
+		assert!(parse_payment_direction("sideways").is_err());
+		assert!(parse_payment_kind("bolt11_jit").is_err());
+	}
 }
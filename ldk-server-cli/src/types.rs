@@ -17,7 +17,11 @@ use std::fmt;
 use std::str::FromStr;
 
 use hex_conservative::{DisplayHex, FromHex};
-use ldk_server_client::ldk_server_grpc::types::{ForwardedPayment, PageToken, Payment};
+use ldk_server_client::ldk_server_grpc::events::WebhookDelivery;
+use ldk_server_client::ldk_server_grpc::types::{
+	AuditLogEntry, ClaimablePayment, ClosedChannel, ForwardedPayment, Invoice, Offer,
+	OnchainTransaction, PageToken, Payment, PendingAction,
+};
 use serde::Serialize;
 
 /// CLI-specific wrapper for paginated responses that formats the page token
@@ -38,7 +42,15 @@ impl<T> CliPaginatedResponse<T> {
 }
 
 pub type CliListPaymentsResponse = CliPaginatedResponse<Payment>;
+pub type CliListOnchainTransactionsResponse = CliPaginatedResponse<OnchainTransaction>;
 pub type CliListForwardedPaymentsResponse = CliPaginatedResponse<ForwardedPayment>;
+pub type CliListAuditLogResponse = CliPaginatedResponse<AuditLogEntry>;
+pub type CliListPendingActionsResponse = CliPaginatedResponse<PendingAction>;
+pub type CliListInvoicesResponse = CliPaginatedResponse<Invoice>;
+pub type CliListOffersResponse = CliPaginatedResponse<Offer>;
+pub type CliListClaimablePaymentsResponse = CliPaginatedResponse<ClaimablePayment>;
+pub type CliListClosedChannelsResponse = CliPaginatedResponse<ClosedChannel>;
+pub type CliListFailedWebhookDeliveriesResponse = CliPaginatedResponse<WebhookDelivery>;
 
 fn format_page_token(token: PageToken) -> String {
 	format!("{}:{}", token.token, token.index)
@@ -141,6 +141,12 @@ pub fn resolve_base_url(override_url: Option<String>, config: Option<&Config>) -
 		.unwrap_or_else(default_grpc_service_address)
 }
 
+/// Returns whether `base_url` points to a Unix socket of the server, e.g.
+/// `unix:///run/ldk-server/grpc.sock`.
+pub fn is_unix_socket_url(base_url: &str) -> bool {
+	base_url.starts_with("unix://")
+}
+
 /// Resolves the API key used to authenticate against the `ldk-server` gRPC endpoint.
 ///
 /// Prefers `override_key`, falls back to reading the API key file from the configured storage
@@ -187,7 +193,7 @@ fn default_grpc_service_address() -> String {
 
 #[cfg(test)]
 mod tests {
-	use super::{resolve_base_url, Config, DEFAULT_GRPC_SERVICE_ADDRESS};
+	use super::{is_unix_socket_url, resolve_base_url, Config, DEFAULT_GRPC_SERVICE_ADDRESS};
 
 	#[test]
 	fn config_defaults_grpc_service_address() {
@@ -282,4 +288,11 @@ mod tests {
 	fn resolve_base_url_falls_back_to_default() {
 		assert_eq!(resolve_base_url(None, None), DEFAULT_GRPC_SERVICE_ADDRESS);
 	}
+
+	#[test]
+	fn is_unix_socket_url_detects_socket_paths() {
+		assert!(is_unix_socket_url("unix:///run/ldk-server/grpc.sock"));
+		assert!(!is_unix_socket_url(DEFAULT_GRPC_SERVICE_ADDRESS));
+		assert!(!is_unix_socket_url("localhost:3536"));
+	}
 }
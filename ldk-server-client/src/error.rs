@@ -53,6 +53,12 @@ pub enum LdkServerErrorCode {
 	/// Please refer to [`ldk_server_grpc::error::ErrorCode::InternalServerError`].
 	InternalServerError,
 
+	/// Please refer to [`ldk_server_grpc::error::ErrorCode::PermissionDeniedError`].
+	PermissionDeniedError,
+
+	/// Please refer to [`ldk_server_grpc::error::ErrorCode::SpendLimitExceededError`].
+	SpendLimitExceededError,
+
 	/// There is an unknown error, it could be a client-side bug, unrecognized error-code, network error
 	/// or something else.
 	InternalError,
@@ -65,6 +71,8 @@ impl fmt::Display for LdkServerErrorCode {
 			LdkServerErrorCode::AuthError => write!(f, "AuthError"),
 			LdkServerErrorCode::LightningError => write!(f, "LightningError"),
 			LdkServerErrorCode::InternalServerError => write!(f, "InternalServerError"),
+			LdkServerErrorCode::PermissionDeniedError => write!(f, "PermissionDeniedError"),
+			LdkServerErrorCode::SpendLimitExceededError => write!(f, "SpendLimitExceededError"),
 			LdkServerErrorCode::InternalError => write!(f, "InternalError"),
 		}
 	}
@@ -22,6 +22,9 @@ pub mod config;
 /// Implements the error type ([`LdkServerError`](error::LdkServerError)) returned on interacting with [`LdkServerClient`](client::LdkServerClient).
 pub mod error;
 
+#[cfg(unix)]
+mod unix;
+
 /// Request/Response structs required for interacting with the client.
 pub use ldk_server_grpc;
 
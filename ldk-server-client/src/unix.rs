@@ -0,0 +1,82 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::future::Future;
+use std::io;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use hyper::client::connect::{Connected, Connection};
+use hyper::service::Service;
+use hyper::Uri;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tokio::net::UnixStream;
+
+/// A hyper connector that connects to the Unix socket at a fixed path, regardless of the host of
+/// the requested URI.
+#[derive(Clone)]
+pub(crate) struct UnixConnector {
+	path: Arc<Path>,
+}
+
+impl UnixConnector {
+	pub(crate) fn new(path: &str) -> Self {
+		Self { path: Path::new(path).into() }
+	}
+}
+
+impl Service<Uri> for UnixConnector {
+	type Response = UnixConnection;
+	type Error = io::Error;
+	type Future = Pin<Box<dyn Future<Output = io::Result<UnixConnection>> + Send>>;
+
+	fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Poll::Ready(Ok(()))
+	}
+
+	fn call(&mut self, _uri: Uri) -> Self::Future {
+		let path = Arc::clone(&self.path);
+		Box::pin(async move { UnixStream::connect(&*path).await.map(UnixConnection) })
+	}
+}
+
+/// A connection established by a [`UnixConnector`].
+pub(crate) struct UnixConnection(UnixStream);
+
+impl Connection for UnixConnection {
+	fn connected(&self) -> Connected {
+		Connected::new()
+	}
+}
+
+impl AsyncRead for UnixConnection {
+	fn poll_read(
+		mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>,
+	) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.0).poll_read(cx, buf)
+	}
+}
+
+impl AsyncWrite for UnixConnection {
+	fn poll_write(
+		mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8],
+	) -> Poll<io::Result<usize>> {
+		Pin::new(&mut self.0).poll_write(cx, buf)
+	}
+
+	fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.0).poll_flush(cx)
+	}
+
+	fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+		Pin::new(&mut self.0).poll_shutdown(cx)
+	}
+}
@@ -41,6 +41,20 @@ fn generate_protos() {
 			"api.DecodeInvoiceResponse.features",
 			"api.DecodeOfferResponse.features",
 			"types.GraphNodeAnnouncement.features",
+			"types.Payment.metadata",
+			"types.OnchainTransaction.metadata",
+			"types.Channel.metadata",
+			"types.Invoice.metadata",
+			"types.UserLabel.metadata",
+			"api.Bolt11ReceiveRequest.metadata",
+			"api.Bolt11SendRequest.metadata",
+			"api.Bolt12SendRequest.metadata",
+			"api.OnchainSendRequest.metadata",
+			"api.OpenChannelRequest.metadata",
+			"api.UpdateLabelRequest.metadata",
+			"api.UpdateLabelResponse.metadata",
+			"events.ChannelStateChanged.metadata",
+			"types.ClosedChannel.metadata",
 		])
 		.type_attribute(
 			".",
@@ -64,6 +78,10 @@ fn generate_protos() {
 			"types.Payment.direction",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_payment_direction\"))]",
 		)
+		.field_attribute(
+			"types.OnchainTransaction.direction",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_payment_direction\"))]",
+		)
 		.field_attribute(
 			"types.Payment.status",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_payment_status\"))]",
@@ -80,6 +98,54 @@ fn generate_protos() {
 			"types.DirectedShortChannelId.direction",
 			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_direction\"))]",
 		)
+		.field_attribute(
+			"types.ApiKeyInfo.scope",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_api_key_scope\"))]",
+		)
+		.field_attribute(
+			"types.AuditLogEntry.outcome",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_audit_outcome\"))]",
+		)
+		.field_attribute(
+			"types.PendingAction.status",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_pending_action_status\"))]",
+		)
+		.field_attribute(
+			"types.Invoice.status",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_invoice_status\"))]",
+		)
+		.field_attribute(
+			"types.Offer.status",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_invoice_status\"))]",
+		)
+		.field_attribute(
+			"types.ClosedChannel.state",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_state\"))]",
+		)
+		.field_attribute(
+			"types.ClosedChannel.closure_initiator",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_closure_initiator\"))]",
+		)
+		.field_attribute(
+			"events.ChannelStateChanged.state",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_state\"))]",
+		)
+		.field_attribute(
+			"events.ChannelStateChanged.closure_initiator",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_closure_initiator\"))]",
+		)
+		.field_attribute(
+			"types.ProbeResult.estimated_status",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_probe_status\"))]",
+		)
+		.field_attribute(
+			"events.WebhookDelivery.status",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_webhook_delivery_status\"))]",
+		)
+		.field_attribute(
+			"types.ChannelStateChangeReason.kind",
+			"#[cfg_attr(feature = \"serde\", serde(serialize_with = \"crate::serde_utils::serialize_channel_state_change_reason_kind\"))]",
+		)
 		.field_attribute(
 			"api.UnifiedSendResponse.payment_result",
 			"#[cfg_attr(feature = \"serde\", serde(flatten))]",
@@ -88,6 +88,11 @@ pub struct GetNodeInfoResponse {
 	/// Features advertised by this node, keyed by the signaled BOLT feature bit.
 	#[prost(btree_map = "uint32, message", tag = "14")]
 	pub features: ::prost::alloc::collections::BTreeMap<u32, super::types::Feature>,
+	/// Whether the server is waiting for its encrypted mnemonic to be unlocked via the `Unlock` RPC.
+	///
+	/// While locked, the node isn't running yet and only `network` is set.
+	#[prost(bool, tag = "15")]
+	pub locked: bool,
 }
 /// Retrieve a new on-chain funding address.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/payment/struct.OnchainPayment.html#method.new_address>
@@ -123,6 +128,16 @@ pub struct OnchainSendRequest {
 	/// a reasonable estimate from BitcoinD.
 	#[prost(uint64, optional, tag = "4")]
 	pub fee_rate_sat_per_vb: ::core::option::Option<u64>,
+	/// An optional label to attach to the payment, returned along with it by the list and get RPCs and
+	/// in events. It can be changed later via `UpdateLabel`.
+	#[prost(string, optional, tag = "5")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata to attach to the payment, see `label`.
+	#[prost(btree_map = "string, string", tag = "6")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 	/// Required. The amount to send.
 	#[prost(oneof = "onchain_send_request::Amount", tags = "2, 3")]
 	pub amount: ::core::option::Option<onchain_send_request::Amount>,
@@ -153,6 +168,61 @@ pub struct OnchainSendResponse {
 	/// The transaction ID of the broadcasted transaction.
 	#[prost(string, tag = "1")]
 	pub txid: ::prost::alloc::string::String,
+	/// The identifier of the pending action, if the send exceeded the approval threshold and was
+	/// parked instead of being executed. The other fields are unset in that case.
+	#[prost(string, optional, tag = "2")]
+	pub pending_action_id: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// Retrieves the transactions of the on-chain wallet, as tracked by LDK Node's BDK wallet.
+/// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.list_payments_with_filter>
+///
+/// Transactions are ordered by the time the server first saw them, most recent first, so the order
+/// doesn't change when they confirm. The server records the wallet's transactions when it starts
+/// and whenever the wallet synced, so transactions received by the wallet are listed once it synced,
+/// while those sent via `OnchainSend` or `UnifiedSend` are listed right away.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOnchainTransactionsRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response, along with the same filters.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+	/// Only return transactions in this direction, i.e. receiving to or sending from the wallet.
+	#[prost(enumeration = "super::types::PaymentDirection", optional, tag = "2")]
+	pub direction: ::core::option::Option<i32>,
+	/// The maximum number of transactions to return per page. Defaults to 100, values above 1000 are
+	/// lowered to 1000. Must not be 0.
+	#[prost(uint32, optional, tag = "3")]
+	pub page_size: ::core::option::Option<u32>,
+}
+/// The response for the `ListOnchainTransactions` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOnchainTransactionsResponse {
+	/// List of on-chain transactions.
+	#[prost(message, repeated, tag = "1")]
+	pub transactions: ::prost::alloc::vec::Vec<super::types::OnchainTransaction>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
 }
 /// Return a BOLT11 payable invoice that can be used to request and receive a payment
 /// for the given amount, if specified.
@@ -176,6 +246,17 @@ pub struct Bolt11ReceiveRequest {
 	/// Invoice expiry time in seconds.
 	#[prost(uint32, tag = "3")]
 	pub expiry_secs: u32,
+	/// An optional label to record the invoice with, see `ListInvoices`. The label is only known to the server and
+	/// is not included in the invoice. It is also attached to the payment for the invoice, and can be changed later
+	/// via `UpdateLabel`.
+	#[prost(string, optional, tag = "4")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata to record the invoice with, see `label`.
+	#[prost(btree_map = "string, string", tag = "5")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
 /// The response for the `Bolt11Receive` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -222,6 +303,15 @@ pub struct Bolt11ReceiveForHashRequest {
 	/// The hex-encoded 32-byte payment hash to use for the invoice.
 	#[prost(string, tag = "4")]
 	pub payment_hash: ::prost::alloc::string::String,
+	/// An optional label to record the invoice with, see `ListInvoices`. The label is only known to the server and
+	/// is not included in the invoice.
+	#[prost(string, optional, tag = "5")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// The hex-encoded 32-byte preimage of `payment_hash`. If set, a payment for the invoice that is still
+	/// claimable when its claim deadline approaches is claimed with this preimage instead of failed back, see
+	/// `ListClaimablePayments`.
+	#[prost(string, optional, tag = "6")]
+	pub auto_claim_preimage: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// The response for the `Bolt11ReceiveForHash` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -306,6 +396,10 @@ pub struct Bolt11ReceiveViaJitChannelRequest {
 	/// Optional upper bound for the total fee an LSP may deduct when opening the JIT channel.
 	#[prost(uint64, optional, tag = "4")]
 	pub max_total_lsp_fee_limit_msat: ::core::option::Option<u64>,
+	/// An optional label to record the invoice with, see `ListInvoices`. The label is only known to the server and
+	/// is not included in the invoice.
+	#[prost(string, optional, tag = "5")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// The response for the `Bolt11ReceiveViaJitChannel` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -338,6 +432,10 @@ pub struct Bolt11ReceiveVariableAmountViaJitChannelRequest {
 	/// LSP may deduct when opening the JIT channel.
 	#[prost(uint64, optional, tag = "3")]
 	pub max_proportional_lsp_fee_limit_ppm_msat: ::core::option::Option<u64>,
+	/// An optional label to record the invoice with, see `ListInvoices`. The label is only known to the server and
+	/// is not included in the invoice.
+	#[prost(string, optional, tag = "4")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// The response for the `Bolt11ReceiveVariableAmountViaJitChannel` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -369,6 +467,16 @@ pub struct Bolt11SendRequest {
 	/// Configuration options for payment routing and pathfinding.
 	#[prost(message, optional, tag = "3")]
 	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// An optional label to attach to the payment, returned along with it by the list and get RPCs and
+	/// in events. It can be changed later via `UpdateLabel`.
+	#[prost(string, optional, tag = "4")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata to attach to the payment, see `label`.
+	#[prost(btree_map = "string, string", tag = "5")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
 /// The response for the `Bolt11Send` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -380,6 +488,10 @@ pub struct Bolt11SendResponse {
 	/// An identifier used to uniquely identify a payment in hex-encoded form.
 	#[prost(string, tag = "1")]
 	pub payment_id: ::prost::alloc::string::String,
+	/// The identifier of the pending action, if the send exceeded the approval threshold and was
+	/// parked instead of being executed. The other fields are unset in that case.
+	#[prost(string, optional, tag = "2")]
+	pub pending_action_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Send part of the amount for a fixed-amount BOLT11 invoice.
 /// Other nodes must send partial payments for the same invoice until the combined amount equals the invoice amount.
@@ -411,6 +523,10 @@ pub struct Bolt11SendUnderpayingResponse {
 	/// An identifier used to uniquely identify a payment in hex-encoded form.
 	#[prost(string, tag = "1")]
 	pub payment_id: ::prost::alloc::string::String,
+	/// The identifier of the pending action, if the send exceeded the approval threshold and was
+	/// parked instead of being executed. The other fields are unset in that case.
+	#[prost(string, optional, tag = "2")]
+	pub pending_action_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Returns a BOLT12 offer for the given amount, if specified.
 ///
@@ -436,6 +552,10 @@ pub struct Bolt12ReceiveRequest {
 	/// If set, it represents the number of items requested, can only be set for fixed-amount offers.
 	#[prost(uint64, optional, tag = "4")]
 	pub quantity: ::core::option::Option<u64>,
+	/// An optional label to record the offer with, see `ListOffers`. The label is only known to the server and
+	/// is not included in the offer.
+	#[prost(string, optional, tag = "5")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// The response for the `Bolt12Receive` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -480,6 +600,16 @@ pub struct Bolt12SendRequest {
 	/// Configuration options for payment routing and pathfinding.
 	#[prost(message, optional, tag = "5")]
 	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// An optional label to attach to the payment, returned along with it by the list and get RPCs and
+	/// in events. It can be changed later via `UpdateLabel`.
+	#[prost(string, optional, tag = "6")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata to attach to the payment, see `label`.
+	#[prost(btree_map = "string, string", tag = "7")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
 /// The response for the `Bolt12Send` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -491,6 +621,10 @@ pub struct Bolt12SendResponse {
 	/// An identifier used to uniquely identify a payment in hex-encoded form.
 	#[prost(string, tag = "1")]
 	pub payment_id: ::prost::alloc::string::String,
+	/// The identifier of the pending action, if the send exceeded the approval threshold and was
+	/// parked instead of being executed. The other fields are unset in that case.
+	#[prost(string, optional, tag = "2")]
+	pub pending_action_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Send a spontaneous payment, also known as "keysend", to a node.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/payment/struct.SpontaneousPayment.html#method.send>
@@ -527,6 +661,10 @@ pub struct SpontaneousSendResponse {
 	/// An identifier used to uniquely identify a payment in hex-encoded form.
 	#[prost(string, tag = "1")]
 	pub payment_id: ::prost::alloc::string::String,
+	/// The identifier of the pending action, if the send exceeded the approval threshold and was
+	/// parked instead of being executed. The other fields are unset in that case.
+	#[prost(string, optional, tag = "2")]
+	pub pending_action_id: ::core::option::Option<::prost::alloc::string::String>,
 }
 /// Selects all available on-chain funds.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -562,6 +700,16 @@ pub struct OpenChannelRequest {
 	/// Allow the counterparty to spend all its channel balance. This cannot be set together with `announce_channel`.
 	#[prost(bool, tag = "7")]
 	pub disable_counterparty_reserve: bool,
+	/// An optional label to attach to the channel, returned along with it by the list and get RPCs and
+	/// in events. It can be changed later via `UpdateLabel`.
+	#[prost(string, optional, tag = "9")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata to attach to the channel, see `label`.
+	#[prost(btree_map = "string, string", tag = "10")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 	/// Required. The funds to commit to the channel.
 	#[prost(oneof = "open_channel_request::Amount", tags = "3, 8")]
 	pub amount: ::core::option::Option<open_channel_request::Amount>,
@@ -759,6 +907,48 @@ pub struct ListChannelsResponse {
 	#[prost(message, repeated, tag = "1")]
 	pub channels: ::prost::alloc::vec::Vec<super::types::Channel>,
 }
+/// Lists the channels that were closed, most recently closed first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListClosedChannelsRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListClosedChannels` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListClosedChannelsResponse {
+	/// List of closed channels.
+	#[prost(message, repeated, tag = "1")]
+	pub closed_channels: ::prost::alloc::vec::Vec<super::types::ClosedChannel>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
 /// Returns payment details for a given payment_id.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.payment>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -783,8 +973,11 @@ pub struct GetPaymentDetailsResponse {
 	#[prost(message, optional, tag = "1")]
 	pub payment: ::core::option::Option<super::types::Payment>,
 }
-/// Retrieves list of all payments.
+/// Retrieves list of all payments, optionally filtered.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.list_payments>
+///
+/// Filters that are set must all match. Payments are ordered by the time the server first recorded
+/// them, most recent first.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
@@ -796,9 +989,40 @@ pub struct ListPaymentsRequest {
 	/// To query for the first page, `page_token` must not be specified.
 	///
 	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
-	/// page's response.
+	/// page's response, along with the same filters.
 	#[prost(message, optional, tag = "1")]
 	pub page_token: ::core::option::Option<super::types::PageToken>,
+	/// Only return payments in this direction.
+	#[prost(enumeration = "super::types::PaymentDirection", optional, tag = "2")]
+	pub direction: ::core::option::Option<i32>,
+	/// Only return payments with this status.
+	#[prost(enumeration = "super::types::PaymentStatus", optional, tag = "3")]
+	pub status: ::core::option::Option<i32>,
+	/// Only return payments of this kind.
+	#[prost(enumeration = "super::types::PaymentKindType", optional, tag = "4")]
+	pub kind: ::core::option::Option<i32>,
+	/// Only return payments of at least this amount. Payments of unknown amount are excluded if set.
+	#[prost(uint64, optional, tag = "5")]
+	pub min_amount_msat: ::core::option::Option<u64>,
+	/// Only return payments of at most this amount. Payments of unknown amount are excluded if set.
+	#[prost(uint64, optional, tag = "6")]
+	pub max_amount_msat: ::core::option::Option<u64>,
+	/// Only return payments first recorded at or after this time, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "7")]
+	pub created_since: ::core::option::Option<u64>,
+	/// Only return payments first recorded before this time, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "8")]
+	pub created_before: ::core::option::Option<u64>,
+	/// Only return payments last updated at or after this time, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "9")]
+	pub updated_since: ::core::option::Option<u64>,
+	/// Only return payments last updated before this time, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "10")]
+	pub updated_before: ::core::option::Option<u64>,
+	/// The maximum number of payments to return per page. Defaults to 100, values above 1000 are
+	/// lowered to 1000. Must not be 0.
+	#[prost(uint32, optional, tag = "11")]
+	pub page_size: ::core::option::Option<u32>,
 }
 /// The response for the `ListPayments` RPC. On failure, a gRPC error status is returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -826,6 +1050,245 @@ pub struct ListPaymentsResponse {
 	#[prost(message, optional, tag = "2")]
 	pub next_page_token: ::core::option::Option<super::types::PageToken>,
 }
+/// Finds the payments with a given payment hash or preimage, or that were made for a given BOLT 12
+/// offer. Payments are ordered by the time the server first recorded them, most recent first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindPaymentsRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response, along with the same identifier.
+	#[prost(message, optional, tag = "4")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+	/// The identifier to find payments by, in hex-encoded form.
+	#[prost(oneof = "find_payments_request::Identifier", tags = "1, 2, 3")]
+	pub identifier: ::core::option::Option<find_payments_request::Identifier>,
+}
+/// Nested message and enum types in `FindPaymentsRequest`.
+pub mod find_payments_request {
+	/// The identifier to find payments by, in hex-encoded form.
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Identifier {
+		/// The payment hash, shared by all payment attempts for the same invoice.
+		#[prost(string, tag = "1")]
+		PaymentHash(::prost::alloc::string::String),
+		/// The payment preimage, which is only known once a payment succeeded.
+		#[prost(string, tag = "2")]
+		Preimage(::prost::alloc::string::String),
+		/// The id of the BOLT 12 offer the payments were made for.
+		#[prost(string, tag = "3")]
+		OfferId(::prost::alloc::string::String),
+	}
+}
+/// The response for the `FindPayments` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FindPaymentsResponse {
+	/// The payments that were found, which is empty if no payment matches.
+	#[prost(message, repeated, tag = "1")]
+	pub payments: ::prost::alloc::vec::Vec<super::types::Payment>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Changes the label and metadata attached to a payment or channel.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateLabelRequest {
+	/// If set, replaces the current label. An empty label removes it.
+	#[prost(string, optional, tag = "3")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Metadata entries to add or replace. Entries with an empty value are removed, other entries
+	/// are kept unchanged.
+	#[prost(btree_map = "string, string", tag = "4")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+	/// Required. What to label.
+	#[prost(oneof = "update_label_request::Target", tags = "1, 2")]
+	pub target: ::core::option::Option<update_label_request::Target>,
+}
+/// Nested message and enum types in `UpdateLabelRequest`.
+pub mod update_label_request {
+	/// Required. What to label.
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Target {
+		/// The hex-encoded ID of the payment. Payments for invoices issued by the server have the
+		/// invoice's payment hash as their ID, so invoices are labeled by their payment hash.
+		#[prost(string, tag = "1")]
+		PaymentId(::prost::alloc::string::String),
+		/// The local `user_channel_id` of the channel.
+		#[prost(string, tag = "2")]
+		UserChannelId(::prost::alloc::string::String),
+	}
+}
+/// The response for the `UpdateLabel` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateLabelResponse {
+	/// The label attached to the payment or channel after the update.
+	#[prost(string, optional, tag = "1")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// The metadata attached to the payment or channel after the update.
+	#[prost(btree_map = "string, string", tag = "2")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
+/// Lists the BOLT11 invoices issued by `Bolt11Receive`, `Bolt11ReceiveForHash` and the JIT channel
+/// variants, most recently created first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListInvoicesRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListInvoices` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListInvoicesResponse {
+	/// List of invoices, with their current status.
+	#[prost(message, repeated, tag = "1")]
+	pub invoices: ::prost::alloc::vec::Vec<super::types::Invoice>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Lists the payments for invoices created via `Bolt11ReceiveForHash` that are waiting to be claimed
+/// or failed back, most recently arrived first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListClaimablePaymentsRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListClaimablePayments` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListClaimablePaymentsResponse {
+	/// List of claimable payments.
+	#[prost(message, repeated, tag = "1")]
+	pub claimable_payments: ::prost::alloc::vec::Vec<super::types::ClaimablePayment>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Lists the BOLT12 offers issued by `Bolt12Receive`, most recently created first.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOffersRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListOffers` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListOffersResponse {
+	/// List of offers, with their current status.
+	#[prost(message, repeated, tag = "1")]
+	pub offers: ::prost::alloc::vec::Vec<super::types::Offer>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
 /// Retrieves list of all forwarded payments.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/enum.Event.html#variant.PaymentForwarded>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -940,6 +1403,173 @@ pub struct ExportPathfindingScoresResponse {
 	#[prost(bytes = "bytes", tag = "1")]
 	pub scores: ::prost::bytes::Bytes,
 }
+/// Estimates the routing fees of a payment without sending anything. The route is found like for
+/// `QueryRoutes`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EstimateRouteFeeRequest {
+	/// The amount in millisatoshis to pay. Required for a `node_id` or a variable-amount invoice, and
+	/// overrides the amount of the invoice otherwise.
+	#[prost(uint64, optional, tag = "3")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// Configuration options for payment routing and pathfinding.
+	#[prost(message, optional, tag = "4")]
+	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// Required. The destination of the payment.
+	#[prost(oneof = "estimate_route_fee_request::Destination", tags = "1, 2")]
+	pub destination: ::core::option::Option<estimate_route_fee_request::Destination>,
+}
+/// Nested message and enum types in `EstimateRouteFeeRequest`.
+pub mod estimate_route_fee_request {
+	/// Required. The destination of the payment.
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Destination {
+		/// A BOLT11 invoice, as paid with `Bolt11Send`.
+		#[prost(string, tag = "1")]
+		Invoice(::prost::alloc::string::String),
+		/// The hex-encoded public key of a node, as paid with `SpontaneousSend`.
+		#[prost(string, tag = "2")]
+		NodeId(::prost::alloc::string::String),
+	}
+}
+/// The response for the `EstimateRouteFee` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EstimateRouteFeeResponse {
+	/// The total routing fees of the route, in millisatoshis.
+	#[prost(uint64, tag = "1")]
+	pub fee_msat: u64,
+	/// The total amount that would be sent, including routing fees, in millisatoshis.
+	#[prost(uint64, tag = "2")]
+	pub total_amount_msat: u64,
+	/// The largest total CLTV expiry delta of the route's paths.
+	#[prost(uint32, tag = "3")]
+	pub max_total_cltv_expiry_delta: u32,
+	/// The number of paths the payment would be split into.
+	#[prost(uint32, tag = "4")]
+	pub path_count: u32,
+}
+/// Finds a route for a payment without sending anything.
+///
+/// LDK's router is run against the current network graph and the pathfinding scores learned by
+/// this node, honoring the `route_parameters` like a send does. The route can differ from the one
+/// an actual payment takes, as the scores change over time and failed paths are retried.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryRoutesRequest {
+	/// The amount in millisatoshis to pay. Required for a `node_id` or a variable-amount invoice, and
+	/// overrides the amount of the invoice otherwise.
+	#[prost(uint64, optional, tag = "3")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// Configuration options for payment routing and pathfinding.
+	#[prost(message, optional, tag = "4")]
+	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// Required. The destination of the payment.
+	#[prost(oneof = "query_routes_request::Destination", tags = "1, 2")]
+	pub destination: ::core::option::Option<query_routes_request::Destination>,
+}
+/// Nested message and enum types in `QueryRoutesRequest`.
+pub mod query_routes_request {
+	/// Required. The destination of the payment.
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Destination {
+		/// A BOLT11 invoice, as paid with `Bolt11Send`.
+		#[prost(string, tag = "1")]
+		Invoice(::prost::alloc::string::String),
+		/// The hex-encoded public key of a node, as paid with `SpontaneousSend`.
+		#[prost(string, tag = "2")]
+		NodeId(::prost::alloc::string::String),
+	}
+}
+/// The response for the `QueryRoutes` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryRoutesResponse {
+	/// The route that was found.
+	#[prost(message, optional, tag = "1")]
+	pub route: ::core::option::Option<super::types::Route>,
+}
+/// Sends probes for a payment without sending the payment, and waits for their estimated outcomes.
+///
+/// A probe is an HTLC that the destination can't claim, and its outcome tells whether its path has
+/// enough liquidity for the payment. LDK Node picks the paths of the probes with its own router and
+/// doesn't report their outcomes. The results are thus estimates: they're given for the paths of
+/// the route `QueryRoutes` finds, which the probes most likely take, and inferred from how the
+/// pathfinding scores for those paths change. Like the probes of the background probing service,
+/// the outcomes are fed into the pathfinding scores. Probes lock the liquidity along their paths
+/// until they resolve.
+/// See more: <https://docs.rs/ldk-node/latest/ldk_node/payment/struct.Bolt11Payment.html#method.send_probes>
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendProbesRequest {
+	/// The amount in millisatoshis to probe for. Required for a `node_id` or a variable-amount
+	/// invoice, and overrides the amount of the invoice otherwise.
+	#[prost(uint64, optional, tag = "3")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// Configuration options for payment routing and pathfinding. Only supported for an `invoice`.
+	#[prost(message, optional, tag = "4")]
+	pub route_parameters: ::core::option::Option<super::types::RouteParametersConfig>,
+	/// How long to wait for the estimated outcomes of the probes, in seconds. Probes that didn't resolve by
+	/// then are returned as pending. Defaults to 60 seconds, and may be at most 600 seconds.
+	#[prost(uint32, optional, tag = "5")]
+	pub timeout_secs: ::core::option::Option<u32>,
+	/// Required. The destination of the payment.
+	#[prost(oneof = "send_probes_request::Destination", tags = "1, 2")]
+	pub destination: ::core::option::Option<send_probes_request::Destination>,
+}
+/// Nested message and enum types in `SendProbesRequest`.
+pub mod send_probes_request {
+	/// Required. The destination of the payment.
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Destination {
+		/// A BOLT11 invoice, as paid with `Bolt11Send`.
+		#[prost(string, tag = "1")]
+		Invoice(::prost::alloc::string::String),
+		/// The hex-encoded public key of a node, as paid with `SpontaneousSend`.
+		#[prost(string, tag = "2")]
+		NodeId(::prost::alloc::string::String),
+	}
+}
+/// The response for the `SendProbes` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SendProbesResponse {
+	/// The estimated outcome of the probe of each path of the route.
+	#[prost(message, repeated, tag = "1")]
+	pub results: ::prost::alloc::vec::Vec<super::types::ProbeResult>,
+	/// The liquidity locked by the probes sent via `SendProbes` that are still pending, including
+	/// those of this request, in millisatoshis. This doesn't include the probes of the background
+	/// probing service.
+	#[prost(uint64, tag = "2")]
+	pub locked_msat: u64,
+}
 /// Retrieves an overview of all known balances.
 /// See more: <https://docs.rs/ldk-node/latest/ldk_node/struct.Node.html#method.list_balances>
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1149,7 +1779,7 @@ pub struct UnifiedSendRequest {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct UnifiedSendResponse {
-	#[prost(oneof = "unified_send_response::PaymentResult", tags = "1, 2, 3")]
+	#[prost(oneof = "unified_send_response::PaymentResult", tags = "1, 2, 3, 4")]
 	#[cfg_attr(feature = "serde", serde(flatten))]
 	pub payment_result: ::core::option::Option<unified_send_response::PaymentResult>,
 }
@@ -1169,6 +1799,10 @@ pub mod unified_send_response {
 		/// A BOLT12 payment was made. Contains the payment ID in hex-encoded form.
 		#[prost(string, tag = "3")]
 		Bolt12PaymentId(::prost::alloc::string::String),
+		/// The send exceeded the approval threshold and was parked instead of being executed.
+		/// Contains the identifier of the pending action.
+		#[prost(string, tag = "4")]
+		PendingActionId(::prost::alloc::string::String),
 	}
 }
 /// Returns information on a node with the given ID from the network graph.
@@ -1316,9 +1950,402 @@ pub struct DecodeOfferResponse {
 	pub is_expired: bool,
 }
 /// Subscribe to a stream of server events.
+///
+/// Every event is persisted with a sequence number before it is streamed, so a subscriber can
+/// resume where it left off after a disconnect or restart.
+///
+/// Only events matching all of the given filters are streamed. An event that doesn't carry what a
+/// filter applies to doesn't match it, e.g. a `ChannelStateChanged` event never matches
+/// `payment_direction`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SubscribeEventsRequest {
+	/// If set, the persisted events with a higher sequence number are replayed before the stream
+	/// continues with live events. Use `0` to replay all persisted events.
+	///
+	/// Events are pruned once they are older than the server's configured retention window. The
+	/// stream fails with `FAILED_PRECONDITION` if events after this sequence number were pruned.
+	///
+	/// If unset, only events published after subscribing are streamed.
+	#[prost(uint64, optional, tag = "1")]
+	pub start_after_sequence: ::core::option::Option<u64>,
+	/// Only stream events of these types. Events of all types are streamed if empty.
+	#[prost(enumeration = "super::events::EventType", repeated, tag = "2")]
+	pub event_types: ::prost::alloc::vec::Vec<i32>,
+	/// Only stream events of these channels: `ChannelStateChanged` and splice events of one of them,
+	/// and `PaymentForwarded` events with an HTLC received or sent on one of them.
+	#[prost(string, repeated, tag = "3")]
+	pub channel_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+	/// Only stream events involving these counterparties: `ChannelStateChanged` and splice events of
+	/// a channel with one of them, `PaymentForwarded` events with an HTLC received from or sent to one
+	/// of them, and `PeerConnected` and `PeerDisconnected` events of one of them.
+	#[prost(string, repeated, tag = "4")]
+	pub counterparty_node_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+	/// Only stream payment events, i.e. `PaymentReceived`, `PaymentSuccessful`, `PaymentFailed`,
+	/// `PaymentClaimable`, `PaymentClaimDeadlineApproaching`, `OnchainFundsReceived` and
+	/// `OnchainFundsConfirmed`, of payments in this direction.
+	#[prost(enumeration = "super::types::PaymentDirection", optional, tag = "5")]
+	pub payment_direction: ::core::option::Option<i32>,
+	/// Only stream events of at least this amount: payment events of payments of at least this
+	/// amount, `PaymentForwarded` events forwarding at least this amount, and `PendingActionCreated`
+	/// events of actions sending at least this amount. Events of unknown amount are excluded.
+	#[prost(uint64, optional, tag = "6")]
+	pub min_amount_msat: ::core::option::Option<u64>,
+}
+/// Create a new named API key.
+///
+/// The key is only returned once, so it must be stored by the caller. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateApiKeyRequest {
+	/// The unique name of the API key. May only contain ASCII letters, digits, `-` and `_`, and must
+	/// be at most 64 characters long.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+	/// The permissions granted to the API key.
+	#[prost(enumeration = "super::types::ApiKeyScope", tag = "2")]
+	pub scope: i32,
+	/// The maximum amount of a single payment sent with this API key, in millisatoshis.
+	///
+	/// Can only be set if `scope` is `SEND`. If unset, the amount of payments is not limited.
+	#[prost(uint64, optional, tag = "3")]
+	pub max_send_amount_msat: ::core::option::Option<u64>,
+}
+/// The response for the `CreateApiKey` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateApiKeyResponse {
+	/// The hex-encoded API key.
+	#[prost(string, tag = "1")]
+	pub api_key: ::prost::alloc::string::String,
+}
+/// List all API keys, including the auto-generated `admin` key and keys defined in the config file.
+///
+/// Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListApiKeysRequest {}
+/// The response for the `ListApiKeys` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListApiKeysResponse {
+	/// List of API keys, sorted by name.
+	#[prost(message, repeated, tag = "1")]
+	pub api_keys: ::prost::alloc::vec::Vec<super::types::ApiKeyInfo>,
+}
+/// Replace an API key with a newly generated one.
+///
+/// The replaced key keeps being accepted for `grace_period_secs`, giving clients time to switch to
+/// the new key. Rotating the `admin` key also replaces the `api_key` file in the network directory.
+/// Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotateApiKeyRequest {
+	/// The name of the API key to rotate.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+	/// For how long the replaced key is still accepted, in seconds.
+	///
+	/// Defaults to 3600 seconds. If set to 0, the replaced key is rejected immediately.
+	#[prost(uint64, optional, tag = "2")]
+	pub grace_period_secs: ::core::option::Option<u64>,
+}
+/// The response for the `RotateApiKey` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RotateApiKeyResponse {
+	/// The new hex-encoded API key.
+	#[prost(string, tag = "1")]
+	pub api_key: ::prost::alloc::string::String,
+}
+/// Revoke an API key, rejecting any further requests made with it.
+///
+/// Open `SubscribeEvents` streams authenticated with the key are closed. The `admin` key can't be
+/// revoked, only rotated. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeApiKeyRequest {
+	/// The name of the API key to revoke.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+}
+/// The response for the `RevokeApiKey` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RevokeApiKeyResponse {}
+/// Lists the audit log of state-changing RPC calls, most recent first. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditLogRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListAuditLog` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListAuditLogResponse {
+	/// List of audit log entries.
+	#[prost(message, repeated, tag = "1")]
+	pub entries: ::prost::alloc::vec::Vec<super::types::AuditLogEntry>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Approve and execute a send that is awaiting approval. Requires the `ADMIN` scope.
+///
+/// If `require_distinct_approver` is enabled in the `\[approvals\]` config, the action must be
+/// approved with a different API key than the one that requested it.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApproveActionRequest {
+	/// The identifier of the pending action.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+}
+/// The response for the `ApproveAction` RPC. On failure, a gRPC error status is returned.
+///
+/// If the action was approved but executing the send failed, the returned action has the `FAILED`
+/// status and the error is given as its `reason`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApproveActionResponse {
+	/// The action after it was approved.
+	#[prost(message, optional, tag = "1")]
+	pub action: ::core::option::Option<super::types::PendingAction>,
+}
+/// Reject a send that is awaiting approval. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RejectActionRequest {
+	/// The identifier of the pending action.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// An optional reason for rejecting the action.
+	#[prost(string, optional, tag = "2")]
+	pub reason: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// The response for the `RejectAction` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RejectActionResponse {
+	/// The action after it was rejected.
+	#[prost(message, optional, tag = "1")]
+	pub action: ::core::option::Option<super::types::PendingAction>,
+}
+/// Lists sends that were parked for approval, most recent first. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPendingActionsRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListPendingActions` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListPendingActionsResponse {
+	/// List of actions, including those that were already approved, rejected or expired.
+	#[prost(message, repeated, tag = "1")]
+	pub actions: ::prost::alloc::vec::Vec<super::types::PendingAction>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Unlocks the encrypted mnemonic of a server that was started in the locked state, after which
+/// the node is built and started. Requires the `ADMIN` scope.
+///
+/// Until the node is unlocked, the server only serves `GetNodeInfo` and `Unlock`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnlockRequest {
+	/// The passphrase the mnemonic was encrypted with.
+	#[prost(string, tag = "1")]
+	pub passphrase: ::prost::alloc::string::String,
+}
+/// The response for the `Unlock` RPC. On failure, a gRPC error status is returned.
+///
+/// The node is started once the response was sent, after which the server serves all RPCs.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UnlockResponse {}
+/// Encrypts the plaintext mnemonic in the storage directory in place with the given passphrase.
+/// Requires the `ADMIN` scope.
+///
+/// The running node is unaffected, but on its next start the server waits in the locked state
+/// until it is unlocked via the `Unlock` RPC.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EncryptMnemonicRequest {
+	/// The passphrase to encrypt the mnemonic with. Must not be empty.
+	#[prost(string, tag = "1")]
+	pub passphrase: ::prost::alloc::string::String,
+}
+/// The response for the `EncryptMnemonic` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EncryptMnemonicResponse {}
+/// Lists the webhook deliveries that still failed after all their attempts, most recent event
+/// first. Requires the `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListFailedWebhookDeliveriesRequest {
+	/// `page_token` is a pagination token.
+	///
+	/// To query for the first page, `page_token` must not be specified.
+	///
+	/// For subsequent pages, use the value that was returned as `next_page_token` in the previous
+	/// page's response.
+	#[prost(message, optional, tag = "1")]
+	pub page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// The response for the `ListFailedWebhookDeliveries` RPC. On failure, a gRPC error status is
+/// returned.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SubscribeEventsRequest {}
+pub struct ListFailedWebhookDeliveriesResponse {
+	/// List of failed deliveries.
+	#[prost(message, repeated, tag = "1")]
+	pub deliveries: ::prost::alloc::vec::Vec<super::events::WebhookDelivery>,
+	/// `next_page_token` is a pagination token, used to retrieve the next page of results.
+	/// Use this value to query for next-page of paginated operation, by specifying
+	/// this value as the `page_token` in the next request.
+	///
+	/// If `next_page_token` is `None`, then the "last page" of results has been processed and
+	/// there is no more data to be retrieved.
+	///
+	/// If `next_page_token` is not `None`, it does not necessarily mean that there is more data in the
+	/// result set. The only way to know when you have reached the end of the result set is when
+	/// `next_page_token` is `None`.
+	///
+	/// **Caution**: Clients must not assume a specific number of records to be present in a page for
+	/// paginated response.
+	#[prost(message, optional, tag = "2")]
+	pub next_page_token: ::core::option::Option<super::types::PageToken>,
+}
+/// Retries a failed webhook delivery right away, with a fresh set of attempts. Requires the
+/// `ADMIN` scope.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RedeliverWebhookRequest {
+	/// The ID of the failed delivery.
+	#[prost(string, tag = "1")]
+	pub delivery_id: ::prost::alloc::string::String,
+}
+/// The response for the `RedeliverWebhook` RPC. On failure, a gRPC error status is returned.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RedeliverWebhookResponse {
+	/// The delivery, queued again.
+	#[prost(message, optional, tag = "1")]
+	pub delivery: ::core::option::Option<super::events::WebhookDelivery>,
+}
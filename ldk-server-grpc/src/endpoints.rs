@@ -14,10 +14,12 @@ pub const GET_NODE_INFO_PATH: &str = "GetNodeInfo";
 pub const GET_BALANCES_PATH: &str = "GetBalances";
 pub const ONCHAIN_RECEIVE_PATH: &str = "OnchainReceive";
 pub const ONCHAIN_SEND_PATH: &str = "OnchainSend";
+pub const LIST_ONCHAIN_TRANSACTIONS_PATH: &str = "ListOnchainTransactions";
 pub const BOLT11_RECEIVE_PATH: &str = "Bolt11Receive";
 pub const BOLT11_RECEIVE_FOR_HASH_PATH: &str = "Bolt11ReceiveForHash";
 pub const BOLT11_CLAIM_FOR_HASH_PATH: &str = "Bolt11ClaimForHash";
 pub const BOLT11_FAIL_FOR_HASH_PATH: &str = "Bolt11FailForHash";
+pub const LIST_CLAIMABLE_PAYMENTS_PATH: &str = "ListClaimablePayments";
 pub const BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH: &str = "Bolt11ReceiveViaJitChannel";
 pub const BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH: &str =
 	"Bolt11ReceiveVariableAmountViaJitChannel";
@@ -31,7 +33,12 @@ pub const SPLICE_OUT_PATH: &str = "SpliceOut";
 pub const CLOSE_CHANNEL_PATH: &str = "CloseChannel";
 pub const FORCE_CLOSE_CHANNEL_PATH: &str = "ForceCloseChannel";
 pub const LIST_CHANNELS_PATH: &str = "ListChannels";
+pub const LIST_CLOSED_CHANNELS_PATH: &str = "ListClosedChannels";
 pub const LIST_PAYMENTS_PATH: &str = "ListPayments";
+pub const FIND_PAYMENTS_PATH: &str = "FindPayments";
+pub const LIST_INVOICES_PATH: &str = "ListInvoices";
+pub const LIST_OFFERS_PATH: &str = "ListOffers";
+pub const UPDATE_LABEL_PATH: &str = "UpdateLabel";
 pub const LIST_FORWARDED_PAYMENTS_PATH: &str = "ListForwardedPayments";
 pub const UPDATE_CHANNEL_CONFIG_PATH: &str = "UpdateChannelConfig";
 pub const GET_PAYMENT_DETAILS_PATH: &str = "GetPaymentDetails";
@@ -42,6 +49,9 @@ pub const SPONTANEOUS_SEND_PATH: &str = "SpontaneousSend";
 pub const SIGN_MESSAGE_PATH: &str = "SignMessage";
 pub const VERIFY_SIGNATURE_PATH: &str = "VerifySignature";
 pub const EXPORT_PATHFINDING_SCORES_PATH: &str = "ExportPathfindingScores";
+pub const ESTIMATE_ROUTE_FEE_PATH: &str = "EstimateRouteFee";
+pub const QUERY_ROUTES_PATH: &str = "QueryRoutes";
+pub const SEND_PROBES_PATH: &str = "SendProbes";
 pub const UNIFIED_SEND_PATH: &str = "UnifiedSend";
 pub const GRAPH_LIST_CHANNELS_PATH: &str = "GraphListChannels";
 pub const GRAPH_GET_CHANNEL_PATH: &str = "GraphGetChannel";
@@ -51,3 +61,15 @@ pub const DECODE_INVOICE_PATH: &str = "DecodeInvoice";
 pub const DECODE_OFFER_PATH: &str = "DecodeOffer";
 pub const GET_METRICS_PATH: &str = "metrics";
 pub const SUBSCRIBE_EVENTS_PATH: &str = "SubscribeEvents";
+pub const CREATE_API_KEY_PATH: &str = "CreateApiKey";
+pub const LIST_API_KEYS_PATH: &str = "ListApiKeys";
+pub const ROTATE_API_KEY_PATH: &str = "RotateApiKey";
+pub const REVOKE_API_KEY_PATH: &str = "RevokeApiKey";
+pub const LIST_AUDIT_LOG_PATH: &str = "ListAuditLog";
+pub const APPROVE_ACTION_PATH: &str = "ApproveAction";
+pub const REJECT_ACTION_PATH: &str = "RejectAction";
+pub const LIST_PENDING_ACTIONS_PATH: &str = "ListPendingActions";
+pub const UNLOCK_PATH: &str = "Unlock";
+pub const ENCRYPT_MNEMONIC_PATH: &str = "EncryptMnemonic";
+pub const LIST_FAILED_WEBHOOK_DELIVERIES_PATH: &str = "ListFailedWebhookDeliveries";
+pub const REDELIVER_WEBHOOK_PATH: &str = "RedeliverWebhook";
@@ -14,7 +14,18 @@
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct EventEnvelope {
-	#[prost(oneof = "event_envelope::Event", tags = "2, 3, 4, 6, 7, 8")]
+	/// The sequence number of the event. It starts at 1 and increases by one with every event, also
+	/// across restarts, so it can be passed as `start_after_sequence` to `SubscribeEvents` to resume
+	/// after this event.
+	#[prost(uint64, tag = "10")]
+	pub sequence: u64,
+	/// The time at which the event was published, in seconds since the UNIX epoch.
+	#[prost(uint64, tag = "11")]
+	pub timestamp: u64,
+	#[prost(
+		oneof = "event_envelope::Event",
+		tags = "2, 3, 4, 6, 7, 8, 9, 12, 13, 14, 15, 16, 17, 18, 19, 20"
+	)]
 	pub event: ::core::option::Option<event_envelope::Event>,
 }
 /// Nested message and enum types in `EventEnvelope`.
@@ -36,6 +47,26 @@ pub mod event_envelope {
 		PaymentClaimable(super::PaymentClaimable),
 		#[prost(message, tag = "8")]
 		ChannelStateChanged(super::ChannelStateChanged),
+		#[prost(message, tag = "9")]
+		PendingActionCreated(super::PendingActionCreated),
+		#[prost(message, tag = "12")]
+		SplicePending(super::SplicePending),
+		#[prost(message, tag = "13")]
+		SpliceCompleted(super::SpliceCompleted),
+		#[prost(message, tag = "14")]
+		SpliceFailed(super::SpliceFailed),
+		#[prost(message, tag = "15")]
+		PeerConnected(super::PeerConnected),
+		#[prost(message, tag = "16")]
+		PeerDisconnected(super::PeerDisconnected),
+		#[prost(message, tag = "17")]
+		OnchainFundsReceived(super::OnchainFundsReceived),
+		#[prost(message, tag = "18")]
+		OnchainFundsConfirmed(super::OnchainFundsConfirmed),
+		#[prost(message, tag = "19")]
+		WalletSyncCompleted(super::WalletSyncCompleted),
+		#[prost(message, tag = "20")]
+		PaymentClaimDeadlineApproaching(super::PaymentClaimDeadlineApproaching),
 	}
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -43,356 +74,406 @@ pub mod event_envelope {
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct CounterpartyForceClosedDetails {
+pub struct ChannelStateChanged {
 	#[prost(string, tag = "1")]
-	pub peer_msg: ::prost::alloc::string::String,
+	pub channel_id: ::prost::alloc::string::String,
+	#[prost(string, tag = "2")]
+	pub user_channel_id: ::prost::alloc::string::String,
+	#[prost(string, optional, tag = "3")]
+	pub counterparty_node_id: ::core::option::Option<::prost::alloc::string::String>,
+	#[prost(enumeration = "super::types::ChannelState", tag = "4")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_channel_state")
+	)]
+	pub state: i32,
+	#[prost(string, optional, tag = "5")]
+	pub funding_txo: ::core::option::Option<::prost::alloc::string::String>,
+	#[prost(message, optional, tag = "6")]
+	pub reason: ::core::option::Option<super::types::ChannelStateChangeReason>,
+	#[prost(enumeration = "super::types::ChannelClosureInitiator", tag = "7")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_channel_closure_initiator")
+	)]
+	pub closure_initiator: i32,
+	/// The label attached to the channel, if any.
+	#[prost(string, optional, tag = "8")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata attached to the channel.
+	#[prost(btree_map = "string, string", tag = "9")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
+/// The delivery of an event to a webhook that didn't succeed yet. Deliveries are removed once they
+/// succeed.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct HolderForceClosedDetails {
-	#[prost(bool, optional, tag = "1")]
-	pub broadcasted_latest_txn: ::core::option::Option<bool>,
+pub struct WebhookDelivery {
+	/// The ID of the delivery, unique per event and webhook. Sent in the `x-ldk-server-delivery-id`
+	/// header, so that receivers can ignore repeated deliveries.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// The name of the webhook the event is delivered to.
 	#[prost(string, tag = "2")]
-	pub message: ::prost::alloc::string::String,
+	pub webhook: ::prost::alloc::string::String,
+	/// The event being delivered.
+	#[prost(message, optional, tag = "3")]
+	pub event: ::core::option::Option<EventEnvelope>,
+	/// The status of the delivery.
+	#[prost(enumeration = "WebhookDeliveryStatus", tag = "4")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_webhook_delivery_status")
+	)]
+	pub status: i32,
+	/// The number of failed attempts so far.
+	#[prost(uint32, tag = "5")]
+	pub attempts: u32,
+	/// Why the last attempt failed, if any.
+	#[prost(string, optional, tag = "6")]
+	pub last_error: ::core::option::Option<::prost::alloc::string::String>,
+	/// The time of the last attempt, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "7")]
+	pub last_attempt_at: ::core::option::Option<u64>,
+	/// The time of the next attempt, in seconds since the UNIX epoch. Unset once the delivery failed.
+	#[prost(uint64, optional, tag = "8")]
+	pub next_attempt_at: ::core::option::Option<u64>,
 }
+/// PaymentReceived indicates a payment has been received.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ProcessingErrorDetails {
-	#[prost(string, tag = "1")]
-	pub err: ::prost::alloc::string::String,
+pub struct PaymentReceived {
+	/// The payment details for the payment in event.
+	#[prost(message, optional, tag = "1")]
+	pub payment: ::core::option::Option<super::types::Payment>,
+	/// Custom TLV records attached to the incoming payment, if any.
+	#[prost(message, repeated, tag = "2")]
+	pub custom_records: ::prost::alloc::vec::Vec<super::types::CustomTlvRecord>,
 }
+/// PaymentSuccessful indicates a sent payment was successful.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct HtlcsTimedOutDetails {
-	#[prost(string, optional, tag = "1")]
-	pub payment_hash: ::core::option::Option<::prost::alloc::string::String>,
+pub struct PaymentSuccessful {
+	/// The payment details for the payment in event.
+	#[prost(message, optional, tag = "1")]
+	pub payment: ::core::option::Option<super::types::Payment>,
 }
+/// PaymentFailed indicates a sent payment has failed.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PeerFeerateTooLowDetails {
-	#[prost(uint32, tag = "1")]
-	pub peer_feerate_sat_per_kw: u32,
-	#[prost(uint32, tag = "2")]
-	pub required_feerate_sat_per_kw: u32,
+pub struct PaymentFailed {
+	/// The payment details for the payment in event.
+	#[prost(message, optional, tag = "1")]
+	pub payment: ::core::option::Option<super::types::Payment>,
 }
+/// PaymentClaimable indicates a payment has arrived and is waiting to be manually claimed or failed.
+/// This event is only emitted for payments created via `Bolt11ReceiveForHash`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ChannelStateChangeReason {
-	#[prost(enumeration = "ChannelStateChangeReasonKind", tag = "1")]
-	pub kind: i32,
-	#[prost(string, tag = "2")]
-	pub message: ::prost::alloc::string::String,
-	#[prost(oneof = "channel_state_change_reason::Details", tags = "3, 4, 5, 6, 7")]
-	pub details: ::core::option::Option<channel_state_change_reason::Details>,
+pub struct PaymentClaimable {
+	/// The payment details for the claimable payment.
+	#[prost(message, optional, tag = "1")]
+	pub payment: ::core::option::Option<super::types::Payment>,
+	/// Custom TLV records attached to the claimable payment, if any.
+	#[prost(message, repeated, tag = "2")]
+	pub custom_records: ::prost::alloc::vec::Vec<super::types::CustomTlvRecord>,
+	/// The block height by which this payment must be claimed before it is failed back.
+	#[prost(uint32, optional, tag = "3")]
+	pub claim_deadline: ::core::option::Option<u32>,
 }
-/// Nested message and enum types in `ChannelStateChangeReason`.
-pub mod channel_state_change_reason {
-	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
-	#[allow(clippy::derive_partial_eq_without_eq)]
-	#[derive(Clone, PartialEq, ::prost::Oneof)]
-	pub enum Details {
-		#[prost(message, tag = "3")]
-		CounterpartyForceClosed(super::CounterpartyForceClosedDetails),
-		#[prost(message, tag = "4")]
-		HolderForceClosed(super::HolderForceClosedDetails),
-		#[prost(message, tag = "5")]
-		ProcessingError(super::ProcessingErrorDetails),
-		#[prost(message, tag = "6")]
-		HtlcsTimedOut(super::HtlcsTimedOutDetails),
-		#[prost(message, tag = "7")]
-		PeerFeerateTooLow(super::PeerFeerateTooLowDetails),
-	}
+/// PaymentForwarded indicates a payment was forwarded through the node.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentForwarded {
+	#[prost(message, optional, tag = "1")]
+	pub forwarded_payment: ::core::option::Option<super::types::ForwardedPayment>,
 }
+/// PendingActionCreated indicates a send exceeded the approval threshold and is awaiting approval.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct ChannelStateChanged {
+pub struct PendingActionCreated {
+	/// The action awaiting approval.
+	#[prost(message, optional, tag = "1")]
+	pub action: ::core::option::Option<super::types::PendingAction>,
+}
+/// SplicePending indicates a splice of a channel was negotiated and its transaction broadcast. The
+/// channel keeps operating on its current funding until the splice transaction confirms.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SplicePending {
+	#[prost(string, tag = "1")]
+	pub channel_id: ::prost::alloc::string::String,
+	#[prost(string, tag = "2")]
+	pub user_channel_id: ::prost::alloc::string::String,
+	#[prost(string, tag = "3")]
+	pub counterparty_node_id: ::prost::alloc::string::String,
+	/// The outpoint of the channel's new funding transaction.
+	#[prost(string, tag = "4")]
+	pub new_funding_txo: ::prost::alloc::string::String,
+}
+/// SpliceCompleted indicates the transaction of a splice confirmed and the channel now operates on
+/// its new funding.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SpliceCompleted {
 	#[prost(string, tag = "1")]
 	pub channel_id: ::prost::alloc::string::String,
 	#[prost(string, tag = "2")]
 	pub user_channel_id: ::prost::alloc::string::String,
 	#[prost(string, optional, tag = "3")]
 	pub counterparty_node_id: ::core::option::Option<::prost::alloc::string::String>,
-	#[prost(enumeration = "ChannelState", tag = "4")]
-	pub state: i32,
-	#[prost(string, optional, tag = "5")]
+	/// The outpoint of the channel's new funding transaction.
+	#[prost(string, optional, tag = "4")]
 	pub funding_txo: ::core::option::Option<::prost::alloc::string::String>,
-	#[prost(message, optional, tag = "6")]
-	pub reason: ::core::option::Option<ChannelStateChangeReason>,
-	#[prost(enumeration = "ChannelClosureInitiator", tag = "7")]
-	pub closure_initiator: i32,
 }
-/// PaymentReceived indicates a payment has been received.
+/// SpliceFailed indicates a splice of a channel was abandoned. The channel keeps operating on its
+/// current funding.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PaymentReceived {
-	/// The payment details for the payment in event.
-	#[prost(message, optional, tag = "1")]
-	pub payment: ::core::option::Option<super::types::Payment>,
-	/// Custom TLV records attached to the incoming payment, if any.
-	#[prost(message, repeated, tag = "2")]
-	pub custom_records: ::prost::alloc::vec::Vec<super::types::CustomTlvRecord>,
+pub struct SpliceFailed {
+	#[prost(string, tag = "1")]
+	pub channel_id: ::prost::alloc::string::String,
+	#[prost(string, tag = "2")]
+	pub user_channel_id: ::prost::alloc::string::String,
+	#[prost(string, tag = "3")]
+	pub counterparty_node_id: ::prost::alloc::string::String,
+	/// The outpoint of the funding transaction of the abandoned splice, if it was created.
+	#[prost(string, optional, tag = "4")]
+	pub abandoned_funding_txo: ::core::option::Option<::prost::alloc::string::String>,
 }
-/// PaymentSuccessful indicates a sent payment was successful.
+/// PeerConnected indicates a connection to a peer was established.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PaymentSuccessful {
-	/// The payment details for the payment in event.
-	#[prost(message, optional, tag = "1")]
-	pub payment: ::core::option::Option<super::types::Payment>,
+pub struct PeerConnected {
+	/// The hex-encoded node ID of the peer.
+	#[prost(string, tag = "1")]
+	pub node_id: ::prost::alloc::string::String,
+	/// The network address of the peer.
+	#[prost(string, tag = "2")]
+	pub address: ::prost::alloc::string::String,
 }
-/// PaymentFailed indicates a sent payment has failed.
+/// PeerDisconnected indicates the connection to a peer was lost.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PaymentFailed {
-	/// The payment details for the payment in event.
-	#[prost(message, optional, tag = "1")]
-	pub payment: ::core::option::Option<super::types::Payment>,
+pub struct PeerDisconnected {
+	/// The hex-encoded node ID of the peer.
+	#[prost(string, tag = "1")]
+	pub node_id: ::prost::alloc::string::String,
+	/// The network address of the peer.
+	#[prost(string, tag = "2")]
+	pub address: ::prost::alloc::string::String,
 }
-/// PaymentClaimable indicates a payment has arrived and is waiting to be manually claimed or failed.
-/// This event is only emitted for payments created via `Bolt11ReceiveForHash`.
+/// OnchainFundsReceived indicates the on-chain wallet detected a transaction paying to it.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PaymentClaimable {
-	/// The payment details for the claimable payment.
+pub struct OnchainFundsReceived {
+	/// The details of the inbound on-chain payment.
 	#[prost(message, optional, tag = "1")]
 	pub payment: ::core::option::Option<super::types::Payment>,
-	/// Custom TLV records attached to the claimable payment, if any.
-	#[prost(message, repeated, tag = "2")]
-	pub custom_records: ::prost::alloc::vec::Vec<super::types::CustomTlvRecord>,
-	/// The block height by which this payment must be claimed before it is failed back.
-	#[prost(uint32, optional, tag = "3")]
-	pub claim_deadline: ::core::option::Option<u32>,
 }
-/// PaymentForwarded indicates a payment was forwarded through the node.
+/// OnchainFundsConfirmed indicates a transaction paying to the on-chain wallet confirmed.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[cfg_attr(feature = "serde", serde(default))]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct PaymentForwarded {
+pub struct OnchainFundsConfirmed {
+	/// The details of the inbound on-chain payment.
 	#[prost(message, optional, tag = "1")]
-	pub forwarded_payment: ::core::option::Option<super::types::ForwardedPayment>,
+	pub payment: ::core::option::Option<super::types::Payment>,
 }
+/// WalletSyncCompleted indicates the on-chain or Lightning wallet finished syncing to the chain.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
-#[repr(i32)]
-pub enum ChannelState {
-	Unspecified = 0,
-	Pending = 1,
-	Ready = 2,
-	OpenFailed = 3,
-	Closed = 4,
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WalletSyncCompleted {
+	/// The time of the last successful sync of the on-chain wallet, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "1")]
+	pub latest_onchain_wallet_sync_timestamp: ::core::option::Option<u64>,
+	/// The time of the last successful sync of the Lightning wallet, in seconds since the UNIX epoch.
+	#[prost(uint64, optional, tag = "2")]
+	pub latest_lightning_wallet_sync_timestamp: ::core::option::Option<u64>,
+	/// The best block the wallets are synced to.
+	#[prost(message, optional, tag = "3")]
+	pub current_best_block: ::core::option::Option<super::types::BestBlock>,
 }
-impl ChannelState {
-	/// String value of the enum field names used in the ProtoBuf definition.
-	///
-	/// The values are not transformed in any way and thus are considered stable
-	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
-	pub fn as_str_name(&self) -> &'static str {
-		match self {
-			ChannelState::Unspecified => "CHANNEL_STATE_UNSPECIFIED",
-			ChannelState::Pending => "CHANNEL_STATE_PENDING",
-			ChannelState::Ready => "CHANNEL_STATE_READY",
-			ChannelState::OpenFailed => "CHANNEL_STATE_OPEN_FAILED",
-			ChannelState::Closed => "CHANNEL_STATE_CLOSED",
-		}
-	}
-	/// Creates an enum from field names used in the ProtoBuf definition.
-	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
-		match value {
-			"CHANNEL_STATE_UNSPECIFIED" => Some(Self::Unspecified),
-			"CHANNEL_STATE_PENDING" => Some(Self::Pending),
-			"CHANNEL_STATE_READY" => Some(Self::Ready),
-			"CHANNEL_STATE_OPEN_FAILED" => Some(Self::OpenFailed),
-			"CHANNEL_STATE_CLOSED" => Some(Self::Closed),
-			_ => None,
-		}
-	}
+/// PaymentClaimDeadlineApproaching indicates a payment for an invoice created via
+/// `Bolt11ReceiveForHash` is still waiting to be claimed or failed, and will soon be resolved by the
+/// server, see `ListClaimablePayments`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PaymentClaimDeadlineApproaching {
+	/// The payment details for the claimable payment.
+	#[prost(message, optional, tag = "1")]
+	pub payment: ::core::option::Option<super::types::Payment>,
+	/// The block height by which this payment must be claimed before it is failed back.
+	#[prost(uint32, tag = "2")]
+	pub claim_deadline: u32,
+	/// The current block height.
+	#[prost(uint32, tag = "3")]
+	pub current_height: u32,
+	/// The block height at which the server claims or fails back the payment.
+	#[prost(uint32, tag = "4")]
+	pub auto_resolve_height: u32,
+	/// Whether the payment will be claimed with its registered preimage, rather than failed back.
+	#[prost(bool, tag = "5")]
+	pub auto_claim: bool,
 }
+/// The type of an event, i.e. the variant of `EventEnvelope.event`.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ChannelClosureInitiator {
+pub enum EventType {
 	Unspecified = 0,
-	Local = 1,
-	Remote = 2,
-	Unknown = 3,
+	PaymentReceived = 1,
+	PaymentSuccessful = 2,
+	PaymentFailed = 3,
+	PaymentForwarded = 4,
+	PaymentClaimable = 5,
+	ChannelStateChanged = 6,
+	PendingActionCreated = 7,
+	SplicePending = 8,
+	SpliceCompleted = 9,
+	SpliceFailed = 10,
+	PeerConnected = 11,
+	PeerDisconnected = 12,
+	OnchainFundsReceived = 13,
+	OnchainFundsConfirmed = 14,
+	WalletSyncCompleted = 15,
+	PaymentClaimDeadlineApproaching = 16,
 }
-impl ChannelClosureInitiator {
+impl EventType {
 	/// String value of the enum field names used in the ProtoBuf definition.
 	///
 	/// The values are not transformed in any way and thus are considered stable
 	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
 	pub fn as_str_name(&self) -> &'static str {
 		match self {
-			ChannelClosureInitiator::Unspecified => "CHANNEL_CLOSURE_INITIATOR_UNSPECIFIED",
-			ChannelClosureInitiator::Local => "CHANNEL_CLOSURE_INITIATOR_LOCAL",
-			ChannelClosureInitiator::Remote => "CHANNEL_CLOSURE_INITIATOR_REMOTE",
-			ChannelClosureInitiator::Unknown => "CHANNEL_CLOSURE_INITIATOR_UNKNOWN",
+			EventType::Unspecified => "EVENT_TYPE_UNSPECIFIED",
+			EventType::PaymentReceived => "EVENT_TYPE_PAYMENT_RECEIVED",
+			EventType::PaymentSuccessful => "EVENT_TYPE_PAYMENT_SUCCESSFUL",
+			EventType::PaymentFailed => "EVENT_TYPE_PAYMENT_FAILED",
+			EventType::PaymentForwarded => "EVENT_TYPE_PAYMENT_FORWARDED",
+			EventType::PaymentClaimable => "EVENT_TYPE_PAYMENT_CLAIMABLE",
+			EventType::ChannelStateChanged => "EVENT_TYPE_CHANNEL_STATE_CHANGED",
+			EventType::PendingActionCreated => "EVENT_TYPE_PENDING_ACTION_CREATED",
+			EventType::SplicePending => "EVENT_TYPE_SPLICE_PENDING",
+			EventType::SpliceCompleted => "EVENT_TYPE_SPLICE_COMPLETED",
+			EventType::SpliceFailed => "EVENT_TYPE_SPLICE_FAILED",
+			EventType::PeerConnected => "EVENT_TYPE_PEER_CONNECTED",
+			EventType::PeerDisconnected => "EVENT_TYPE_PEER_DISCONNECTED",
+			EventType::OnchainFundsReceived => "EVENT_TYPE_ONCHAIN_FUNDS_RECEIVED",
+			EventType::OnchainFundsConfirmed => "EVENT_TYPE_ONCHAIN_FUNDS_CONFIRMED",
+			EventType::WalletSyncCompleted => "EVENT_TYPE_WALLET_SYNC_COMPLETED",
+			EventType::PaymentClaimDeadlineApproaching => {
+				"EVENT_TYPE_PAYMENT_CLAIM_DEADLINE_APPROACHING"
+			},
 		}
 	}
 	/// Creates an enum from field names used in the ProtoBuf definition.
 	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
 		match value {
-			"CHANNEL_CLOSURE_INITIATOR_UNSPECIFIED" => Some(Self::Unspecified),
-			"CHANNEL_CLOSURE_INITIATOR_LOCAL" => Some(Self::Local),
-			"CHANNEL_CLOSURE_INITIATOR_REMOTE" => Some(Self::Remote),
-			"CHANNEL_CLOSURE_INITIATOR_UNKNOWN" => Some(Self::Unknown),
+			"EVENT_TYPE_UNSPECIFIED" => Some(Self::Unspecified),
+			"EVENT_TYPE_PAYMENT_RECEIVED" => Some(Self::PaymentReceived),
+			"EVENT_TYPE_PAYMENT_SUCCESSFUL" => Some(Self::PaymentSuccessful),
+			"EVENT_TYPE_PAYMENT_FAILED" => Some(Self::PaymentFailed),
+			"EVENT_TYPE_PAYMENT_FORWARDED" => Some(Self::PaymentForwarded),
+			"EVENT_TYPE_PAYMENT_CLAIMABLE" => Some(Self::PaymentClaimable),
+			"EVENT_TYPE_CHANNEL_STATE_CHANGED" => Some(Self::ChannelStateChanged),
+			"EVENT_TYPE_PENDING_ACTION_CREATED" => Some(Self::PendingActionCreated),
+			"EVENT_TYPE_SPLICE_PENDING" => Some(Self::SplicePending),
+			"EVENT_TYPE_SPLICE_COMPLETED" => Some(Self::SpliceCompleted),
+			"EVENT_TYPE_SPLICE_FAILED" => Some(Self::SpliceFailed),
+			"EVENT_TYPE_PEER_CONNECTED" => Some(Self::PeerConnected),
+			"EVENT_TYPE_PEER_DISCONNECTED" => Some(Self::PeerDisconnected),
+			"EVENT_TYPE_ONCHAIN_FUNDS_RECEIVED" => Some(Self::OnchainFundsReceived),
+			"EVENT_TYPE_ONCHAIN_FUNDS_CONFIRMED" => Some(Self::OnchainFundsConfirmed),
+			"EVENT_TYPE_WALLET_SYNC_COMPLETED" => Some(Self::WalletSyncCompleted),
+			"EVENT_TYPE_PAYMENT_CLAIM_DEADLINE_APPROACHING" => {
+				Some(Self::PaymentClaimDeadlineApproaching)
+			},
 			_ => None,
 		}
 	}
 }
+/// The status of a webhook delivery.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
-pub enum ChannelStateChangeReasonKind {
+pub enum WebhookDeliveryStatus {
 	Unspecified = 0,
-	CounterpartyForceClosed = 1,
-	HolderForceClosed = 2,
-	LegacyCooperativeClosure = 3,
-	CounterpartyInitiatedCooperativeClosure = 4,
-	LocallyInitiatedCooperativeClosure = 5,
-	CommitmentTxConfirmed = 6,
-	FundingTimedOut = 7,
-	ProcessingError = 8,
-	DisconnectedPeer = 9,
-	OutdatedChannelManager = 10,
-	CounterpartyCoopClosedUnfundedChannel = 11,
-	LocallyCoopClosedUnfundedChannel = 12,
-	FundingBatchClosure = 13,
-	HtlcsTimedOut = 14,
-	PeerFeerateTooLow = 15,
+	/// The delivery is retried until it succeeds or runs out of attempts.
+	Pending = 1,
+	/// The delivery ran out of attempts. It is only retried if requested via `RedeliverWebhook`.
+	Failed = 2,
 }
-impl ChannelStateChangeReasonKind {
+impl WebhookDeliveryStatus {
 	/// String value of the enum field names used in the ProtoBuf definition.
 	///
 	/// The values are not transformed in any way and thus are considered stable
 	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
 	pub fn as_str_name(&self) -> &'static str {
 		match self {
-			ChannelStateChangeReasonKind::Unspecified => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_UNSPECIFIED"
-			},
-			ChannelStateChangeReasonKind::CounterpartyForceClosed => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_FORCE_CLOSED"
-			},
-			ChannelStateChangeReasonKind::HolderForceClosed => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_HOLDER_FORCE_CLOSED"
-			},
-			ChannelStateChangeReasonKind::LegacyCooperativeClosure => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_LEGACY_COOPERATIVE_CLOSURE"
-			},
-			ChannelStateChangeReasonKind::CounterpartyInitiatedCooperativeClosure => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_INITIATED_COOPERATIVE_CLOSURE"
-			},
-			ChannelStateChangeReasonKind::LocallyInitiatedCooperativeClosure => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_INITIATED_COOPERATIVE_CLOSURE"
-			},
-			ChannelStateChangeReasonKind::CommitmentTxConfirmed => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_COMMITMENT_TX_CONFIRMED"
-			},
-			ChannelStateChangeReasonKind::FundingTimedOut => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_TIMED_OUT"
-			},
-			ChannelStateChangeReasonKind::ProcessingError => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_PROCESSING_ERROR"
-			},
-			ChannelStateChangeReasonKind::DisconnectedPeer => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_DISCONNECTED_PEER"
-			},
-			ChannelStateChangeReasonKind::OutdatedChannelManager => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_OUTDATED_CHANNEL_MANAGER"
-			},
-			ChannelStateChangeReasonKind::CounterpartyCoopClosedUnfundedChannel => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_COOP_CLOSED_UNFUNDED_CHANNEL"
-			},
-			ChannelStateChangeReasonKind::LocallyCoopClosedUnfundedChannel => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_COOP_CLOSED_UNFUNDED_CHANNEL"
-			},
-			ChannelStateChangeReasonKind::FundingBatchClosure => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_BATCH_CLOSURE"
-			},
-			ChannelStateChangeReasonKind::HtlcsTimedOut => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_HTLCS_TIMED_OUT"
-			},
-			ChannelStateChangeReasonKind::PeerFeerateTooLow => {
-				"CHANNEL_STATE_CHANGE_REASON_KIND_PEER_FEERATE_TOO_LOW"
-			},
+			WebhookDeliveryStatus::Unspecified => "WEBHOOK_DELIVERY_STATUS_UNSPECIFIED",
+			WebhookDeliveryStatus::Pending => "WEBHOOK_DELIVERY_STATUS_PENDING",
+			WebhookDeliveryStatus::Failed => "WEBHOOK_DELIVERY_STATUS_FAILED",
 		}
 	}
 	/// Creates an enum from field names used in the ProtoBuf definition.
 	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
 		match value {
-			"CHANNEL_STATE_CHANGE_REASON_KIND_UNSPECIFIED" => Some(Self::Unspecified),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_FORCE_CLOSED" => {
-				Some(Self::CounterpartyForceClosed)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_HOLDER_FORCE_CLOSED" => Some(Self::HolderForceClosed),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_LEGACY_COOPERATIVE_CLOSURE" => {
-				Some(Self::LegacyCooperativeClosure)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_INITIATED_COOPERATIVE_CLOSURE" => {
-				Some(Self::CounterpartyInitiatedCooperativeClosure)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_INITIATED_COOPERATIVE_CLOSURE" => {
-				Some(Self::LocallyInitiatedCooperativeClosure)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_COMMITMENT_TX_CONFIRMED" => {
-				Some(Self::CommitmentTxConfirmed)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_TIMED_OUT" => Some(Self::FundingTimedOut),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_PROCESSING_ERROR" => Some(Self::ProcessingError),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_DISCONNECTED_PEER" => Some(Self::DisconnectedPeer),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_OUTDATED_CHANNEL_MANAGER" => {
-				Some(Self::OutdatedChannelManager)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_COOP_CLOSED_UNFUNDED_CHANNEL" => {
-				Some(Self::CounterpartyCoopClosedUnfundedChannel)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_COOP_CLOSED_UNFUNDED_CHANNEL" => {
-				Some(Self::LocallyCoopClosedUnfundedChannel)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_BATCH_CLOSURE" => {
-				Some(Self::FundingBatchClosure)
-			},
-			"CHANNEL_STATE_CHANGE_REASON_KIND_HTLCS_TIMED_OUT" => Some(Self::HtlcsTimedOut),
-			"CHANNEL_STATE_CHANGE_REASON_KIND_PEER_FEERATE_TOO_LOW" => {
-				Some(Self::PeerFeerateTooLow)
-			},
+			"WEBHOOK_DELIVERY_STATUS_UNSPECIFIED" => Some(Self::Unspecified),
+			"WEBHOOK_DELIVERY_STATUS_PENDING" => Some(Self::Pending),
+			"WEBHOOK_DELIVERY_STATUS_FAILED" => Some(Self::Failed),
 			_ => None,
 		}
 	}
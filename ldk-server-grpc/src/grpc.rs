@@ -18,6 +18,8 @@ use bytes::{BufMut, Bytes, BytesMut};
 pub const GRPC_STATUS_OK: u32 = 0;
 pub const GRPC_STATUS_INVALID_ARGUMENT: u32 = 3;
 pub const GRPC_STATUS_DEADLINE_EXCEEDED: u32 = 4;
+pub const GRPC_STATUS_PERMISSION_DENIED: u32 = 7;
+pub const GRPC_STATUS_RESOURCE_EXHAUSTED: u32 = 8;
 pub const GRPC_STATUS_FAILED_PRECONDITION: u32 = 9;
 pub const GRPC_STATUS_UNIMPLEMENTED: u32 = 12;
 pub const GRPC_STATUS_INTERNAL: u32 = 13;
@@ -776,7 +778,7 @@ mod tests {
 
 			let response = client
 				.server_streaming(
-					tonic::Request::new(crate::api::SubscribeEventsRequest {}),
+					tonic::Request::new(crate::api::SubscribeEventsRequest::default()),
 					grpc_path(SUBSCRIBE_EVENTS_PATH).parse().unwrap(),
 					tonic::codec::ProstCodec::default(),
 				)
@@ -804,7 +806,7 @@ mod tests {
 
 			let response = client
 				.server_streaming(
-					tonic::Request::new(crate::api::SubscribeEventsRequest {}),
+					tonic::Request::new(crate::api::SubscribeEventsRequest::default()),
 					grpc_path("SubscribeEventsError").parse().unwrap(),
 					tonic::codec::ProstCodec::default(),
 				)
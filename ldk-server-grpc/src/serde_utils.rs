@@ -39,6 +39,21 @@ stringify_enum_serializer!(serialize_payment_status, crate::types::PaymentStatus
 stringify_enum_serializer!(serialize_balance_source, crate::types::BalanceSource);
 stringify_enum_serializer!(serialize_network, crate::types::Network);
 stringify_enum_serializer!(serialize_channel_direction, crate::types::ChannelDirection);
+stringify_enum_serializer!(serialize_api_key_scope, crate::types::ApiKeyScope);
+stringify_enum_serializer!(serialize_audit_outcome, crate::types::AuditOutcome);
+stringify_enum_serializer!(serialize_pending_action_status, crate::types::PendingActionStatus);
+stringify_enum_serializer!(serialize_invoice_status, crate::types::InvoiceStatus);
+stringify_enum_serializer!(serialize_probe_status, crate::types::ProbeStatus);
+stringify_enum_serializer!(serialize_channel_state, crate::types::ChannelState);
+stringify_enum_serializer!(
+	serialize_channel_closure_initiator,
+	crate::types::ChannelClosureInitiator
+);
+stringify_enum_serializer!(
+	serialize_channel_state_change_reason_kind,
+	crate::types::ChannelStateChangeReasonKind
+);
+stringify_enum_serializer!(serialize_webhook_delivery_status, crate::events::WebhookDeliveryStatus);
 
 /// Serializes `Option<prost::bytes::Bytes>` as a hex string (or null).
 pub fn serialize_opt_bytes_hex<S>(
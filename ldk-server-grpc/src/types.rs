@@ -47,6 +47,16 @@ pub struct Payment {
 	/// The timestamp, in seconds since start of the UNIX epoch, when this entry was last updated.
 	#[prost(uint64, tag = "6")]
 	pub latest_update_timestamp: u64,
+	/// The label attached to the payment by the caller that initiated or requested it, or via
+	/// `UpdateLabel`.
+	#[prost(string, optional, tag = "8")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata attached to the payment, see `label`.
+	#[prost(btree_map = "string, string", tag = "9")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -136,6 +146,52 @@ pub struct Confirmed {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Unconfirmed {}
+/// A transaction of the on-chain wallet.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct OnchainTransaction {
+	/// The transaction ID.
+	#[prost(string, tag = "1")]
+	pub txid: ::prost::alloc::string::String,
+	/// The identifier of the payment the transaction is recorded as, e.g. to label it via
+	/// `UpdateLabel`.
+	#[prost(string, tag = "2")]
+	pub payment_id: ::prost::alloc::string::String,
+	/// Whether the transaction received funds to the wallet or sent funds from it.
+	#[prost(enumeration = "PaymentDirection", tag = "3")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_payment_direction")
+	)]
+	pub direction: i32,
+	/// The amount received or sent, excluding the fee, in satoshis.
+	#[prost(uint64, optional, tag = "4")]
+	pub amount_sats: ::core::option::Option<u64>,
+	/// The fee paid by the transaction, in satoshis.
+	#[prost(uint64, optional, tag = "5")]
+	pub fee_sats: ::core::option::Option<u64>,
+	/// The confirmation status of the transaction.
+	#[prost(message, optional, tag = "6")]
+	pub status: ::core::option::Option<ConfirmationStatus>,
+	/// The number of confirmations of the transaction, or 0 if it's unconfirmed.
+	#[prost(uint32, tag = "7")]
+	pub confirmations: u32,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the transaction was last updated.
+	#[prost(uint64, tag = "8")]
+	pub latest_update_timestamp: u64,
+	/// The label attached to the transaction's payment, see `Payment.label`.
+	#[prost(string, optional, tag = "9")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata attached to the transaction's payment, see `label`.
+	#[prost(btree_map = "string, string", tag = "10")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
 /// Represents a BOLT 11 payment.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -464,6 +520,15 @@ pub struct Channel {
 	/// such that the outgoing HTLC is forwardable to this counterparty.
 	#[prost(uint32, optional, tag = "25")]
 	pub counterparty_forwarding_info_cltv_expiry_delta: ::core::option::Option<u32>,
+	/// The label attached to the channel when it was opened, or via `UpdateLabel`.
+	#[prost(string, optional, tag = "26")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata attached to the channel, see `label`.
+	#[prost(btree_map = "string, string", tag = "27")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
 }
 /// ChannelConfig represents the configuration settings for a channel in a Lightning Network node.
 /// See more: <https://docs.rs/lightning/latest/lightning/util/config/struct.ChannelConfig.html>
@@ -932,6 +997,92 @@ pub struct RouteParametersConfig {
 	#[prost(uint32, tag = "4")]
 	pub max_channel_saturation_power_of_half: u32,
 }
+/// A route to the destination of a payment, as found by the router.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Route {
+	/// The paths the payment is split into. A payment that isn't split takes a single path.
+	#[prost(message, repeated, tag = "1")]
+	pub paths: ::prost::alloc::vec::Vec<RoutePath>,
+	/// The total routing fees of all paths, in millisatoshis.
+	#[prost(uint64, tag = "2")]
+	pub total_fee_msat: u64,
+	/// The total amount sent over all paths, including routing fees, in millisatoshis.
+	#[prost(uint64, tag = "3")]
+	pub total_amount_msat: u64,
+}
+/// A path of a `Route`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RoutePath {
+	/// The hops of the path, from the first node after this node to the destination.
+	#[prost(message, repeated, tag = "1")]
+	pub hops: ::prost::alloc::vec::Vec<RouteHop>,
+	/// The amount delivered to the destination over this path, in millisatoshis.
+	#[prost(uint64, tag = "2")]
+	pub amount_msat: u64,
+	/// The routing fees paid along this path, in millisatoshis.
+	#[prost(uint64, tag = "3")]
+	pub fee_msat: u64,
+	/// The sum of the CLTV expiry deltas of all hops, i.e. the number of blocks an HTLC sent over this
+	/// path may be locked up for.
+	#[prost(uint32, tag = "4")]
+	pub total_cltv_expiry_delta: u32,
+}
+/// A hop of a `RoutePath`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RouteHop {
+	/// The hex-encoded public key of the node at this hop.
+	#[prost(string, tag = "1")]
+	pub node_id: ::prost::alloc::string::String,
+	/// The short channel id of the channel to the node at this hop.
+	#[prost(uint64, tag = "2")]
+	pub short_channel_id: u64,
+	/// The fee the node at this hop charges for forwarding over the next channel, in millisatoshis.
+	/// Zero for the destination.
+	#[prost(uint64, tag = "3")]
+	pub fee_msat: u64,
+	/// The CLTV expiry delta the node at this hop requires for forwarding over the next channel. For
+	/// the destination, its final CLTV expiry delta.
+	#[prost(uint32, tag = "4")]
+	pub cltv_expiry_delta: u32,
+}
+/// The estimated outcome of a probe for one path of a route.
+///
+/// LDK Node doesn't report the outcomes of probes, so they're inferred from how the pathfinding
+/// scores for the path change. LDK Node may also have sent the probe along another path.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProbeResult {
+	/// The path the probe was most likely sent along. A last hop over a likely unannounced channel
+	/// isn't probed, so the path then ends at the node before the destination.
+	#[prost(message, optional, tag = "1")]
+	pub path: ::core::option::Option<RoutePath>,
+	/// The status of the probe, as estimated from how the pathfinding scores changed.
+	#[prost(enumeration = "ProbeStatus", tag = "2")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_probe_status")
+	)]
+	pub estimated_status: i32,
+	/// The short channel ID of the channel the probe is estimated to have failed at. Only set if the
+	/// probe is estimated to have failed.
+	#[prost(uint64, optional, tag = "3")]
+	pub estimated_failed_short_channel_id: ::core::option::Option<u64>,
+}
 /// Routing fees for a channel as part of the network graph.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -1243,6 +1394,455 @@ pub struct CustomTlvRecord {
 	#[prost(bytes = "bytes", tag = "2")]
 	pub value: ::prost::bytes::Bytes,
 }
+/// Metadata of an API key. The key itself is only returned when it is created or rotated.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiKeyInfo {
+	/// The unique name of the API key.
+	#[prost(string, tag = "1")]
+	pub name: ::prost::alloc::string::String,
+	/// The permissions granted to the API key.
+	#[prost(enumeration = "ApiKeyScope", tag = "2")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_api_key_scope")
+	)]
+	pub scope: i32,
+	/// The maximum amount of a single payment sent with this API key, in millisatoshis.
+	///
+	/// Only set for API keys with the `SEND` scope that are limited.
+	#[prost(uint64, optional, tag = "3")]
+	pub max_send_amount_msat: ::core::option::Option<u64>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the API key was created or
+	/// last rotated.
+	///
+	/// Will be `None` for API keys defined in the config file.
+	#[prost(uint64, optional, tag = "4")]
+	pub created_at: ::core::option::Option<u64>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the API key was last used to
+	/// authenticate a request.
+	///
+	/// Will be `None` if the API key hasn't been used yet. The timestamp is persisted at most once a
+	/// minute, so it may lag behind after a restart.
+	#[prost(uint64, optional, tag = "5")]
+	pub last_used_at: ::core::option::Option<u64>,
+	/// The timestamp, in seconds since start of the UNIX epoch, until which the key replaced by the
+	/// last rotation is still accepted.
+	///
+	/// Will be `None` if there is no previous key that is still accepted.
+	#[prost(uint64, optional, tag = "6")]
+	pub previous_key_expires_at: ::core::option::Option<u64>,
+	/// Whether the API key is defined in the config file, in which case it can't be rotated or
+	/// revoked via the API.
+	#[prost(bool, tag = "7")]
+	pub from_config: bool,
+}
+/// A record of a state-changing RPC call, such as sending a payment or opening a channel.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AuditLogEntry {
+	/// The unique identifier of the entry.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// The name of the called RPC method, e.g. `OnchainSend`.
+	#[prost(string, tag = "2")]
+	pub method: ::prost::alloc::string::String,
+	/// The name of the API key or client certificate identity the call was authenticated as.
+	#[prost(string, tag = "3")]
+	pub caller: ::prost::alloc::string::String,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the call was received.
+	#[prost(uint64, tag = "4")]
+	pub timestamp: u64,
+	/// The request as JSON. Secrets such as payment preimages and passphrases are redacted and
+	/// overly long requests are truncated.
+	#[prost(string, tag = "5")]
+	pub request: ::prost::alloc::string::String,
+	/// The outcome of the call.
+	#[prost(enumeration = "AuditOutcome", tag = "6")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_audit_outcome")
+	)]
+	pub outcome: i32,
+	/// The error message returned to the caller, if the call failed.
+	#[prost(string, optional, tag = "7")]
+	pub error_message: ::core::option::Option<::prost::alloc::string::String>,
+}
+/// A send that exceeded the configured approval threshold and was parked until it is approved or
+/// rejected.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingAction {
+	/// The unique identifier of the action.
+	#[prost(string, tag = "1")]
+	pub id: ::prost::alloc::string::String,
+	/// The name of the RPC method that was called, e.g. `OnchainSend`.
+	#[prost(string, tag = "2")]
+	pub method: ::prost::alloc::string::String,
+	/// The name of the API key or client certificate identity that requested the action.
+	#[prost(string, tag = "3")]
+	pub requested_by: ::prost::alloc::string::String,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the action was requested.
+	#[prost(uint64, tag = "4")]
+	pub created_at: u64,
+	/// The amount to be sent, in millisatoshis.
+	///
+	/// Will be `None` if the amount isn't known before the send is executed.
+	#[prost(uint64, optional, tag = "5")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// The request as JSON. Secrets such as payment preimages are redacted.
+	#[prost(string, tag = "6")]
+	pub request: ::prost::alloc::string::String,
+	/// The status of the action.
+	#[prost(enumeration = "PendingActionStatus", tag = "7")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_pending_action_status")
+	)]
+	pub status: i32,
+	/// The name of the API key or client certificate identity that approved or rejected the action.
+	#[prost(string, optional, tag = "8")]
+	pub decided_by: ::core::option::Option<::prost::alloc::string::String>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the action was approved,
+	/// rejected or expired.
+	#[prost(uint64, optional, tag = "9")]
+	pub decided_at: ::core::option::Option<u64>,
+	/// The reason given for rejecting the action, or the error if executing it failed.
+	#[prost(string, optional, tag = "10")]
+	pub reason: ::core::option::Option<::prost::alloc::string::String>,
+	/// The payment ID or transaction ID of the executed send.
+	#[prost(string, optional, tag = "11")]
+	pub result: ::core::option::Option<::prost::alloc::string::String>,
+	/// The timestamp, in seconds since start of the UNIX epoch, after which the action can no longer
+	/// be approved.
+	#[prost(uint64, tag = "12")]
+	pub expires_at: u64,
+}
+/// A BOLT11 invoice issued by the server.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Invoice {
+	/// The hex-encoded payment hash of the invoice, which identifies it.
+	#[prost(string, tag = "1")]
+	pub payment_hash: ::prost::alloc::string::String,
+	/// The encoded invoice.
+	#[prost(string, tag = "2")]
+	pub invoice: ::prost::alloc::string::String,
+	/// The description the invoice was created with.
+	#[prost(message, optional, tag = "3")]
+	pub description: ::core::option::Option<Bolt11InvoiceDescription>,
+	/// The amount requested by the invoice, in millisatoshis.
+	///
+	/// Will be `None` for variable-amount invoices.
+	#[prost(uint64, optional, tag = "4")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// The label the invoice was created with, which is only known to the server.
+	#[prost(string, optional, tag = "5")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the invoice was created.
+	#[prost(uint64, tag = "6")]
+	pub created_at: u64,
+	/// The timestamp, in seconds since start of the UNIX epoch, after which the invoice can no
+	/// longer be paid.
+	#[prost(uint64, tag = "7")]
+	pub expires_at: u64,
+	/// The status of the invoice.
+	#[prost(enumeration = "InvoiceStatus", tag = "8")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_invoice_status")
+	)]
+	pub status: i32,
+	/// The ID of the payment that paid the invoice.
+	#[prost(string, optional, tag = "9")]
+	pub payment_id: ::core::option::Option<::prost::alloc::string::String>,
+	/// The amount received for the invoice, in millisatoshis.
+	#[prost(uint64, optional, tag = "10")]
+	pub amount_received_msat: ::core::option::Option<u64>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the payment for the invoice was
+	/// received or failed back.
+	#[prost(uint64, optional, tag = "11")]
+	pub settled_at: ::core::option::Option<u64>,
+	/// Free-form key/value metadata attached to the invoice, see `label`.
+	#[prost(btree_map = "string, string", tag = "12")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
+/// A BOLT12 offer issued by the server.
+///
+/// Unlike an invoice, an offer can be paid more than once, so it remains payable after it was
+/// first paid until it expires.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Offer {
+	/// The hex-encoded ID of the offer.
+	#[prost(string, tag = "1")]
+	pub offer_id: ::prost::alloc::string::String,
+	/// The encoded offer.
+	#[prost(string, tag = "2")]
+	pub offer: ::prost::alloc::string::String,
+	/// The description the offer was created with.
+	#[prost(string, tag = "3")]
+	pub description: ::prost::alloc::string::String,
+	/// The amount requested by the offer, in millisatoshis.
+	///
+	/// Will be `None` for variable-amount offers.
+	#[prost(uint64, optional, tag = "4")]
+	pub amount_msat: ::core::option::Option<u64>,
+	/// The number of items the offer was created for.
+	#[prost(uint64, optional, tag = "5")]
+	pub quantity: ::core::option::Option<u64>,
+	/// The label the offer was created with, which is only known to the server.
+	#[prost(string, optional, tag = "6")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the offer was created.
+	#[prost(uint64, tag = "7")]
+	pub created_at: u64,
+	/// The timestamp, in seconds since start of the UNIX epoch, after which the offer can no longer
+	/// be paid.
+	///
+	/// Will be `None` if the offer doesn't expire.
+	#[prost(uint64, optional, tag = "8")]
+	pub expires_at: ::core::option::Option<u64>,
+	/// The status of the offer. An offer is `INVOICE_STATUS_PAID` once at least one payment for it
+	/// was received.
+	#[prost(enumeration = "InvoiceStatus", tag = "9")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_invoice_status")
+	)]
+	pub status: i32,
+	/// The number of payments received for the offer.
+	#[prost(uint64, tag = "10")]
+	pub payment_count: u64,
+	/// The total amount received for the offer, in millisatoshis.
+	#[prost(uint64, tag = "11")]
+	pub amount_received_msat: u64,
+	/// The ID of the most recent payment received for the offer.
+	#[prost(string, optional, tag = "12")]
+	pub last_payment_id: ::core::option::Option<::prost::alloc::string::String>,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the most recent payment for the
+	/// offer was received.
+	#[prost(uint64, optional, tag = "13")]
+	pub last_paid_at: ::core::option::Option<u64>,
+}
+/// A payment for an invoice created via `Bolt11ReceiveForHash` that arrived and is waiting to be
+/// claimed via `Bolt11ClaimForHash` or failed back via `Bolt11FailForHash`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClaimablePayment {
+	/// The hex-encoded payment hash of the payment, which identifies it.
+	#[prost(string, tag = "1")]
+	pub payment_hash: ::prost::alloc::string::String,
+	/// The hex-encoded ID of the payment, see `GetPaymentDetails`.
+	#[prost(string, tag = "2")]
+	pub payment_id: ::prost::alloc::string::String,
+	/// The amount that can be claimed, in millisatoshis.
+	#[prost(uint64, tag = "3")]
+	pub claimable_amount_msat: u64,
+	/// The block height at which the payment is failed back and can no longer be claimed.
+	#[prost(uint32, optional, tag = "4")]
+	pub claim_deadline: ::core::option::Option<u32>,
+	/// The block height at which the server claims or fails back the payment if it is still
+	/// claimable, the configured safety margin before `claim_deadline`.
+	#[prost(uint32, optional, tag = "5")]
+	pub auto_resolve_height: ::core::option::Option<u32>,
+	/// Whether the payment is claimed with the `auto_claim_preimage` registered with its invoice at
+	/// `auto_resolve_height`, rather than failed back.
+	#[prost(bool, tag = "6")]
+	pub auto_claim: bool,
+	/// The timestamp, in seconds since start of the UNIX epoch, when the payment became claimable.
+	#[prost(uint64, tag = "7")]
+	pub created_at: u64,
+}
+/// A free-form label and key/value metadata attached to a payment or channel.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UserLabel {
+	/// The label, if one is set.
+	#[prost(string, optional, tag = "1")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata.
+	#[prost(btree_map = "string, string", tag = "2")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CounterpartyForceClosedDetails {
+	#[prost(string, tag = "1")]
+	pub peer_msg: ::prost::alloc::string::String,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HolderForceClosedDetails {
+	#[prost(bool, optional, tag = "1")]
+	pub broadcasted_latest_txn: ::core::option::Option<bool>,
+	#[prost(string, tag = "2")]
+	pub message: ::prost::alloc::string::String,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ProcessingErrorDetails {
+	#[prost(string, tag = "1")]
+	pub err: ::prost::alloc::string::String,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HtlcsTimedOutDetails {
+	#[prost(string, optional, tag = "1")]
+	pub payment_hash: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PeerFeerateTooLowDetails {
+	#[prost(uint32, tag = "1")]
+	pub peer_feerate_sat_per_kw: u32,
+	#[prost(uint32, tag = "2")]
+	pub required_feerate_sat_per_kw: u32,
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChannelStateChangeReason {
+	#[prost(enumeration = "ChannelStateChangeReasonKind", tag = "1")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_channel_state_change_reason_kind")
+	)]
+	pub kind: i32,
+	#[prost(string, tag = "2")]
+	pub message: ::prost::alloc::string::String,
+	#[prost(oneof = "channel_state_change_reason::Details", tags = "3, 4, 5, 6, 7")]
+	pub details: ::core::option::Option<channel_state_change_reason::Details>,
+}
+/// Nested message and enum types in `ChannelStateChangeReason`.
+pub mod channel_state_change_reason {
+	#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+	#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+	#[allow(clippy::derive_partial_eq_without_eq)]
+	#[derive(Clone, PartialEq, ::prost::Oneof)]
+	pub enum Details {
+		#[prost(message, tag = "3")]
+		CounterpartyForceClosed(super::CounterpartyForceClosedDetails),
+		#[prost(message, tag = "4")]
+		HolderForceClosed(super::HolderForceClosedDetails),
+		#[prost(message, tag = "5")]
+		ProcessingError(super::ProcessingErrorDetails),
+		#[prost(message, tag = "6")]
+		HtlcsTimedOut(super::HtlcsTimedOutDetails),
+		#[prost(message, tag = "7")]
+		PeerFeerateTooLow(super::PeerFeerateTooLowDetails),
+	}
+}
+/// A channel that was closed, as recorded by the server when it processed the closure.
+///
+/// The capacity, balance and direction are taken from the channel's details as last observed while
+/// it was open, and are unset if the server never observed the channel open.
+///
+/// The closing transaction's ID is not recorded, as LDK Node does not report it when a channel
+/// closes. It can be found by looking up the spend of `funding_txo`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ClosedChannel {
+	/// The channel's ID.
+	#[prost(string, tag = "1")]
+	pub channel_id: ::prost::alloc::string::String,
+	/// The local `user_channel_id` of the channel.
+	#[prost(string, tag = "2")]
+	pub user_channel_id: ::prost::alloc::string::String,
+	/// The node ID of the channel's counterparty.
+	#[prost(string, tag = "3")]
+	pub counterparty_node_id: ::prost::alloc::string::String,
+	/// The channel's funding output. The channel was closed by the transaction spending it.
+	#[prost(message, optional, tag = "4")]
+	pub funding_txo: ::core::option::Option<OutPoint>,
+	/// The channel's total capacity.
+	#[prost(uint64, optional, tag = "5")]
+	pub channel_value_sats: ::core::option::Option<u64>,
+	/// Our balance in the channel as last observed before it closed, including our channel reserve
+	/// and excluding pending HTLCs.
+	#[prost(uint64, optional, tag = "6")]
+	pub last_local_balance_msat: ::core::option::Option<u64>,
+	/// Whether we initiated the channel.
+	#[prost(bool, optional, tag = "7")]
+	pub is_outbound: ::core::option::Option<bool>,
+	/// Either `CHANNEL_STATE_CLOSED`, or `CHANNEL_STATE_OPEN_FAILED` if the channel closed before it
+	/// became ready.
+	#[prost(enumeration = "ChannelState", tag = "8")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_channel_state")
+	)]
+	pub state: i32,
+	/// Why the channel was closed.
+	#[prost(message, optional, tag = "9")]
+	pub reason: ::core::option::Option<ChannelStateChangeReason>,
+	/// Which side initiated the closure.
+	#[prost(enumeration = "ChannelClosureInitiator", tag = "10")]
+	#[cfg_attr(
+		feature = "serde",
+		serde(serialize_with = "crate::serde_utils::serialize_channel_closure_initiator")
+	)]
+	pub closure_initiator: i32,
+	/// The time at which the channel was closed, in seconds since the UNIX epoch.
+	#[prost(uint64, tag = "11")]
+	pub closed_at: u64,
+	/// The label attached to the channel, if any.
+	#[prost(string, optional, tag = "12")]
+	pub label: ::core::option::Option<::prost::alloc::string::String>,
+	/// Free-form key/value metadata attached to the channel.
+	#[prost(btree_map = "string, string", tag = "13")]
+	pub metadata: ::prost::alloc::collections::BTreeMap<
+		::prost::alloc::string::String,
+		::prost::alloc::string::String,
+	>,
+}
 /// Represents the direction of a payment.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -1309,6 +1909,49 @@ impl PaymentStatus {
 		}
 	}
 }
+/// The kind of a payment, without its details. See `PaymentKind`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PaymentKindType {
+	/// An on-chain payment.
+	Onchain = 0,
+	/// A BOLT 11 payment.
+	Bolt11 = 1,
+	/// A BOLT 12 offer payment.
+	Bolt12Offer = 2,
+	/// A BOLT 12 refund payment.
+	Bolt12Refund = 3,
+	/// A spontaneous ("keysend") payment.
+	Spontaneous = 4,
+}
+impl PaymentKindType {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			PaymentKindType::Onchain => "PAYMENT_KIND_TYPE_ONCHAIN",
+			PaymentKindType::Bolt11 => "PAYMENT_KIND_TYPE_BOLT11",
+			PaymentKindType::Bolt12Offer => "PAYMENT_KIND_TYPE_BOLT12_OFFER",
+			PaymentKindType::Bolt12Refund => "PAYMENT_KIND_TYPE_BOLT12_REFUND",
+			PaymentKindType::Spontaneous => "PAYMENT_KIND_TYPE_SPONTANEOUS",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"PAYMENT_KIND_TYPE_ONCHAIN" => Some(Self::Onchain),
+			"PAYMENT_KIND_TYPE_BOLT11" => Some(Self::Bolt11),
+			"PAYMENT_KIND_TYPE_BOLT12_OFFER" => Some(Self::Bolt12Offer),
+			"PAYMENT_KIND_TYPE_BOLT12_REFUND" => Some(Self::Bolt12Refund),
+			"PAYMENT_KIND_TYPE_SPONTANEOUS" => Some(Self::Spontaneous),
+			_ => None,
+		}
+	}
+}
 /// The Bitcoin network the node is running on.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
@@ -1392,6 +2035,46 @@ impl BalanceSource {
 		}
 	}
 }
+/// Represents the status of a probe.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ProbeStatus {
+	/// No outcome was observed yet. The probe may still lock liquidity along its path.
+	Pending = 0,
+	/// The scores indicate the probe reached the end of its path, so the path had enough liquidity.
+	Succeeded = 1,
+	/// The scores indicate the probe failed at a channel without enough liquidity.
+	Failed = 2,
+	/// The path wasn't probed, as it has less than two hops or probing it could use up too much of
+	/// the liquidity of its first channel.
+	Skipped = 3,
+}
+impl ProbeStatus {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ProbeStatus::Pending => "PROBE_STATUS_PENDING",
+			ProbeStatus::Succeeded => "PROBE_STATUS_SUCCEEDED",
+			ProbeStatus::Failed => "PROBE_STATUS_FAILED",
+			ProbeStatus::Skipped => "PROBE_STATUS_SKIPPED",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"PROBE_STATUS_PENDING" => Some(Self::Pending),
+			"PROBE_STATUS_SUCCEEDED" => Some(Self::Succeeded),
+			"PROBE_STATUS_FAILED" => Some(Self::Failed),
+			"PROBE_STATUS_SKIPPED" => Some(Self::Skipped),
+			_ => None,
+		}
+	}
+}
 /// Identifies one of the two endpoints of a channel, by lexicographic order of
 /// node ids.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -1424,3 +2107,353 @@ impl ChannelDirection {
 		}
 	}
 }
+/// The permissions granted to an API key.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ApiKeyScope {
+	/// Only RPCs that read node state, e.g. `GetNodeInfo`, `ListPayments` and `SubscribeEvents`.
+	ReadOnly = 0,
+	/// `READ_ONLY`, plus creating and settling invoices, offers and on-chain addresses.
+	InvoiceOnly = 1,
+	/// `INVOICE_ONLY`, plus sending payments, optionally limited to a maximum amount per payment.
+	Send = 2,
+	/// Every RPC, including channel, peer and API key management.
+	Admin = 3,
+}
+impl ApiKeyScope {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ApiKeyScope::ReadOnly => "READ_ONLY",
+			ApiKeyScope::InvoiceOnly => "INVOICE_ONLY",
+			ApiKeyScope::Send => "SEND",
+			ApiKeyScope::Admin => "ADMIN",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"READ_ONLY" => Some(Self::ReadOnly),
+			"INVOICE_ONLY" => Some(Self::InvoiceOnly),
+			"SEND" => Some(Self::Send),
+			"ADMIN" => Some(Self::Admin),
+			_ => None,
+		}
+	}
+}
+/// The outcome of an audited RPC call.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AuditOutcome {
+	/// The call was recorded, but its outcome wasn't, e.g. because the server stopped while
+	/// handling it.
+	Incomplete = 0,
+	/// The call succeeded.
+	Success = 1,
+	/// The call failed.
+	Failure = 2,
+}
+impl AuditOutcome {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			AuditOutcome::Incomplete => "INCOMPLETE",
+			AuditOutcome::Success => "SUCCESS",
+			AuditOutcome::Failure => "FAILURE",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"INCOMPLETE" => Some(Self::Incomplete),
+			"SUCCESS" => Some(Self::Success),
+			"FAILURE" => Some(Self::Failure),
+			_ => None,
+		}
+	}
+}
+/// The status of an action awaiting approval.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PendingActionStatus {
+	/// The action is waiting to be approved or rejected.
+	AwaitingApproval = 0,
+	/// The action was approved and executed successfully.
+	Approved = 1,
+	/// The action was rejected.
+	Rejected = 2,
+	/// The action was neither approved nor rejected in time.
+	Expired = 3,
+	/// The action was approved, but executing it failed.
+	Failed = 4,
+}
+impl PendingActionStatus {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			PendingActionStatus::AwaitingApproval => "PENDING_ACTION_STATUS_AWAITING_APPROVAL",
+			PendingActionStatus::Approved => "PENDING_ACTION_STATUS_APPROVED",
+			PendingActionStatus::Rejected => "PENDING_ACTION_STATUS_REJECTED",
+			PendingActionStatus::Expired => "PENDING_ACTION_STATUS_EXPIRED",
+			PendingActionStatus::Failed => "PENDING_ACTION_STATUS_FAILED",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"PENDING_ACTION_STATUS_AWAITING_APPROVAL" => Some(Self::AwaitingApproval),
+			"PENDING_ACTION_STATUS_APPROVED" => Some(Self::Approved),
+			"PENDING_ACTION_STATUS_REJECTED" => Some(Self::Rejected),
+			"PENDING_ACTION_STATUS_EXPIRED" => Some(Self::Expired),
+			"PENDING_ACTION_STATUS_FAILED" => Some(Self::Failed),
+			_ => None,
+		}
+	}
+}
+/// The status of an invoice or offer issued by the server.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum InvoiceStatus {
+	/// The invoice or offer can still be paid.
+	Open = 0,
+	/// A payment for the invoice or offer was received.
+	Paid = 1,
+	/// The invoice or offer expired without being paid.
+	Expired = 2,
+	/// The payment for the invoice was failed back via `Bolt11FailForHash`.
+	Cancelled = 3,
+}
+impl InvoiceStatus {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			InvoiceStatus::Open => "INVOICE_STATUS_OPEN",
+			InvoiceStatus::Paid => "INVOICE_STATUS_PAID",
+			InvoiceStatus::Expired => "INVOICE_STATUS_EXPIRED",
+			InvoiceStatus::Cancelled => "INVOICE_STATUS_CANCELLED",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"INVOICE_STATUS_OPEN" => Some(Self::Open),
+			"INVOICE_STATUS_PAID" => Some(Self::Paid),
+			"INVOICE_STATUS_EXPIRED" => Some(Self::Expired),
+			"INVOICE_STATUS_CANCELLED" => Some(Self::Cancelled),
+			_ => None,
+		}
+	}
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChannelState {
+	Unspecified = 0,
+	Pending = 1,
+	Ready = 2,
+	OpenFailed = 3,
+	Closed = 4,
+}
+impl ChannelState {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ChannelState::Unspecified => "CHANNEL_STATE_UNSPECIFIED",
+			ChannelState::Pending => "CHANNEL_STATE_PENDING",
+			ChannelState::Ready => "CHANNEL_STATE_READY",
+			ChannelState::OpenFailed => "CHANNEL_STATE_OPEN_FAILED",
+			ChannelState::Closed => "CHANNEL_STATE_CLOSED",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"CHANNEL_STATE_UNSPECIFIED" => Some(Self::Unspecified),
+			"CHANNEL_STATE_PENDING" => Some(Self::Pending),
+			"CHANNEL_STATE_READY" => Some(Self::Ready),
+			"CHANNEL_STATE_OPEN_FAILED" => Some(Self::OpenFailed),
+			"CHANNEL_STATE_CLOSED" => Some(Self::Closed),
+			_ => None,
+		}
+	}
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChannelClosureInitiator {
+	Unspecified = 0,
+	Local = 1,
+	Remote = 2,
+	Unknown = 3,
+}
+impl ChannelClosureInitiator {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ChannelClosureInitiator::Unspecified => "CHANNEL_CLOSURE_INITIATOR_UNSPECIFIED",
+			ChannelClosureInitiator::Local => "CHANNEL_CLOSURE_INITIATOR_LOCAL",
+			ChannelClosureInitiator::Remote => "CHANNEL_CLOSURE_INITIATOR_REMOTE",
+			ChannelClosureInitiator::Unknown => "CHANNEL_CLOSURE_INITIATOR_UNKNOWN",
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"CHANNEL_CLOSURE_INITIATOR_UNSPECIFIED" => Some(Self::Unspecified),
+			"CHANNEL_CLOSURE_INITIATOR_LOCAL" => Some(Self::Local),
+			"CHANNEL_CLOSURE_INITIATOR_REMOTE" => Some(Self::Remote),
+			"CHANNEL_CLOSURE_INITIATOR_UNKNOWN" => Some(Self::Unknown),
+			_ => None,
+		}
+	}
+}
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChannelStateChangeReasonKind {
+	Unspecified = 0,
+	CounterpartyForceClosed = 1,
+	HolderForceClosed = 2,
+	LegacyCooperativeClosure = 3,
+	CounterpartyInitiatedCooperativeClosure = 4,
+	LocallyInitiatedCooperativeClosure = 5,
+	CommitmentTxConfirmed = 6,
+	FundingTimedOut = 7,
+	ProcessingError = 8,
+	DisconnectedPeer = 9,
+	OutdatedChannelManager = 10,
+	CounterpartyCoopClosedUnfundedChannel = 11,
+	LocallyCoopClosedUnfundedChannel = 12,
+	FundingBatchClosure = 13,
+	HtlcsTimedOut = 14,
+	PeerFeerateTooLow = 15,
+}
+impl ChannelStateChangeReasonKind {
+	/// String value of the enum field names used in the ProtoBuf definition.
+	///
+	/// The values are not transformed in any way and thus are considered stable
+	/// (if the ProtoBuf definition does not change) and safe for programmatic use.
+	pub fn as_str_name(&self) -> &'static str {
+		match self {
+			ChannelStateChangeReasonKind::Unspecified => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_UNSPECIFIED"
+			},
+			ChannelStateChangeReasonKind::CounterpartyForceClosed => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_FORCE_CLOSED"
+			},
+			ChannelStateChangeReasonKind::HolderForceClosed => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_HOLDER_FORCE_CLOSED"
+			},
+			ChannelStateChangeReasonKind::LegacyCooperativeClosure => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_LEGACY_COOPERATIVE_CLOSURE"
+			},
+			ChannelStateChangeReasonKind::CounterpartyInitiatedCooperativeClosure => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_INITIATED_COOPERATIVE_CLOSURE"
+			},
+			ChannelStateChangeReasonKind::LocallyInitiatedCooperativeClosure => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_INITIATED_COOPERATIVE_CLOSURE"
+			},
+			ChannelStateChangeReasonKind::CommitmentTxConfirmed => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_COMMITMENT_TX_CONFIRMED"
+			},
+			ChannelStateChangeReasonKind::FundingTimedOut => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_TIMED_OUT"
+			},
+			ChannelStateChangeReasonKind::ProcessingError => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_PROCESSING_ERROR"
+			},
+			ChannelStateChangeReasonKind::DisconnectedPeer => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_DISCONNECTED_PEER"
+			},
+			ChannelStateChangeReasonKind::OutdatedChannelManager => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_OUTDATED_CHANNEL_MANAGER"
+			},
+			ChannelStateChangeReasonKind::CounterpartyCoopClosedUnfundedChannel => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_COOP_CLOSED_UNFUNDED_CHANNEL"
+			},
+			ChannelStateChangeReasonKind::LocallyCoopClosedUnfundedChannel => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_COOP_CLOSED_UNFUNDED_CHANNEL"
+			},
+			ChannelStateChangeReasonKind::FundingBatchClosure => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_BATCH_CLOSURE"
+			},
+			ChannelStateChangeReasonKind::HtlcsTimedOut => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_HTLCS_TIMED_OUT"
+			},
+			ChannelStateChangeReasonKind::PeerFeerateTooLow => {
+				"CHANNEL_STATE_CHANGE_REASON_KIND_PEER_FEERATE_TOO_LOW"
+			},
+		}
+	}
+	/// Creates an enum from field names used in the ProtoBuf definition.
+	pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+		match value {
+			"CHANNEL_STATE_CHANGE_REASON_KIND_UNSPECIFIED" => Some(Self::Unspecified),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_FORCE_CLOSED" => {
+				Some(Self::CounterpartyForceClosed)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_HOLDER_FORCE_CLOSED" => Some(Self::HolderForceClosed),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_LEGACY_COOPERATIVE_CLOSURE" => {
+				Some(Self::LegacyCooperativeClosure)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_INITIATED_COOPERATIVE_CLOSURE" => {
+				Some(Self::CounterpartyInitiatedCooperativeClosure)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_INITIATED_COOPERATIVE_CLOSURE" => {
+				Some(Self::LocallyInitiatedCooperativeClosure)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_COMMITMENT_TX_CONFIRMED" => {
+				Some(Self::CommitmentTxConfirmed)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_TIMED_OUT" => Some(Self::FundingTimedOut),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_PROCESSING_ERROR" => Some(Self::ProcessingError),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_DISCONNECTED_PEER" => Some(Self::DisconnectedPeer),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_OUTDATED_CHANNEL_MANAGER" => {
+				Some(Self::OutdatedChannelManager)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_COUNTERPARTY_COOP_CLOSED_UNFUNDED_CHANNEL" => {
+				Some(Self::CounterpartyCoopClosedUnfundedChannel)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_LOCALLY_COOP_CLOSED_UNFUNDED_CHANNEL" => {
+				Some(Self::LocallyCoopClosedUnfundedChannel)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_FUNDING_BATCH_CLOSURE" => {
+				Some(Self::FundingBatchClosure)
+			},
+			"CHANNEL_STATE_CHANGE_REASON_KIND_HTLCS_TIMED_OUT" => Some(Self::HtlcsTimedOut),
+			"CHANNEL_STATE_CHANGE_REASON_KIND_PEER_FEERATE_TOO_LOW" => {
+				Some(Self::PeerFeerateTooLow)
+			},
+			_ => None,
+		}
+	}
+}
@@ -10,21 +10,51 @@
 use std::path::PathBuf;
 
 use ldk_server_client::config::{
-	get_default_config_path, load_config, resolve_api_key, resolve_base_url, resolve_cert_path,
+	get_default_config_path, is_unix_socket_url, load_config, resolve_api_key, resolve_base_url,
+	resolve_cert_path,
 };
 
 pub struct ResolvedConfig {
 	pub base_url: String,
-	pub api_key: String,
-	pub tls_cert_pem: Vec<u8>,
+	/// The API key requests are signed with. Only `None` if a client certificate is configured.
+	pub api_key: Option<String>,
+	/// The server's TLS certificate. Only `None` when connecting to a Unix socket without TLS.
+	pub tls_cert_pem: Option<Vec<u8>>,
+	pub tls_client_cert: Option<ClientCertPem>,
+}
+
+/// A TLS client certificate and its private key, for servers requiring mutual TLS.
+pub struct ClientCertPem {
+	pub cert_pem: Vec<u8>,
+	pub key_pem: Vec<u8>,
 }
 
 pub fn resolve_config(config_path: Option<String>) -> Result<ResolvedConfig, String> {
 	let env_base_url = std::env::var("LDK_BASE_URL").ok();
 	let env_api_key = std::env::var("LDK_API_KEY").ok();
 	let env_tls_cert_path = std::env::var("LDK_TLS_CERT_PATH").ok().map(PathBuf::from);
-	let env_overrides_complete =
-		env_base_url.is_some() && env_api_key.is_some() && env_tls_cert_path.is_some();
+	let env_tls_client_cert_path = std::env::var("LDK_TLS_CLIENT_CERT_PATH").ok();
+	let env_tls_client_key_path = std::env::var("LDK_TLS_CLIENT_KEY_PATH").ok();
+
+	let tls_client_cert = match (env_tls_client_cert_path, env_tls_client_key_path) {
+		(Some(cert_path), Some(key_path)) => {
+			let cert_pem = std::fs::read(&cert_path).map_err(|e| {
+				format!("Failed to read client certificate file '{cert_path}': {e}")
+			})?;
+			let key_pem = std::fs::read(&key_path)
+				.map_err(|e| format!("Failed to read client key file '{key_path}': {e}"))?;
+			Some(ClientCertPem { cert_pem, key_pem })
+		},
+		(None, None) => None,
+		_ => {
+			return Err("LDK_TLS_CLIENT_CERT_PATH and LDK_TLS_CLIENT_KEY_PATH must be set together"
+				.to_string())
+		},
+	};
+
+	let env_overrides_complete = env_base_url.is_some()
+		&& (env_api_key.is_some() || tls_client_cert.is_some())
+		&& env_tls_cert_path.is_some();
 
 	let explicit_config_path = config_path.map(PathBuf::from);
 	let config_path = explicit_config_path.clone().or_else(get_default_config_path);
@@ -39,9 +69,26 @@ pub fn resolve_config(config_path: Option<String>) -> Result<ResolvedConfig, Str
 
 	let base_url = resolve_base_url(env_base_url, config.as_ref());
 
-	let api_key = resolve_api_key(env_api_key, config.as_ref()).ok_or_else(
-		|| "API key not provided. Set LDK_API_KEY or ensure the api_key file exists at ~/.ldk-server/[network]/api_key".to_string()
-	)?;
+	// Access to a Unix socket is governed by its file permissions, so requests on it are only
+	// signed if an API key is given explicitly, and TLS is only used if a certificate is.
+	if is_unix_socket_url(&base_url) && env_tls_cert_path.is_none() && tls_client_cert.is_none() {
+		return Ok(ResolvedConfig {
+			base_url,
+			api_key: env_api_key,
+			tls_cert_pem: None,
+			tls_client_cert,
+		});
+	}
+
+	// With a client certificate, requests are only signed if an API key is given explicitly, as
+	// the admin key from the default location would override the certificate's identity.
+	let api_key = if tls_client_cert.is_some() {
+		env_api_key
+	} else {
+		Some(resolve_api_key(env_api_key, config.as_ref()).ok_or_else(
+			|| "API key not provided. Set LDK_API_KEY or ensure the api_key file exists at ~/.ldk-server/[network]/api_key".to_string()
+		)?)
+	};
 
 	let tls_cert_path = resolve_cert_path(env_tls_cert_path, config.as_ref()).ok_or_else(|| {
 		"TLS cert path not provided. Set LDK_TLS_CERT_PATH or ensure config file exists at ~/.ldk-server/config.toml"
@@ -52,7 +99,7 @@ pub fn resolve_config(config_path: Option<String>) -> Result<ResolvedConfig, Str
 		format!("Failed to read server certificate file '{}': {}", tls_cert_path.display(), e)
 	})?;
 
-	Ok(ResolvedConfig { base_url, api_key, tls_cert_pem })
+	Ok(ResolvedConfig { base_url, api_key, tls_cert_pem: Some(tls_cert_pem), tls_client_cert })
 }
 
 #[cfg(test)]
@@ -124,8 +171,8 @@ mod tests {
 		std::env::remove_var("LDK_TLS_CERT_PATH");
 
 		assert_eq!(resolved.base_url, "127.0.0.1:4242");
-		assert_eq!(resolved.api_key, "deadbeef");
-		assert_eq!(resolved.tls_cert_pem, b"test-cert");
+		assert_eq!(resolved.api_key.as_deref(), Some("deadbeef"));
+		assert_eq!(resolved.tls_cert_pem.as_deref(), Some(&b"test-cert"[..]));
 
 		std::fs::remove_dir_all(temp_dir).unwrap();
 	}
@@ -183,8 +230,8 @@ mod tests {
 		restore_env_var(&default_dir_env_var, old_default_dir);
 
 		assert_eq!(resolved.base_url, "127.0.0.1:4242");
-		assert_eq!(resolved.api_key, "deadbeef");
-		assert_eq!(resolved.tls_cert_pem, b"env-cert");
+		assert_eq!(resolved.api_key.as_deref(), Some("deadbeef"));
+		assert_eq!(resolved.tls_cert_pem.as_deref(), Some(&b"env-cert"[..]));
 
 		std::fs::remove_dir_all(temp_dir).unwrap();
 	}
@@ -226,8 +273,78 @@ mod tests {
 		let resolved = resolve_config(Some(config_path.display().to_string())).unwrap();
 
 		assert_eq!(resolved.base_url, DEFAULT_GRPC_SERVICE_ADDRESS);
-		assert_eq!(resolved.api_key, "abcd");
-		assert_eq!(resolved.tls_cert_pem, b"storage-cert");
+		assert_eq!(resolved.api_key.as_deref(), Some("abcd"));
+		assert_eq!(resolved.tls_cert_pem.as_deref(), Some(&b"storage-cert"[..]));
+
+		std::fs::remove_dir_all(temp_dir).unwrap();
+	}
+
+	#[test]
+	fn resolve_config_uses_client_cert_without_api_key() {
+		let _lock = ENV_LOCK.lock().unwrap();
+
+		let temp_dir =
+			std::env::temp_dir().join(format!("ldk-server-mcp-client-cert-{}", std::process::id()));
+		std::fs::create_dir_all(&temp_dir).unwrap();
+
+		let cert_path = temp_dir.join("tls.crt");
+		let client_cert_path = temp_dir.join("client.crt");
+		let client_key_path = temp_dir.join("client.key");
+		std::fs::write(&cert_path, b"test-cert").unwrap();
+		std::fs::write(&client_cert_path, b"client-cert").unwrap();
+		std::fs::write(&client_key_path, b"client-key").unwrap();
+
+		std::env::remove_var("LDK_API_KEY");
+		std::env::remove_var("LDK_BASE_URL");
+		std::env::set_var("LDK_TLS_CERT_PATH", &cert_path);
+		std::env::set_var("LDK_TLS_CLIENT_CERT_PATH", &client_cert_path);
+		let missing_key =
+			resolve_config(Some(temp_dir.join("nonexistent.toml").display().to_string()));
+		std::env::set_var("LDK_TLS_CLIENT_KEY_PATH", &client_key_path);
+		let resolved =
+			resolve_config(Some(temp_dir.join("nonexistent.toml").display().to_string())).unwrap();
+		std::env::remove_var("LDK_TLS_CERT_PATH");
+		std::env::remove_var("LDK_TLS_CLIENT_CERT_PATH");
+		std::env::remove_var("LDK_TLS_CLIENT_KEY_PATH");
+
+		assert!(missing_key.is_err());
+		assert_eq!(resolved.api_key, None);
+		let tls_client_cert = resolved.tls_client_cert.unwrap();
+		assert_eq!(tls_client_cert.cert_pem, b"client-cert");
+		assert_eq!(tls_client_cert.key_pem, b"client-key");
+
+		std::fs::remove_dir_all(temp_dir).unwrap();
+	}
+
+	#[test]
+	fn resolve_config_connects_to_unix_socket_without_tls() {
+		let _lock = ENV_LOCK.lock().unwrap();
+
+		let temp_dir =
+			std::env::temp_dir().join(format!("ldk-server-mcp-unix-socket-{}", std::process::id()));
+		std::fs::create_dir_all(&temp_dir).unwrap();
+		let config_path = temp_dir.join("nonexistent.toml").display().to_string();
+
+		std::env::remove_var("LDK_API_KEY");
+		std::env::remove_var("LDK_TLS_CERT_PATH");
+		std::env::set_var("LDK_BASE_URL", "unix:///run/ldk-server/grpc.sock");
+		let resolved = resolve_config(Some(config_path.clone())).unwrap();
+		assert_eq!(resolved.base_url, "unix:///run/ldk-server/grpc.sock");
+		assert_eq!(resolved.api_key, None);
+		assert!(resolved.tls_cert_pem.is_none());
+
+		// A server certificate enables TLS on the socket.
+		let cert_path = temp_dir.join("tls.crt");
+		std::fs::write(&cert_path, b"test-cert").unwrap();
+		std::env::set_var("LDK_API_KEY", "deadbeef");
+		std::env::set_var("LDK_TLS_CERT_PATH", &cert_path);
+		let resolved = resolve_config(Some(config_path)).unwrap();
+		std::env::remove_var("LDK_API_KEY");
+		std::env::remove_var("LDK_TLS_CERT_PATH");
+		std::env::remove_var("LDK_BASE_URL");
+
+		assert_eq!(resolved.api_key.as_deref(), Some("deadbeef"));
+		assert_eq!(resolved.tls_cert_pem.as_deref(), Some(&b"test-cert"[..]));
 
 		std::fs::remove_dir_all(temp_dir).unwrap();
 	}
@@ -52,7 +52,21 @@ async fn main() {
 		},
 	};
 
-	let client = match LdkServerClient::new(cfg.base_url, cfg.api_key, &cfg.tls_cert_pem) {
+	let client = match (cfg.tls_cert_pem, cfg.tls_client_cert) {
+		(None, _) => LdkServerClient::new_unix_socket(cfg.base_url, cfg.api_key),
+		(Some(tls_cert_pem), Some(client_cert)) => LdkServerClient::new_with_client_cert(
+			cfg.base_url,
+			cfg.api_key,
+			&tls_cert_pem,
+			&client_cert.cert_pem,
+			&client_cert.key_pem,
+		),
+		(Some(tls_cert_pem), None) => match cfg.api_key {
+			Some(api_key) => LdkServerClient::new(cfg.base_url, api_key, &tls_cert_pem),
+			None => Err("API key not provided".to_string()),
+		},
+	};
+	let client = match client {
 		Ok(c) => c,
 		Err(e) => {
 			eprintln!("Error: Failed to create client: {e}");
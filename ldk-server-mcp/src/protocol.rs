@@ -50,6 +50,8 @@ impl From<LdkServerError> for McpError {
 			LdkServerErrorCode::AuthError
 			| LdkServerErrorCode::LightningError
 			| LdkServerErrorCode::InternalServerError
+			| LdkServerErrorCode::PermissionDeniedError
+			| LdkServerErrorCode::SpendLimitExceededError
 			| LdkServerErrorCode::InternalError => INTERNAL_ERROR,
 		};
 		Self { code, message: e.message }
@@ -14,15 +14,20 @@ use ldk_server_client::ldk_server_grpc::api::{
 	Bolt11ReceiveRequest, Bolt11ReceiveVariableAmountViaJitChannelRequest,
 	Bolt11ReceiveViaJitChannelRequest, Bolt11SendRequest, Bolt11SendUnderpayingRequest,
 	Bolt12ReceiveRequest, Bolt12SendRequest, CloseChannelRequest, ConnectPeerRequest,
-	DecodeInvoiceRequest, DecodeOfferRequest, DisconnectPeerRequest,
-	ExportPathfindingScoresRequest, ForceCloseChannelRequest, GetBalancesRequest,
-	GetNodeInfoRequest, GetPaymentDetailsRequest, GraphGetChannelRequest, GraphGetNodeRequest,
-	GraphListChannelsRequest, GraphListNodesRequest, ListChannelsRequest,
-	ListForwardedPaymentsRequest, ListPaymentsRequest, ListPeersRequest, OnchainReceiveRequest,
-	OnchainSendRequest, OpenChannelRequest, SignMessageRequest, SpliceInRequest, SpliceOutRequest,
-	SpontaneousSendRequest, UnifiedSendRequest, UpdateChannelConfigRequest, VerifySignatureRequest,
+	DecodeInvoiceRequest, DecodeOfferRequest, DisconnectPeerRequest, EstimateRouteFeeRequest,
+	ExportPathfindingScoresRequest, FindPaymentsRequest, ForceCloseChannelRequest,
+	GetBalancesRequest, GetNodeInfoRequest, GetPaymentDetailsRequest, GraphGetChannelRequest,
+	GraphGetNodeRequest, GraphListChannelsRequest, GraphListNodesRequest, ListChannelsRequest,
+	ListClaimablePaymentsRequest, ListClosedChannelsRequest, ListForwardedPaymentsRequest,
+	ListInvoicesRequest, ListOffersRequest, ListOnchainTransactionsRequest, ListPaymentsRequest,
+	ListPeersRequest, OnchainReceiveRequest, OnchainSendRequest, OpenChannelRequest,
+	QueryRoutesRequest, SendProbesRequest, SignMessageRequest, SpliceInRequest, SpliceOutRequest,
+	SpontaneousSendRequest, UnifiedSendRequest, UpdateChannelConfigRequest, UpdateLabelRequest,
+	VerifySignatureRequest,
+};
+use ldk_server_client::ldk_server_grpc::types::{
+	PaymentDirection, PaymentKindType, PaymentStatus, RouteParametersConfig,
 };
-use ldk_server_client::ldk_server_grpc::types::RouteParametersConfig;
 use ldk_server_client::{
 	DEFAULT_EXPIRY_SECS, DEFAULT_MAX_CHANNEL_SATURATION_POWER_OF_HALF, DEFAULT_MAX_PATH_COUNT,
 	DEFAULT_MAX_TOTAL_CLTV_EXPIRY_DELTA,
@@ -64,6 +69,48 @@ fn parse_request_with_amount<T: DeserializeOwned>(
 	parse_request(args)
 }
 
+/// Looks up the numeric value of an uppercase enum value name.
+type EnumFromName = fn(&str) -> Option<i32>;
+
+/// Replaces the lowercase enum value names in the given fields of `args` with their numeric
+/// values, looking them up with the given function.
+fn parse_request_with_enum_names<T: DeserializeOwned>(
+	mut args: Value, fields: &[(&str, EnumFromName)],
+) -> Result<T, McpError> {
+	if let Some(args) = args.as_object_mut() {
+		for (field, from_name) in fields {
+			let Some(Value::String(name)) = args.get(*field) else { continue };
+			let value = from_name(&name.to_ascii_uppercase())
+				.ok_or_else(|| McpError::invalid_params(format!("Unknown `{field}`: {name}")))?;
+			args.insert(field.to_string(), json!(value));
+		}
+	}
+	parse_request(args)
+}
+
+/// Moves the one of the given fields that is set in `args` into an object under `oneof_field`, as
+/// oneofs are deserialized.
+fn parse_request_with_oneof<T: DeserializeOwned>(
+	mut args: Value, oneof_field: &str, fields: &[&str],
+) -> Result<T, McpError> {
+	{
+		let args =
+			args.as_object_mut().ok_or_else(|| McpError::invalid_params("Expected an object"))?;
+		let mut set_fields = fields.iter().filter_map(|field| Some((*field, args.remove(*field)?)));
+		let oneof = match (set_fields.next(), set_fields.next()) {
+			(Some((field, value)), None) => json!({ field: value }),
+			_ => {
+				return Err(McpError::invalid_params(format!(
+					"Exactly one of `{}` must be set",
+					fields.join("`, `")
+				)));
+			},
+		};
+		args.insert(oneof_field.to_string(), oneof);
+	}
+	parse_request(args)
+}
+
 fn serialize_response<T: Serialize>(response: T) -> Result<Value, McpError> {
 	serde_json::to_value(response)
 		.map_err(|e| McpError::internal(format!("Failed to serialize response: {e}")))
@@ -102,6 +149,23 @@ impl RouteParameterDefaults {
 	}
 }
 
+/// Parses a request for a route to an invoice or a node, with [`parse_request_with_oneof`], and
+/// fills in the route parameter defaults as [`parse_request_with_route_parameters`] does.
+fn parse_route_query_request<T, F>(args: Value, route_parameters: F) -> Result<T, McpError>
+where
+	T: DeserializeOwned,
+	F: FnOnce(&mut T) -> &mut Option<RouteParametersConfig>,
+{
+	let route_defaults = RouteParameterDefaults::from_args(&args);
+	let mut request = parse_request_with_oneof(args, "destination", &["invoice", "node_id"])?;
+	if let Some(route_defaults) = route_defaults {
+		if let Some(route_parameters) = route_parameters(&mut request).as_mut() {
+			route_defaults.apply(route_parameters);
+		}
+	}
+	Ok(request)
+}
+
 fn parse_request_with_route_parameters<T, F>(
 	args: Value, route_parameters: F,
 ) -> Result<T, McpError>
@@ -147,6 +211,18 @@ pub async fn handle_onchain_send(client: &LdkServerClient, args: Value) -> Resul
 	serialize_response(response)
 }
 
+const LIST_ONCHAIN_TRANSACTIONS_ENUM_FIELDS: &[(&str, EnumFromName)] =
+	&[("direction", |name| PaymentDirection::from_str_name(name).map(Into::into))];
+
+pub async fn handle_list_onchain_transactions(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: ListOnchainTransactionsRequest =
+		parse_request_with_enum_names(args, LIST_ONCHAIN_TRANSACTIONS_ENUM_FIELDS)?;
+	let response = client.list_onchain_transactions(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_bolt11_receive(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -185,6 +261,14 @@ pub async fn handle_bolt11_fail_for_hash(
 	serialize_response(response)
 }
 
+pub async fn handle_list_claimable_payments(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: ListClaimablePaymentsRequest = parse_request(args)?;
+	let response = client.list_claimable_payments(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_bolt11_receive_via_jit_channel(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -308,6 +392,14 @@ pub async fn handle_list_channels(
 	serialize_response(response)
 }
 
+pub async fn handle_list_closed_channels(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: ListClosedChannelsRequest = parse_request(args)?;
+	let response = client.list_closed_channels(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_update_channel_config(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -316,10 +408,19 @@ pub async fn handle_update_channel_config(
 	serialize_response(response)
 }
 
+const LIST_PAYMENTS_ENUM_FIELDS: &[(&str, EnumFromName)] = &[
+	("direction", |name| PaymentDirection::from_str_name(name).map(Into::into)),
+	("status", |name| PaymentStatus::from_str_name(name).map(Into::into)),
+	("kind", |name| {
+		PaymentKindType::from_str_name(&format!("PAYMENT_KIND_TYPE_{name}")).map(Into::into)
+	}),
+];
+
 pub async fn handle_list_payments(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
-	let request: ListPaymentsRequest = parse_request(args)?;
+	let request: ListPaymentsRequest =
+		parse_request_with_enum_names(args, LIST_PAYMENTS_ENUM_FIELDS)?;
 	let response = client.list_payments(request).await.map_err(McpError::from)?;
 	serialize_response(response)
 }
@@ -332,6 +433,36 @@ pub async fn handle_get_payment_details(
 	serialize_response(response)
 }
 
+pub async fn handle_find_payments(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: FindPaymentsRequest =
+		parse_request_with_oneof(args, "identifier", &["payment_hash", "preimage", "offer_id"])?;
+	let response = client.find_payments(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_list_invoices(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: ListInvoicesRequest = parse_request(args)?;
+	let response = client.list_invoices(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_list_offers(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
+	let request: ListOffersRequest = parse_request(args)?;
+	let response = client.list_offers(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_update_label(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
+	let request: UpdateLabelRequest =
+		parse_request_with_oneof(args, "target", &["payment_id", "user_channel_id"])?;
+	let response = client.update_label(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_list_forwarded_payments(
 	client: &LdkServerClient, args: Value,
 ) -> Result<Value, McpError> {
@@ -415,6 +546,35 @@ pub async fn handle_export_pathfinding_scores(
 	Ok(json!({ "pathfinding_scores": response.scores.to_lower_hex_string() }))
 }
 
+pub async fn handle_estimate_route_fee(
+	client: &LdkServerClient, args: Value,
+) -> Result<Value, McpError> {
+	let request: EstimateRouteFeeRequest =
+		parse_route_query_request(args, |request: &mut EstimateRouteFeeRequest| {
+			&mut request.route_parameters
+		})?;
+	let response = client.estimate_route_fee(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_query_routes(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
+	let request: QueryRoutesRequest =
+		parse_route_query_request(args, |request: &mut QueryRoutesRequest| {
+			&mut request.route_parameters
+		})?;
+	let response = client.query_routes(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
+pub async fn handle_send_probes(client: &LdkServerClient, args: Value) -> Result<Value, McpError> {
+	let request: SendProbesRequest =
+		parse_route_query_request(args, |request: &mut SendProbesRequest| {
+			&mut request.route_parameters
+		})?;
+	let response = client.send_probes(request).await.map_err(McpError::from)?;
+	serialize_response(response)
+}
+
 pub async fn handle_graph_list_channels(
 	client: &LdkServerClient, _args: Value,
 ) -> Result<Value, McpError> {
@@ -450,13 +610,70 @@ pub async fn handle_graph_get_node(
 #[cfg(test)]
 mod tests {
 	use ldk_server_client::ldk_server_grpc::api::{
-		onchain_send_request, open_channel_request, splice_in_request,
+		find_payments_request, onchain_send_request, open_channel_request, splice_in_request,
+		update_label_request,
 	};
 
 	use super::*;
 
+	#[test]
+	fn parse_request_with_oneof_maps_find_payments_identifier() {
+		let fields = &["payment_hash", "preimage", "offer_id"];
+		let request: FindPaymentsRequest =
+			parse_request_with_oneof(json!({ "offer_id": "ab" }), "identifier", fields).unwrap();
+		assert_eq!(
+			request.identifier,
+			Some(find_payments_request::Identifier::OfferId("ab".to_string()))
+		);
+
+		let err = parse_request_with_oneof::<FindPaymentsRequest>(
+			json!({ "payment_hash": "ab", "preimage": "cd" }),
+			"identifier",
+			fields,
+		)
+		.unwrap_err();
+		assert!(err.message.contains("Exactly one of"));
+		assert!(parse_request_with_oneof::<FindPaymentsRequest>(json!({}), "identifier", fields)
+			.is_err());
+	}
+
+	#[test]
+	fn parse_request_with_oneof_maps_update_label_target() {
+		let request: UpdateLabelRequest = parse_request_with_oneof(
+			json!({ "user_channel_id": "42", "label": "lsp", "metadata": { "region": "eu" } }),
+			"target",
+			&["payment_id", "user_channel_id"],
+		)
+		.unwrap();
+		assert_eq!(
+			request.target,
+			Some(update_label_request::Target::UserChannelId("42".to_string()))
+		);
+		assert_eq!(request.label.as_deref(), Some("lsp"));
+		assert_eq!(request.metadata.get("region").map(String::as_str), Some("eu"));
+	}
+
 	const NODE_PUBKEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
 
+	#[test]
+	fn parse_request_with_enum_names_maps_list_payments_filters() {
+		let request: ListPaymentsRequest = parse_request_with_enum_names(
+			json!({ "direction": "outbound", "kind": "bolt12_offer", "min_amount_msat": 1000 }),
+			LIST_PAYMENTS_ENUM_FIELDS,
+		)
+		.unwrap();
+		assert_eq!(request.direction, Some(PaymentDirection::Outbound as i32));
+		assert_eq!(request.kind, Some(PaymentKindType::Bolt12Offer as i32));
+		assert_eq!(request.min_amount_msat, Some(1000));
+
+		let err = parse_request_with_enum_names::<ListPaymentsRequest>(
+			json!({ "direction": "sideways" }),
+			LIST_PAYMENTS_ENUM_FIELDS,
+		)
+		.unwrap_err();
+		assert!(err.message.contains("Unknown `direction`"));
+	}
+
 	#[test]
 	fn parse_request_with_amount_accepts_all() {
 		let request: OpenChannelRequest = parse_request_with_amount(
@@ -93,6 +93,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::onchain_send_schema,
 			|client, args| Box::pin(handlers::handle_onchain_send(client, args)),
 		),
+		tool_spec(
+			"list_onchain_transactions",
+			"List the transactions of the on-chain wallet with their fees, confirmation status and labels, newest first",
+			schema::list_onchain_transactions_schema,
+			|client, args| Box::pin(handlers::handle_list_onchain_transactions(client, args)),
+		),
 		tool_spec(
 			"bolt11_receive",
 			"Create a BOLT11 Lightning invoice to receive a payment",
@@ -117,6 +123,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::bolt11_fail_for_hash_schema,
 			|client, args| Box::pin(handlers::handle_bolt11_fail_for_hash(client, args)),
 		),
+		tool_spec(
+			"list_claimable_payments",
+			"List the payments waiting to be claimed or failed back",
+			schema::list_claimable_payments_schema,
+			|client, args| Box::pin(handlers::handle_list_claimable_payments(client, args)),
+		),
 		tool_spec(
 			"bolt11_receive_via_jit_channel",
 			"Create a BOLT11 Lightning invoice to receive via an LSPS2 JIT channel",
@@ -205,6 +217,12 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::list_channels_schema,
 			|client, args| Box::pin(handlers::handle_list_channels(client, args)),
 		),
+		tool_spec(
+			"list_closed_channels",
+			"List the channels that were closed with their final balance and closure reason, most recently closed first (supports pagination via page_token)",
+			schema::list_closed_channels_schema,
+			|client, args| Box::pin(handlers::handle_list_closed_channels(client, args)),
+		),
 		tool_spec(
 			"update_channel_config",
 			"Update forwarding fees and CLTV delta for a channel",
@@ -213,16 +231,40 @@ pub fn build_tool_registry() -> ToolRegistry {
 		),
 		tool_spec(
 			"list_payments",
-			"List all payments (supports pagination via page_token)",
+			"List payments, optionally filtered by direction, status, kind, amount and time (supports pagination via page_token)",
 			schema::list_payments_schema,
 			|client, args| Box::pin(handlers::handle_list_payments(client, args)),
 		),
+		tool_spec(
+			"find_payments",
+			"Find payments by exactly one of payment_hash, preimage or offer_id (supports pagination via page_token)",
+			schema::find_payments_schema,
+			|client, args| Box::pin(handlers::handle_find_payments(client, args)),
+		),
 		tool_spec(
 			"get_payment_details",
 			"Get details of a specific payment by its ID",
 			schema::get_payment_details_schema,
 			|client, args| Box::pin(handlers::handle_get_payment_details(client, args)),
 		),
+		tool_spec(
+			"list_invoices",
+			"List the BOLT11 invoices issued by the node with their status, most recent first (supports pagination via page_token)",
+			schema::list_invoices_schema,
+			|client, args| Box::pin(handlers::handle_list_invoices(client, args)),
+		),
+		tool_spec(
+			"list_offers",
+			"List the BOLT12 offers issued by the node with the payments received for them, most recent first (supports pagination via page_token)",
+			schema::list_offers_schema,
+			|client, args| Box::pin(handlers::handle_list_offers(client, args)),
+		),
+		tool_spec(
+			"update_label",
+			"Change the label and key/value metadata attached to a payment (by payment_id) or a channel (by user_channel_id)",
+			schema::update_label_schema,
+			|client, args| Box::pin(handlers::handle_update_label(client, args)),
+		),
 		tool_spec(
 			"list_forwarded_payments",
 			"List all forwarded payments (supports pagination via page_token)",
@@ -277,6 +319,24 @@ pub fn build_tool_registry() -> ToolRegistry {
 			schema::export_pathfinding_scores_schema,
 			|client, args| Box::pin(handlers::handle_export_pathfinding_scores(client, args)),
 		),
+		tool_spec(
+			"estimate_route_fee",
+			"Estimate the routing fees of a Lightning payment without sending it",
+			schema::estimate_route_fee_schema,
+			|client, args| Box::pin(handlers::handle_estimate_route_fee(client, args)),
+		),
+		tool_spec(
+			"query_routes",
+			"Find the route a Lightning payment would take without sending it",
+			schema::query_routes_schema,
+			|client, args| Box::pin(handlers::handle_query_routes(client, args)),
+		),
+		tool_spec(
+			"send_probes",
+			"Probe the liquidity of the route of a Lightning payment without sending it. The outcomes are estimated from the pathfinding scores and returned as estimated_status, and probes lock liquidity until they resolve",
+			schema::send_probes_schema,
+			|client, args| Box::pin(handlers::handle_send_probes(client, args)),
+		),
 		tool_spec(
 			"graph_list_channels",
 			"List all known short channel IDs in the network graph",
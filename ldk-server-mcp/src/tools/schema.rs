@@ -142,6 +142,14 @@ fn page_token_schema() -> Value {
 	})
 }
 
+fn metadata_schema(description: &str) -> Value {
+	json!({
+		"type": "object",
+		"description": description,
+		"additionalProperties": { "type": "string" }
+	})
+}
+
 pub fn get_node_info_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
@@ -172,12 +180,37 @@ pub fn onchain_send_schema() -> Value {
 			"fee_rate_sat_per_vb": {
 				"type": "integer",
 				"description": "Fee rate in satoshis per virtual byte. If not set, a reasonable estimate will be used"
-			}
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to attach to the payment, returned along with it by the payment RPCs"
+			},
+			"metadata": metadata_schema("Key/value metadata to attach to the payment")
 		},
 		"required": ["address", "amount_sats"]
 	})
 }
 
+pub fn list_onchain_transactions_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema(),
+			"direction": {
+				"type": "string",
+				"enum": ["inbound", "outbound"],
+				"description": "Only list transactions in this direction"
+			},
+			"page_size": {
+				"type": "integer",
+				"minimum": 1,
+				"description": "Maximum number of transactions per page, up to 1000 (default: 100)"
+			}
+		},
+		"required": []
+	})
+}
+
 pub fn bolt11_receive_schema() -> Value {
 	json!({
 		"type": "object",
@@ -190,7 +223,12 @@ pub fn bolt11_receive_schema() -> Value {
 			"expiry_secs": {
 				"type": "integer",
 				"description": "Invoice expiry time in seconds (defaults to 86400 if omitted or 0)"
-			}
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to record the invoice with. It is only stored by the server and not included in the invoice"
+			},
+			"metadata": metadata_schema("Key/value metadata to record the invoice with. It is only stored by the server and not included in the invoice")
 		},
 		"required": []
 	})
@@ -212,6 +250,14 @@ pub fn bolt11_receive_for_hash_schema() -> Value {
 			"payment_hash": {
 				"type": "string",
 				"description": "The hex-encoded 32-byte payment hash to use for the invoice"
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to record the invoice with. It is only stored by the server and not included in the invoice"
+			},
+			"auto_claim_preimage": {
+				"type": "string",
+				"description": "The hex-encoded 32-byte preimage to claim the payment with if it is neither claimed nor failed before its claim deadline. If unset, the payment is failed back"
 			}
 		},
 		"required": ["payment_hash"]
@@ -252,6 +298,16 @@ pub fn bolt11_fail_for_hash_schema() -> Value {
 	})
 }
 
+pub fn list_claimable_payments_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema()
+		},
+		"required": []
+	})
+}
+
 pub fn bolt11_receive_via_jit_channel_schema() -> Value {
 	json!({
 		"type": "object",
@@ -268,6 +324,10 @@ pub fn bolt11_receive_via_jit_channel_schema() -> Value {
 			"max_total_lsp_fee_limit_msat": {
 				"type": "integer",
 				"description": "Optional upper bound for the total fee an LSP may deduct when opening the JIT channel"
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to record the invoice with. It is only stored by the server and not included in the invoice"
 			}
 		},
 		"required": ["amount_msat"]
@@ -286,6 +346,10 @@ pub fn bolt11_receive_variable_amount_via_jit_channel_schema() -> Value {
 			"max_proportional_lsp_fee_limit_ppm_msat": {
 				"type": "integer",
 				"description": "Optional upper bound for the proportional fee, in parts-per-million millisatoshis, that an LSP may deduct when opening the JIT channel"
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to record the invoice with. It is only stored by the server and not included in the invoice"
 			}
 		},
 		"required": []
@@ -304,7 +368,12 @@ pub fn bolt11_send_schema() -> Value {
 				"type": "integer",
 				"description": "Amount in millisatoshis. Required when paying a zero-amount invoice"
 			},
-			"route_parameters": route_parameters_config_schema()
+			"route_parameters": route_parameters_config_schema(),
+			"label": {
+				"type": "string",
+				"description": "Label to attach to the payment, returned along with it by the payment RPCs"
+			},
+			"metadata": metadata_schema("Key/value metadata to attach to the payment")
 		},
 		"required": ["invoice"]
 	})
@@ -347,6 +416,10 @@ pub fn bolt12_receive_schema() -> Value {
 			"quantity": {
 				"type": "integer",
 				"description": "Number of items requested. Can only be set for fixed-amount offers"
+			},
+			"label": {
+				"type": "string",
+				"description": "Label to record the offer with. It is only stored by the server and not included in the offer"
 			}
 		},
 		"required": ["description"]
@@ -373,7 +446,12 @@ pub fn bolt12_send_schema() -> Value {
 				"type": "string",
 				"description": "Note to include for the payee. Reflected back in the invoice"
 			},
-			"route_parameters": route_parameters_config_schema()
+			"route_parameters": route_parameters_config_schema(),
+			"label": {
+				"type": "string",
+				"description": "Label to attach to the payment, returned along with it by the payment RPCs"
+			},
+			"metadata": metadata_schema("Key/value metadata to attach to the payment")
 		},
 		"required": ["offer"]
 	})
@@ -450,7 +528,12 @@ pub fn open_channel_schema() -> Value {
 				"type": "boolean",
 				"description": "Allow the counterparty to spend all its channel balance. Cannot be set together with announce_channel"
 			},
-			"channel_config": channel_config_schema()
+			"channel_config": channel_config_schema(),
+			"label": {
+				"type": "string",
+				"description": "Label to attach to the channel, returned along with it by list_channels"
+			},
+			"metadata": metadata_schema("Key/value metadata to attach to the channel")
 		},
 		"required": ["node_pubkey", "address", "channel_amount_sats"]
 	})
@@ -547,6 +630,16 @@ pub fn list_channels_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
 
+pub fn list_closed_channels_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema()
+		},
+		"required": []
+	})
+}
+
 pub fn update_channel_config_schema() -> Value {
 	json!({
 		"type": "object",
@@ -569,7 +662,51 @@ pub fn list_payments_schema() -> Value {
 	json!({
 		"type": "object",
 		"properties": {
-			"page_token": page_token_schema()
+			"page_token": page_token_schema(),
+			"direction": {
+				"type": "string",
+				"enum": ["inbound", "outbound"],
+				"description": "Only list payments in this direction"
+			},
+			"status": {
+				"type": "string",
+				"enum": ["pending", "succeeded", "failed"],
+				"description": "Only list payments with this status"
+			},
+			"kind": {
+				"type": "string",
+				"enum": ["onchain", "bolt11", "bolt12_offer", "bolt12_refund", "spontaneous"],
+				"description": "Only list payments of this kind"
+			},
+			"min_amount_msat": {
+				"type": "integer",
+				"description": "Only list payments of at least this amount in millisatoshis"
+			},
+			"max_amount_msat": {
+				"type": "integer",
+				"description": "Only list payments of at most this amount in millisatoshis"
+			},
+			"created_since": {
+				"type": "integer",
+				"description": "Only list payments first recorded at or after this UNIX timestamp in seconds"
+			},
+			"created_before": {
+				"type": "integer",
+				"description": "Only list payments first recorded before this UNIX timestamp in seconds"
+			},
+			"updated_since": {
+				"type": "integer",
+				"description": "Only list payments last updated at or after this UNIX timestamp in seconds"
+			},
+			"updated_before": {
+				"type": "integer",
+				"description": "Only list payments last updated before this UNIX timestamp in seconds"
+			},
+			"page_size": {
+				"type": "integer",
+				"minimum": 1,
+				"description": "Maximum number of payments per page, up to 1000 (default: 100)"
+			}
 		},
 		"required": []
 	})
@@ -588,6 +725,68 @@ pub fn get_payment_details_schema() -> Value {
 	})
 }
 
+pub fn find_payments_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"payment_hash": {
+				"type": "string",
+				"description": "The hex-encoded payment hash"
+			},
+			"preimage": {
+				"type": "string",
+				"description": "The hex-encoded payment preimage"
+			},
+			"offer_id": {
+				"type": "string",
+				"description": "The hex-encoded ID of the BOLT12 offer the payments were made for"
+			},
+			"page_token": page_token_schema()
+		}
+	})
+}
+
+pub fn list_invoices_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema()
+		},
+		"required": []
+	})
+}
+
+pub fn list_offers_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"page_token": page_token_schema()
+		},
+		"required": []
+	})
+}
+
+pub fn update_label_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"payment_id": {
+				"type": "string",
+				"description": "The hex-encoded ID of the payment to update"
+			},
+			"user_channel_id": {
+				"type": "string",
+				"description": "The local user_channel_id of the channel to update"
+			},
+			"label": {
+				"type": "string",
+				"description": "New label, replacing the current one. An empty label removes it"
+			},
+			"metadata": metadata_schema("Metadata entries to set. An entry with an empty value is removed")
+		}
+	})
+}
+
 pub fn list_forwarded_payments_schema() -> Value {
 	json!({
 		"type": "object",
@@ -700,6 +899,73 @@ pub fn export_pathfinding_scores_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
 
+pub fn estimate_route_fee_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"invoice": {
+				"type": "string",
+				"description": "A BOLT11 invoice to find the route for. Set exactly one of invoice or node_id"
+			},
+			"node_id": {
+				"type": "string",
+				"description": "The hex-encoded public key of a node to find the route of a spontaneous payment to"
+			},
+			"amount_msat": {
+				"type": "integer",
+				"description": "The amount in millisatoshis to route. Required for node_id or a variable-amount invoice"
+			},
+			"route_parameters": route_parameters_config_schema()
+		}
+	})
+}
+
+pub fn query_routes_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"invoice": {
+				"type": "string",
+				"description": "A BOLT11 invoice to find the route for. Set exactly one of invoice or node_id"
+			},
+			"node_id": {
+				"type": "string",
+				"description": "The hex-encoded public key of a node to find the route of a spontaneous payment to"
+			},
+			"amount_msat": {
+				"type": "integer",
+				"description": "The amount in millisatoshis to route. Required for node_id or a variable-amount invoice"
+			},
+			"route_parameters": route_parameters_config_schema()
+		}
+	})
+}
+
+pub fn send_probes_schema() -> Value {
+	json!({
+		"type": "object",
+		"properties": {
+			"invoice": {
+				"type": "string",
+				"description": "A BOLT11 invoice to probe the route of. Set exactly one of invoice or node_id"
+			},
+			"node_id": {
+				"type": "string",
+				"description": "The hex-encoded public key of a node to probe the route of a spontaneous payment to"
+			},
+			"amount_msat": {
+				"type": "integer",
+				"description": "The amount in millisatoshis to probe for. Required for node_id or a variable-amount invoice"
+			},
+			"route_parameters": route_parameters_config_schema(),
+			"timeout_secs": {
+				"type": "integer",
+				"description": "Seconds to wait for the estimated outcomes of the probes, at most 600 (default: 60)"
+			}
+		}
+	})
+}
+
 pub fn graph_list_channels_schema() -> Value {
 	json!({ "type": "object", "properties": {}, "required": [] })
 }
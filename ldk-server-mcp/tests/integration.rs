@@ -11,7 +11,7 @@ use std::io::{BufRead, BufReader, Write};
 
 use serde_json::{json, Value};
 
-const NUM_TOOLS: usize = 38;
+const NUM_TOOLS: usize = 48;
 const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"bolt11_claim_for_hash",
 	"bolt11_fail_for_hash",
@@ -28,7 +28,9 @@ const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"decode_invoice",
 	"decode_offer",
 	"disconnect_peer",
+	"estimate_route_fee",
 	"export_pathfinding_scores",
+	"find_payments",
 	"force_close_channel",
 	"get_balances",
 	"get_node_info",
@@ -38,18 +40,26 @@ const EXPECTED_TOOLS: [&str; NUM_TOOLS] = [
 	"graph_list_channels",
 	"graph_list_nodes",
 	"list_channels",
+	"list_claimable_payments",
+	"list_closed_channels",
 	"list_forwarded_payments",
+	"list_invoices",
+	"list_offers",
+	"list_onchain_transactions",
 	"list_payments",
 	"list_peers",
 	"onchain_receive",
 	"onchain_send",
 	"open_channel",
+	"query_routes",
+	"send_probes",
 	"sign_message",
 	"splice_in",
 	"splice_out",
 	"spontaneous_send",
 	"unified_send",
 	"update_channel_config",
+	"update_label",
 	"verify_signature",
 ];
 
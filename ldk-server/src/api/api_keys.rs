@@ -0,0 +1,65 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{
+	CreateApiKeyRequest, CreateApiKeyResponse, ListApiKeysRequest, ListApiKeysResponse,
+	RevokeApiKeyRequest, RevokeApiKeyResponse, RotateApiKeyRequest, RotateApiKeyResponse,
+};
+use ldk_server_grpc::types::ApiKeyScope;
+use log::info;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+use crate::util::auth::{Scope, DEFAULT_ROTATION_GRACE_PERIOD_SECS};
+
+pub(crate) async fn handle_create_api_key_request(
+	context: Arc<Context>, request: CreateApiKeyRequest,
+) -> Result<CreateApiKeyResponse, LdkServerError> {
+	let scope = ApiKeyScope::from_i32(request.scope).ok_or_else(|| {
+		LdkServerError::new(InvalidRequestError, format!("Unknown scope: {}", request.scope))
+	})?;
+	let scope = Scope::from_proto(scope, request.max_send_amount_msat)?;
+
+	let api_key = context.api_key_store.create(request.name.clone(), scope)?;
+	info!("API key '{}' created with scope {} by '{}'", request.name, scope, context.caller.name);
+
+	Ok(CreateApiKeyResponse { api_key })
+}
+
+pub(crate) async fn handle_list_api_keys_request(
+	context: Arc<Context>, _request: ListApiKeysRequest,
+) -> Result<ListApiKeysResponse, LdkServerError> {
+	Ok(ListApiKeysResponse { api_keys: context.api_key_store.list() })
+}
+
+pub(crate) async fn handle_rotate_api_key_request(
+	context: Arc<Context>, request: RotateApiKeyRequest,
+) -> Result<RotateApiKeyResponse, LdkServerError> {
+	let grace_period_secs = request.grace_period_secs.unwrap_or(DEFAULT_ROTATION_GRACE_PERIOD_SECS);
+
+	let api_key = context.api_key_store.rotate(&request.name, grace_period_secs)?;
+	info!(
+		"API key '{}' rotated with a grace period of {}s by '{}'",
+		request.name, grace_period_secs, context.caller.name
+	);
+
+	Ok(RotateApiKeyResponse { api_key })
+}
+
+pub(crate) async fn handle_revoke_api_key_request(
+	context: Arc<Context>, request: RevokeApiKeyRequest,
+) -> Result<RevokeApiKeyResponse, LdkServerError> {
+	context.api_key_store.revoke(&request.name)?;
+	info!("API key '{}' revoked by '{}'", request.name, context.caller.name);
+
+	Ok(RevokeApiKeyResponse {})
+}
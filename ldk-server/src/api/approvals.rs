@@ -0,0 +1,177 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::unified_send_response::PaymentResult;
+use ldk_server_grpc::api::{
+	ApproveActionRequest, ApproveActionResponse, ListPendingActionsRequest,
+	ListPendingActionsResponse, RejectActionRequest, RejectActionResponse,
+};
+use ldk_server_grpc::endpoints::{
+	BOLT11_SEND_PATH, BOLT11_SEND_UNDERPAYING_PATH, BOLT12_SEND_PATH, ONCHAIN_SEND_PATH,
+	SPONTANEOUS_SEND_PATH, UNIFIED_SEND_PATH,
+};
+use prost::Message;
+
+use crate::api::bolt11_send::{handle_bolt11_send_request, handle_bolt11_send_underpaying_request};
+use crate::api::bolt12_send::handle_bolt12_send_request;
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{
+	InternalServerError, InvalidRequestError, PermissionDeniedError,
+};
+use crate::api::onchain_send::handle_onchain_send_request;
+use crate::api::spontaneous_send::handle_spontaneous_send_request;
+use crate::api::unified_send::handle_unified_send_request;
+use crate::service::Context;
+use crate::util::approvals::{list_pending_actions, ApprovalQueue, ApprovedAction};
+use crate::util::auth::Identity;
+
+pub(crate) async fn handle_approve_action_request(
+	context: Arc<Context>, request: ApproveActionRequest,
+) -> Result<ApproveActionResponse, LdkServerError> {
+	let approval_queue = approval_queue(&context)?;
+	let ApprovedAction { action, request: action_request, requester, requester_uses_api_key } =
+		approval_queue.approve(&request.id, &context.caller.name)?;
+
+	let requester =
+		match current_requester(&context, requester, requester_uses_api_key, &action.method) {
+			Ok(requester) => requester,
+			Err(e) => {
+				let action = approval_queue.complete(action, Err(&e))?;
+				return Ok(ApproveActionResponse { action: Some(action) });
+			},
+		};
+
+	// The send is executed on behalf of the requester, so it is still subject to the requester's
+	// scope and spending limits.
+	let execution_context = Arc::new(Context {
+		node: Arc::clone(&context.node),
+		paginated_kv_store: Arc::clone(&context.paginated_kv_store),
+		api_key_store: Arc::clone(&context.api_key_store),
+		spend_tracker: Arc::clone(&context.spend_tracker),
+		probe_tracker: Arc::clone(&context.probe_tracker),
+		routing_snapshots: Arc::clone(&context.routing_snapshots),
+		approval_queue: context.approval_queue.clone(),
+		storage_dir: context.storage_dir.clone(),
+		webhooks: context.webhooks.clone(),
+		caller: requester,
+		caller_uses_api_key: requester_uses_api_key,
+		method: action.method.clone(),
+		is_approved: true,
+	});
+	let result = execute_action(execution_context, &action.method, &action_request).await;
+	let action = approval_queue.complete(action, result.as_ref().map(String::clone))?;
+	Ok(ApproveActionResponse { action: Some(action) })
+}
+
+pub(crate) async fn handle_reject_action_request(
+	context: Arc<Context>, request: RejectActionRequest,
+) -> Result<RejectActionResponse, LdkServerError> {
+	let action =
+		approval_queue(&context)?.reject(&request.id, &context.caller.name, request.reason)?;
+	Ok(RejectActionResponse { action: Some(action) })
+}
+
+pub(crate) async fn handle_list_pending_actions_request(
+	context: Arc<Context>, request: ListPendingActionsRequest,
+) -> Result<ListPendingActionsResponse, LdkServerError> {
+	list_pending_actions(&*context.paginated_kv_store, request.page_token)
+}
+
+/// Returns the identity an approved action to `method` is executed with.
+///
+/// Requesters that authenticated with an API key are executed with the key's current scope, so
+/// an action fails if the key was revoked or its scope no longer permits `method` since the
+/// action was requested.
+fn current_requester(
+	context: &Context, requester: Identity, uses_api_key: bool, method: &str,
+) -> Result<Identity, LdkServerError> {
+	let requester = if uses_api_key {
+		let scope = context.api_key_store.scope(&requester.name).ok_or_else(|| {
+			LdkServerError::new(
+				PermissionDeniedError,
+				format!("API key '{}', which requested the action, was revoked", requester.name),
+			)
+		})?;
+		Identity { scope, ..requester }
+	} else {
+		requester
+	};
+
+	if !requester.scope.permits(method) {
+		return Err(LdkServerError::new(
+			PermissionDeniedError,
+			format!("'{}' is no longer permitted to call {method}", requester.name),
+		));
+	}
+	Ok(requester)
+}
+
+fn approval_queue(context: &Context) -> Result<&ApprovalQueue, LdkServerError> {
+	context.approval_queue.as_deref().ok_or_else(|| {
+		LdkServerError::new(InvalidRequestError, "Approvals are not configured on this server")
+	})
+}
+
+/// Executes the encoded `request` to `method` of an approved action, returning the payment ID or
+/// transaction ID of the send.
+async fn execute_action(
+	context: Arc<Context>, method: &str, request: &[u8],
+) -> Result<String, LdkServerError> {
+	match method {
+		BOLT11_SEND_PATH => {
+			let response = handle_bolt11_send_request(context, decode_request(request)?).await?;
+			Ok(response.payment_id)
+		},
+		BOLT11_SEND_UNDERPAYING_PATH => {
+			let response =
+				handle_bolt11_send_underpaying_request(context, decode_request(request)?).await?;
+			Ok(response.payment_id)
+		},
+		BOLT12_SEND_PATH => {
+			let response = handle_bolt12_send_request(context, decode_request(request)?).await?;
+			Ok(response.payment_id)
+		},
+		SPONTANEOUS_SEND_PATH => {
+			let response =
+				handle_spontaneous_send_request(context, decode_request(request)?).await?;
+			Ok(response.payment_id)
+		},
+		ONCHAIN_SEND_PATH => {
+			let response = handle_onchain_send_request(context, decode_request(request)?).await?;
+			Ok(response.txid)
+		},
+		UNIFIED_SEND_PATH => {
+			let response = handle_unified_send_request(context, decode_request(request)?).await?;
+			match response.payment_result {
+				Some(PaymentResult::Txid(id))
+				| Some(PaymentResult::Bolt11PaymentId(id))
+				| Some(PaymentResult::Bolt12PaymentId(id)) => Ok(id),
+				Some(PaymentResult::PendingActionId(_)) | None => Err(LdkServerError::new(
+					InternalServerError,
+					"Unified send of an approved action did not return a payment",
+				)),
+			}
+		},
+		_ => Err(LdkServerError::new(
+			InternalServerError,
+			format!("Pending action has an unsupported method: {method}"),
+		)),
+	}
+}
+
+fn decode_request<T: Message + Default>(request: &[u8]) -> Result<T, LdkServerError> {
+	T::decode(request).map_err(|e| {
+		LdkServerError::new(
+			InternalServerError,
+			format!("Failed to decode request of pending action: {e}"),
+		)
+	})
+}
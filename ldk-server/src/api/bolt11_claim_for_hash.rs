@@ -9,7 +9,7 @@
 
 use std::sync::Arc;
 
-use hex::FromHex;
+use hex::{DisplayHex, FromHex};
 use ldk_node::bitcoin::hashes::{sha256, Hash};
 use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
 use ldk_server_grpc::api::{Bolt11ClaimForHashRequest, Bolt11ClaimForHashResponse};
@@ -17,6 +17,7 @@ use ldk_server_grpc::api::{Bolt11ClaimForHashRequest, Bolt11ClaimForHashResponse
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::claimable_payments::{remove_auto_claim_preimage, remove_claimable_payment};
 
 pub(crate) async fn handle_bolt11_claim_for_hash_request(
 	context: Arc<Context>, request: Bolt11ClaimForHashRequest,
@@ -44,6 +45,9 @@ pub(crate) async fn handle_bolt11_claim_for_hash_request(
 	let claimable_amount_msat = request.claimable_amount_msat.unwrap_or(u64::MAX);
 
 	context.node.bolt11_payment().claim_for_hash(payment_hash, claimable_amount_msat, preimage)?;
+	let payment_hash = payment_hash.0.to_lower_hex_string();
+	remove_claimable_payment(&*context.paginated_kv_store, &payment_hash)?;
+	remove_auto_claim_preimage(&*context.paginated_kv_store, &payment_hash)?;
 
 	Ok(Bolt11ClaimForHashResponse {})
 }
@@ -9,13 +9,15 @@
 
 use std::sync::Arc;
 
-use hex::FromHex;
+use hex::{DisplayHex, FromHex};
 use ldk_node::lightning_types::payment::PaymentHash;
 use ldk_server_grpc::api::{Bolt11FailForHashRequest, Bolt11FailForHashResponse};
 
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::claimable_payments::{remove_auto_claim_preimage, remove_claimable_payment};
+use crate::util::invoices::cancel_invoice;
 
 pub(crate) async fn handle_bolt11_fail_for_hash_request(
 	context: Arc<Context>, request: Bolt11FailForHashRequest,
@@ -29,6 +31,10 @@ pub(crate) async fn handle_bolt11_fail_for_hash_request(
 	let payment_hash = PaymentHash(hash_bytes);
 
 	context.node.bolt11_payment().fail_for_hash(payment_hash)?;
+	let payment_hash = hash_bytes.to_lower_hex_string();
+	cancel_invoice(&*context.paginated_kv_store, &payment_hash)?;
+	remove_claimable_payment(&*context.paginated_kv_store, &payment_hash)?;
+	remove_auto_claim_preimage(&*context.paginated_kv_store, &payment_hash)?;
 
 	Ok(Bolt11FailForHashResponse {})
 }
@@ -13,13 +13,16 @@ use hex::DisplayHex;
 use ldk_server_grpc::api::{Bolt11ReceiveRequest, Bolt11ReceiveResponse};
 
 use crate::api::error::LdkServerError;
+use crate::api::record_issued_invoice;
 use crate::service::Context;
+use crate::util::labels::new_label;
 use crate::util::proto_adapter::proto_to_bolt11_description;
 
 pub(crate) async fn handle_bolt11_receive_request(
 	context: Arc<Context>, request: Bolt11ReceiveRequest,
 ) -> Result<Bolt11ReceiveResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description = proto_to_bolt11_description(request.description.clone())?;
+	let user_label = new_label(request.label, request.metadata)?;
 	let invoice = match request.amount_msat {
 		Some(amount_msat) => {
 			context.node.bolt11_payment().receive(amount_msat, &description, request.expiry_secs)?
@@ -29,6 +32,7 @@ pub(crate) async fn handle_bolt11_receive_request(
 			.bolt11_payment()
 			.receive_variable_amount(&description, request.expiry_secs)?,
 	};
+	record_issued_invoice(&context, &invoice, request.description, user_label);
 
 	let payment_hash = invoice.payment_hash().0.to_lower_hex_string();
 	let payment_secret = invoice.payment_secret().0.to_lower_hex_string();
@@ -7,21 +7,27 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
-use hex::FromHex;
+use hex::{DisplayHex, FromHex};
+use ldk_node::bitcoin::hashes::{sha256, Hash};
 use ldk_node::lightning_types::payment::PaymentHash;
 use ldk_server_grpc::api::{Bolt11ReceiveForHashRequest, Bolt11ReceiveForHashResponse};
 
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::api::record_issued_invoice;
 use crate::service::Context;
+use crate::util::claimable_payments::register_auto_claim_preimage;
+use crate::util::labels::new_label;
 use crate::util::proto_adapter::proto_to_bolt11_description;
 
 pub(crate) async fn handle_bolt11_receive_for_hash_request(
 	context: Arc<Context>, request: Bolt11ReceiveForHashRequest,
 ) -> Result<Bolt11ReceiveForHashResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description = proto_to_bolt11_description(request.description.clone())?;
+	let user_label = new_label(request.label, BTreeMap::new())?;
 	let hash_bytes = <[u8; 32]>::from_hex(&request.payment_hash).map_err(|_| {
 		LdkServerError::new(
 			InvalidRequestError,
@@ -29,6 +35,20 @@ pub(crate) async fn handle_bolt11_receive_for_hash_request(
 		)
 	})?;
 	let payment_hash = PaymentHash(hash_bytes);
+	let auto_claim_preimage = request
+		.auto_claim_preimage
+		.map(|preimage_hex| {
+			<[u8; 32]>::from_hex(&preimage_hex)
+				.ok()
+				.filter(|preimage| sha256::Hash::hash(preimage).to_byte_array() == hash_bytes)
+				.ok_or_else(|| {
+					LdkServerError::new(
+						InvalidRequestError,
+						"Invalid auto_claim_preimage, must be the 32-byte hex-encoded preimage of payment_hash.",
+					)
+				})
+		})
+		.transpose()?;
 
 	let invoice = match request.amount_msat {
 		Some(amount_msat) => context.node.bolt11_payment().receive_for_hash(
@@ -43,6 +63,14 @@ pub(crate) async fn handle_bolt11_receive_for_hash_request(
 			payment_hash,
 		)?,
 	};
+	record_issued_invoice(&context, &invoice, request.description, user_label);
+	if let Some(preimage) = auto_claim_preimage {
+		register_auto_claim_preimage(
+			&*context.paginated_kv_store,
+			&hash_bytes.to_lower_hex_string(),
+			preimage,
+		)?;
+	}
 
 	Ok(Bolt11ReceiveForHashResponse { invoice: invoice.to_string() })
 }
@@ -7,6 +7,7 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use ldk_server_grpc::api::{
@@ -16,19 +17,23 @@ use ldk_server_grpc::api::{
 };
 
 use crate::api::error::LdkServerError;
+use crate::api::record_issued_invoice;
 use crate::service::Context;
+use crate::util::labels::new_label;
 use crate::util::proto_adapter::proto_to_bolt11_description;
 
 pub(crate) async fn handle_bolt11_receive_via_jit_channel_request(
 	context: Arc<Context>, request: Bolt11ReceiveViaJitChannelRequest,
 ) -> Result<Bolt11ReceiveViaJitChannelResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description = proto_to_bolt11_description(request.description.clone())?;
+	let user_label = new_label(request.label, BTreeMap::new())?;
 	let invoice = context.node.bolt11_payment().receive_via_jit_channel(
 		request.amount_msat,
 		&description,
 		request.expiry_secs,
 		request.max_total_lsp_fee_limit_msat,
 	)?;
+	record_issued_invoice(&context, &invoice, request.description, user_label);
 
 	Ok(Bolt11ReceiveViaJitChannelResponse { invoice: invoice.to_string() })
 }
@@ -36,12 +41,14 @@ pub(crate) async fn handle_bolt11_receive_via_jit_channel_request(
 pub(crate) async fn handle_bolt11_receive_variable_amount_via_jit_channel_request(
 	context: Arc<Context>, request: Bolt11ReceiveVariableAmountViaJitChannelRequest,
 ) -> Result<Bolt11ReceiveVariableAmountViaJitChannelResponse, LdkServerError> {
-	let description = proto_to_bolt11_description(request.description)?;
+	let description = proto_to_bolt11_description(request.description.clone())?;
+	let user_label = new_label(request.label, BTreeMap::new())?;
 	let invoice = context.node.bolt11_payment().receive_variable_amount_via_jit_channel(
 		&description,
 		request.expiry_secs,
 		request.max_proportional_lsp_fee_limit_ppm_msat,
 	)?;
+	record_issued_invoice(&context, &invoice, request.description, user_label);
 
 	Ok(Bolt11ReceiveVariableAmountViaJitChannelResponse { invoice: invoice.to_string() })
 }
@@ -16,9 +16,13 @@ use ldk_server_grpc::api::{
 	Bolt11SendUnderpayingResponse,
 };
 
-use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
+use crate::api::{
+	build_route_parameters_config_from_proto, require_approval, sent_lightning_payment,
+};
 use crate::service::Context;
+use crate::util::labels::{new_label, set_label, LabelTarget};
+use crate::util::spend_limits::SpendKind;
 
 pub(crate) async fn handle_bolt11_send_request(
 	context: Arc<Context>, request: Bolt11SendRequest,
@@ -26,16 +30,43 @@ pub(crate) async fn handle_bolt11_send_request(
 	let invoice = Bolt11Invoice::from_str(request.invoice.as_str())
 		.map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
 
+	let amount_msat = request.amount_msat.or(invoice.amount_milli_satoshis());
+	context.caller.scope.check_send_amount(amount_msat)?;
+	let user_label = new_label(request.label.clone(), request.metadata.clone())?;
+
+	if let Some(pending_action_id) =
+		require_approval(&context, &[SpendKind::Lightning], amount_msat, &request)?
+	{
+		return Ok(Bolt11SendResponse {
+			pending_action_id: Some(pending_action_id),
+			..Default::default()
+		});
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
+	let spend_reservation = context.spend_tracker.reserve(
+		&context.caller.name,
+		&[SpendKind::Lightning],
+		amount_msat,
+	)?;
+
 	let payment_id = match request.amount_msat {
 		None => context.node.bolt11_payment().send(&invoice, route_parameters),
 		Some(amount_msat) => {
 			context.node.bolt11_payment().send_using_amount(&invoice, amount_msat, route_parameters)
 		},
 	}?;
+	spend_reservation.record(sent_lightning_payment(&context.node, payment_id));
+	set_label(
+		&*context.paginated_kv_store,
+		LabelTarget::Payment,
+		&payment_id.to_string(),
+		&user_label,
+	);
 
-	let response = Bolt11SendResponse { payment_id: payment_id.to_string() };
+	let response =
+		Bolt11SendResponse { payment_id: payment_id.to_string(), pending_action_id: None };
 	Ok(response)
 }
 
@@ -45,14 +76,35 @@ pub(crate) async fn handle_bolt11_send_underpaying_request(
 	let invoice = Bolt11Invoice::from_str(request.invoice.as_str())
 		.map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
 
+	context.caller.scope.check_send_amount(Some(request.amount_msat))?;
+
+	if let Some(pending_action_id) =
+		require_approval(&context, &[SpendKind::Lightning], Some(request.amount_msat), &request)?
+	{
+		return Ok(Bolt11SendUnderpayingResponse {
+			pending_action_id: Some(pending_action_id),
+			..Default::default()
+		});
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
+	let spend_reservation = context.spend_tracker.reserve(
+		&context.caller.name,
+		&[SpendKind::Lightning],
+		Some(request.amount_msat),
+	)?;
+
 	let payment_id = context.node.bolt11_payment().send_using_amount_underpaying(
 		&invoice,
 		request.amount_msat,
 		route_parameters,
 	)?;
+	spend_reservation.record(sent_lightning_payment(&context.node, payment_id));
 
-	let response = Bolt11SendUnderpayingResponse { payment_id: payment_id.to_string() };
+	let response = Bolt11SendUnderpayingResponse {
+		payment_id: payment_id.to_string(),
+		pending_action_id: None,
+	};
 	Ok(response)
 }
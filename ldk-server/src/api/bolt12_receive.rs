@@ -11,9 +11,13 @@ use std::sync::Arc;
 
 use hex::DisplayHex;
 use ldk_server_grpc::api::{Bolt12ReceiveRequest, Bolt12ReceiveResponse};
+use ldk_server_grpc::types::Offer;
+use log::error;
 
 use crate::api::error::LdkServerError;
 use crate::service::Context;
+use crate::util::current_time_secs;
+use crate::util::invoices::record_offer;
 
 pub(crate) async fn handle_bolt12_receive_request(
 	context: Arc<Context>, request: Bolt12ReceiveRequest,
@@ -32,6 +36,22 @@ pub(crate) async fn handle_bolt12_receive_request(
 	};
 
 	let offer_id = offer.id().0.to_lower_hex_string();
+	let record = Offer {
+		offer_id: offer_id.clone(),
+		offer: offer.to_string(),
+		description: request.description,
+		amount_msat: request.amount_msat,
+		quantity: request.quantity,
+		label: request.label,
+		created_at: current_time_secs(),
+		expires_at: offer.absolute_expiry().map(|expiry| expiry.as_secs()),
+		..Default::default()
+	};
+	// The offer was already created, so it is returned even if it can't be recorded.
+	if let Err(e) = record_offer(&*context.paginated_kv_store, &record) {
+		error!("Failed to record offer {offer_id}: {e}");
+	}
+
 	let response = Bolt12ReceiveResponse { offer: offer.to_string(), offer_id };
 	Ok(response)
 }
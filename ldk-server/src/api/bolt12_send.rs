@@ -10,12 +10,16 @@
 use std::str::FromStr;
 use std::sync::Arc;
 
-use ldk_node::lightning::offers::offer::Offer;
+use ldk_node::lightning::offers::offer::{Amount, Offer};
 use ldk_server_grpc::api::{Bolt12SendRequest, Bolt12SendResponse};
 
-use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
+use crate::api::{
+	build_route_parameters_config_from_proto, require_approval, sent_lightning_payment,
+};
 use crate::service::Context;
+use crate::util::labels::{new_label, set_label, LabelTarget};
+use crate::util::spend_limits::SpendKind;
 
 pub(crate) async fn handle_bolt12_send_request(
 	context: Arc<Context>, request: Bolt12SendRequest,
@@ -23,8 +27,33 @@ pub(crate) async fn handle_bolt12_send_request(
 	let offer =
 		Offer::from_str(request.offer.as_str()).map_err(|_| ldk_node::NodeError::InvalidOffer)?;
 
+	let amount_msat = request.amount_msat.or_else(|| match offer.amount() {
+		Some(Amount::Bitcoin { amount_msats }) => {
+			amount_msats.checked_mul(request.quantity.unwrap_or(1))
+		},
+		// Amounts denominated in a currency can't be checked before the payment is sent.
+		Some(Amount::Currency { .. }) | None => None,
+	});
+	context.caller.scope.check_send_amount(amount_msat)?;
+	let user_label = new_label(request.label.clone(), request.metadata.clone())?;
+
+	if let Some(pending_action_id) =
+		require_approval(&context, &[SpendKind::Lightning], amount_msat, &request)?
+	{
+		return Ok(Bolt12SendResponse {
+			pending_action_id: Some(pending_action_id),
+			..Default::default()
+		});
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
+	let spend_reservation = context.spend_tracker.reserve(
+		&context.caller.name,
+		&[SpendKind::Lightning],
+		amount_msat,
+	)?;
+
 	let payment_id = match request.amount_msat {
 		None => context.node.bolt12_payment().send(
 			&offer,
@@ -40,7 +69,15 @@ pub(crate) async fn handle_bolt12_send_request(
 			route_parameters,
 		),
 	}?;
+	spend_reservation.record(sent_lightning_payment(&context.node, payment_id));
+	set_label(
+		&*context.paginated_kv_store,
+		LabelTarget::Payment,
+		&payment_id.to_string(),
+		&user_label,
+	);
 
-	let response = Bolt12SendResponse { payment_id: payment_id.to_string() };
+	let response =
+		Bolt12SendResponse { payment_id: payment_id.to_string(), pending_action_id: None };
 	Ok(response)
 }
@@ -0,0 +1,71 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use hex::{DisplayHex, FromHex};
+use ldk_server_grpc::api::find_payments_request::Identifier;
+use ldk_server_grpc::api::{FindPaymentsRequest, FindPaymentsResponse};
+use ldk_server_grpc::types::PageToken;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::api::list_payments::{read_payments, DEFAULT_PAGE_SIZE};
+use crate::io::persist::paginated_kv_store::PaymentFilter;
+use crate::service::Context;
+
+pub(crate) async fn handle_find_payments_request(
+	context: Arc<Context>, request: FindPaymentsRequest,
+) -> Result<FindPaymentsResponse, LdkServerError> {
+	let filter = match request.identifier {
+		Some(Identifier::PaymentHash(hash)) => PaymentFilter {
+			payment_hash: Some(normalize_id(&hash, "payment_hash")?),
+			..Default::default()
+		},
+		Some(Identifier::Preimage(preimage)) => PaymentFilter {
+			preimage: Some(normalize_id(&preimage, "preimage")?),
+			..Default::default()
+		},
+		Some(Identifier::OfferId(offer_id)) => PaymentFilter {
+			offer_id: Some(normalize_id(&offer_id, "offer_id")?),
+			..Default::default()
+		},
+		None => {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				"One of payment_hash, preimage or offer_id must be set",
+			))
+		},
+	};
+
+	let page_token = request.page_token.map(|p| (p.token, p.index));
+	let list_response =
+		context.paginated_kv_store.list_payments(&filter, DEFAULT_PAGE_SIZE, page_token).map_err(
+			|e| LdkServerError::new(InternalServerError, format!("Failed to find payments: {}", e)),
+		)?;
+
+	let response = FindPaymentsResponse {
+		payments: read_payments(&context, list_response.keys)?,
+		next_page_token: list_response
+			.next_page_token
+			.map(|(token, index)| PageToken { token, index }),
+	};
+	Ok(response)
+}
+
+/// Validates a hex-encoded 32-byte identifier and returns it in lowercase, as it is indexed.
+fn normalize_id(id: &str, field: &str) -> Result<String, LdkServerError> {
+	let bytes = <[u8; 32]>::from_hex(id).map_err(|_| {
+		LdkServerError::new(
+			InvalidRequestError,
+			format!("Invalid {field}, must be a 32-byte hex-string."),
+		)
+	})?;
+	Ok(bytes.to_lower_hex_string())
+}
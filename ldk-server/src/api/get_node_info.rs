@@ -9,6 +9,7 @@
 
 use std::sync::Arc;
 
+use ldk_node::bitcoin::Network;
 use ldk_node::lightning_types::features::NodeFeatures;
 use ldk_server_grpc::api::{GetNodeInfoRequest, GetNodeInfoResponse};
 use ldk_server_grpc::types::BestBlock;
@@ -72,6 +73,18 @@ pub(crate) async fn handle_get_node_info_request(
 		node_uris,
 		network,
 		features,
+		locked: false,
 	};
 	Ok(response)
 }
+
+/// Handles `GetNodeInfo` while the server is locked, when nothing but the network is known.
+pub(crate) async fn handle_locked_get_node_info_request(
+	network: Network, _request: GetNodeInfoRequest,
+) -> Result<GetNodeInfoResponse, LdkServerError> {
+	Ok(GetNodeInfoResponse {
+		network: network_to_proto(network) as i32,
+		locked: true,
+		..Default::default()
+	})
+}
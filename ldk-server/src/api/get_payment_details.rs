@@ -16,6 +16,7 @@ use ldk_server_grpc::api::{GetPaymentDetailsRequest, GetPaymentDetailsResponse};
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
 use crate::service::Context;
+use crate::util::labels::apply_payment_label;
 use crate::util::proto_adapter::payment_to_proto;
 
 pub(crate) async fn handle_get_payment_details_request(
@@ -29,9 +30,16 @@ pub(crate) async fn handle_get_payment_details_request(
 			)
 		})?;
 
-	let payment_details = context.node.payment(&PaymentId(payment_id_bytes));
+	let payment = match context.node.payment(&PaymentId(payment_id_bytes)) {
+		Some(payment_details) => {
+			let mut payment = payment_to_proto(payment_details);
+			apply_payment_label(&*context.paginated_kv_store, &mut payment)?;
+			Some(payment)
+		},
+		None => None,
+	};
 
-	let response = GetPaymentDetailsResponse { payment: payment_details.map(payment_to_proto) };
+	let response = GetPaymentDetailsResponse { payment };
 
 	Ok(response)
 }
@@ -0,0 +1,69 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use bytes::Bytes;
+use ldk_server_grpc::api::{ListAuditLogRequest, ListAuditLogResponse};
+use ldk_server_grpc::types::{AuditLogEntry, PageToken};
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::{
+	AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE, AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::service::Context;
+
+pub(crate) async fn handle_list_audit_log_request(
+	context: Arc<Context>, request: ListAuditLogRequest,
+) -> Result<ListAuditLogResponse, LdkServerError> {
+	let page_token = request.page_token.map(|p| (p.token, p.index));
+	let list_response = context
+		.paginated_kv_store
+		.list(
+			AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE,
+			AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token,
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list audit log: {}", e))
+		})?;
+
+	let mut entries: Vec<AuditLogEntry> = Vec::with_capacity(list_response.keys.len());
+	for key in list_response.keys {
+		let entry_bytes = context
+			.paginated_kv_store
+			.read(
+				AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE,
+				AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+				&key,
+			)
+			.map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Failed to read audit log entry: {}", e),
+				)
+			})?;
+		let entry = AuditLogEntry::decode(Bytes::from(entry_bytes)).map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to decode audit log entry: {}", e),
+			)
+		})?;
+		entries.push(entry);
+	}
+	let response = ListAuditLogResponse {
+		entries,
+		next_page_token: list_response
+			.next_page_token
+			.map(|(token, index)| PageToken { token, index }),
+	};
+	Ok(response)
+}
@@ -13,12 +13,17 @@ use ldk_server_grpc::api::{ListChannelsRequest, ListChannelsResponse};
 
 use crate::api::error::LdkServerError;
 use crate::service::Context;
+use crate::util::labels::apply_channel_label;
 use crate::util::proto_adapter::channel_to_proto;
 
 pub(crate) async fn handle_list_channels_request(
 	context: Arc<Context>, _request: ListChannelsRequest,
 ) -> Result<ListChannelsResponse, LdkServerError> {
-	let channels = context.node.list_channels().into_iter().map(channel_to_proto).collect();
+	let mut channels: Vec<_> =
+		context.node.list_channels().into_iter().map(channel_to_proto).collect();
+	for channel in &mut channels {
+		apply_channel_label(&*context.paginated_kv_store, channel)?;
+	}
 
 	let response = ListChannelsResponse { channels };
 	Ok(response)
@@ -0,0 +1,22 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{ListClaimablePaymentsRequest, ListClaimablePaymentsResponse};
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+use crate::util::claimable_payments::list_claimable_payments;
+
+pub(crate) async fn handle_list_claimable_payments_request(
+	context: Arc<Context>, request: ListClaimablePaymentsRequest,
+) -> Result<ListClaimablePaymentsResponse, LdkServerError> {
+	list_claimable_payments(&*context.paginated_kv_store, request.page_token)
+}
@@ -0,0 +1,22 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{ListClosedChannelsRequest, ListClosedChannelsResponse};
+
+use crate::api::error::LdkServerError;
+use crate::service::Context;
+use crate::util::closed_channels::list_closed_channels;
+
+pub(crate) async fn handle_list_closed_channels_request(
+	context: Arc<Context>, request: ListClosedChannelsRequest,
+) -> Result<ListClosedChannelsResponse, LdkServerError> {
+	list_closed_channels(&*context.paginated_kv_store, request.page_token)
+}
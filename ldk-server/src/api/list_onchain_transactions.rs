@@ -0,0 +1,90 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{ListOnchainTransactionsRequest, ListOnchainTransactionsResponse};
+use ldk_server_grpc::types::confirmation_status::Status;
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{
+	OnchainTransaction, PageToken, Payment, PaymentDirection, PaymentKindType,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::api::list_payments::{page_size, read_payments};
+use crate::io::persist::paginated_kv_store::PaymentFilter;
+use crate::service::Context;
+
+pub(crate) async fn handle_list_onchain_transactions_request(
+	context: Arc<Context>, request: ListOnchainTransactionsRequest,
+) -> Result<ListOnchainTransactionsResponse, LdkServerError> {
+	let direction = request
+		.direction
+		.map(|direction| {
+			PaymentDirection::from_i32(direction).ok_or_else(|| {
+				LdkServerError::new(
+					InvalidRequestError,
+					"Invalid direction in ListOnchainTransactionsRequest",
+				)
+			})
+		})
+		.transpose()?;
+	let filter =
+		PaymentFilter { direction, kind: Some(PaymentKindType::Onchain), ..Default::default() };
+	let page_size = page_size(request.page_size)?;
+	let page_token = request.page_token.map(|p| (p.token, p.index));
+	let list_response =
+		context.paginated_kv_store.list_payments(&filter, page_size, page_token).map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to list on-chain transactions: {}", e),
+			)
+		})?;
+
+	let best_block_height = context.node.status().current_best_block.height;
+	let transactions = read_payments(&context, list_response.keys)?
+		.into_iter()
+		.map(|payment| onchain_transaction(payment, best_block_height))
+		.collect();
+
+	let response = ListOnchainTransactionsResponse {
+		transactions,
+		next_page_token: list_response
+			.next_page_token
+			.map(|(token, index)| PageToken { token, index }),
+	};
+	Ok(response)
+}
+
+fn onchain_transaction(payment: Payment, best_block_height: u32) -> OnchainTransaction {
+	let direction = payment.direction;
+	let (txid, status) = match payment.kind.and_then(|kind| kind.kind) {
+		Some(Kind::Onchain(onchain)) => (onchain.txid, onchain.status),
+		_ => (String::new(), None),
+	};
+	let confirmations = match status.as_ref().and_then(|status| status.status.as_ref()) {
+		Some(Status::Confirmed(confirmed)) => {
+			best_block_height.saturating_sub(confirmed.height).saturating_add(1)
+		},
+		_ => 0,
+	};
+	OnchainTransaction {
+		txid,
+		payment_id: payment.id,
+		direction,
+		amount_sats: payment.amount_msat.map(|amount_msat| amount_msat / 1000),
+		fee_sats: payment.fee_paid_msat.map(|fee_paid_msat| fee_paid_msat / 1000),
+		status,
+		confirmations,
+		latest_update_timestamp: payment.latest_update_timestamp,
+		label: payment.label,
+		metadata: payment.metadata,
+	}
+}
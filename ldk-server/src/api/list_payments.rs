@@ -11,33 +11,58 @@ use std::sync::Arc;
 
 use bytes::Bytes;
 use ldk_server_grpc::api::{ListPaymentsRequest, ListPaymentsResponse};
-use ldk_server_grpc::types::{PageToken, Payment};
+use ldk_server_grpc::types::{
+	PageToken, Payment, PaymentDirection, PaymentKindType, PaymentStatus,
+};
 use prost::Message;
 
 use crate::api::error::LdkServerError;
-use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::io::persist::paginated_kv_store::PaymentFilter;
 use crate::io::persist::{
 	PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
 };
 use crate::service::Context;
+use crate::util::labels::apply_payment_label;
+
+pub(crate) const DEFAULT_PAGE_SIZE: u32 = 100;
+const MAX_PAGE_SIZE: u32 = 1000;
 
 pub(crate) async fn handle_list_payments_request(
 	context: Arc<Context>, request: ListPaymentsRequest,
 ) -> Result<ListPaymentsResponse, LdkServerError> {
+	let filter = payment_filter(&request)?;
+	let page_size = page_size(request.page_size)?;
 	let page_token = request.page_token.map(|p| (p.token, p.index));
-	let list_response = context
-		.paginated_kv_store
-		.list(
-			PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
-			PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
-			page_token,
-		)
-		.map_err(|e| {
+	let list_response =
+		context.paginated_kv_store.list_payments(&filter, page_size, page_token).map_err(|e| {
 			LdkServerError::new(InternalServerError, format!("Failed to list payments: {}", e))
 		})?;
 
-	let mut payments: Vec<Payment> = Vec::with_capacity(list_response.keys.len());
-	for key in list_response.keys {
+	let response = ListPaymentsResponse {
+		payments: read_payments(&context, list_response.keys)?,
+		next_page_token: list_response
+			.next_page_token
+			.map(|(token, index)| PageToken { token, index }),
+	};
+	Ok(response)
+}
+
+/// Returns the number of items per page for a requested `page_size`, rejecting an empty page size
+/// rather than returning empty pages.
+pub(crate) fn page_size(page_size: Option<u32>) -> Result<u32, LdkServerError> {
+	match page_size {
+		Some(0) => Err(LdkServerError::new(InvalidRequestError, "page_size must be at least 1")),
+		page_size => Ok(page_size.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE)),
+	}
+}
+
+/// Reads the persisted payments with the given keys, along with their labels.
+pub(crate) fn read_payments(
+	context: &Context, keys: Vec<String>,
+) -> Result<Vec<Payment>, LdkServerError> {
+	let mut payments: Vec<Payment> = Vec::with_capacity(keys.len());
+	for key in keys {
 		let payment_bytes = context
 			.paginated_kv_store
 			.read(
@@ -51,16 +76,40 @@ pub(crate) async fn handle_list_payments_request(
 					format!("Failed to read payment data: {}", e),
 				)
 			})?;
-		let payment = Payment::decode(Bytes::from(payment_bytes)).map_err(|e| {
+		let mut payment = Payment::decode(Bytes::from(payment_bytes)).map_err(|e| {
 			LdkServerError::new(InternalServerError, format!("Failed to decode payment: {}", e))
 		})?;
+		apply_payment_label(&*context.paginated_kv_store, &mut payment)?;
 		payments.push(payment);
 	}
-	let response = ListPaymentsResponse {
-		payments,
-		next_page_token: list_response
-			.next_page_token
-			.map(|(token, index)| PageToken { token, index }),
+	Ok(payments)
+}
+
+fn payment_filter(request: &ListPaymentsRequest) -> Result<PaymentFilter, LdkServerError> {
+	let invalid = |field: &str| {
+		LdkServerError::new(InvalidRequestError, format!("Invalid {field} in ListPaymentsRequest"))
 	};
-	Ok(response)
+	let time = |secs: Option<u64>| secs.map(|secs| i64::try_from(secs).unwrap_or(i64::MAX));
+
+	Ok(PaymentFilter {
+		direction: request
+			.direction
+			.map(|d| PaymentDirection::from_i32(d).ok_or_else(|| invalid("direction")))
+			.transpose()?,
+		status: request
+			.status
+			.map(|s| PaymentStatus::from_i32(s).ok_or_else(|| invalid("status")))
+			.transpose()?,
+		kind: request
+			.kind
+			.map(|k| PaymentKindType::from_i32(k).ok_or_else(|| invalid("kind")))
+			.transpose()?,
+		min_amount_msat: request.min_amount_msat,
+		max_amount_msat: request.max_amount_msat,
+		created_since: time(request.created_since),
+		created_before: time(request.created_before),
+		updated_since: time(request.updated_since),
+		updated_before: time(request.updated_before),
+		..Default::default()
+	})
 }
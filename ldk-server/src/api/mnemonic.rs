@@ -0,0 +1,44 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::io;
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{
+	EncryptMnemonicRequest, EncryptMnemonicResponse, UnlockRequest, UnlockResponse,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::service::Context;
+use crate::util::entropy::{encrypt_mnemonic_file, NodeUnlocker};
+
+pub(crate) async fn handle_unlock_request(
+	unlocker: Arc<NodeUnlocker>, request: UnlockRequest,
+) -> Result<UnlockResponse, LdkServerError> {
+	unlocker.unlock(&request.passphrase)?;
+	Ok(UnlockResponse {})
+}
+
+pub(crate) async fn handle_encrypt_mnemonic_request(
+	context: Arc<Context>, request: EncryptMnemonicRequest,
+) -> Result<EncryptMnemonicResponse, LdkServerError> {
+	let storage_dir = context.storage_dir.as_deref().ok_or_else(|| {
+		LdkServerError::new(InvalidRequestError, "Encrypting the mnemonic is not supported")
+	})?;
+
+	encrypt_mnemonic_file(storage_dir, &request.passphrase).map_err(|e| {
+		if e.kind() == io::ErrorKind::InvalidInput {
+			LdkServerError::new(InvalidRequestError, e.to_string())
+		} else {
+			LdkServerError::new(InternalServerError, format!("Failed to encrypt mnemonic: {e}"))
+		}
+	})?;
+	Ok(EncryptMnemonicResponse {})
+}
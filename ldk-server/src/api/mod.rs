@@ -7,15 +7,37 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hex::DisplayHex;
+use ldk_node::bitcoin::hashes::Hash;
+use ldk_node::bitcoin::Txid;
 use ldk_node::config::{ChannelConfig, MaxDustHTLCExposure};
+use ldk_node::lightning::ln::channelmanager::PaymentId;
 use ldk_node::lightning::routing::router::RouteParametersConfig;
-use ldk_node::CustomTlvRecord as NodeCustomTlvRecord;
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_node::{CustomTlvRecord as NodeCustomTlvRecord, Node};
 use ldk_server_grpc::types::channel_config::MaxDustHtlcExposure;
-use ldk_server_grpc::types::CustomTlvRecord as ProtoCustomTlvRecord;
+use ldk_server_grpc::types::confirmation_status::Status::Unconfirmed;
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{
+	Bolt11InvoiceDescription, ConfirmationStatus, CustomTlvRecord as ProtoCustomTlvRecord, Invoice,
+	Onchain, Payment, PaymentDirection, PaymentKind, PaymentStatus, UserLabel,
+};
+use log::error;
+use serde::Serialize;
 
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+use crate::util::invoices::record_invoice;
+use crate::util::labels::{set_label, LabelTarget};
+use crate::util::onchain_payments::persist_onchain_payment;
+use crate::util::proto_adapter::payment_to_proto;
+use crate::util::spend_limits::SpendKind;
 
+pub(crate) mod api_keys;
+pub(crate) mod approvals;
 pub(crate) mod bolt11_claim_for_hash;
 pub(crate) mod bolt11_fail_for_hash;
 pub(crate) mod bolt11_receive;
@@ -31,6 +53,7 @@ pub(crate) mod decode_offer;
 pub(crate) mod disconnect_peer;
 pub(crate) mod error;
 pub(crate) mod export_pathfinding_scores;
+pub(crate) mod find_payments;
 pub(crate) mod get_balances;
 pub(crate) mod get_node_info;
 pub(crate) mod get_payment_details;
@@ -38,19 +61,30 @@ pub(crate) mod graph_get_channel;
 pub(crate) mod graph_get_node;
 pub(crate) mod graph_list_channels;
 pub(crate) mod graph_list_nodes;
+pub(crate) mod list_audit_log;
 pub(crate) mod list_channels;
+pub(crate) mod list_claimable_payments;
+pub(crate) mod list_closed_channels;
 pub(crate) mod list_forwarded_payments;
+pub(crate) mod list_invoices;
+pub(crate) mod list_offers;
+pub(crate) mod list_onchain_transactions;
 pub(crate) mod list_payments;
 pub(crate) mod list_peers;
+pub(crate) mod mnemonic;
 pub(crate) mod onchain_receive;
 pub(crate) mod onchain_send;
 pub(crate) mod open_channel;
+pub(crate) mod query_routes;
+pub(crate) mod send_probes;
 pub(crate) mod sign_message;
 pub(crate) mod splice_channel;
 pub(crate) mod spontaneous_send;
 pub(crate) mod unified_send;
 pub(crate) mod update_channel_config;
+pub(crate) mod update_label;
 pub(crate) mod verify_signature;
+pub(crate) mod webhooks;
 
 pub(crate) fn require_amount<T>(amount: Option<T>) -> Result<T, LdkServerError> {
 	amount.ok_or_else(|| {
@@ -61,6 +95,105 @@ pub(crate) fn require_amount<T>(amount: Option<T>) -> Result<T, LdkServerError>
 	})
 }
 
+/// Parks the send of `request` in the approval queue if it exceeds the configured thresholds,
+/// returning the identifier of the created pending action.
+///
+/// Returns `None` if the send can be executed right away, which includes sends that are executed
+/// because they were approved.
+pub(crate) fn require_approval<T: prost::Message + Serialize>(
+	context: &Context, kinds: &[SpendKind], amount_msat: Option<u64>, request: &T,
+) -> Result<Option<String>, LdkServerError> {
+	match &context.approval_queue {
+		Some(approval_queue) if !context.is_approved => Ok(approval_queue
+			.submit(
+				&context.caller,
+				context.caller_uses_api_key,
+				&context.method,
+				kinds,
+				amount_msat,
+				request,
+			)?
+			.map(|action| action.id)),
+		_ => Ok(None),
+	}
+}
+
+/// Records an invoice that was just issued in the invoice registry, so it is listed by
+/// `ListInvoices`.
+///
+/// `user_label` is also attached to the payment for the invoice, whose ID is the invoice's payment
+/// hash. Failures are logged rather than returned, as the invoice was already created.
+pub(crate) fn record_issued_invoice(
+	context: &Context, invoice: &Bolt11Invoice, description: Option<Bolt11InvoiceDescription>,
+	user_label: UserLabel,
+) {
+	let payment_hash = invoice.payment_hash().0.to_lower_hex_string();
+	set_label(&*context.paginated_kv_store, LabelTarget::Payment, &payment_hash, &user_label);
+
+	let created_at = invoice.duration_since_epoch().as_secs();
+	let record = Invoice {
+		payment_hash,
+		invoice: invoice.to_string(),
+		description,
+		amount_msat: invoice.amount_milli_satoshis(),
+		label: user_label.label,
+		metadata: user_label.metadata,
+		created_at,
+		expires_at: created_at.saturating_add(invoice.expiry_time().as_secs()),
+		..Default::default()
+	};
+	if let Err(e) = record_invoice(&*context.paginated_kv_store, &record) {
+		error!("Failed to record invoice {}: {e}", record.payment_hash);
+	}
+}
+
+/// Returns the details of a Lightning payment that was just sent, to record it with the spending
+/// limits.
+pub(crate) fn sent_lightning_payment(node: &Node, payment_id: PaymentId) -> Payment {
+	node.payment(&payment_id)
+		.map(payment_to_proto)
+		.unwrap_or_else(|| pending_outbound_payment(payment_id, None, None))
+}
+
+/// Returns the details of an on-chain payment that was just broadcast, to record it with the
+/// spending limits.
+///
+/// The payment is also persisted, so that it's listed before the on-chain wallet syncs next.
+pub(crate) fn sent_onchain_payment(context: &Context, txid: Txid, amount_msat: u64) -> Payment {
+	let payment_id = PaymentId(txid.to_byte_array());
+	let payment = context.node.payment(&payment_id).map(payment_to_proto).unwrap_or_else(|| {
+		let kind = Kind::Onchain(Onchain {
+			txid: txid.to_string(),
+			status: Some(ConfirmationStatus {
+				status: Some(Unconfirmed(ldk_server_grpc::types::Unconfirmed {})),
+			}),
+		});
+		pending_outbound_payment(payment_id, Some(kind), Some(amount_msat))
+	});
+	if let Err(e) = persist_onchain_payment(&*context.paginated_kv_store, &payment) {
+		error!("{}", e.message);
+	}
+	payment
+}
+
+fn pending_outbound_payment(
+	payment_id: PaymentId, kind: Option<Kind>, amount_msat: Option<u64>,
+) -> Payment {
+	Payment {
+		id: payment_id.to_string(),
+		kind: kind.map(|kind| PaymentKind { kind: Some(kind) }),
+		amount_msat,
+		fee_paid_msat: None,
+		direction: PaymentDirection::Outbound as i32,
+		status: PaymentStatus::Pending as i32,
+		latest_update_timestamp: SystemTime::now()
+			.duration_since(UNIX_EPOCH)
+			.unwrap_or_default()
+			.as_secs(),
+		..Default::default()
+	}
+}
+
 pub(crate) fn build_channel_config_from_proto(
 	default_config: ChannelConfig, proto_channel_config: ldk_server_grpc::types::ChannelConfig,
 ) -> Result<ChannelConfig, LdkServerError> {
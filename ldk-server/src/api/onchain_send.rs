@@ -16,8 +16,10 @@ use ldk_server_grpc::api::{OnchainSendRequest, OnchainSendResponse};
 
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
-use crate::api::require_amount;
+use crate::api::{require_amount, require_approval, sent_onchain_payment};
 use crate::service::Context;
+use crate::util::labels::{new_label, set_label, LabelTarget};
+use crate::util::spend_limits::SpendKind;
 
 pub(crate) async fn handle_onchain_send_request(
 	context: Arc<Context>, request: OnchainSendRequest,
@@ -32,8 +34,35 @@ pub(crate) async fn handle_onchain_send_request(
 			)
 		})?;
 
+	let amount = require_amount(request.amount.clone())?;
+	context.caller.scope.check_send_amount(match amount {
+		Amount::AmountSats(amount_sats) => amount_sats.checked_mul(1000),
+		Amount::AllFunds(_) => None,
+	})?;
+	let user_label = new_label(request.label.clone(), request.metadata.clone())?;
+
+	// When sending all funds, at most the spendable balance is sent.
+	let amount_msat = match amount {
+		Amount::AmountSats(amount_sats) => amount_sats.checked_mul(1000),
+		Amount::AllFunds(_) => {
+			context.node.list_balances().spendable_onchain_balance_sats.checked_mul(1000)
+		},
+	};
+
+	if let Some(pending_action_id) =
+		require_approval(&context, &[SpendKind::Onchain], amount_msat, &request)?
+	{
+		return Ok(OnchainSendResponse {
+			pending_action_id: Some(pending_action_id),
+			..Default::default()
+		});
+	}
+
+	let spend_reservation =
+		context.spend_tracker.reserve(&context.caller.name, &[SpendKind::Onchain], amount_msat)?;
+
 	let fee_rate = request.fee_rate_sat_per_vb.and_then(FeeRate::from_sat_per_vb);
-	let txid = match require_amount(request.amount)? {
+	let txid = match amount {
 		Amount::AmountSats(amount_sats) => {
 			context.node.onchain_payment().send_to_address(&address, amount_sats, fee_rate)?
 		},
@@ -41,6 +70,10 @@ pub(crate) async fn handle_onchain_send_request(
 			context.node.onchain_payment().send_all_to_address(&address, true, fee_rate)?
 		},
 	};
-	let response = OnchainSendResponse { txid: txid.to_string() };
+	let payment = sent_onchain_payment(&context, txid, amount_msat.unwrap_or(0));
+	set_label(&*context.paginated_kv_store, LabelTarget::Payment, &payment.id, &user_label);
+	spend_reservation.record(payment);
+
+	let response = OnchainSendResponse { txid: txid.to_string(), pending_action_id: None };
 	Ok(response)
 }
@@ -19,6 +19,7 @@ use ldk_server_grpc::api::{OpenChannelRequest, OpenChannelResponse};
 use crate::api::error::{LdkServerError, LdkServerErrorCode};
 use crate::api::{build_channel_config_from_proto, require_amount};
 use crate::service::Context;
+use crate::util::labels::{new_label, set_label, LabelTarget};
 
 pub(crate) async fn handle_open_channel(
 	context: Arc<Context>, request: OpenChannelRequest,
@@ -29,6 +30,7 @@ pub(crate) async fn handle_open_channel(
 		.map_err(|_| ldk_node::NodeError::InvalidSocketAddress)?;
 
 	let amount = require_amount(request.amount)?;
+	let user_label = new_label(request.label, request.metadata)?;
 
 	let channel_config = request
 		.channel_config
@@ -88,6 +90,9 @@ pub(crate) async fn handle_open_channel(
 			},
 		};
 
-	let response = OpenChannelResponse { user_channel_id: user_channel_id.0.to_string() };
+	let user_channel_id = user_channel_id.0.to_string();
+	set_label(&*context.paginated_kv_store, LabelTarget::Channel, &user_channel_id, &user_label);
+
+	let response = OpenChannelResponse { user_channel_id };
 	Ok(response)
 }
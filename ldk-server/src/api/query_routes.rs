@@ -0,0 +1,152 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::str::FromStr;
+use std::sync::Arc;
+
+use ldk_node::bitcoin::secp256k1::PublicKey;
+use ldk_node::lightning::routing::router::{PaymentParameters, RouteParameters};
+use ldk_node::lightning_invoice::Bolt11Invoice;
+use ldk_server_grpc::api::{
+	estimate_route_fee_request, query_routes_request, EstimateRouteFeeRequest,
+	EstimateRouteFeeResponse, QueryRoutesRequest, QueryRoutesResponse,
+};
+use ldk_server_grpc::types::RouteParametersConfig;
+
+use crate::api::build_route_parameters_config_from_proto;
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+use crate::util::proto_adapter::route_to_proto;
+use crate::util::routing::{query_route, run_blocking};
+
+/// The final CLTV expiry delta assumed for payments to a node, which don't come with an invoice
+/// stating it.
+const SPONTANEOUS_FINAL_CLTV_EXPIRY_DELTA: u32 = 144;
+
+/// The destination of a payment to find a route for.
+pub(crate) enum Destination {
+	Invoice(Bolt11Invoice),
+	NodeId(PublicKey),
+}
+
+impl Destination {
+	pub(crate) fn from_invoice(invoice: &str) -> Result<Self, LdkServerError> {
+		let invoice =
+			Bolt11Invoice::from_str(invoice).map_err(|_| ldk_node::NodeError::InvalidInvoice)?;
+		Ok(Destination::Invoice(invoice))
+	}
+
+	pub(crate) fn from_node_id(node_id: &str) -> Result<Self, LdkServerError> {
+		let node_id = PublicKey::from_str(node_id).map_err(|_| {
+			LdkServerError::new(InvalidRequestError, "Invalid node_id provided.".to_string())
+		})?;
+		Ok(Destination::NodeId(node_id))
+	}
+}
+
+pub(crate) async fn handle_estimate_route_fee_request(
+	context: Arc<Context>, request: EstimateRouteFeeRequest,
+) -> Result<EstimateRouteFeeResponse, LdkServerError> {
+	let destination = match request.destination {
+		Some(estimate_route_fee_request::Destination::Invoice(invoice)) => {
+			Destination::from_invoice(&invoice)?
+		},
+		Some(estimate_route_fee_request::Destination::NodeId(node_id)) => {
+			Destination::from_node_id(&node_id)?
+		},
+		None => return Err(missing_destination()),
+	};
+	let route_params =
+		build_route_parameters(&destination, request.amount_msat, request.route_parameters)?;
+	let route = run_blocking(move || {
+		let snapshot = context.routing_snapshots.get(&context.node);
+		query_route(&context.node, &snapshot, &route_params)
+	})
+	.await?;
+	let route = route_to_proto(&route);
+
+	let response = EstimateRouteFeeResponse {
+		fee_msat: route.total_fee_msat,
+		total_amount_msat: route.total_amount_msat,
+		max_total_cltv_expiry_delta: route
+			.paths
+			.iter()
+			.map(|path| path.total_cltv_expiry_delta)
+			.max()
+			.unwrap_or(0),
+		path_count: route.paths.len() as u32,
+	};
+	Ok(response)
+}
+
+pub(crate) async fn handle_query_routes_request(
+	context: Arc<Context>, request: QueryRoutesRequest,
+) -> Result<QueryRoutesResponse, LdkServerError> {
+	let destination = match request.destination {
+		Some(query_routes_request::Destination::Invoice(invoice)) => {
+			Destination::from_invoice(&invoice)?
+		},
+		Some(query_routes_request::Destination::NodeId(node_id)) => {
+			Destination::from_node_id(&node_id)?
+		},
+		None => return Err(missing_destination()),
+	};
+	let route_params =
+		build_route_parameters(&destination, request.amount_msat, request.route_parameters)?;
+	let route = run_blocking(move || {
+		let snapshot = context.routing_snapshots.get(&context.node);
+		query_route(&context.node, &snapshot, &route_params)
+	})
+	.await?;
+
+	let response = QueryRoutesResponse { route: Some(route_to_proto(&route)) };
+	Ok(response)
+}
+
+pub(crate) fn missing_destination() -> LdkServerError {
+	LdkServerError::new(InvalidRequestError, "One of invoice or node_id must be set")
+}
+
+/// Builds the parameters LDK's router is run with, the way a send to `destination` does.
+pub(crate) fn build_route_parameters(
+	destination: &Destination, amount_msat: Option<u64>,
+	route_parameters: Option<RouteParametersConfig>,
+) -> Result<RouteParameters, LdkServerError> {
+	let (payment_params, amount_msat) = match destination {
+		Destination::Invoice(invoice) => {
+			let amount_msat = amount_msat.or(invoice.amount_milli_satoshis());
+			(PaymentParameters::from_bolt11_invoice(invoice), amount_msat)
+		},
+		Destination::NodeId(node_id) => (
+			PaymentParameters::from_node_id(*node_id, SPONTANEOUS_FINAL_CLTV_EXPIRY_DELTA),
+			amount_msat,
+		),
+	};
+	let amount_msat = amount_msat.ok_or_else(|| {
+		LdkServerError::new(
+			InvalidRequestError,
+			"amount_msat is required for a node_id or a variable-amount invoice.",
+		)
+	})?;
+
+	let mut route_params =
+		RouteParameters::from_payment_params_and_value(payment_params, amount_msat);
+	if let Some(config) = build_route_parameters_config_from_proto(route_parameters)? {
+		route_params.payment_params = route_params
+			.payment_params
+			.with_max_total_cltv_expiry_delta(config.max_total_cltv_expiry_delta)
+			.with_max_path_count(config.max_path_count)
+			.with_max_channel_saturation_power_of_half(config.max_channel_saturation_power_of_half);
+		if config.max_total_routing_fee_msat.is_some() {
+			route_params.max_total_routing_fee_msat = config.max_total_routing_fee_msat;
+		}
+	}
+	Ok(route_params)
+}
@@ -0,0 +1,171 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ldk_server_grpc::api::{send_probes_request, SendProbesRequest, SendProbesResponse};
+use ldk_server_grpc::types::{ProbeResult, ProbeStatus};
+use log::error;
+use tokio::time::Instant;
+
+use crate::api::build_route_parameters_config_from_proto;
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::api::query_routes::{build_route_parameters, missing_destination, Destination};
+use crate::service::Context;
+use crate::util::probes::{LiquidityRange, PROBE_POLL_INTERVAL};
+use crate::util::proto_adapter::route_path_to_proto;
+use crate::util::routing::{
+	estimated_liquidity, preflight_probe_paths, query_route, routing_scorer, run_blocking,
+	RoutingGraph,
+};
+
+const DEFAULT_PROBE_TIMEOUT_SECS: u32 = 60;
+
+const MAX_PROBE_TIMEOUT_SECS: u32 = 600;
+
+pub(crate) async fn handle_send_probes_request(
+	context: Arc<Context>, request: SendProbesRequest,
+) -> Result<SendProbesResponse, LdkServerError> {
+	let timeout_secs = request.timeout_secs.unwrap_or(DEFAULT_PROBE_TIMEOUT_SECS);
+	if timeout_secs > MAX_PROBE_TIMEOUT_SECS {
+		return Err(LdkServerError::new(
+			InvalidRequestError,
+			format!("timeout_secs must be at most {MAX_PROBE_TIMEOUT_SECS}"),
+		));
+	}
+	let destination = match request.destination {
+		Some(send_probes_request::Destination::Invoice(invoice)) => {
+			Destination::from_invoice(&invoice)?
+		},
+		Some(send_probes_request::Destination::NodeId(node_id)) => {
+			if request.route_parameters.is_some() {
+				return Err(LdkServerError::new(
+					InvalidRequestError,
+					"route_parameters are only supported for an invoice",
+				));
+			}
+			Destination::from_node_id(&node_id)?
+		},
+		None => return Err(missing_destination()),
+	};
+	let route_params = build_route_parameters(
+		&destination,
+		request.amount_msat,
+		request.route_parameters.clone(),
+	)?;
+
+	// LDK Node runs its own router when sending the probes and doesn't report their outcomes. The
+	// paths it most likely probes are thus looked up the same way beforehand, to estimate the
+	// outcomes from how the pathfinding scores change for them.
+	let routing_context = Arc::clone(&context);
+	let final_value_msat = route_params.final_value_msat;
+	let (graph, probes) = run_blocking(move || {
+		let node = &routing_context.node;
+		let snapshot = routing_context.routing_snapshots.get(node);
+		let route = query_route(node, &snapshot, &route_params)?;
+		let liquidity_limit_multiplier = node.config().probing_liquidity_limit_multiplier;
+		let probe_paths = preflight_probe_paths(node, &route, liquidity_limit_multiplier);
+
+		// The baselines are read from the current scores rather than the snapshot's, which may not
+		// reflect the outcomes of the latest probes yet.
+		let graph = Arc::clone(&snapshot.graph);
+		let probes: Vec<_> = {
+			let scorer = routing_scorer(node, Arc::clone(&graph));
+			probe_paths
+				.iter()
+				.map(|(path, is_probed)| {
+					let path = route_path_to_proto(path);
+					let baseline = is_probed.then(|| estimated_liquidity(&scorer, &path));
+					(path, baseline)
+				})
+				.collect()
+		};
+		Ok((graph, probes))
+	})
+	.await?;
+
+	let node = &context.node;
+	if probes.iter().any(|(_, baseline)| baseline.is_some()) {
+		let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
+		match &destination {
+			Destination::Invoice(invoice) => match request.amount_msat {
+				Some(amount_msat) => node.bolt11_payment().send_probes_using_amount(
+					invoice,
+					amount_msat,
+					route_parameters,
+				)?,
+				None => node.bolt11_payment().send_probes(invoice, route_parameters)?,
+			},
+			Destination::NodeId(node_id) => {
+				node.spontaneous_payment().send_probes(final_value_msat, *node_id)?
+			},
+		}
+	}
+
+	let probe_tracker = &context.probe_tracker;
+	let probes: Vec<_> = probes
+		.into_iter()
+		.map(|(path, baseline)| {
+			let id = baseline.map(|baseline| probe_tracker.track(path.clone(), baseline));
+			(path, id)
+		})
+		.collect();
+	let ids: Vec<u64> = probes.iter().filter_map(|(_, id)| *id).collect();
+
+	let deadline = Instant::now() + Duration::from_secs(timeout_secs.into());
+	loop {
+		if probe_tracker.claim_poll() {
+			let (context, graph) = (Arc::clone(&context), Arc::clone(&graph));
+			match run_blocking(move || Ok(pending_liquidity(&context, &graph))).await {
+				Ok(liquidity) => probe_tracker.update(&liquidity),
+				Err(e) => error!("Failed to check the outcomes of probes: {e}"),
+			}
+		}
+		if probe_tracker.is_resolved(&ids) || Instant::now() >= deadline {
+			break;
+		}
+		let remaining = deadline.saturating_duration_since(Instant::now());
+		tokio::time::sleep(PROBE_POLL_INTERVAL.min(remaining)).await;
+	}
+
+	let mut tracked_results = probe_tracker.finish(&ids).into_iter();
+	let results = probes
+		.into_iter()
+		.filter_map(|(path, id)| match id {
+			Some(_) => tracked_results.next(),
+			None => Some(ProbeResult {
+				path: Some(path),
+				estimated_status: ProbeStatus::Skipped.into(),
+				estimated_failed_short_channel_id: None,
+			}),
+		})
+		.collect();
+
+	let response = SendProbesResponse { results, locked_msat: probe_tracker.locked_msat() };
+	Ok(response)
+}
+
+/// Looks up the liquidity currently estimated for the hops of the paths of all pending probes.
+///
+/// This reads the whole scorer, so it blocks and is only done once per poll for all requests, see
+/// [`crate::util::probes::ProbeTracker::claim_poll`].
+fn pending_liquidity(
+	context: &Context, graph: &Arc<RoutingGraph>,
+) -> HashMap<u64, Vec<LiquidityRange>> {
+	let scorer = routing_scorer(&context.node, Arc::clone(graph));
+	context
+		.probe_tracker
+		.pending_paths()
+		.into_iter()
+		.map(|(id, path)| (id, estimated_liquidity(&scorer, &path)))
+		.collect()
+}
@@ -18,8 +18,12 @@ use ldk_server_grpc::api::{SpontaneousSendRequest, SpontaneousSendResponse};
 
 use crate::api::error::LdkServerError;
 use crate::api::error::LdkServerErrorCode::InvalidRequestError;
-use crate::api::{build_route_parameters_config_from_proto, proto_to_node_custom_tlv};
+use crate::api::{
+	build_route_parameters_config_from_proto, proto_to_node_custom_tlv, require_approval,
+	sent_lightning_payment,
+};
 use crate::service::Context;
+use crate::util::spend_limits::SpendKind;
 
 pub(crate) async fn handle_spontaneous_send_request(
 	context: Arc<Context>, request: SpontaneousSendRequest,
@@ -28,6 +32,17 @@ pub(crate) async fn handle_spontaneous_send_request(
 		LdkServerError::new(InvalidRequestError, "Invalid node_id provided.".to_string())
 	})?;
 
+	context.caller.scope.check_send_amount(Some(request.amount_msat))?;
+
+	if let Some(pending_action_id) =
+		require_approval(&context, &[SpendKind::Lightning], Some(request.amount_msat), &request)?
+	{
+		return Ok(SpontaneousSendResponse {
+			pending_action_id: Some(pending_action_id),
+			..Default::default()
+		});
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
 	let preimage = request
@@ -48,6 +63,12 @@ pub(crate) async fn handle_spontaneous_send_request(
 		Some(request.custom_tlvs.iter().map(proto_to_node_custom_tlv).collect())
 	};
 
+	let spend_reservation = context.spend_tracker.reserve(
+		&context.caller.name,
+		&[SpendKind::Lightning],
+		Some(request.amount_msat),
+	)?;
+
 	let payment_id = match (preimage, custom_tlvs) {
 		(None, None) => context.node.spontaneous_payment().send(
 			request.amount_msat,
@@ -76,6 +97,7 @@ pub(crate) async fn handle_spontaneous_send_request(
 			)?
 		},
 	};
+	spend_reservation.record(sent_lightning_payment(&context.node, payment_id));
 
-	Ok(SpontaneousSendResponse { payment_id: payment_id.to_string() })
+	Ok(SpontaneousSendResponse { payment_id: payment_id.to_string(), pending_action_id: None })
 }
@@ -9,25 +9,60 @@
 
 use std::sync::Arc;
 
+use ldk_node::bitcoin::{Amount, Denomination};
 use ldk_node::payment::UnifiedPaymentResult;
 use ldk_server_grpc::api::unified_send_response::PaymentResult;
 use ldk_server_grpc::api::{UnifiedSendRequest, UnifiedSendResponse};
 
-use crate::api::build_route_parameters_config_from_proto;
 use crate::api::error::LdkServerError;
+use crate::api::{
+	build_route_parameters_config_from_proto, require_approval, sent_lightning_payment,
+	sent_onchain_payment,
+};
 use crate::service::Context;
+use crate::util::spend_limits::SpendKind;
 
 pub(crate) async fn handle_unified_send_request(
 	context: Arc<Context>, request: UnifiedSendRequest,
 ) -> Result<UnifiedSendResponse, LdkServerError> {
+	// The amount is either part of a BIP 21 URI or given with the request, e.g. for a payment to a
+	// human-readable name. If both are set, we check against the larger one.
+	let amount_msat = match (bip21_amount_msat(&request.uri), request.amount_msat) {
+		(Some(uri_amount_msat), Some(amount_msat)) => Some(uri_amount_msat.max(amount_msat)),
+		(uri_amount_msat, amount_msat) => uri_amount_msat.or(amount_msat),
+	};
+	context.caller.scope.check_send_amount(amount_msat)?;
+
+	let kinds = [SpendKind::Lightning, SpendKind::Onchain];
+	if let Some(pending_action_id) = require_approval(&context, &kinds, amount_msat, &request)? {
+		let payment_result = PaymentResult::PendingActionId(pending_action_id);
+		return Ok(UnifiedSendResponse { payment_result: Some(payment_result) });
+	}
+
 	let route_parameters = build_route_parameters_config_from_proto(request.route_parameters)?;
 
+	// The URI may resolve to either an on-chain or a Lightning payment, so it has to be within
+	// both budgets.
+	let spend_reservation =
+		context.spend_tracker.reserve(&context.caller.name, &kinds, amount_msat)?;
+
 	let result = context
 		.node
 		.unified_payment()
 		.send(&request.uri, request.amount_msat, route_parameters)
 		.await?;
 
+	let payment = match &result {
+		UnifiedPaymentResult::Onchain { txid } => {
+			sent_onchain_payment(&context, *txid, amount_msat.unwrap_or(0))
+		},
+		UnifiedPaymentResult::Bolt11 { payment_id }
+		| UnifiedPaymentResult::Bolt12 { payment_id } => {
+			sent_lightning_payment(&context.node, *payment_id)
+		},
+	};
+	spend_reservation.record(payment);
+
 	let payment_result = match result {
 		UnifiedPaymentResult::Onchain { txid } => PaymentResult::Txid(txid.to_string()),
 		UnifiedPaymentResult::Bolt11 { payment_id } => {
@@ -40,3 +75,38 @@ pub(crate) async fn handle_unified_send_request(
 
 	Ok(UnifiedSendResponse { payment_result: Some(payment_result) })
 }
+
+/// Returns the amount of a BIP 21 URI, if it is one and has an amount.
+fn bip21_amount_msat(uri: &str) -> Option<u64> {
+	let (scheme, rest) = uri.split_once(':')?;
+	if !scheme.eq_ignore_ascii_case("bitcoin") {
+		return None;
+	}
+	let (_, query) = rest.split_once('?')?;
+	let amount = query.split('&').find_map(|param| {
+		let (key, value) = param.split_once('=')?;
+		key.eq_ignore_ascii_case("amount").then_some(value)
+	})?;
+	Amount::from_str_in(amount, Denomination::Bitcoin).ok()?.to_sat().checked_mul(1000)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_bip21_amount_msat() {
+		let address = "bcrt1qw508d6qejxtdg4y5r3zarvary0c5xw7kygt080";
+		assert_eq!(
+			bip21_amount_msat(&format!("bitcoin:{address}?amount=0.0001")),
+			Some(10_000_000)
+		);
+		assert_eq!(
+			bip21_amount_msat(&format!("BITCOIN:{address}?label=test&AMOUNT=1&lightning=lnbcrt1")),
+			Some(100_000_000_000)
+		);
+		assert_eq!(bip21_amount_msat(&format!("bitcoin:{address}?lightning=lnbcrt1")), None);
+		assert_eq!(bip21_amount_msat(&format!("bitcoin:{address}")), None);
+		assert_eq!(bip21_amount_msat("alice@example.com"), None);
+	}
+}
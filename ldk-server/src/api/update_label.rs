@@ -0,0 +1,81 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use hex::{DisplayHex, FromHex};
+use ldk_node::lightning::ln::channelmanager::PaymentId;
+use ldk_server_grpc::api::update_label_request::Target;
+use ldk_server_grpc::api::{UpdateLabelRequest, UpdateLabelResponse};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+use crate::util::closed_channels::is_closed_channel;
+use crate::util::invoices::is_issued_invoice;
+use crate::util::labels::{update_label, LabelTarget};
+
+pub(crate) async fn handle_update_label_request(
+	context: Arc<Context>, request: UpdateLabelRequest,
+) -> Result<UpdateLabelResponse, LdkServerError> {
+	let (target, key) = match request.target {
+		Some(Target::PaymentId(payment_id)) => {
+			let payment_id_bytes =
+				<[u8; PaymentId::LENGTH]>::from_hex(&payment_id).map_err(|_| {
+					LdkServerError::new(
+						InvalidRequestError,
+						format!(
+							"Invalid payment_id, must be a {}-byte hex-string.",
+							PaymentId::LENGTH
+						),
+					)
+				})?;
+			let payment_id = payment_id_bytes.to_lower_hex_string();
+			// The payment for an issued invoice can be labeled before it was received.
+			let exists = context.node.payment(&PaymentId(payment_id_bytes)).is_some()
+				|| is_issued_invoice(&*context.paginated_kv_store, &payment_id)?;
+			if !exists {
+				return Err(LdkServerError::new(
+					InvalidRequestError,
+					"Payment not found for given payment_id.",
+				));
+			}
+			(LabelTarget::Payment, payment_id)
+		},
+		Some(Target::UserChannelId(user_channel_id)) => {
+			let user_channel_id = user_channel_id
+				.parse::<u128>()
+				.map_err(|_| LdkServerError::new(InvalidRequestError, "Invalid UserChannelId."))?;
+			let user_channel_id = user_channel_id.to_string();
+			let exists = context
+				.node
+				.list_channels()
+				.iter()
+				.any(|channel| channel.user_channel_id.0.to_string() == user_channel_id)
+				|| is_closed_channel(&*context.paginated_kv_store, &user_channel_id)?;
+			if !exists {
+				return Err(LdkServerError::new(
+					InvalidRequestError,
+					"Channel not found for given user_channel_id.",
+				));
+			}
+			(LabelTarget::Channel, user_channel_id)
+		},
+		None => {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				"One of payment_id or user_channel_id must be set",
+			))
+		},
+	};
+
+	let user_label =
+		update_label(&*context.paginated_kv_store, target, &key, request.label, request.metadata)?;
+	Ok(UpdateLabelResponse { label: user_label.label, metadata: user_label.metadata })
+}
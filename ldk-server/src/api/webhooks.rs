@@ -0,0 +1,36 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::Arc;
+
+use ldk_server_grpc::api::{
+	ListFailedWebhookDeliveriesRequest, ListFailedWebhookDeliveriesResponse,
+	RedeliverWebhookRequest, RedeliverWebhookResponse,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+use crate::service::Context;
+use crate::util::webhooks::list_failed_webhook_deliveries;
+
+pub(crate) async fn handle_list_failed_webhook_deliveries_request(
+	context: Arc<Context>, request: ListFailedWebhookDeliveriesRequest,
+) -> Result<ListFailedWebhookDeliveriesResponse, LdkServerError> {
+	list_failed_webhook_deliveries(&*context.paginated_kv_store, request.page_token)
+}
+
+pub(crate) async fn handle_redeliver_webhook_request(
+	context: Arc<Context>, request: RedeliverWebhookRequest,
+) -> Result<RedeliverWebhookResponse, LdkServerError> {
+	let webhooks = context.webhooks.as_deref().ok_or_else(|| {
+		LdkServerError::new(InvalidRequestError, "Webhooks are not configured on this server")
+	})?;
+	let delivery = webhooks.redeliver(&request.delivery_id)?;
+	Ok(RedeliverWebhookResponse { delivery: Some(delivery) })
+}
@@ -10,6 +10,12 @@
 pub(crate) mod paginated_kv_store;
 pub(crate) mod sqlite_store;
 
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+
 /// The forwarded payments will be persisted under this prefix.
 pub(crate) const FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "forwarded_payments";
 pub(crate) const FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
@@ -17,3 +23,119 @@ pub(crate) const FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
 /// The payments will be persisted under this prefix.
 pub(crate) const PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "payments";
 pub(crate) const PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The API keys will be persisted under this prefix.
+pub(crate) const API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "api_keys";
+pub(crate) const API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The audit log of state-changing RPC calls will be persisted under this prefix.
+pub(crate) const AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE: &str = "audit_log";
+pub(crate) const AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The sends counted against the spending limits will be persisted under this prefix.
+pub(crate) const SPENDS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "spends";
+pub(crate) const SPENDS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The sends awaiting approval will be persisted under this prefix.
+pub(crate) const PENDING_ACTIONS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "pending_actions";
+pub(crate) const PENDING_ACTIONS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The invoices issued by the server will be persisted under this prefix.
+pub(crate) const INVOICES_PERSISTENCE_PRIMARY_NAMESPACE: &str = "invoices";
+pub(crate) const INVOICES_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The offers issued by the server will be persisted under this prefix.
+pub(crate) const OFFERS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "offers";
+pub(crate) const OFFERS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The payments for invoices created via `Bolt11ReceiveForHash` that are waiting to be claimed or
+/// failed back will be persisted under this prefix, keyed by their payment hash.
+pub(crate) const CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "claimable_payments";
+pub(crate) const CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The preimages registered to claim payments for invoices created via `Bolt11ReceiveForHash`
+/// automatically will be persisted under this prefix, keyed by their payment hash.
+pub(crate) const AUTO_CLAIM_PREIMAGES_PERSISTENCE_PRIMARY_NAMESPACE: &str = "auto_claim_preimages";
+pub(crate) const AUTO_CLAIM_PREIMAGES_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The events published to subscribers will be persisted under this prefix, keyed by their
+/// sequence number.
+pub(crate) const EVENTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "events";
+pub(crate) const EVENTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The state of the event log, i.e. the sequence number of the oldest event that wasn't pruned,
+/// will be persisted under this prefix.
+pub(crate) const EVENT_LOG_STATE_PERSISTENCE_PRIMARY_NAMESPACE: &str = "events";
+pub(crate) const EVENT_LOG_STATE_PERSISTENCE_SECONDARY_NAMESPACE: &str = "state";
+
+/// The inbound on-chain payments that events were emitted for will be persisted under this prefix,
+/// keyed by their payment ID, along with whether they were reported as confirmed.
+pub(crate) const ONCHAIN_RECEIPTS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "onchain_receipts";
+pub(crate) const ONCHAIN_RECEIPTS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The state of the node monitor, i.e. whether it took a baseline of the on-chain receipts, will
+/// be persisted under this prefix.
+pub(crate) const NODE_MONITOR_STATE_PERSISTENCE_PRIMARY_NAMESPACE: &str = "onchain_receipts";
+pub(crate) const NODE_MONITOR_STATE_PERSISTENCE_SECONDARY_NAMESPACE: &str = "state";
+
+/// The channels that were closed will be persisted under this prefix.
+pub(crate) const CLOSED_CHANNELS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "closed_channels";
+pub(crate) const CLOSED_CHANNELS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The labels attached to payments will be persisted under this prefix.
+pub(crate) const PAYMENT_LABELS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "payment_labels";
+pub(crate) const PAYMENT_LABELS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The labels attached to channels will be persisted under this prefix.
+pub(crate) const CHANNEL_LABELS_PERSISTENCE_PRIMARY_NAMESPACE: &str = "channel_labels";
+pub(crate) const CHANNEL_LABELS_PERSISTENCE_SECONDARY_NAMESPACE: &str = "";
+
+/// The webhook deliveries that didn't succeed yet will be persisted under this prefix, split into
+/// those that are still being attempted and those that ran out of attempts.
+pub(crate) const WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE: &str = "webhook_deliveries";
+pub(crate) const WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE: &str = "pending";
+pub(crate) const WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE: &str = "failed";
+
+/// Reads and decodes the record stored under `key`, or returns `None` if there is none.
+pub(crate) fn read_record<T: Message + Default>(
+	paginated_kv_store: &dyn PaginatedKVStore, primary_namespace: &str, secondary_namespace: &str,
+	key: &str,
+) -> Result<Option<T>, LdkServerError> {
+	let bytes = match paginated_kv_store.read(primary_namespace, secondary_namespace, key) {
+		Ok(bytes) => bytes,
+		Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+		Err(e) => {
+			return Err(LdkServerError::new(
+				InternalServerError,
+				format!("Failed to read {primary_namespace} record '{key}': {e}"),
+			))
+		},
+	};
+	T::decode(bytes.as_slice()).map(Some).map_err(|e| {
+		LdkServerError::new(
+			InternalServerError,
+			format!("Failed to decode {primary_namespace} record '{key}': {e}"),
+		)
+	})
+}
+
+/// Encodes and stores `record` under `key`, with `created_at` as the time it is listed by.
+pub(crate) fn write_record<T: Message>(
+	paginated_kv_store: &dyn PaginatedKVStore, primary_namespace: &str, secondary_namespace: &str,
+	key: &str, created_at: u64, record: &T,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.write(
+			primary_namespace,
+			secondary_namespace,
+			key,
+			created_at as i64,
+			&record.encode_to_vec(),
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to persist {primary_namespace} record '{key}': {e}"),
+			)
+		})
+}
@@ -9,6 +9,9 @@
 
 use std::io;
 
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{Payment, PaymentDirection, PaymentKindType, PaymentStatus};
+
 /// Provides an interface that allows storage and retrieval of persisted values that are associated
 /// with given keys, with support for pagination with time-based ordering.
 ///
@@ -58,6 +61,13 @@ pub trait PaginatedKVStore: Send + Sync {
 		&self, primary_namespace: &str, secondary_namespace: &str, key: &str, time: i64, buf: &[u8],
 	) -> Result<(), io::Error>;
 
+	/// Removes any data that had previously been persisted under the given `key`.
+	///
+	/// Returns successfully if no data was stored under the given `key`.
+	fn remove(
+		&self, primary_namespace: &str, secondary_namespace: &str, key: &str,
+	) -> Result<(), io::Error>;
+
 	/// Returns a paginated list of keys that are stored under the given `secondary_namespace` in
 	/// `primary_namespace`, ordered in descending order of `time`.
 	///
@@ -78,6 +88,102 @@ pub trait PaginatedKVStore: Send + Sync {
 		&self, primary_namespace: &str, secondary_namespace: &str,
 		next_page_token: Option<(String, i64)>,
 	) -> Result<ListResponse, io::Error>;
+
+	/// Persists the given payment data under the given `key` in the payments namespace, like
+	/// [`PaginatedKVStore::write`], and indexes it by the given attributes so it can be listed
+	/// with [`PaginatedKVStore::list_payments`].
+	///
+	/// Payments must always be persisted with this method rather than `write`, so that the index
+	/// stays consistent with the stored data.
+	fn write_payment(
+		&self, key: &str, time: i64, buf: &[u8], index: &PaymentIndex,
+	) -> Result<(), io::Error>;
+
+	/// Returns a paginated list of the keys of the payments matching `filter`, ordered and
+	/// paginated like [`PaginatedKVStore::list`], with at most `page_size` keys per page.
+	fn list_payments(
+		&self, filter: &PaymentFilter, page_size: u32, next_page_token: Option<(String, i64)>,
+	) -> Result<ListResponse, io::Error>;
+}
+
+/// The attributes a payment is indexed by, see [`PaginatedKVStore::write_payment`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentIndex {
+	/// The direction of the payment.
+	pub direction: PaymentDirection,
+
+	/// The status of the payment.
+	pub status: PaymentStatus,
+
+	/// The kind of the payment, if known.
+	pub kind: Option<PaymentKindType>,
+
+	/// The amount of the payment, if known.
+	pub amount_msat: Option<u64>,
+
+	/// The time the payment was last updated, in seconds since the UNIX epoch.
+	pub update_time: i64,
+
+	/// The hex-encoded payment hash, if known.
+	pub payment_hash: Option<String>,
+
+	/// The hex-encoded payment preimage, if known.
+	pub preimage: Option<String>,
+
+	/// The hex-encoded id of the BOLT 12 offer the payment was made for, if any.
+	pub offer_id: Option<String>,
+}
+
+impl From<&Payment> for PaymentIndex {
+	fn from(payment: &Payment) -> Self {
+		let kind = payment.kind.as_ref().and_then(|kind| kind.kind.as_ref());
+		let (payment_hash, preimage, offer_id) = match kind {
+			None | Some(Kind::Onchain(_)) => (None, None, None),
+			Some(Kind::Bolt11(k)) => (Some(&k.hash), k.preimage.as_ref(), None),
+			Some(Kind::Bolt12Offer(k)) => (k.hash.as_ref(), k.preimage.as_ref(), Some(&k.offer_id)),
+			Some(Kind::Bolt12Refund(k)) => (k.hash.as_ref(), k.preimage.as_ref(), None),
+			Some(Kind::Spontaneous(k)) => (Some(&k.hash), k.preimage.as_ref(), None),
+		};
+		Self {
+			direction: payment.direction(),
+			status: payment.status(),
+			kind: kind.map(|kind| match kind {
+				Kind::Onchain(_) => PaymentKindType::Onchain,
+				Kind::Bolt11(_) => PaymentKindType::Bolt11,
+				Kind::Bolt12Offer(_) => PaymentKindType::Bolt12Offer,
+				Kind::Bolt12Refund(_) => PaymentKindType::Bolt12Refund,
+				Kind::Spontaneous(_) => PaymentKindType::Spontaneous,
+			}),
+			amount_msat: payment.amount_msat,
+			update_time: payment.latest_update_timestamp as i64,
+			payment_hash: payment_hash.map(|hash| hash.to_lowercase()),
+			preimage: preimage.map(|preimage| preimage.to_lowercase()),
+			offer_id: offer_id.map(|offer_id| offer_id.to_lowercase()),
+		}
+	}
+}
+
+/// Selects the payments returned by [`PaginatedKVStore::list_payments`].
+///
+/// Every condition that is set must match. Amount conditions never match payments of unknown
+/// amount. Time ranges include their start and exclude their end.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaymentFilter {
+	pub direction: Option<PaymentDirection>,
+	pub status: Option<PaymentStatus>,
+	pub kind: Option<PaymentKindType>,
+	pub min_amount_msat: Option<u64>,
+	pub max_amount_msat: Option<u64>,
+	/// The range of times the payment was first persisted at, in seconds since the UNIX epoch.
+	pub created_since: Option<i64>,
+	pub created_before: Option<i64>,
+	/// The range of times the payment was last updated at, in seconds since the UNIX epoch.
+	pub updated_since: Option<i64>,
+	pub updated_before: Option<i64>,
+	/// Hex-encoded identifiers, which must be given in lowercase.
+	pub payment_hash: Option<String>,
+	pub preimage: Option<String>,
+	pub offer_id: Option<String>,
 }
 
 /// Represents the response from a paginated `list` operation.
@@ -12,9 +12,17 @@ use std::sync::{Arc, Mutex};
 use std::{fs, io};
 
 use ldk_node::lightning::types::string::PrintableString;
-use rusqlite::{named_params, Connection};
-
-use crate::io::persist::paginated_kv_store::{ListResponse, PaginatedKVStore};
+use ldk_server_grpc::types::Payment;
+use prost::Message;
+use rusqlite::types::{ToSql, Value};
+use rusqlite::{named_params, Connection, Row};
+
+use crate::io::persist::paginated_kv_store::{
+	ListResponse, PaginatedKVStore, PaymentFilter, PaymentIndex,
+};
+use crate::io::persist::{
+	PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
 use crate::io::utils::check_namespace_key_validity;
 
 /// The default database file name.
@@ -24,7 +32,9 @@ pub const DEFAULT_SQLITE_DB_FILE_NAME: &str = "ldk_server_data.sqlite";
 pub const DEFAULT_PAGINATED_KV_TABLE_NAME: &str = "ldk_paginated_data";
 
 // The current SQLite `user_version`, which we can use if we'd ever need to do a schema migration.
-const SCHEMA_USER_VERSION: u16 = 1;
+//
+// Version 2 added the payment index table, version 3 added the payment identifiers to it.
+const SCHEMA_USER_VERSION: u16 = 3;
 
 // The maximum number of keys retrieved per page in paginated list operation.
 const LIST_KEYS_MAX_PAGE_SIZE: i32 = 100;
@@ -32,6 +42,7 @@ const LIST_KEYS_MAX_PAGE_SIZE: i32 = 100;
 pub struct SqliteStore {
 	connection: Arc<Mutex<Connection>>,
 	paginated_kv_table_name: String,
+	payment_index_table_name: String,
 }
 
 impl SqliteStore {
@@ -41,6 +52,7 @@ impl SqliteStore {
 	/// given `db_file_name` (or the default to [`DEFAULT_SQLITE_DB_FILE_NAME`] if set to `None`).
 	///
 	/// Similarly, the given `paginated_kv_table_name` will be used or default to [`DEFAULT_PAGINATED_KV_TABLE_NAME`].
+	/// Payments are indexed in a table named after it, with a `_payment_index` suffix.
 	pub fn new(
 		data_dir: PathBuf, db_file_name: Option<String>, paginated_kv_table_name: Option<String>,
 	) -> io::Result<Self> {
@@ -59,7 +71,7 @@ impl SqliteStore {
 		let mut db_file_path = data_dir;
 		db_file_path.push(db_file_name);
 
-		let connection = Connection::open(db_file_path.clone()).map_err(|e| {
+		let mut connection = Connection::open(db_file_path.clone()).map_err(|e| {
 			let msg =
 				format!("Failed to open/create database file {}: {}", db_file_path.display(), e);
 			io::Error::other(msg)
@@ -68,20 +80,7 @@ impl SqliteStore {
 		let sql = "SELECT user_version FROM pragma_user_version".to_string();
 		let version_res: u16 = connection.query_row(&sql, [], |row| row.get(0)).unwrap();
 
-		if version_res == 0 {
-			// New database, set our SCHEMA_USER_VERSION and continue
-			connection
-				.pragma(
-					Some(rusqlite::DatabaseName::Main),
-					"user_version",
-					SCHEMA_USER_VERSION,
-					|_| Ok(()),
-				)
-				.map_err(|e| {
-					let msg = format!("Failed to set PRAGMA user_version: {}", e);
-					io::Error::other(msg)
-				})?;
-		} else if version_res > SCHEMA_USER_VERSION {
+		if version_res > SCHEMA_USER_VERSION {
 			let msg = format!(
 				"Failed to open database: incompatible schema version {}. Expected: {}",
 				version_res, SCHEMA_USER_VERSION
@@ -118,8 +117,86 @@ impl SqliteStore {
 			io::Error::other(msg)
 		})?;
 
+		let payment_index_table_name = format!("{}_payment_index", paginated_kv_table_name);
+		let create_payment_index_table_sql = format!(
+			"CREATE TABLE IF NOT EXISTS {0} (
+			key TEXT PRIMARY KEY NOT NULL,
+			direction INTEGER NOT NULL,
+			status INTEGER NOT NULL,
+			kind INTEGER,
+			amount_msat INTEGER,
+			creation_time INTEGER NOT NULL,
+			update_time INTEGER NOT NULL,
+			payment_hash TEXT,
+			preimage TEXT,
+			offer_id TEXT
+			);",
+			payment_index_table_name
+		);
+
+		connection.execute(&create_payment_index_table_sql, []).map_err(|e| {
+			let msg = format!("Failed to create table {}: {}", payment_index_table_name, e);
+			io::Error::other(msg)
+		})?;
+
+		if version_res < SCHEMA_USER_VERSION {
+			let tx = connection.transaction().map_err(|e| {
+				let msg = format!("Failed to start schema migration: {}", e);
+				io::Error::other(msg)
+			})?;
+
+			if version_res == 2 {
+				let add_identifier_columns_sql = format!(
+					"ALTER TABLE {0} ADD COLUMN payment_hash TEXT;
+					ALTER TABLE {0} ADD COLUMN preimage TEXT;
+					ALTER TABLE {0} ADD COLUMN offer_id TEXT;",
+					payment_index_table_name
+				);
+				tx.execute_batch(&add_identifier_columns_sql).map_err(|e| {
+					let msg =
+						format!("Failed to migrate table {}: {}", payment_index_table_name, e);
+					io::Error::other(msg)
+				})?;
+			}
+
+			// Payments persisted by an older version are (re-)indexed with all attributes the
+			// current schema indexes them by.
+			if version_res > 0 {
+				index_persisted_payments(&tx, &paginated_kv_table_name, &payment_index_table_name)?;
+			}
+
+			tx.pragma_update(
+				Some(rusqlite::DatabaseName::Main),
+				"user_version",
+				SCHEMA_USER_VERSION,
+			)
+			.and_then(|_| tx.commit())
+			.map_err(|e| {
+				let msg = format!("Failed to set PRAGMA user_version: {}", e);
+				io::Error::other(msg)
+			})?;
+		}
+
+		let create_payment_indices_sql = format!(
+			"CREATE INDEX IF NOT EXISTS idx_{0}_creation_time ON {0} (creation_time, key);
+			CREATE INDEX IF NOT EXISTS idx_{0}_direction_status ON {0} (direction, status, creation_time);
+			CREATE INDEX IF NOT EXISTS idx_{0}_kind ON {0} (kind, creation_time);
+			CREATE INDEX IF NOT EXISTS idx_{0}_amount_msat ON {0} (amount_msat);
+			CREATE INDEX IF NOT EXISTS idx_{0}_update_time ON {0} (update_time);
+			CREATE INDEX IF NOT EXISTS idx_{0}_payment_hash ON {0} (payment_hash);
+			CREATE INDEX IF NOT EXISTS idx_{0}_preimage ON {0} (preimage);
+			CREATE INDEX IF NOT EXISTS idx_{0}_offer_id ON {0} (offer_id, creation_time);",
+			payment_index_table_name
+		);
+
+		connection.execute_batch(&create_payment_indices_sql).map_err(|e| {
+			let msg =
+				format!("Failed to create indices on table {}: {}", payment_index_table_name, e);
+			io::Error::other(msg)
+		})?;
+
 		let connection = Arc::new(Mutex::new(connection));
-		Ok(Self { connection, paginated_kv_table_name })
+		Ok(Self { connection, paginated_kv_table_name, payment_index_table_name })
 	}
 
 	fn read_internal(
@@ -189,14 +266,25 @@ impl PaginatedKVStore for SqliteStore {
 		check_namespace_key_validity(primary_namespace, secondary_namespace, Some(key), "write")?;
 
 		let locked_conn = self.connection.lock().unwrap();
+		write_value(
+			&locked_conn,
+			&self.paginated_kv_table_name,
+			primary_namespace,
+			secondary_namespace,
+			key,
+			time,
+			buf,
+		)
+	}
 
-		let sql = format!(
-			"INSERT INTO {} (primary_namespace, secondary_namespace, key, creation_time, value)
-         VALUES (:primary_namespace, :secondary_namespace, :key, :creation_time, :value)
-         ON CONFLICT(primary_namespace, secondary_namespace, key)
-         DO UPDATE SET value = excluded.value;",
-			self.paginated_kv_table_name
-		);
+	fn remove(
+		&self, primary_namespace: &str, secondary_namespace: &str, key: &str,
+	) -> io::Result<()> {
+		check_namespace_key_validity(primary_namespace, secondary_namespace, Some(key), "remove")?;
+
+		let locked_conn = self.connection.lock().unwrap();
+
+		let sql = format!("DELETE FROM {} WHERE primary_namespace=:primary_namespace AND secondary_namespace=:secondary_namespace AND key=:key;", self.paginated_kv_table_name);
 
 		let mut stmt = locked_conn.prepare_cached(&sql).map_err(|e| {
 			let msg = format!("Failed to prepare statement: {}", e);
@@ -207,13 +295,11 @@ impl PaginatedKVStore for SqliteStore {
 			":primary_namespace": primary_namespace,
 			":secondary_namespace": secondary_namespace,
 			":key": key,
-			":creation_time": time,
-			":value": buf,
 		})
 		.map(|_| ())
 		.map_err(|e| {
 			let msg = format!(
-				"Failed to write to key {}/{}/{}: {}",
+				"Failed to delete key {}/{}/{}: {}",
 				PrintableString(primary_namespace),
 				PrintableString(secondary_namespace),
 				PrintableString(key),
@@ -285,14 +371,263 @@ impl PaginatedKVStore for SqliteStore {
 
 		Ok(ListResponse { keys, next_page_token })
 	}
+
+	fn write_payment(
+		&self, key: &str, time: i64, buf: &[u8], index: &PaymentIndex,
+	) -> io::Result<()> {
+		check_namespace_key_validity(
+			PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			Some(key),
+			"write",
+		)?;
+
+		let mut locked_conn = self.connection.lock().unwrap();
+		let tx = locked_conn.transaction().map_err(|e| {
+			let msg = format!("Failed to start transaction: {}", e);
+			io::Error::other(msg)
+		})?;
+
+		write_value(
+			&tx,
+			&self.paginated_kv_table_name,
+			PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			key,
+			time,
+			buf,
+		)?;
+		write_payment_index(&tx, &self.payment_index_table_name, key, time, index)?;
+
+		tx.commit().map_err(|e| {
+			let msg = format!("Failed to write payment {}: {}", PrintableString(key), e);
+			io::Error::other(msg)
+		})
+	}
+
+	fn list_payments(
+		&self, filter: &PaymentFilter, page_size: u32, page_token: Option<(String, i64)>,
+	) -> io::Result<ListResponse> {
+		let page_token = page_token.unwrap_or(("".to_string(), i64::MAX));
+		let page_size = i64::from(page_size);
+
+		let integer = |value: Option<i64>| value.map(Value::Integer);
+		let text = |value: &Option<String>| value.clone().map(Value::Text);
+		let conditions = [
+			("direction = :direction", ":direction", integer(filter.direction.map(|d| d as i64))),
+			("status = :status", ":status", integer(filter.status.map(|s| s as i64))),
+			("kind = :kind", ":kind", integer(filter.kind.map(|k| k as i64))),
+			(
+				"amount_msat >= :min_amount_msat",
+				":min_amount_msat",
+				integer(filter.min_amount_msat.map(to_sql_int)),
+			),
+			(
+				"amount_msat <= :max_amount_msat",
+				":max_amount_msat",
+				integer(filter.max_amount_msat.map(to_sql_int)),
+			),
+			("creation_time >= :created_since", ":created_since", integer(filter.created_since)),
+			("creation_time < :created_before", ":created_before", integer(filter.created_before)),
+			("update_time >= :updated_since", ":updated_since", integer(filter.updated_since)),
+			("update_time < :updated_before", ":updated_before", integer(filter.updated_before)),
+			("payment_hash = :payment_hash", ":payment_hash", text(&filter.payment_hash)),
+			("preimage = :preimage", ":preimage", text(&filter.preimage)),
+			("offer_id = :offer_id", ":offer_id", text(&filter.offer_id)),
+		];
+
+		// Only the conditions that are set are added to the query, so SQLite can pick the most
+		// selective index for them.
+		let mut where_clause = String::from(
+			"( creation_time < :creation_time_token OR (creation_time = :creation_time_token AND key > :key_token) )",
+		);
+		let mut params: Vec<(&str, &dyn ToSql)> = vec![
+			(":key_token", &page_token.0),
+			(":creation_time_token", &page_token.1),
+			(":page_size", &page_size),
+		];
+		for (condition, name, value) in &conditions {
+			if let Some(value) = value {
+				where_clause.push_str(" AND ");
+				where_clause.push_str(condition);
+				params.push((*name, value));
+			}
+		}
+
+		let sql = format!(
+			"SELECT key, creation_time FROM {} WHERE {} ORDER BY creation_time DESC, key ASC LIMIT :page_size",
+			self.payment_index_table_name, where_clause
+		);
+
+		let locked_conn = self.connection.lock().unwrap();
+		let mut stmt = locked_conn.prepare_cached(&sql).map_err(|e| {
+			let msg = format!("Failed to prepare statement: {}", e);
+			io::Error::other(msg)
+		})?;
+
+		let rows = stmt
+			.query_map(params.as_slice(), |row| {
+				Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+			})
+			.and_then(|rows| rows.collect::<Result<Vec<_>, _>>())
+			.map_err(|e| {
+				let msg = format!("Failed to retrieve queried rows: {}", e);
+				io::Error::other(msg)
+			})?;
+
+		// A short page means there are no more matching payments.
+		let next_page_token =
+			if rows.len() as i64 == page_size { rows.last().cloned() } else { None };
+		let keys = rows.into_iter().map(|(key, _)| key).collect();
+		Ok(ListResponse { keys, next_page_token })
+	}
+}
+
+fn write_value(
+	connection: &Connection, kv_table_name: &str, primary_namespace: &str,
+	secondary_namespace: &str, key: &str, time: i64, buf: &[u8],
+) -> io::Result<()> {
+	let sql = format!(
+		"INSERT INTO {} (primary_namespace, secondary_namespace, key, creation_time, value)
+         VALUES (:primary_namespace, :secondary_namespace, :key, :creation_time, :value)
+         ON CONFLICT(primary_namespace, secondary_namespace, key)
+         DO UPDATE SET value = excluded.value;",
+		kv_table_name
+	);
+
+	let mut stmt = connection.prepare_cached(&sql).map_err(|e| {
+		let msg = format!("Failed to prepare statement: {}", e);
+		io::Error::other(msg)
+	})?;
+
+	stmt.execute(named_params! {
+		":primary_namespace": primary_namespace,
+		":secondary_namespace": secondary_namespace,
+		":key": key,
+		":creation_time": time,
+		":value": buf,
+	})
+	.map(|_| ())
+	.map_err(|e| {
+		let msg = format!(
+			"Failed to write to key {}/{}/{}: {}",
+			PrintableString(primary_namespace),
+			PrintableString(secondary_namespace),
+			PrintableString(key),
+			e
+		);
+		io::Error::other(msg)
+	})
+}
+
+/// Inserts or updates the index entry of a payment. Like the payment data itself, the entry keeps
+/// the `creation_time` it was first written with.
+fn write_payment_index(
+	connection: &Connection, payment_index_table_name: &str, key: &str, creation_time: i64,
+	index: &PaymentIndex,
+) -> io::Result<()> {
+	let sql = format!(
+		"INSERT INTO {} (key, direction, status, kind, amount_msat, creation_time, update_time,
+		payment_hash, preimage, offer_id)
+		VALUES (:key, :direction, :status, :kind, :amount_msat, :creation_time, :update_time,
+		:payment_hash, :preimage, :offer_id)
+		ON CONFLICT(key) DO UPDATE SET direction = excluded.direction, status = excluded.status,
+		kind = excluded.kind, amount_msat = excluded.amount_msat, update_time = excluded.update_time,
+		payment_hash = excluded.payment_hash, preimage = excluded.preimage,
+		offer_id = excluded.offer_id;",
+		payment_index_table_name
+	);
+
+	let mut stmt = connection.prepare_cached(&sql).map_err(|e| {
+		let msg = format!("Failed to prepare statement: {}", e);
+		io::Error::other(msg)
+	})?;
+
+	stmt.execute(named_params! {
+		":key": key,
+		":direction": index.direction as i64,
+		":status": index.status as i64,
+		":kind": index.kind.map(|kind| kind as i64),
+		":amount_msat": index.amount_msat.map(to_sql_int),
+		":creation_time": creation_time,
+		":update_time": index.update_time,
+		":payment_hash": index.payment_hash,
+		":preimage": index.preimage,
+		":offer_id": index.offer_id,
+	})
+	.map(|_| ())
+	.map_err(|e| {
+		let msg = format!("Failed to index payment {}: {}", PrintableString(key), e);
+		io::Error::other(msg)
+	})
+}
+
+/// Adds all payments in the payments namespace to the payment index.
+fn index_persisted_payments(
+	connection: &Connection, kv_table_name: &str, payment_index_table_name: &str,
+) -> io::Result<()> {
+	let sql = format!(
+		"SELECT key, creation_time, value FROM {} WHERE primary_namespace=:primary_namespace AND secondary_namespace=:secondary_namespace;",
+		kv_table_name
+	);
+
+	let mut stmt = connection.prepare(&sql).map_err(|e| {
+		let msg = format!("Failed to prepare statement: {}", e);
+		io::Error::other(msg)
+	})?;
+
+	let read_row = |row: &Row| -> rusqlite::Result<(String, i64, Vec<u8>)> {
+		Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+	};
+	let rows = stmt
+		.query_map(
+			named_params! {
+				":primary_namespace": PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				":secondary_namespace": PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			},
+			read_row,
+		)
+		.map_err(|e| {
+			let msg = format!("Failed to retrieve queried rows: {}", e);
+			io::Error::other(msg)
+		})?;
+
+	for row in rows {
+		let (key, creation_time, value) = row.map_err(|e| {
+			let msg = format!("Failed to retrieve queried rows: {}", e);
+			io::Error::other(msg)
+		})?;
+		let payment = Payment::decode(value.as_slice()).map_err(|e| {
+			let msg = format!("Failed to decode payment {}: {}", PrintableString(&key), e);
+			io::Error::other(msg)
+		})?;
+		write_payment_index(
+			connection,
+			payment_index_table_name,
+			&key,
+			creation_time,
+			&PaymentIndex::from(&payment),
+		)?;
+	}
+	Ok(())
+}
+
+/// Converts an amount or timestamp to an SQLite integer, saturating values that don't fit.
+fn to_sql_int(value: u64) -> i64 {
+	i64::try_from(value).unwrap_or(i64::MAX)
 }
 
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
 	use std::panic::RefUnwindSafe;
 
 	use hex::DisplayHex;
 	use ldk_node::lightning::util::persist::KVSTORE_NAMESPACE_KEY_MAX_LEN;
+	use ldk_server_grpc::types::payment_kind::Kind;
+	use ldk_server_grpc::types::{
+		Bolt11, Bolt12Offer, Onchain, PaymentDirection, PaymentKind, PaymentKindType,
+		PaymentStatus, Spontaneous,
+	};
 
 	use super::*;
 
@@ -309,6 +644,256 @@ mod tests {
 		do_read_write_remove_list_persist(&store);
 	}
 
+	fn payment(
+		id: &str, kind: Kind, direction: PaymentDirection, status: PaymentStatus, amount_msat: u64,
+		latest_update_timestamp: u64,
+	) -> Payment {
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind { kind: Some(kind) }),
+			amount_msat: Some(amount_msat),
+			fee_paid_msat: None,
+			direction: direction as i32,
+			status: status as i32,
+			latest_update_timestamp,
+			..Default::default()
+		}
+	}
+
+	fn write_payment(store: &SqliteStore, payment: &Payment, time: i64) {
+		store
+			.write_payment(
+				&payment.id,
+				time,
+				&payment.encode_to_vec(),
+				&PaymentIndex::from(payment),
+			)
+			.unwrap();
+	}
+
+	fn list_all_payments(
+		store: &SqliteStore, filter: &PaymentFilter, page_size: u32,
+	) -> Vec<String> {
+		let mut keys = Vec::new();
+		let mut page_token = None;
+		loop {
+			let list_response = store.list_payments(filter, page_size, page_token).unwrap();
+			assert!(list_response.keys.len() <= page_size as usize);
+			keys.extend(list_response.keys);
+			match list_response.next_page_token {
+				Some(token) => page_token = Some(token),
+				None => return keys,
+			}
+		}
+	}
+
+	#[test]
+	fn write_list_payments() {
+		let mut temp_path = random_storage_path();
+		temp_path.push("write_list_payments");
+		let store = SqliteStore::new(temp_path, None, None).unwrap();
+
+		let bolt11 = || Kind::Bolt11(Bolt11 { hash: "00".repeat(32), ..Default::default() });
+		let onchain = || Kind::Onchain(Onchain { txid: "00".repeat(32), ..Default::default() });
+		let payments = [
+			payment("a", bolt11(), PaymentDirection::Inbound, PaymentStatus::Succeeded, 1_000, 110),
+			payment(
+				"b",
+				bolt11(),
+				PaymentDirection::Outbound,
+				PaymentStatus::Succeeded,
+				2_000,
+				120,
+			),
+			payment("c", onchain(), PaymentDirection::Inbound, PaymentStatus::Pending, 3_000, 130),
+			payment("d", bolt11(), PaymentDirection::Inbound, PaymentStatus::Failed, 4_000, 140),
+			payment("e", bolt11(), PaymentDirection::Inbound, PaymentStatus::Succeeded, 5_000, 150),
+		];
+		for (time, payment) in (100..).step_by(10).zip(&payments) {
+			write_payment(&store, payment, time);
+		}
+
+		// Payments are listed most recently created first, also across pages.
+		let all = PaymentFilter::default();
+		assert_eq!(list_all_payments(&store, &all, 100), ["e", "d", "c", "b", "a"]);
+		assert_eq!(list_all_payments(&store, &all, 2), ["e", "d", "c", "b", "a"]);
+
+		let inbound_succeeded = PaymentFilter {
+			direction: Some(PaymentDirection::Inbound),
+			status: Some(PaymentStatus::Succeeded),
+			..Default::default()
+		};
+		assert_eq!(list_all_payments(&store, &inbound_succeeded, 1), ["e", "a"]);
+
+		let onchain_kind =
+			PaymentFilter { kind: Some(PaymentKindType::Onchain), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &onchain_kind, 100), ["c"]);
+
+		let amount_range = PaymentFilter {
+			min_amount_msat: Some(2_000),
+			max_amount_msat: Some(4_000),
+			..Default::default()
+		};
+		assert_eq!(list_all_payments(&store, &amount_range, 100), ["d", "c", "b"]);
+
+		let created_window = PaymentFilter {
+			created_since: Some(110),
+			created_before: Some(130),
+			..Default::default()
+		};
+		assert_eq!(list_all_payments(&store, &created_window, 100), ["c", "b"]);
+
+		// Updating a payment keeps its creation time, but updates its index entry.
+		let mut failed = payments[0].clone();
+		failed.status = PaymentStatus::Failed as i32;
+		failed.latest_update_timestamp = 200;
+		write_payment(&store, &failed, 200);
+		assert_eq!(list_all_payments(&store, &inbound_succeeded, 100), ["e"]);
+		let updated_window = PaymentFilter { updated_since: Some(200), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &updated_window, 100), ["a"]);
+		assert_eq!(list_all_payments(&store, &all, 100), ["e", "d", "c", "b", "a"]);
+
+		let stored = store
+			.read(
+				PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				"a",
+			)
+			.unwrap();
+		assert_eq!(Payment::decode(&*stored).unwrap(), failed);
+	}
+
+	#[test]
+	fn index_payments_persisted_before_schema_migration() {
+		let mut temp_path = random_storage_path();
+		temp_path.push("index_payments_persisted_before_schema_migration");
+		let store = SqliteStore::new(temp_path.clone(), None, None).unwrap();
+
+		// Payments that were persisted without an index entry, as before schema version 2.
+		let bolt11 = Kind::Bolt11(Bolt11 { hash: "00".repeat(32), ..Default::default() });
+		let payment =
+			payment("a", bolt11, PaymentDirection::Outbound, PaymentStatus::Succeeded, 1_000, 100);
+		store
+			.write(
+				PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&payment.id,
+				100,
+				&payment.encode_to_vec(),
+			)
+			.unwrap();
+		store.connection.lock().unwrap().pragma_update(None, "user_version", 1).unwrap();
+		assert!(list_all_payments(&store, &PaymentFilter::default(), 100).is_empty());
+		drop(store);
+
+		let store = SqliteStore::new(temp_path, None, None).unwrap();
+		let outbound =
+			PaymentFilter { direction: Some(PaymentDirection::Outbound), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &outbound, 100), ["a"]);
+	}
+
+	#[test]
+	fn find_payments_by_identifier() {
+		let mut temp_path = random_storage_path();
+		temp_path.push("find_payments_by_identifier");
+		let store = SqliteStore::new(temp_path, None, None).unwrap();
+
+		let hash = "aa".repeat(32);
+		let preimage = "bb".repeat(32);
+		let offer_id = "cc".repeat(32);
+		let bolt11 = Kind::Bolt11(Bolt11 {
+			hash: hash.to_uppercase(),
+			preimage: Some(preimage.clone()),
+			..Default::default()
+		});
+		let bolt12 = |hash: Option<String>| {
+			Kind::Bolt12Offer(Bolt12Offer {
+				hash,
+				offer_id: offer_id.clone(),
+				..Default::default()
+			})
+		};
+		let spontaneous = Kind::Spontaneous(Spontaneous { hash: "dd".repeat(32), preimage: None });
+		let payments = [
+			payment("a", bolt11, PaymentDirection::Inbound, PaymentStatus::Succeeded, 1_000, 100),
+			payment(
+				"b",
+				bolt12(None),
+				PaymentDirection::Inbound,
+				PaymentStatus::Failed,
+				2_000,
+				110,
+			),
+			payment(
+				"c",
+				bolt12(Some(hash.clone())),
+				PaymentDirection::Inbound,
+				PaymentStatus::Succeeded,
+				2_000,
+				120,
+			),
+			payment(
+				"d",
+				spontaneous,
+				PaymentDirection::Outbound,
+				PaymentStatus::Pending,
+				3_000,
+				130,
+			),
+		];
+		for (time, payment) in (100..).step_by(10).zip(&payments) {
+			write_payment(&store, payment, time);
+		}
+
+		// Identifiers are indexed in lowercase, whatever the case they were persisted in.
+		let by_hash = PaymentFilter { payment_hash: Some(hash), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &by_hash, 1), ["c", "a"]);
+		let by_preimage = PaymentFilter { preimage: Some(preimage), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &by_preimage, 100), ["a"]);
+		let by_offer_id = PaymentFilter { offer_id: Some(offer_id), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &by_offer_id, 100), ["c", "b"]);
+		let unknown = PaymentFilter { payment_hash: Some("ee".repeat(32)), ..Default::default() };
+		assert!(list_all_payments(&store, &unknown, 100).is_empty());
+	}
+
+	#[test]
+	fn index_payment_identifiers_after_schema_migration() {
+		let mut temp_path = random_storage_path();
+		temp_path.push("index_payment_identifiers_after_schema_migration");
+		let store = SqliteStore::new(temp_path.clone(), None, None).unwrap();
+
+		let hash = "aa".repeat(32);
+		let bolt11 = Kind::Bolt11(Bolt11 { hash: hash.clone(), ..Default::default() });
+		let payment =
+			payment("a", bolt11, PaymentDirection::Outbound, PaymentStatus::Succeeded, 1_000, 100);
+		write_payment(&store, &payment, 100);
+
+		// Replace the payment index with one as created by schema version 2.
+		let downgrade_sql = format!(
+			"DROP TABLE {0};
+			CREATE TABLE {0} (
+			key TEXT PRIMARY KEY NOT NULL,
+			direction INTEGER NOT NULL,
+			status INTEGER NOT NULL,
+			kind INTEGER,
+			amount_msat INTEGER,
+			creation_time INTEGER NOT NULL,
+			update_time INTEGER NOT NULL
+			);
+			PRAGMA user_version = 2;",
+			store.payment_index_table_name
+		);
+		store.connection.lock().unwrap().execute_batch(&downgrade_sql).unwrap();
+		drop(store);
+
+		let store = SqliteStore::new(temp_path, None, None).unwrap();
+		let by_hash = PaymentFilter { payment_hash: Some(hash), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &by_hash, 100), ["a"]);
+		let outbound =
+			PaymentFilter { direction: Some(PaymentDirection::Outbound), ..Default::default() };
+		assert_eq!(list_all_payments(&store, &outbound, 100), ["a"]);
+	}
+
 	pub(crate) fn random_storage_path() -> PathBuf {
 		let mut temp_path = std::env::temp_dir();
 		let mut bytes = [0u8; 8];
@@ -318,6 +903,11 @@ mod tests {
 		temp_path
 	}
 
+	/// Returns a store in a new directory named `dir_name` under a [`random_storage_path`].
+	pub(crate) fn test_store(dir_name: &str) -> SqliteStore {
+		SqliteStore::new(random_storage_path().join(dir_name), None, None).unwrap()
+	}
+
 	pub(crate) fn do_read_write_remove_list_persist<K: PaginatedKVStore + RefUnwindSafe>(
 		kv_store: &K,
 	) {
@@ -368,6 +958,18 @@ mod tests {
 		let read_data = kv_store.read(primary_namespace, secondary_namespace, testkey).unwrap();
 		assert_eq!(data, &*read_data);
 
+		kv_store.remove(primary_namespace, secondary_namespace, testkey).unwrap();
+
+		let listed_keys = list_all_keys(primary_namespace, secondary_namespace);
+		assert_eq!(listed_keys.len(), 109);
+		assert!(!listed_keys.contains(&testkey.to_string()));
+
+		let err = kv_store.read(primary_namespace, secondary_namespace, testkey).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::NotFound);
+
+		// Removing a key that doesn't exist is a no-op.
+		kv_store.remove(primary_namespace, secondary_namespace, testkey).unwrap();
+
 		// Ensure we have no issue operating with primary_namespace/secondary_namespace/key being KVSTORE_NAMESPACE_KEY_MAX_LEN
 		let max_chars: String = "A".repeat(KVSTORE_NAMESPACE_KEY_MAX_LEN);
 		kv_store.write(&max_chars, &max_chars, &max_chars, 0, &data).unwrap();
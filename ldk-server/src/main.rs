@@ -12,8 +12,9 @@ mod io;
 mod service;
 mod util;
 
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::fs;
+use std::net::SocketAddr;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -21,42 +22,73 @@ use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
 use clap::Parser;
-use hex::DisplayHex;
+use hex::{DisplayHex, FromHex};
+use hyper::body::Incoming;
 use hyper::server::conn::http2;
+use hyper::service::Service;
+use hyper::{Request, Response};
 use hyper_util::rt::{TokioExecutor, TokioIo};
-use ldk_node::bitcoin::Network;
+use ldk_node::bitcoin::{Network, OutPoint};
 use ldk_node::config::{Config, ElectrumSyncConfig, EsploraSyncConfig};
+use ldk_node::entropy::NodeEntropy;
 use ldk_node::lightning::events::ClosureReason;
 use ldk_node::lightning::ln::channelmanager::PaymentId;
 use ldk_node::lightning::ln::types::ChannelId;
-use ldk_node::{Builder, CustomTlvRecord, Event, Node};
-use ldk_server_grpc::events;
-use ldk_server_grpc::events::{event_envelope, EventEnvelope};
-use ldk_server_grpc::types::{HtlcLocator, Payment};
+use ldk_node::lightning_types::payment::{PaymentHash, PaymentPreimage};
+use ldk_node::payment::PaymentKind;
+use ldk_node::{Builder, ChannelDetails, CustomTlvRecord, Event, Node};
+use ldk_server_grpc::events::event_envelope;
+use ldk_server_grpc::grpc::GrpcBody;
+use ldk_server_grpc::types::{BestBlock, HtlcLocator, Payment};
+use ldk_server_grpc::{events, types};
 use log::{debug, error, info};
 use prost::Message;
-use tokio::net::TcpListener;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::{TcpListener, UnixListener, UnixStream};
 use tokio::select;
 use tokio::signal::unix::SignalKind;
-use tokio::sync::broadcast;
+use tokio::sync::oneshot;
+use tokio_rustls::TlsAcceptor;
 
 use crate::api::node_to_proto_custom_tlv;
-use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::paginated_kv_store::{PaginatedKVStore, PaymentIndex};
 use crate::io::persist::sqlite_store::SqliteStore;
 use crate::io::persist::{
 	FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
-	FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE, PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
-	PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+	FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
 };
-use crate::service::NodeService;
-use crate::util::config::{load_config, ArgsConfig, ChainSource};
+use crate::service::{LockedService, NodeService};
+use crate::util::approvals::ApprovalQueue;
+use crate::util::auth::{identity_for_client_cert, ApiKeyStore, ClientCertIdentity, Identity};
+use crate::util::claimable_payments::{
+	record_claimable_payment, remove_auto_claim_preimage, remove_claimable_payment,
+	ClaimDeadlineEnforcer, DeadlineAction, CLAIM_DEADLINE_CHECK_INTERVAL,
+};
+use crate::util::closed_channels::{closed_channel_from_snapshot, record_closed_channel};
+use crate::util::config::{load_config, ArgsConfig, ChainSource, UnixSocketConfig};
+use crate::util::entropy::{is_mnemonic_encrypted, load_or_generate_node_entropy, NodeUnlocker};
+use crate::util::event_log::{EventLog, EVENT_PRUNE_INTERVAL};
+use crate::util::invoices::{cancel_invoice, record_received_payment};
+use crate::util::labels::{apply_payment_label, read_label, LabelTarget};
 use crate::util::logger::{LogConfig, ServerLogger};
 use crate::util::metrics::Metrics;
-use crate::util::proto_adapter::{forwarded_payment_to_proto, payment_to_proto};
-use crate::util::tls::get_or_generate_tls_config;
-use crate::util::{systemd, write_new};
+use crate::util::node_monitor::{NodeMonitor, NodeSnapshot, NODE_MONITOR_POLL_INTERVAL};
+use crate::util::nonce_cache::{NonceCache, DEFAULT_NONCE_CACHE_CAPACITY};
+use crate::util::onchain_payments::OnchainPaymentRecorder;
+use crate::util::probes::ProbeTracker;
+use crate::util::proto_adapter::{
+	channel_to_proto, forwarded_payment_to_proto, payment_to_proto, peer_to_proto,
+};
+use crate::util::routing::RoutingSnapshotCache;
+use crate::util::spend_limits::SpendTracker;
+use crate::util::tls::ReloadableTlsAcceptor;
+use crate::util::unix_socket::bind_unix_socket;
+use crate::util::webhooks::WebhookDispatcher;
+use crate::util::{current_time_secs, systemd, write_new};
 
 const API_KEY_FILE: &str = "api_key";
+/// How often the TLS certificate and key files are checked for changes.
+const TLS_RELOAD_INTERVAL: Duration = Duration::from_secs(60);
 const FULL_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), " (", env!("GIT_HASH"), ")");
 
 pub fn get_default_data_dir() -> Option<PathBuf> {
@@ -150,6 +182,7 @@ fn main() {
 			std::process::exit(-1);
 		},
 	};
+	let config_api_keys = config_file.api_keys;
 
 	ldk_node_config.storage_dir_path = network_dir.to_str().unwrap().to_string();
 	ldk_node_config.listening_addresses = config_file.listening_addrs;
@@ -251,10 +284,94 @@ fn main() {
 
 	builder.set_runtime(runtime.handle().clone());
 
-	let node_entropy = match crate::util::entropy::load_or_generate_node_entropy(&storage_dir) {
-		Ok(entropy) => entropy,
+	let paginated_store: Arc<dyn PaginatedKVStore> =
+		Arc::new(match SqliteStore::new(network_dir.clone(), None, None) {
+			Ok(store) => store,
+			Err(e) => {
+				error!("Failed to create SqliteStore: {e:?}");
+				std::process::exit(-1);
+			},
+		});
+
+	let api_key_store = match ApiKeyStore::new(
+		Arc::clone(&paginated_store),
+		network_dir.join(API_KEY_FILE),
+		api_key,
+		config_api_keys,
+	) {
+		Ok(api_key_store) => Arc::new(api_key_store),
 		Err(e) => {
-			error!("Failed to load or generate node entropy: {e}");
+			error!("Failed to load API keys: {e}");
+			std::process::exit(-1);
+		},
+	};
+	let nonce_cache = Arc::new(NonceCache::new(DEFAULT_NONCE_CACHE_CAPACITY));
+	let client_identities: Arc<Vec<ClientCertIdentity>> = Arc::new(
+		config_file
+			.tls_config
+			.as_ref()
+			.map(|tls_config| tls_config.client_identities.clone())
+			.unwrap_or_default(),
+	);
+	let tls_acceptor =
+		match ReloadableTlsAcceptor::new(config_file.tls_config, storage_dir.to_str().unwrap()) {
+			Ok(tls_acceptor) => Arc::new(tls_acceptor),
+			Err(e) => {
+				error!("Failed to set up TLS: {e}");
+				std::process::exit(-1);
+			},
+		};
+	// Pick up renewed certificates, e.g. from an ACME client, without a restart.
+	let reload_tls_acceptor = Arc::clone(&tls_acceptor);
+	runtime.spawn(async move {
+		let mut interval = tokio::time::interval(TLS_RELOAD_INTERVAL);
+		loop {
+			interval.tick().await;
+			match reload_tls_acceptor.reload_if_changed() {
+				Ok(true) => info!("Reloaded TLS certificate after it changed on disk"),
+				Ok(false) => {},
+				Err(e) => error!("Failed to reload changed TLS certificate: {e}"),
+			}
+		}
+	});
+
+	let node_entropy = match is_mnemonic_encrypted(&storage_dir) {
+		Ok(true) => {
+			let (unlocker, entropy_receiver) = NodeUnlocker::new(storage_dir.clone());
+			let locked_service = LockedService::new(
+				Arc::new(unlocker),
+				config_file.network,
+				Arc::clone(&paginated_store),
+				Arc::clone(&api_key_store),
+				Arc::clone(&nonce_cache),
+			);
+			let node_entropy = runtime.block_on(serve_locked(
+				locked_service,
+				config_file.grpc_service_addr,
+				Arc::clone(&tls_acceptor),
+				Arc::clone(&client_identities),
+				config_file.unix_socket_config.as_ref(),
+				entropy_receiver,
+				&logger,
+			));
+			match node_entropy {
+				Some(node_entropy) => node_entropy,
+				None => {
+					info!("Shutdown complete..");
+					log::logger().flush();
+					return;
+				},
+			}
+		},
+		Ok(false) => match load_or_generate_node_entropy(&storage_dir) {
+			Ok(entropy) => entropy,
+			Err(e) => {
+				error!("Failed to load or generate node entropy: {e}");
+				std::process::exit(-1);
+			},
+		},
+		Err(e) => {
+			error!("Failed to read node mnemonic: {e}");
 			std::process::exit(-1);
 		},
 	};
@@ -267,17 +384,53 @@ fn main() {
 		},
 	};
 
-	let paginated_store: Arc<dyn PaginatedKVStore> =
-		Arc::new(match SqliteStore::new(network_dir.clone(), None, None) {
-			Ok(store) => store,
+	let spend_tracker = match SpendTracker::new(
+		Arc::clone(&paginated_store),
+		config_file.spend_limits,
+		config_file.key_spend_limits,
+	) {
+		Ok(spend_tracker) => Arc::new(spend_tracker),
+		Err(e) => {
+			error!("Failed to load spends from persistence: {e}");
+			std::process::exit(-1);
+		},
+	};
+
+	let probe_tracker = Arc::new(ProbeTracker::new());
+	let routing_snapshots = Arc::new(RoutingSnapshotCache::new());
+
+	let webhook_dispatcher = if config_file.webhooks.is_empty() {
+		None
+	} else {
+		match WebhookDispatcher::new(Arc::clone(&paginated_store), config_file.webhooks) {
+			Ok(webhook_dispatcher) => Some(Arc::new(webhook_dispatcher)),
 			Err(e) => {
-				error!("Failed to create SqliteStore: {e:?}");
+				error!("Failed to set up webhooks: {e}");
 				std::process::exit(-1);
 			},
-		});
+		}
+	};
 
-	let (event_sender, _) = broadcast::channel::<EventEnvelope>(1024);
+	let event_log = match EventLog::new(Arc::clone(&paginated_store), config_file.event_log_config)
+		.map(|event_log| event_log.with_webhooks(webhook_dispatcher.clone()))
+	{
+		Ok(event_log) => Arc::new(event_log),
+		Err(e) => {
+			error!("Failed to load events from persistence: {e}");
+			std::process::exit(-1);
+		},
+	};
+	let approval_queue = config_file.approval_config.map(|approval_config| {
+		Arc::new(ApprovalQueue::new(
+			Arc::clone(&paginated_store),
+			approval_config,
+			Arc::clone(&event_log),
+		))
+	});
 	let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+	if let Some(webhook_dispatcher) = &webhook_dispatcher {
+		runtime.spawn(Arc::clone(webhook_dispatcher).run(shutdown_rx.clone()));
+	}
 
 	info!("Starting ldk-server version {FULL_VERSION}");
 	match node.start() {
@@ -317,12 +470,86 @@ fn main() {
 			}
 		};
 		let event_node = Arc::clone(&node);
-		let mut ready_channel_ids: HashSet<ChannelId> = event_node
+		// The funding outpoints of the channels that are ready, to tell a locked splice from a
+		// `ChannelReady` that is delivered again after a restart.
+		let mut ready_channels: HashMap<ChannelId, Option<OutPoint>> = event_node
 			.list_channels()
 			.into_iter()
 			.filter(|channel| channel.is_channel_ready)
-			.map(|channel| channel.channel_id)
+			.map(|channel| (channel.channel_id, channel.funding_txo))
 			.collect();
+		// The details of open channels as last observed, which are gone by the time a channel's
+		// closure is processed.
+		let mut channel_snapshots: HashMap<ChannelId, ChannelDetails> = HashMap::new();
+		refresh_channel_snapshots(&event_node, &mut channel_snapshots);
+		let mut node_monitor = match NodeMonitor::new(Arc::clone(&paginated_store)) {
+			Ok(node_monitor) => node_monitor,
+			Err(e) => {
+				error!("Failed to load on-chain receipts from persistence: {e}");
+				std::process::exit(-1);
+			},
+		};
+		let mut onchain_payment_recorder = OnchainPaymentRecorder::new(Arc::clone(&paginated_store));
+		// Catch up on the transactions the wallet found while the server wasn't running before
+		// serving `ListOnchainTransactions`, rather than on the first poll of the node monitor.
+		onchain_payment_recorder.record(&list_onchain_payments(&node));
+		let monitor_node = Arc::clone(&node);
+		let monitor_event_log = Arc::clone(&event_log);
+		runtime.spawn(async move {
+			let mut interval = tokio::time::interval(NODE_MONITOR_POLL_INTERVAL);
+			loop {
+				interval.tick().await;
+				let node = Arc::clone(&monitor_node);
+				let event_log = Arc::clone(&monitor_event_log);
+				let poll = tokio::task::spawn_blocking(move || {
+					let snapshot = node_snapshot(&node, &node_monitor);
+					if let Some(onchain_payments) = &snapshot.onchain_payments {
+						onchain_payment_recorder.record(onchain_payments);
+					}
+					for event in node_monitor.poll(snapshot) {
+						event_log.publish(event);
+					}
+					(node_monitor, onchain_payment_recorder)
+				});
+				(node_monitor, onchain_payment_recorder) = match poll.await {
+					Ok(state) => state,
+					Err(e) => {
+						error!("Node monitor failed: {e}");
+						return;
+					},
+				};
+			}
+		});
+		let mut claim_deadline_enforcer = ClaimDeadlineEnforcer::new(Arc::clone(&paginated_store));
+		let claim_deadline_node = Arc::clone(&node);
+		let claim_deadline_event_log = Arc::clone(&event_log);
+		let claim_deadline_store = Arc::clone(&paginated_store);
+		runtime.spawn(async move {
+			let mut interval = tokio::time::interval(CLAIM_DEADLINE_CHECK_INTERVAL);
+			loop {
+				interval.tick().await;
+				let node = Arc::clone(&claim_deadline_node);
+				let event_log = Arc::clone(&claim_deadline_event_log);
+				let paginated_store = Arc::clone(&claim_deadline_store);
+				let enforce = tokio::task::spawn_blocking(move || {
+					enforce_claim_deadlines(
+						&node,
+						&event_log,
+						&*paginated_store,
+						&mut claim_deadline_enforcer,
+					);
+					claim_deadline_enforcer
+				});
+				claim_deadline_enforcer = match enforce.await {
+					Ok(enforcer) => enforcer,
+					Err(e) => {
+						error!("Claim deadline enforcement failed: {e}");
+						return;
+					},
+				};
+			}
+		});
+		let mut event_prune_interval = tokio::time::interval(EVENT_PRUNE_INTERVAL);
 
 		let metrics: Option<Arc<Metrics>> = if config_file.metrics_enabled {
 			let poll_metrics_interval = Duration::from_secs(config_file.poll_metrics_interval.unwrap_or(60));
@@ -358,18 +585,26 @@ fn main() {
 			.await
 			.expect("Failed to bind listening port");
 
-		let server_config = match get_or_generate_tls_config(
-			config_file.tls_config,
-			storage_dir.to_str().unwrap(),
-		) {
-			Ok(config) => config,
-			Err(e) => {
-				error!("Failed to set up TLS: {e}");
-				std::process::exit(-1);
-			}
-		};
-		let tls_acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(server_config));
 		info!("gRPC service listening on {}", config_file.grpc_service_addr);
+		let unix_listener = bind_unix_listener(config_file.unix_socket_config.as_ref());
+
+		let new_node_service = || {
+			NodeService::new(
+				Arc::clone(&node),
+				Arc::clone(&paginated_store),
+				Arc::clone(&api_key_store),
+				Arc::clone(&nonce_cache),
+				Arc::clone(&spend_tracker),
+				Arc::clone(&probe_tracker),
+				Arc::clone(&routing_snapshots),
+				Arc::clone(&event_log),
+				shutdown_rx.clone(),
+			)
+			.with_metrics(metrics.clone(), metrics_auth_header.clone())
+			.with_approval_queue(approval_queue.clone())
+			.with_webhooks(webhook_dispatcher.clone())
+			.with_storage_dir(storage_dir.clone())
+		};
 
 		systemd::notify_ready();
 
@@ -390,16 +625,18 @@ fn main() {
 								);
 
 								send_channel_state_event(
-									event_envelope::Event::ChannelStateChanged(events::ChannelStateChanged {
+									events::ChannelStateChanged {
 										channel_id: channel_id.0.to_lower_hex_string(),
 										user_channel_id: user_channel_id.0.to_string(),
 										counterparty_node_id: Some(counterparty_node_id.to_string()),
-										state: events::ChannelState::Pending.into(),
+										state: types::ChannelState::Pending.into(),
 										funding_txo: Some(funding_txo.to_string()),
 										reason: None,
-										closure_initiator: events::ChannelClosureInitiator::Unspecified.into(),
-									}),
-									&event_sender,
+										closure_initiator: types::ChannelClosureInitiator::Unspecified.into(),
+										..Default::default()
+									},
+									&event_log,
+									&*paginated_store,
 								);
 
 								if let Err(e) = event_node.event_handled() {
@@ -418,30 +655,98 @@ fn main() {
 								);
 
 								let channel_id_hex = channel_id.0.to_lower_hex_string();
-								ready_channel_ids.insert(channel_id);
+								let previous_funding_txo =
+									ready_channels.insert(channel_id, funding_txo);
+								let is_splice = previous_funding_txo
+									.is_some_and(|previous| previous != funding_txo);
+								if is_splice {
+									// The channel was ready before with another funding output, so a
+									// splice of it was locked.
+									event_log.publish(event_envelope::Event::SpliceCompleted(
+										events::SpliceCompleted {
+											channel_id: channel_id_hex,
+											user_channel_id: user_channel_id.0.to_string(),
+											counterparty_node_id: counterparty_node_id
+												.map(|node_id| node_id.to_string()),
+											funding_txo: funding_txo
+												.map(|outpoint| outpoint.to_string()),
+										},
+									));
+								} else {
+									send_channel_state_event(
+										events::ChannelStateChanged {
+											channel_id: channel_id_hex,
+											user_channel_id: user_channel_id.0.to_string(),
+											counterparty_node_id: counterparty_node_id
+												.map(|node_id| node_id.to_string()),
+											state: types::ChannelState::Ready.into(),
+											funding_txo: funding_txo.map(|outpoint| outpoint.to_string()),
+											reason: None,
+											closure_initiator: types::ChannelClosureInitiator::Unspecified.into(),
+											..Default::default()
+										},
+										&event_log,
+										&*paginated_store,
+									);
 
-								send_channel_state_event(
-									event_envelope::Event::ChannelStateChanged(events::ChannelStateChanged {
-										channel_id: channel_id_hex,
-										user_channel_id: user_channel_id.0.to_string(),
-										counterparty_node_id: counterparty_node_id
-											.map(|node_id| node_id.to_string()),
-										state: events::ChannelState::Ready.into(),
-										funding_txo: funding_txo.map(|outpoint| outpoint.to_string()),
-										reason: None,
-										closure_initiator: events::ChannelClosureInitiator::Unspecified.into(),
-									}),
-									&event_sender,
+									if let Some(metrics) = &metrics {
+										metrics.update_channels_count(false);
+									}
+								}
+
+								if let Err(e) = event_node.event_handled() {
+									error!("Failed to mark event as handled: {e}");
+								}
+							},
+							Event::SplicePending {
+								channel_id,
+								user_channel_id,
+								counterparty_node_id,
+								new_funding_txo,
+								..
+							} => {
+								info!(
+									"SPLICE_PENDING: {} from counterparty {}, new funding {}",
+									channel_id, counterparty_node_id, new_funding_txo
 								);
 
+								let event = events::SplicePending {
+									channel_id: channel_id.0.to_lower_hex_string(),
+									user_channel_id: user_channel_id.0.to_string(),
+									counterparty_node_id: counterparty_node_id.to_string(),
+									new_funding_txo: new_funding_txo.to_string(),
+								};
+								event_log.publish(event_envelope::Event::SplicePending(event));
+
 								if let Err(e) = event_node.event_handled() {
 									error!("Failed to mark event as handled: {e}");
 								}
+							},
+							Event::SpliceFailed {
+								channel_id,
+								user_channel_id,
+								counterparty_node_id,
+								abandoned_funding_txo,
+								..
+							} => {
+								info!(
+									"SPLICE_FAILED: {} from counterparty {}",
+									channel_id, counterparty_node_id
+								);
 
-							if let Some(metrics) = &metrics {
-								metrics.update_channels_count(false);
-							}
-						},
+								let event = events::SpliceFailed {
+									channel_id: channel_id.0.to_lower_hex_string(),
+									user_channel_id: user_channel_id.0.to_string(),
+									counterparty_node_id: counterparty_node_id.to_string(),
+									abandoned_funding_txo: abandoned_funding_txo
+										.map(|outpoint| outpoint.to_string()),
+								};
+								event_log.publish(event_envelope::Event::SpliceFailed(event));
+
+								if let Err(e) = event_node.event_handled() {
+									error!("Failed to mark event as handled: {e}");
+								}
+							},
 							Event::ChannelClosed {
 								channel_id,
 								user_channel_id,
@@ -454,25 +759,49 @@ fn main() {
 								);
 
 								let channel_id_hex = channel_id.0.to_lower_hex_string();
-								let was_ready = ready_channel_ids.remove(&channel_id);
+								let was_ready = ready_channels.contains_key(&channel_id);
 								let reason_ref = reason.as_ref();
 								let is_open_failure = !was_ready && is_channel_open_failure(reason_ref);
+								let state = if is_open_failure {
+									types::ChannelState::OpenFailed
+								} else {
+									types::ChannelState::Closed
+								};
+								let closure_initiator = closure_initiator_from_reason(reason_ref);
+
+								let snapshot = channel_snapshots.get(&channel_id).cloned().map(channel_to_proto);
+								let mut closed_channel = closed_channel_from_snapshot(
+									channel_id_hex.clone(),
+									user_channel_id.0.to_string(),
+									counterparty_node_id.to_string(),
+									snapshot.as_ref(),
+								);
+								closed_channel.state = state.into();
+								closed_channel.reason = reason_ref.map(closure_reason_to_proto);
+								closed_channel.closure_initiator = closure_initiator.into();
+								closed_channel.closed_at = current_time_secs();
+								// The event isn't marked as handled if the closure can't be recorded, so it is
+								// replayed. The channel's state is only dropped once it was recorded.
+								if let Err(e) = record_closed_channel(&*paginated_store, &closed_channel) {
+									error!("Failed to record closed channel {channel_id}: {e}");
+									continue;
+								}
+								ready_channels.remove(&channel_id);
+								channel_snapshots.remove(&channel_id);
 
 								send_channel_state_event(
-									event_envelope::Event::ChannelStateChanged(events::ChannelStateChanged {
+									events::ChannelStateChanged {
 										channel_id: channel_id_hex,
 										user_channel_id: user_channel_id.0.to_string(),
 										counterparty_node_id: Some(counterparty_node_id.to_string()),
-										state: if is_open_failure {
-											events::ChannelState::OpenFailed.into()
-										} else {
-											events::ChannelState::Closed.into()
-										},
+										state: state.into(),
 										funding_txo: None,
-										reason: reason_ref.map(closure_reason_to_proto),
-										closure_initiator: closure_initiator_from_reason(reason_ref).into(),
-									}),
-									&event_sender,
+										reason: closed_channel.reason,
+										closure_initiator: closure_initiator.into(),
+										..Default::default()
+									},
+									&event_log,
+									&*paginated_store,
 								);
 
 								if let Err(e) = event_node.event_handled() {
@@ -495,6 +824,7 @@ fn main() {
 								payment_id, payment_hash, amount_msat
 							);
 							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
+							forget_claimable_payment(&*paginated_store, &payment_hash);
 
 							let proto_custom_records: Vec<_> = custom_records
 								.iter()
@@ -510,7 +840,7 @@ fn main() {
 									})
 								},
 								&event_node,
-								&event_sender,
+								&event_log,
 								Arc::clone(&paginated_store),
 							);
 
@@ -518,15 +848,18 @@ fn main() {
 								metrics.update_all_balances(&event_node);
 							}
 						},
-						Event::PaymentSuccessful {payment_id, ..} => {
+						Event::PaymentSuccessful {payment_id, fee_paid_msat, ..} => {
 							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
+							if let Some(fee_paid_msat) = fee_paid_msat {
+								spend_tracker.payment_fee_paid(&payment_id.to_string(), fee_paid_msat);
+							}
 
 							send_event_and_upsert_payment(&payment_id,
 								|payment_ref| event_envelope::Event::PaymentSuccessful(events::PaymentSuccessful {
 									payment: Some(payment_ref.clone()),
 								}),
 								&event_node,
-								&event_sender,
+								&event_log,
 								Arc::clone(&paginated_store));
 
 							if let Some(metrics) = &metrics {
@@ -534,22 +867,43 @@ fn main() {
 								metrics.update_all_balances(&event_node);
 							}
 						},
-						Event::PaymentFailed {payment_id, ..} => {
+						Event::PaymentFailed {payment_id, payment_hash, ..} => {
 							let payment_id = payment_id.expect("PaymentId expected for ldk-server >=0.1");
+							spend_tracker.payment_failed(&payment_id.to_string());
+							if let Some(payment_hash) = payment_hash {
+								forget_claimable_payment(&*paginated_store, &payment_hash);
+							}
 
 							send_event_and_upsert_payment(&payment_id,
 								|payment_ref| event_envelope::Event::PaymentFailed(events::PaymentFailed {
 									payment: Some(payment_ref.clone()),
 								}),
 								&event_node,
-								&event_sender,
+								&event_log,
 								Arc::clone(&paginated_store));
 
 							if let Some(metrics) = &metrics {
 								metrics.update_payments_count(false);
 							}
 						},
-						Event::PaymentClaimable { payment_id, custom_records, claim_deadline, .. } => {
+						Event::PaymentClaimable {
+							payment_id,
+							payment_hash,
+							claimable_amount_msat,
+							custom_records,
+							claim_deadline,
+							..
+						} => {
+							if let Err(e) = record_claimable_payment(
+								&*paginated_store,
+								&config_file.hodl_invoice_config,
+								payment_hash.0.to_lower_hex_string(),
+								payment_id.to_string(),
+								claimable_amount_msat,
+								claim_deadline,
+							) {
+								error!("Failed to record claimable payment {payment_hash}: {e}");
+							}
 							send_event_and_upsert_payment(
 								&payment_id,
 								|payment_ref| {
@@ -558,7 +912,7 @@ fn main() {
 									)
 								},
 								&event_node,
-								&event_sender,
+								&event_log,
 								Arc::clone(&paginated_store),
 							);
 						},
@@ -609,13 +963,9 @@ fn main() {
 
 							let forwarded_payment_creation_time = SystemTime::now().duration_since(UNIX_EPOCH).expect("Time must be > 1970").as_secs() as i64;
 
-							if let Err(e) = event_sender.send(EventEnvelope {
-								event: Some(event_envelope::Event::PaymentForwarded(events::PaymentForwarded {
-									forwarded_payment: Some(forwarded_payment.clone()),
-								})),
-							}) {
-								debug!("No event subscribers connected, skipping event: {e}");
-							}
+							event_log.publish(event_envelope::Event::PaymentForwarded(events::PaymentForwarded {
+								forwarded_payment: Some(forwarded_payment.clone()),
+							}));
 
 							match paginated_store.write(FORWARDED_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,FORWARDED_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
 								&forwarded_payment_id.to_lower_hex_string(),
@@ -638,45 +988,57 @@ fn main() {
 							}
 						},
 					}
+					refresh_channel_snapshots(&event_node, &mut channel_snapshots);
 				},
+				_ = event_prune_interval.tick() => {
+					let event_log = Arc::clone(&event_log);
+					runtime.spawn_blocking(move || {
+						if let Err(e) = event_log.prune() {
+							error!("Failed to prune events: {e}");
+						}
+					});
+				}
 				res = grpc_listener.accept() => {
 					match res {
 						Ok((stream, _)) => {
-							let node_service = NodeService::new(
-								Arc::clone(&node),
-								Arc::clone(&paginated_store),
-								api_key.clone(),
-								metrics.clone(),
-								metrics_auth_header.clone(),
-								event_sender.clone(),
-								shutdown_rx.clone(),
-							);
-							let acceptor = tls_acceptor.clone();
-							runtime.spawn(async move {
-								match acceptor.accept(stream).await {
-									Ok(tls_stream) => {
-										let io_stream = TokioIo::new(tls_stream);
-										if let Err(err) = http2::Builder::new(TokioExecutor::new()).serve_connection(io_stream, node_service).await {
-											error!("Failed to serve TLS connection: {err}");
-										}
-									},
-									Err(e) => error!("TLS handshake failed: {e}"),
-								}
-							});
+							let node_service = new_node_service();
+							runtime.spawn(serve_connection(
+								stream,
+								Some(tls_acceptor.acceptor()),
+								Arc::clone(&client_identities),
+								None,
+								move |identity| node_service.with_connection_identity(identity),
+							));
 						},
 						Err(e) => error!("Failed to accept connection: {}", e),
 					}
 				}
+				res = accept_unix(unix_listener.as_ref()) => {
+					match res {
+						Ok(stream) => {
+							let unix_socket_config = config_file
+								.unix_socket_config
+								.as_ref()
+								.expect("Unix socket is configured");
+							let node_service = new_node_service();
+							runtime.spawn(serve_connection(
+								stream,
+								unix_socket_config.tls.then(|| tls_acceptor.acceptor()),
+								Arc::clone(&client_identities),
+								Some(unix_socket_config.identity.clone()),
+								move |identity| node_service.with_connection_identity(identity),
+							));
+						},
+						Err(e) => error!("Failed to accept Unix socket connection: {}", e),
+					}
+				}
 				_ = tokio::signal::ctrl_c() => {
 					info!("Received CTRL-C, shutting down..");
 					let _ = shutdown_tx.send(true);
 					break;
 				}
 				_ = sighup_stream.recv() => {
-					info!("Received SIGHUP, reopening log file..");
-					if let Err(e) = logger.reopen() {
-						error!("Failed to reopen log file on SIGHUP: {e}");
-					}
+					handle_sighup(&logger, &tls_acceptor);
 				}
 				_ = sigterm_stream.recv() => {
 					info!("Received SIGTERM, shutting down..");
@@ -688,36 +1050,224 @@ fn main() {
 	});
 
 	systemd::notify_stopping();
+	if let Some(unix_socket_config) = &config_file.unix_socket_config {
+		let _ = fs::remove_file(&unix_socket_config.path);
+	}
 	node.stop().expect("Shutdown should always succeed.");
 	info!("Shutdown complete..");
 	log::logger().flush();
 }
 
+/// Serves the [`LockedService`] until the encrypted mnemonic was unlocked, returning the node
+/// entropy, or `None` if the server was shut down before.
+async fn serve_locked(
+	locked_service: LockedService, grpc_service_addr: SocketAddr,
+	tls_acceptor: Arc<ReloadableTlsAcceptor>, client_identities: Arc<Vec<ClientCertIdentity>>,
+	unix_socket_config: Option<&UnixSocketConfig>,
+	mut entropy_receiver: oneshot::Receiver<NodeEntropy>, logger: &ServerLogger,
+) -> Option<NodeEntropy> {
+	let mut sighup_stream = match tokio::signal::unix::signal(SignalKind::hangup()) {
+		Ok(stream) => stream,
+		Err(e) => {
+			error!("Failed to register SIGHUP handler: {e}");
+			std::process::exit(-1);
+		},
+	};
+	let mut sigterm_stream = match tokio::signal::unix::signal(SignalKind::terminate()) {
+		Ok(stream) => stream,
+		Err(e) => {
+			error!("Failed to register for SIGTERM stream: {e}");
+			std::process::exit(-1);
+		},
+	};
+
+	let grpc_listener =
+		TcpListener::bind(grpc_service_addr).await.expect("Failed to bind listening port");
+	info!("Node mnemonic is encrypted, waiting for it to be unlocked via the Unlock RPC");
+	info!("gRPC service listening on {grpc_service_addr}");
+	let unix_listener = bind_unix_listener(unix_socket_config);
+
+	// The API is available, even though the node is only started once unlocked.
+	systemd::notify_ready();
+
+	loop {
+		select! {
+			node_entropy = &mut entropy_receiver => {
+				info!("Node mnemonic was unlocked");
+				return node_entropy.ok();
+			},
+			res = grpc_listener.accept() => {
+				match res {
+					Ok((stream, _)) => {
+						let locked_service = locked_service.clone();
+						tokio::spawn(serve_connection(
+							stream,
+							Some(tls_acceptor.acceptor()),
+							Arc::clone(&client_identities),
+							None,
+							move |identity| locked_service.with_connection_identity(identity),
+						));
+					},
+					Err(e) => error!("Failed to accept connection: {}", e),
+				}
+			},
+			res = accept_unix(unix_listener.as_ref()) => {
+				match res {
+					Ok(stream) => {
+						let unix_socket_config =
+							unix_socket_config.expect("Unix socket is configured");
+						let locked_service = locked_service.clone();
+						tokio::spawn(serve_connection(
+							stream,
+							unix_socket_config.tls.then(|| tls_acceptor.acceptor()),
+							Arc::clone(&client_identities),
+							Some(unix_socket_config.identity.clone()),
+							move |identity| locked_service.with_connection_identity(identity),
+						));
+					},
+					Err(e) => error!("Failed to accept Unix socket connection: {}", e),
+				}
+			},
+			_ = sighup_stream.recv() => {
+				handle_sighup(logger, &tls_acceptor);
+			},
+			_ = tokio::signal::ctrl_c() => {
+				info!("Received CTRL-C, shutting down..");
+				return None;
+			},
+			_ = sigterm_stream.recv() => {
+				info!("Received SIGTERM, shutting down..");
+				return None;
+			},
+		}
+	}
+}
+
+/// Binds the configured Unix socket, if any, exiting if that fails.
+fn bind_unix_listener(unix_socket_config: Option<&UnixSocketConfig>) -> Option<UnixListener> {
+	let unix_socket_config = unix_socket_config?;
+	match bind_unix_socket(unix_socket_config).and_then(UnixListener::from_std) {
+		Ok(listener) => {
+			info!("gRPC service listening on unix://{}", unix_socket_config.path.display());
+			Some(listener)
+		},
+		Err(e) => {
+			error!("Failed to bind Unix socket {}: {e}", unix_socket_config.path.display());
+			std::process::exit(-1);
+		},
+	}
+}
+
+/// Accepts a connection on the Unix socket, or never resolves if none is configured.
+async fn accept_unix(listener: Option<&UnixListener>) -> std::io::Result<UnixStream> {
+	match listener {
+		Some(listener) => listener.accept().await.map(|(stream, _)| stream),
+		None => std::future::pending().await,
+	}
+}
+
+/// Serves the service built by `make_service` on an accepted connection, after a TLS handshake if
+/// a `tls_acceptor` is given.
+///
+/// The service authenticates requests that aren't signed with an API key as the identity of the
+/// presented TLS client certificate, or else as the given `identity`.
+async fn serve_connection<IO, S>(
+	stream: IO, tls_acceptor: Option<TlsAcceptor>, client_identities: Arc<Vec<ClientCertIdentity>>,
+	identity: Option<Identity>, make_service: impl FnOnce(Option<Identity>) -> S,
+) where
+	IO: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+	S: Service<Request<Incoming>, Response = Response<GrpcBody>, Error = hyper::Error>
+		+ Send
+		+ 'static,
+	S::Future: Send + 'static,
+{
+	let Some(tls_acceptor) = tls_acceptor else {
+		serve_http2(stream, make_service(identity)).await;
+		return;
+	};
+	match tls_acceptor.accept(stream).await {
+		Ok(tls_stream) => {
+			let client_cert_identity = tls_stream
+				.get_ref()
+				.1
+				.peer_certificates()
+				.and_then(|certs| certs.first())
+				.and_then(|cert| identity_for_client_cert(&client_identities, cert));
+			serve_http2(tls_stream, make_service(client_cert_identity.or(identity))).await;
+		},
+		Err(e) => error!("TLS handshake failed: {e}"),
+	}
+}
+
+async fn serve_http2<IO, S>(stream: IO, service: S)
+where
+	IO: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+	S: Service<Request<Incoming>, Response = Response<GrpcBody>, Error = hyper::Error>
+		+ Send
+		+ 'static,
+	S::Future: Send + 'static,
+{
+	if let Err(err) = http2::Builder::new(TokioExecutor::new())
+		.serve_connection(TokioIo::new(stream), service)
+		.await
+	{
+		error!("Failed to serve connection: {err}");
+	}
+}
+
+/// Reopens the log file, e.g. after it was rotated by logrotate, and reloads the TLS certificate.
+fn handle_sighup(logger: &ServerLogger, tls_acceptor: &ReloadableTlsAcceptor) {
+	info!("Received SIGHUP, reopening log file and reloading TLS certificate..");
+	if let Err(e) = logger.reopen() {
+		error!("Failed to reopen log file on SIGHUP: {e}");
+	}
+	if let Err(e) = tls_acceptor.reload() {
+		error!("Failed to reload TLS certificate on SIGHUP: {e}");
+	}
+}
+
 fn send_event_and_upsert_payment(
 	payment_id: &PaymentId, payment_to_event: impl FnOnce(&Payment) -> event_envelope::Event,
-	event_node: &Node, event_sender: &broadcast::Sender<EventEnvelope>,
-	paginated_store: Arc<dyn PaginatedKVStore>,
+	event_node: &Node, event_log: &EventLog, paginated_store: Arc<dyn PaginatedKVStore>,
 ) {
 	if let Some(payment_details) = event_node.payment(payment_id) {
-		let payment = payment_to_proto(payment_details);
-
-		let event = payment_to_event(&payment);
-		if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
-			debug!("No event subscribers connected, skipping event: {e}");
+		let mut payment = payment_to_proto(payment_details);
+		if let Err(e) = apply_payment_label(&*paginated_store, &mut payment) {
+			error!("Failed to read label of payment {payment_id}: {e}");
 		}
 
+		event_log.publish(payment_to_event(&payment));
+
 		upsert_payment_details(event_node, Arc::clone(&paginated_store), &payment);
 	} else {
 		error!("Unable to find payment with paymentId: {payment_id}");
 	}
 }
 
+/// Records the current details of all open channels, so they are still known once a channel's
+/// closure is processed.
+fn refresh_channel_snapshots(
+	node: &Node, channel_snapshots: &mut HashMap<ChannelId, ChannelDetails>,
+) {
+	for channel in node.list_channels() {
+		channel_snapshots.insert(channel.channel_id, channel);
+	}
+}
+
 fn send_channel_state_event(
-	event: event_envelope::Event, event_sender: &broadcast::Sender<EventEnvelope>,
+	mut event: events::ChannelStateChanged, event_log: &EventLog,
+	paginated_store: &dyn PaginatedKVStore,
 ) {
-	if let Err(e) = event_sender.send(EventEnvelope { event: Some(event) }) {
-		debug!("No event subscribers connected, skipping event: {e}");
+	match read_label(paginated_store, LabelTarget::Channel, &event.user_channel_id) {
+		Ok(Some(user_label)) => {
+			event.label = user_label.label;
+			event.metadata = user_label.metadata;
+		},
+		Ok(None) => {},
+		Err(e) => error!("Failed to read label of channel {}: {e}", event.user_channel_id),
 	}
+
+	event_log.publish(event_envelope::Event::ChannelStateChanged(event));
 }
 
 fn is_channel_open_failure(reason: Option<&ClosureReason>) -> bool {
@@ -743,15 +1293,15 @@ fn is_channel_open_failure(reason: Option<&ClosureReason>) -> bool {
 
 fn closure_initiator_from_reason(
 	reason: Option<&ClosureReason>,
-) -> events::ChannelClosureInitiator {
+) -> types::ChannelClosureInitiator {
 	match reason {
 		Some(ClosureReason::HolderForceClosed { .. })
 		| Some(ClosureReason::LocallyInitiatedCooperativeClosure)
-		| Some(ClosureReason::LocallyCoopClosedUnfundedChannel) => events::ChannelClosureInitiator::Local,
+		| Some(ClosureReason::LocallyCoopClosedUnfundedChannel) => types::ChannelClosureInitiator::Local,
 		Some(ClosureReason::CounterpartyForceClosed { .. })
 		| Some(ClosureReason::CounterpartyInitiatedCooperativeClosure)
 		| Some(ClosureReason::CounterpartyCoopClosedUnfundedChannel) => {
-			events::ChannelClosureInitiator::Remote
+			types::ChannelClosureInitiator::Remote
 		},
 		Some(ClosureReason::LegacyCooperativeClosure)
 		| Some(ClosureReason::CommitmentTxConfirmed)
@@ -761,93 +1311,93 @@ fn closure_initiator_from_reason(
 		| Some(ClosureReason::OutdatedChannelManager)
 		| Some(ClosureReason::FundingBatchClosure)
 		| Some(ClosureReason::HTLCsTimedOut { .. })
-		| Some(ClosureReason::PeerFeerateTooLow { .. }) => events::ChannelClosureInitiator::Unknown,
-		None => events::ChannelClosureInitiator::Unspecified,
+		| Some(ClosureReason::PeerFeerateTooLow { .. }) => types::ChannelClosureInitiator::Unknown,
+		None => types::ChannelClosureInitiator::Unspecified,
 	}
 }
 
-fn closure_reason_to_proto(reason: &ClosureReason) -> events::ChannelStateChangeReason {
-	events::ChannelStateChangeReason {
+fn closure_reason_to_proto(reason: &ClosureReason) -> types::ChannelStateChangeReason {
+	types::ChannelStateChangeReason {
 		kind: closure_reason_kind(reason).into(),
 		message: reason.to_string(),
 		details: closure_reason_details(reason),
 	}
 }
 
-fn closure_reason_kind(reason: &ClosureReason) -> events::ChannelStateChangeReasonKind {
+fn closure_reason_kind(reason: &ClosureReason) -> types::ChannelStateChangeReasonKind {
 	match reason {
 		ClosureReason::CounterpartyForceClosed { .. } => {
-			events::ChannelStateChangeReasonKind::CounterpartyForceClosed
+			types::ChannelStateChangeReasonKind::CounterpartyForceClosed
 		},
 		ClosureReason::HolderForceClosed { .. } => {
-			events::ChannelStateChangeReasonKind::HolderForceClosed
+			types::ChannelStateChangeReasonKind::HolderForceClosed
 		},
 		ClosureReason::LegacyCooperativeClosure => {
-			events::ChannelStateChangeReasonKind::LegacyCooperativeClosure
+			types::ChannelStateChangeReasonKind::LegacyCooperativeClosure
 		},
 		ClosureReason::CounterpartyInitiatedCooperativeClosure => {
-			events::ChannelStateChangeReasonKind::CounterpartyInitiatedCooperativeClosure
+			types::ChannelStateChangeReasonKind::CounterpartyInitiatedCooperativeClosure
 		},
 		ClosureReason::LocallyInitiatedCooperativeClosure => {
-			events::ChannelStateChangeReasonKind::LocallyInitiatedCooperativeClosure
+			types::ChannelStateChangeReasonKind::LocallyInitiatedCooperativeClosure
 		},
 		ClosureReason::CommitmentTxConfirmed => {
-			events::ChannelStateChangeReasonKind::CommitmentTxConfirmed
+			types::ChannelStateChangeReasonKind::CommitmentTxConfirmed
 		},
-		ClosureReason::FundingTimedOut => events::ChannelStateChangeReasonKind::FundingTimedOut,
+		ClosureReason::FundingTimedOut => types::ChannelStateChangeReasonKind::FundingTimedOut,
 		ClosureReason::ProcessingError { .. } => {
-			events::ChannelStateChangeReasonKind::ProcessingError
+			types::ChannelStateChangeReasonKind::ProcessingError
 		},
-		ClosureReason::DisconnectedPeer => events::ChannelStateChangeReasonKind::DisconnectedPeer,
+		ClosureReason::DisconnectedPeer => types::ChannelStateChangeReasonKind::DisconnectedPeer,
 		ClosureReason::OutdatedChannelManager => {
-			events::ChannelStateChangeReasonKind::OutdatedChannelManager
+			types::ChannelStateChangeReasonKind::OutdatedChannelManager
 		},
 		ClosureReason::CounterpartyCoopClosedUnfundedChannel => {
-			events::ChannelStateChangeReasonKind::CounterpartyCoopClosedUnfundedChannel
+			types::ChannelStateChangeReasonKind::CounterpartyCoopClosedUnfundedChannel
 		},
 		ClosureReason::LocallyCoopClosedUnfundedChannel => {
-			events::ChannelStateChangeReasonKind::LocallyCoopClosedUnfundedChannel
+			types::ChannelStateChangeReasonKind::LocallyCoopClosedUnfundedChannel
 		},
 		ClosureReason::FundingBatchClosure => {
-			events::ChannelStateChangeReasonKind::FundingBatchClosure
+			types::ChannelStateChangeReasonKind::FundingBatchClosure
 		},
-		ClosureReason::HTLCsTimedOut { .. } => events::ChannelStateChangeReasonKind::HtlcsTimedOut,
+		ClosureReason::HTLCsTimedOut { .. } => types::ChannelStateChangeReasonKind::HtlcsTimedOut,
 		ClosureReason::PeerFeerateTooLow { .. } => {
-			events::ChannelStateChangeReasonKind::PeerFeerateTooLow
+			types::ChannelStateChangeReasonKind::PeerFeerateTooLow
 		},
 	}
 }
 
 fn closure_reason_details(
 	reason: &ClosureReason,
-) -> Option<events::channel_state_change_reason::Details> {
-	use events::channel_state_change_reason::Details;
+) -> Option<types::channel_state_change_reason::Details> {
+	use types::channel_state_change_reason::Details;
 
 	match reason {
 		ClosureReason::CounterpartyForceClosed { peer_msg } => {
-			Some(Details::CounterpartyForceClosed(events::CounterpartyForceClosedDetails {
+			Some(Details::CounterpartyForceClosed(types::CounterpartyForceClosedDetails {
 				peer_msg: peer_msg.to_string(),
 			}))
 		},
 		ClosureReason::HolderForceClosed {
 			broadcasted_latest_txn,
 			message: force_close_message,
-		} => Some(Details::HolderForceClosed(events::HolderForceClosedDetails {
+		} => Some(Details::HolderForceClosed(types::HolderForceClosedDetails {
 			broadcasted_latest_txn: *broadcasted_latest_txn,
 			message: force_close_message.clone(),
 		})),
 		ClosureReason::ProcessingError { err } => {
-			Some(Details::ProcessingError(events::ProcessingErrorDetails { err: err.clone() }))
+			Some(Details::ProcessingError(types::ProcessingErrorDetails { err: err.clone() }))
 		},
 		ClosureReason::HTLCsTimedOut { payment_hash } => {
-			Some(Details::HtlcsTimedOut(events::HtlcsTimedOutDetails {
+			Some(Details::HtlcsTimedOut(types::HtlcsTimedOutDetails {
 				payment_hash: payment_hash.map(|hash| hash.to_string()),
 			}))
 		},
 		ClosureReason::PeerFeerateTooLow {
 			peer_feerate_sat_per_kw,
 			required_feerate_sat_per_kw,
-		} => Some(Details::PeerFeerateTooLow(events::PeerFeerateTooLowDetails {
+		} => Some(Details::PeerFeerateTooLow(types::PeerFeerateTooLowDetails {
 			peer_feerate_sat_per_kw: *peer_feerate_sat_per_kw,
 			required_feerate_sat_per_kw: *required_feerate_sat_per_kw,
 		})),
@@ -870,12 +1420,15 @@ fn upsert_payment_details(
 	let time =
 		SystemTime::now().duration_since(UNIX_EPOCH).expect("Time must be > 1970").as_secs() as i64;
 
-	match paginated_store.write(
-		PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
-		PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+	if let Err(e) = record_received_payment(&*paginated_store, payment) {
+		error!("Failed to update the invoice or offer of payment {}: {e}", payment.id);
+	}
+
+	match paginated_store.write_payment(
 		&payment.id,
 		time,
 		&payment.encode_to_vec(),
+		&PaymentIndex::from(payment),
 	) {
 		Ok(_) => {
 			if let Err(e) = event_node.event_handled() {
@@ -911,6 +1464,116 @@ fn load_or_generate_api_key(storage_dir: &Path) -> std::io::Result<String> {
 	}
 }
 
+/// Lists the on-chain payments of the node, i.e. the transactions known to its wallet.
+fn list_onchain_payments(node: &Node) -> Vec<Payment> {
+	node.list_payments_with_filter(|payment| matches!(payment.kind, PaymentKind::Onchain { .. }))
+		.into_iter()
+		.map(payment_to_proto)
+		.collect()
+}
+
+/// Captures the state of the node that [`NodeMonitor`] derives events from.
+fn node_snapshot(node: &Node, node_monitor: &NodeMonitor) -> NodeSnapshot {
+	let status = node.status();
+	let onchain_payments = node_monitor
+		.should_list_onchain_payments(status.latest_onchain_wallet_sync_timestamp)
+		.then(|| list_onchain_payments(node));
+	NodeSnapshot {
+		peers: node.list_peers().into_iter().map(peer_to_proto).collect(),
+		onchain_payments,
+		latest_onchain_wallet_sync_timestamp: status.latest_onchain_wallet_sync_timestamp,
+		latest_lightning_wallet_sync_timestamp: status.latest_lightning_wallet_sync_timestamp,
+		current_best_block: BestBlock {
+			block_hash: status.current_best_block.block_hash.to_string(),
+			height: status.current_best_block.height,
+		},
+	}
+}
+
+/// Warns about and resolves the claimable payments whose claim deadline is approaching.
+fn enforce_claim_deadlines(
+	node: &Node, event_log: &EventLog, paginated_store: &dyn PaginatedKVStore,
+	enforcer: &mut ClaimDeadlineEnforcer,
+) {
+	let current_height = node.status().current_best_block.height;
+	let due_actions = match enforcer.due_actions(current_height) {
+		Ok(due_actions) => due_actions,
+		Err(e) => {
+			error!("Failed to check claim deadlines: {e}");
+			return;
+		},
+	};
+	for (claimable, action) in due_actions {
+		let Ok(hash_bytes) = <[u8; 32]>::from_hex(&claimable.payment_hash) else {
+			error!("Invalid payment hash of claimable payment: {}", claimable.payment_hash);
+			continue;
+		};
+		let payment_hash = PaymentHash(hash_bytes);
+		let result = match action {
+			DeadlineAction::Warn => {
+				let payment = <[u8; 32]>::from_hex(&claimable.payment_id)
+					.ok()
+					.and_then(|payment_id| node.payment(&PaymentId(payment_id)))
+					.map(|payment_details| {
+						let mut payment = payment_to_proto(payment_details);
+						if let Err(e) = apply_payment_label(paginated_store, &mut payment) {
+							error!("Failed to read label of payment {}: {e}", payment.id);
+						}
+						payment
+					});
+				event_log.publish(event_envelope::Event::PaymentClaimDeadlineApproaching(
+					events::PaymentClaimDeadlineApproaching {
+						payment,
+						claim_deadline: claimable.claim_deadline.unwrap_or_default(),
+						current_height,
+						auto_resolve_height: claimable.auto_resolve_height.unwrap_or_default(),
+						auto_claim: claimable.auto_claim,
+					},
+				));
+				continue;
+			},
+			DeadlineAction::Fail => {
+				info!("Failing back claimable payment {payment_hash} ahead of its claim deadline");
+				node.bolt11_payment()
+					.fail_for_hash(payment_hash)
+					.map_err(|e| e.to_string())
+					.and_then(|()| {
+						cancel_invoice(paginated_store, &claimable.payment_hash)
+							.map_err(|e| e.to_string())
+					})
+			},
+			DeadlineAction::Claim(preimage) => {
+				info!("Claiming claimable payment {payment_hash} ahead of its claim deadline");
+				node.bolt11_payment()
+					.claim_for_hash(
+						payment_hash,
+						claimable.claimable_amount_msat,
+						PaymentPreimage(preimage),
+					)
+					.map_err(|e| e.to_string())
+			},
+		};
+		// The payment can't be resolved by later attempts either if this one failed, e.g. because
+		// it was already resolved.
+		if let Err(e) = result {
+			error!("Failed to resolve claimable payment {payment_hash}: {e}");
+		}
+		forget_claimable_payment(paginated_store, &payment_hash);
+	}
+}
+
+/// Removes the claimable payment with the given payment hash and deletes its auto-claim preimage
+/// once it settled or was failed back.
+fn forget_claimable_payment(paginated_store: &dyn PaginatedKVStore, payment_hash: &PaymentHash) {
+	let payment_hash_hex = payment_hash.0.to_lower_hex_string();
+	if let Err(e) = remove_claimable_payment(paginated_store, &payment_hash_hex) {
+		error!("Failed to remove claimable payment {payment_hash}: {e}");
+	}
+	if let Err(e) = remove_auto_claim_preimage(paginated_store, &payment_hash_hex) {
+		error!("Failed to remove auto-claim preimage of payment {payment_hash}: {e}");
+	}
+}
+
 fn build_payment_claimable_proto(
 	payment_ref: &Payment, custom_records: &[CustomTlvRecord], claim_deadline: Option<u32>,
 ) -> events::PaymentClaimable {
@@ -925,7 +1588,7 @@ fn build_payment_claimable_proto(
 
 #[cfg(test)]
 mod tests {
-	use ldk_server_grpc::events::channel_state_change_reason::Details;
+	use ldk_server_grpc::types::channel_state_change_reason::Details;
 
 	use super::*;
 
@@ -950,35 +1613,35 @@ mod tests {
 				broadcasted_latest_txn: Some(true),
 				message: "local close".to_string(),
 			})),
-			events::ChannelClosureInitiator::Local
+			types::ChannelClosureInitiator::Local
 		);
 		assert_eq!(
 			closure_initiator_from_reason(
 				Some(&ClosureReason::LocallyInitiatedCooperativeClosure,)
 			),
-			events::ChannelClosureInitiator::Local
+			types::ChannelClosureInitiator::Local
 		);
 
 		assert_eq!(
 			closure_initiator_from_reason(Some(
 				&ClosureReason::CounterpartyInitiatedCooperativeClosure,
 			)),
-			events::ChannelClosureInitiator::Remote
+			types::ChannelClosureInitiator::Remote
 		);
 		assert_eq!(
 			closure_initiator_from_reason(Some(
 				&ClosureReason::CounterpartyCoopClosedUnfundedChannel,
 			)),
-			events::ChannelClosureInitiator::Remote
+			types::ChannelClosureInitiator::Remote
 		);
 
 		assert_eq!(
 			closure_initiator_from_reason(Some(&ClosureReason::CommitmentTxConfirmed)),
-			events::ChannelClosureInitiator::Unknown
+			types::ChannelClosureInitiator::Unknown
 		);
 		assert_eq!(
 			closure_initiator_from_reason(None),
-			events::ChannelClosureInitiator::Unspecified
+			types::ChannelClosureInitiator::Unspecified
 		);
 	}
 
@@ -989,7 +1652,7 @@ mod tests {
 			message: "manual force close".to_string(),
 		});
 
-		assert_eq!(proto.kind, events::ChannelStateChangeReasonKind::HolderForceClosed as i32);
+		assert_eq!(proto.kind, types::ChannelStateChangeReasonKind::HolderForceClosed as i32);
 		assert!(proto.message.contains("manual force close"));
 		match proto.details {
 			Some(Details::HolderForceClosed(details)) => {
@@ -1007,7 +1670,7 @@ mod tests {
 			required_feerate_sat_per_kw: 250,
 		});
 
-		assert_eq!(proto.kind, events::ChannelStateChangeReasonKind::PeerFeerateTooLow as i32);
+		assert_eq!(proto.kind, types::ChannelStateChangeReasonKind::PeerFeerateTooLow as i32);
 		match proto.details {
 			Some(Details::PeerFeerateTooLow(details)) => {
 				assert_eq!(details.peer_feerate_sat_per_kw, 100);
@@ -1020,7 +1683,7 @@ mod tests {
 	#[test]
 	fn test_closure_reason_to_proto_without_details() {
 		let proto = closure_reason_to_proto(&ClosureReason::FundingTimedOut);
-		assert_eq!(proto.kind, events::ChannelStateChangeReasonKind::FundingTimedOut as i32);
+		assert_eq!(proto.kind, types::ChannelStateChangeReasonKind::FundingTimedOut as i32);
 		assert!(proto.details.is_none());
 	}
 
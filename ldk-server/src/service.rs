@@ -8,40 +8,60 @@
 // licenses.
 
 use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::Arc;
 
+use hex::FromHex;
 use http_body_util::{BodyExt, Limited};
 use hyper::body::Incoming;
 use hyper::service::Service;
 use hyper::{HeaderMap, Request, Response};
 use ldk_node::bitcoin::hashes::hmac::{Hmac, HmacEngine};
 use ldk_node::bitcoin::hashes::{sha256, Hash, HashEngine};
+use ldk_node::bitcoin::Network;
 use ldk_node::Node;
+use ldk_server_grpc::api::SubscribeEventsRequest;
 use ldk_server_grpc::endpoints::{
-	BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH, BOLT11_RECEIVE_FOR_HASH_PATH,
-	BOLT11_RECEIVE_PATH, BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH,
-	BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH, BOLT11_SEND_PATH, BOLT11_SEND_UNDERPAYING_PATH,
-	BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH, CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH,
-	DECODE_INVOICE_PATH, DECODE_OFFER_PATH, DISCONNECT_PEER_PATH, EXPORT_PATHFINDING_SCORES_PATH,
-	FORCE_CLOSE_CHANNEL_PATH, GET_BALANCES_PATH, GET_METRICS_PATH, GET_NODE_INFO_PATH,
-	GET_PAYMENT_DETAILS_PATH, GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH,
-	GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH, LIST_CHANNELS_PATH,
-	LIST_FORWARDED_PAYMENTS_PATH, LIST_PAYMENTS_PATH, LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH,
-	ONCHAIN_SEND_PATH, OPEN_CHANNEL_PATH, SIGN_MESSAGE_PATH, SPLICE_IN_PATH, SPLICE_OUT_PATH,
-	SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH, UNIFIED_SEND_PATH, UPDATE_CHANNEL_CONFIG_PATH,
-	VERIFY_SIGNATURE_PATH,
+	APPROVE_ACTION_PATH, BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH,
+	BOLT11_RECEIVE_FOR_HASH_PATH, BOLT11_RECEIVE_PATH,
+	BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH, BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH,
+	BOLT11_SEND_PATH, BOLT11_SEND_UNDERPAYING_PATH, BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH,
+	CLOSE_CHANNEL_PATH, CONNECT_PEER_PATH, CREATE_API_KEY_PATH, DECODE_INVOICE_PATH,
+	DECODE_OFFER_PATH, DISCONNECT_PEER_PATH, ENCRYPT_MNEMONIC_PATH, ESTIMATE_ROUTE_FEE_PATH,
+	EXPORT_PATHFINDING_SCORES_PATH, FIND_PAYMENTS_PATH, FORCE_CLOSE_CHANNEL_PATH,
+	GET_BALANCES_PATH, GET_METRICS_PATH, GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH,
+	GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH, GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH,
+	LIST_API_KEYS_PATH, LIST_AUDIT_LOG_PATH, LIST_CHANNELS_PATH, LIST_CLAIMABLE_PAYMENTS_PATH,
+	LIST_CLOSED_CHANNELS_PATH, LIST_FAILED_WEBHOOK_DELIVERIES_PATH, LIST_FORWARDED_PAYMENTS_PATH,
+	LIST_INVOICES_PATH, LIST_OFFERS_PATH, LIST_ONCHAIN_TRANSACTIONS_PATH, LIST_PAYMENTS_PATH,
+	LIST_PEERS_PATH, LIST_PENDING_ACTIONS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH,
+	OPEN_CHANNEL_PATH, QUERY_ROUTES_PATH, REDELIVER_WEBHOOK_PATH, REJECT_ACTION_PATH,
+	REVOKE_API_KEY_PATH, ROTATE_API_KEY_PATH, SEND_PROBES_PATH, SIGN_MESSAGE_PATH, SPLICE_IN_PATH,
+	SPLICE_OUT_PATH, SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH, UNIFIED_SEND_PATH, UNLOCK_PATH,
+	UPDATE_CHANNEL_CONFIG_PATH, UPDATE_LABEL_PATH, VERIFY_SIGNATURE_PATH,
 };
 use ldk_server_grpc::events::EventEnvelope;
 use ldk_server_grpc::grpc::{
 	decode_grpc_body, encode_grpc_frame, grpc_error_response, grpc_response, parse_grpc_timeout,
 	validate_grpc_request, GrpcBody, GrpcStatus, GRPC_STATUS_DEADLINE_EXCEEDED,
 	GRPC_STATUS_FAILED_PRECONDITION, GRPC_STATUS_INTERNAL, GRPC_STATUS_INVALID_ARGUMENT,
-	GRPC_STATUS_UNAUTHENTICATED, GRPC_STATUS_UNAVAILABLE, GRPC_STATUS_UNIMPLEMENTED,
+	GRPC_STATUS_PERMISSION_DENIED, GRPC_STATUS_RESOURCE_EXHAUSTED, GRPC_STATUS_UNAUTHENTICATED,
+	GRPC_STATUS_UNAVAILABLE, GRPC_STATUS_UNIMPLEMENTED,
 };
+use log::error;
 use prost::Message;
+use serde::Serialize;
 use tokio::sync::{broadcast, mpsc};
 
+use crate::api::api_keys::{
+	handle_create_api_key_request, handle_list_api_keys_request, handle_revoke_api_key_request,
+	handle_rotate_api_key_request,
+};
+use crate::api::approvals::{
+	handle_approve_action_request, handle_list_pending_actions_request,
+	handle_reject_action_request,
+};
 use crate::api::bolt11_claim_for_hash::handle_bolt11_claim_for_hash_request;
 use crate::api::bolt11_fail_for_hash::handle_bolt11_fail_for_hash_request;
 use crate::api::bolt11_receive::handle_bolt11_receive_request;
@@ -60,28 +80,55 @@ use crate::api::decode_offer::handle_decode_offer_request;
 use crate::api::disconnect_peer::handle_disconnect_peer;
 use crate::api::error::{LdkServerError, LdkServerErrorCode};
 use crate::api::export_pathfinding_scores::handle_export_pathfinding_scores_request;
+use crate::api::find_payments::handle_find_payments_request;
 use crate::api::get_balances::handle_get_balances_request;
-use crate::api::get_node_info::handle_get_node_info_request;
+use crate::api::get_node_info::{
+	handle_get_node_info_request, handle_locked_get_node_info_request,
+};
 use crate::api::get_payment_details::handle_get_payment_details_request;
 use crate::api::graph_get_channel::handle_graph_get_channel_request;
 use crate::api::graph_get_node::handle_graph_get_node_request;
 use crate::api::graph_list_channels::handle_graph_list_channels_request;
 use crate::api::graph_list_nodes::handle_graph_list_nodes_request;
+use crate::api::list_audit_log::handle_list_audit_log_request;
 use crate::api::list_channels::handle_list_channels_request;
+use crate::api::list_claimable_payments::handle_list_claimable_payments_request;
+use crate::api::list_closed_channels::handle_list_closed_channels_request;
 use crate::api::list_forwarded_payments::handle_list_forwarded_payments_request;
+use crate::api::list_invoices::handle_list_invoices_request;
+use crate::api::list_offers::handle_list_offers_request;
+use crate::api::list_onchain_transactions::handle_list_onchain_transactions_request;
 use crate::api::list_payments::handle_list_payments_request;
 use crate::api::list_peers::handle_list_peers_request;
+use crate::api::mnemonic::{handle_encrypt_mnemonic_request, handle_unlock_request};
 use crate::api::onchain_receive::handle_onchain_receive_request;
 use crate::api::onchain_send::handle_onchain_send_request;
 use crate::api::open_channel::handle_open_channel;
+use crate::api::query_routes::{handle_estimate_route_fee_request, handle_query_routes_request};
+use crate::api::send_probes::handle_send_probes_request;
 use crate::api::sign_message::handle_sign_message_request;
 use crate::api::splice_channel::{handle_splice_in_request, handle_splice_out_request};
 use crate::api::spontaneous_send::handle_spontaneous_send_request;
 use crate::api::unified_send::handle_unified_send_request;
 use crate::api::update_channel_config::handle_update_channel_config_request;
+use crate::api::update_label::handle_update_label_request;
 use crate::api::verify_signature::handle_verify_signature_request;
+use crate::api::webhooks::{
+	handle_list_failed_webhook_deliveries_request, handle_redeliver_webhook_request,
+};
 use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::util::approvals::ApprovalQueue;
+use crate::util::audit::{is_audited, record_call, record_outcome, sanitize_request};
+use crate::util::auth::{ApiKey, ApiKeyStore, Identity};
+use crate::util::entropy::NodeUnlocker;
+use crate::util::event_filter::EventFilter;
+use crate::util::event_log::EventLog;
 use crate::util::metrics::Metrics;
+use crate::util::nonce_cache::{NonceCache, AUTH_NONCE_LEN};
+use crate::util::probes::ProbeTracker;
+use crate::util::routing::RoutingSnapshotCache;
+use crate::util::spend_limits::SpendTracker;
+use crate::util::webhooks::WebhookDispatcher;
 
 /// gRPC path prefix for the LightningNode service.
 const GRPC_SERVICE_PREFIX: &str = "/api.LightningNode/";
@@ -91,39 +138,151 @@ const MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 
 #[derive(Clone)]
 pub(crate) struct NodeService {
-	context: Arc<Context>,
-	api_key: String,
+	node: Arc<Node>,
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	api_key_store: Arc<ApiKeyStore>,
+	nonce_cache: Arc<NonceCache>,
+	spend_tracker: Arc<SpendTracker>,
+	probe_tracker: Arc<ProbeTracker>,
+	routing_snapshots: Arc<RoutingSnapshotCache>,
+	approval_queue: Option<Arc<ApprovalQueue>>,
+	storage_dir: Option<Arc<Path>>,
+	webhooks: Option<Arc<WebhookDispatcher>>,
 	metrics: Option<Arc<Metrics>>,
 	metrics_auth_header: Option<String>,
-	event_sender: broadcast::Sender<EventEnvelope>,
+	event_log: Arc<EventLog>,
 	shutdown_rx: tokio::sync::watch::Receiver<bool>,
+	/// The identity established for this connection by a client certificate or Unix socket, if
+	/// any.
+	connection_identity: Option<Identity>,
 }
 
 impl NodeService {
 	pub(crate) fn new(
-		node: Arc<Node>, paginated_kv_store: Arc<dyn PaginatedKVStore>, api_key: String,
-		metrics: Option<Arc<Metrics>>, metrics_auth_header: Option<String>,
-		event_sender: broadcast::Sender<EventEnvelope>,
+		node: Arc<Node>, paginated_kv_store: Arc<dyn PaginatedKVStore>,
+		api_key_store: Arc<ApiKeyStore>, nonce_cache: Arc<NonceCache>,
+		spend_tracker: Arc<SpendTracker>, probe_tracker: Arc<ProbeTracker>,
+		routing_snapshots: Arc<RoutingSnapshotCache>, event_log: Arc<EventLog>,
 		shutdown_rx: tokio::sync::watch::Receiver<bool>,
 	) -> Self {
-		let context = Arc::new(Context { node, paginated_kv_store });
-		Self { context, api_key, metrics, metrics_auth_header, event_sender, shutdown_rx }
+		Self {
+			node,
+			paginated_kv_store,
+			api_key_store,
+			nonce_cache,
+			spend_tracker,
+			probe_tracker,
+			routing_snapshots,
+			approval_queue: None,
+			storage_dir: None,
+			webhooks: None,
+			metrics: None,
+			metrics_auth_header: None,
+			event_log,
+			shutdown_rx,
+			connection_identity: None,
+		}
+	}
+
+	/// Serves the metrics endpoint, protected by the given `authorization` header if any.
+	pub(crate) fn with_metrics(
+		mut self, metrics: Option<Arc<Metrics>>, metrics_auth_header: Option<String>,
+	) -> Self {
+		self.metrics = metrics;
+		self.metrics_auth_header = metrics_auth_header;
+		self
+	}
+
+	/// Parks sends above the configured thresholds in the given queue until they are approved.
+	pub(crate) fn with_approval_queue(
+		mut self, approval_queue: Option<Arc<ApprovalQueue>>,
+	) -> Self {
+		self.approval_queue = approval_queue;
+		self
+	}
+
+	/// Allows encrypting the mnemonic in the given storage directory via `EncryptMnemonic`.
+	pub(crate) fn with_storage_dir(mut self, storage_dir: PathBuf) -> Self {
+		self.storage_dir = Some(storage_dir.into());
+		self
+	}
+
+	/// Allows redelivering failed webhook deliveries via `RedeliverWebhook`.
+	pub(crate) fn with_webhooks(mut self, webhooks: Option<Arc<WebhookDispatcher>>) -> Self {
+		self.webhooks = webhooks;
+		self
+	}
+
+	/// Authenticates requests on this connection that aren't signed with an API key as the given
+	/// identity, which was established from the TLS client certificate or the Unix socket the
+	/// connection was accepted on.
+	pub(crate) fn with_connection_identity(mut self, identity: Option<Identity>) -> Self {
+		self.connection_identity = identity;
+		self
+	}
+}
+
+/// Serves the API while the server is locked, i.e. until its encrypted mnemonic was unlocked and
+/// the node can be built.
+///
+/// Only `GetNodeInfo` and `Unlock` are served, all other RPCs fail with `UNAVAILABLE`.
+#[derive(Clone)]
+pub(crate) struct LockedService {
+	unlocker: Arc<NodeUnlocker>,
+	network: Network,
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	api_key_store: Arc<ApiKeyStore>,
+	nonce_cache: Arc<NonceCache>,
+	/// The identity established for this connection by a client certificate or Unix socket, if
+	/// any.
+	connection_identity: Option<Identity>,
+}
+
+impl LockedService {
+	pub(crate) fn new(
+		unlocker: Arc<NodeUnlocker>, network: Network,
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, api_key_store: Arc<ApiKeyStore>,
+		nonce_cache: Arc<NonceCache>,
+	) -> Self {
+		Self {
+			unlocker,
+			network,
+			paginated_kv_store,
+			api_key_store,
+			nonce_cache,
+			connection_identity: None,
+		}
+	}
+
+	/// Authenticates requests on this connection that aren't signed with an API key as the given
+	/// identity, which was established from the TLS client certificate or the Unix socket the
+	/// connection was accepted on.
+	pub(crate) fn with_connection_identity(mut self, identity: Option<Identity>) -> Self {
+		self.connection_identity = identity;
+		self
 	}
 }
 
 // Maximum allowed time difference between client timestamp and server time (1 minute)
 const AUTH_TIMESTAMP_TOLERANCE_SECS: u64 = 60;
 
-fn compute_auth_hmac(api_key: &str, timestamp: u64, body: &[u8]) -> Hmac<sha256::Hash> {
+fn compute_auth_hmac(
+	api_key: &str, timestamp: u64, nonce: &[u8; AUTH_NONCE_LEN], body: &[u8],
+) -> Hmac<sha256::Hash> {
 	let mut hmac_engine: HmacEngine<sha256::Hash> = HmacEngine::new(api_key.as_bytes());
 	hmac_engine.input(&timestamp.to_be_bytes());
+	hmac_engine.input(nonce);
 	hmac_engine.input(body);
 	Hmac::<sha256::Hash>::from_engine(hmac_engine)
 }
 
-/// Validates HMAC authentication from request headers.
-/// The signature covers the timestamp and raw gRPC request body bytes.
-fn validate_auth<B>(req: &Request<B>, api_key: &str, body: &[u8]) -> Result<(), LdkServerError> {
+/// Validates HMAC authentication from request headers, returning the API key the request was
+/// signed with.
+/// The signature covers the timestamp, a random nonce and raw gRPC request body bytes. Requests
+/// reusing the nonce of a previously authenticated request are rejected as replays.
+fn validate_auth<B>(
+	req: &Request<B>, api_keys: &[ApiKey], nonce_cache: &NonceCache, body: &[u8],
+) -> Result<ApiKey, LdkServerError> {
 	let auth_err = |msg: &str| LdkServerError::new(LdkServerErrorCode::AuthError, msg.to_string());
 
 	let auth_header = req
@@ -135,11 +294,18 @@ fn validate_auth<B>(req: &Request<B>, api_key: &str, body: &[u8]) -> Result<(),
 	let auth_data =
 		auth_header.strip_prefix("HMAC ").ok_or_else(|| auth_err("Invalid x-auth format"))?;
 
-	let (timestamp_str, provided_hmac_hex) =
-		auth_data.split_once(':').ok_or_else(|| auth_err("Invalid x-auth format"))?;
+	let mut parts = auth_data.splitn(3, ':');
+	let (timestamp_str, nonce_hex, provided_hmac_hex) =
+		match (parts.next(), parts.next(), parts.next()) {
+			(Some(timestamp), Some(nonce), Some(hmac)) => (timestamp, nonce, hmac),
+			_ => return Err(auth_err("Invalid x-auth format")),
+		};
 
 	let timestamp = timestamp_str.parse::<u64>().map_err(|_| auth_err("Invalid timestamp"))?;
 
+	let nonce = <[u8; AUTH_NONCE_LEN]>::from_hex(nonce_hex)
+		.map_err(|_| auth_err("Invalid nonce in x-auth"))?;
+
 	let now = std::time::SystemTime::now()
 		.duration_since(std::time::UNIX_EPOCH)
 		.map_err(|_| auth_err("System time error"))?
@@ -149,22 +315,78 @@ fn validate_auth<B>(req: &Request<B>, api_key: &str, body: &[u8]) -> Result<(),
 		return Err(auth_err("Request timestamp expired"));
 	}
 
-	let expected_hmac = compute_auth_hmac(api_key, timestamp, body);
-
 	let provided_hmac = provided_hmac_hex
 		.parse::<Hmac<sha256::Hash>>()
 		.map_err(|_| auth_err("Invalid HMAC in x-auth"))?;
 
-	if expected_hmac != provided_hmac {
-		return Err(auth_err("Invalid credentials"));
+	let api_key = api_keys
+		.iter()
+		.find(|api_key| compute_auth_hmac(&api_key.key, timestamp, &nonce, body) == provided_hmac)
+		.cloned()
+		.ok_or_else(|| auth_err("Invalid credentials"))?;
+
+	// Only record nonces of authenticated requests, so unauthenticated callers can't fill the cache.
+	let min_valid_timestamp = now.saturating_sub(AUTH_TIMESTAMP_TOLERANCE_SECS);
+	if !nonce_cache.insert(timestamp, nonce, min_valid_timestamp) {
+		return Err(auth_err("Request was already processed"));
 	}
 
-	Ok(())
+	Ok(api_key)
+}
+
+/// Authenticates the caller of a request to `method` and checks it is permitted to call it.
+///
+/// Returns the caller's identity along with the hex-encoded API key it was authenticated with, or
+/// `None` if it was authenticated with the connection's identity.
+fn authenticate_caller<B>(
+	req: &Request<B>, method: &str, connection_identity: Option<Identity>,
+	api_key_store: &ApiKeyStore, nonce_cache: &NonceCache, body: &[u8],
+) -> Result<(Identity, Option<String>), GrpcStatus> {
+	// Requests signed with an API key are authenticated as that key, even on connections with a
+	// known client certificate or on the Unix socket.
+	let uses_api_key = connection_identity.is_none() || req.headers().contains_key("x-auth");
+	let (caller, api_key) = match connection_identity {
+		Some(identity) if !uses_api_key => (identity, None),
+		_ => {
+			let api_key = validate_auth(req, &api_key_store.active_keys(), nonce_cache, body)
+				.map_err(ldk_error_to_grpc_status)?;
+			api_key_store.record_use(&api_key.name);
+			(api_key.identity(), Some(api_key.key))
+		},
+	};
+
+	if !caller.scope.permits(method) {
+		return Err(GrpcStatus::new(
+			GRPC_STATUS_PERMISSION_DENIED,
+			format!("'{}' is not permitted to call {method}", caller.name),
+		));
+	}
+	Ok((caller, api_key))
 }
 
 pub(crate) struct Context {
 	pub(crate) node: Arc<Node>,
 	pub(crate) paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	pub(crate) api_key_store: Arc<ApiKeyStore>,
+	pub(crate) spend_tracker: Arc<SpendTracker>,
+	/// The probes sent via `SendProbes`, whose outcomes are observed across requests.
+	pub(crate) probe_tracker: Arc<ProbeTracker>,
+	/// The cached network graph and pathfinding scores that routes are looked up against.
+	pub(crate) routing_snapshots: Arc<RoutingSnapshotCache>,
+	/// The queue sends above the approval thresholds are parked in, if approvals are configured.
+	pub(crate) approval_queue: Option<Arc<ApprovalQueue>>,
+	/// The storage directory holding the mnemonic, if it may be encrypted via the API.
+	pub(crate) storage_dir: Option<Arc<Path>>,
+	/// The dispatcher delivering events to webhooks, if webhooks are configured.
+	pub(crate) webhooks: Option<Arc<WebhookDispatcher>>,
+	/// The identity of the authenticated caller of the current request.
+	pub(crate) caller: Identity,
+	/// Whether the caller authenticated with an API key rather than the connection's identity.
+	pub(crate) caller_uses_api_key: bool,
+	/// The RPC method of the current request, e.g. `OnchainSend`.
+	pub(crate) method: String,
+	/// Whether the current request executes an approved action, which is never parked again.
+	pub(crate) is_approved: bool,
 }
 
 impl Service<Request<Incoming>> for NodeService {
@@ -218,7 +440,6 @@ impl Service<Request<Incoming>> for NodeService {
 			return Box::pin(async move { Ok(grpc_error_response(status)) });
 		}
 
-		let context = Arc::clone(&self.context);
 		let path = req.uri().path().to_string();
 		let deadline = match req.headers().get("grpc-timeout") {
 			Some(value) => {
@@ -252,8 +473,18 @@ impl Service<Request<Incoming>> for NodeService {
 		};
 
 		let is_streaming = method == SUBSCRIBE_EVENTS_PATH;
-		let api_key = self.api_key.clone();
-		let event_sender = self.event_sender.clone();
+		let node = Arc::clone(&self.node);
+		let paginated_kv_store = Arc::clone(&self.paginated_kv_store);
+		let api_key_store = Arc::clone(&self.api_key_store);
+		let nonce_cache = Arc::clone(&self.nonce_cache);
+		let spend_tracker = Arc::clone(&self.spend_tracker);
+		let probe_tracker = Arc::clone(&self.probe_tracker);
+		let routing_snapshots = Arc::clone(&self.routing_snapshots);
+		let approval_queue = self.approval_queue.clone();
+		let storage_dir = self.storage_dir.clone();
+		let webhooks = self.webhooks.clone();
+		let connection_identity = self.connection_identity.clone();
+		let event_log = Arc::clone(&self.event_log);
 		let shutdown_rx = self.shutdown_rx.clone();
 		let (request_parts, request_body) = req.into_parts();
 		let future: Self::Future = Box::pin(async move {
@@ -267,10 +498,33 @@ impl Service<Request<Incoming>> for NodeService {
 			};
 
 			let auth_req = Request::from_parts(request_parts, ());
-			if let Err(e) = validate_auth(&auth_req, &api_key, &body_bytes) {
-				let status = ldk_error_to_grpc_status(e);
-				return Ok(grpc_error_response(status));
-			}
+			let (caller, api_key) = match authenticate_caller(
+				&auth_req,
+				&method,
+				connection_identity,
+				&api_key_store,
+				&nonce_cache,
+				&body_bytes,
+			) {
+				Ok(authenticated) => authenticated,
+				Err(status) => return Ok(grpc_error_response(status)),
+			};
+
+			let context = Arc::new(Context {
+				node,
+				paginated_kv_store,
+				api_key_store: Arc::clone(&api_key_store),
+				spend_tracker,
+				probe_tracker,
+				routing_snapshots,
+				approval_queue,
+				storage_dir,
+				webhooks,
+				caller,
+				caller_uses_api_key: api_key.is_some(),
+				method: method.clone(),
+				is_approved: false,
+			});
 
 			match method.as_str() {
 				GET_NODE_INFO_PATH => {
@@ -285,6 +539,10 @@ impl Service<Request<Incoming>> for NodeService {
 				ONCHAIN_SEND_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_onchain_send_request).await
 				},
+				LIST_ONCHAIN_TRANSACTIONS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_onchain_transactions_request)
+						.await
+				},
 				BOLT11_RECEIVE_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_bolt11_receive_request).await
 				},
@@ -300,6 +558,10 @@ impl Service<Request<Incoming>> for NodeService {
 					handle_grpc_unary(context, body_bytes, handle_bolt11_fail_for_hash_request)
 						.await
 				},
+				LIST_CLAIMABLE_PAYMENTS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_claimable_payments_request)
+						.await
+				},
 				BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH => {
 					handle_grpc_unary(
 						context,
@@ -347,6 +609,10 @@ impl Service<Request<Incoming>> for NodeService {
 				LIST_CHANNELS_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_list_channels_request).await
 				},
+				LIST_CLOSED_CHANNELS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_closed_channels_request)
+						.await
+				},
 				UPDATE_CHANNEL_CONFIG_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_update_channel_config_request)
 						.await
@@ -357,6 +623,18 @@ impl Service<Request<Incoming>> for NodeService {
 				LIST_PAYMENTS_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_list_payments_request).await
 				},
+				FIND_PAYMENTS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_find_payments_request).await
+				},
+				LIST_INVOICES_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_invoices_request).await
+				},
+				LIST_OFFERS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_offers_request).await
+				},
+				UPDATE_LABEL_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_update_label_request).await
+				},
 				LIST_FORWARDED_PAYMENTS_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_list_forwarded_payments_request)
 						.await
@@ -386,6 +664,15 @@ impl Service<Request<Incoming>> for NodeService {
 					handle_grpc_unary(context, body_bytes, handle_export_pathfinding_scores_request)
 						.await
 				},
+				ESTIMATE_ROUTE_FEE_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_estimate_route_fee_request).await
+				},
+				QUERY_ROUTES_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_query_routes_request).await
+				},
+				SEND_PROBES_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_send_probes_request).await
+				},
 				GRAPH_LIST_CHANNELS_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_graph_list_channels_request).await
 				},
@@ -404,46 +691,84 @@ impl Service<Request<Incoming>> for NodeService {
 				DECODE_OFFER_PATH => {
 					handle_grpc_unary(context, body_bytes, handle_decode_offer_request).await
 				},
+				CREATE_API_KEY_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_create_api_key_request).await
+				},
+				LIST_API_KEYS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_api_keys_request).await
+				},
+				ROTATE_API_KEY_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_rotate_api_key_request).await
+				},
+				LIST_AUDIT_LOG_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_audit_log_request).await
+				},
+				REVOKE_API_KEY_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_revoke_api_key_request).await
+				},
+				APPROVE_ACTION_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_approve_action_request).await
+				},
+				REJECT_ACTION_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_reject_action_request).await
+				},
+				LIST_PENDING_ACTIONS_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_list_pending_actions_request)
+						.await
+				},
+				ENCRYPT_MNEMONIC_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_encrypt_mnemonic_request).await
+				},
+				LIST_FAILED_WEBHOOK_DELIVERIES_PATH => {
+					handle_grpc_unary(
+						context,
+						body_bytes,
+						handle_list_failed_webhook_deliveries_request,
+					)
+					.await
+				},
+				REDELIVER_WEBHOOK_PATH => {
+					handle_grpc_unary(context, body_bytes, handle_redeliver_webhook_request).await
+				},
+				UNLOCK_PATH => {
+					let status = GrpcStatus::new(
+						GRPC_STATUS_FAILED_PRECONDITION,
+						"Node is already unlocked",
+					);
+					Ok(grpc_error_response(status))
+				},
 				SUBSCRIBE_EVENTS_PATH => {
+					let request = decode_grpc_body(&body_bytes).and_then(|b| {
+						SubscribeEventsRequest::decode(b).map_err(|_| {
+							GrpcStatus::new(GRPC_STATUS_INVALID_ARGUMENT, "Malformed request")
+						})
+					});
+					let request = match request {
+						Ok(request) => request,
+						Err(status) => return Ok(grpc_error_response(status)),
+					};
+					let filter = match EventFilter::new(&request) {
+						Ok(filter) => filter,
+						Err(e) => return Ok(grpc_error_response(ldk_error_to_grpc_status(e))),
+					};
 					let mut shutdown_rx = shutdown_rx;
-					let mut rx = event_sender.subscribe();
-					let (tx, mpsc_rx) = mpsc::channel::<Result<bytes::Bytes, GrpcStatus>>(64);
+					let (tx, mpsc_rx) = mpsc::channel(64);
 					tokio::spawn(async move {
-						loop {
-							tokio::select! {
-								biased;
-								_ = shutdown_rx.changed() => {
-									let _ = tx
-										.send(Err(GrpcStatus::new(
-											GRPC_STATUS_UNAVAILABLE,
-											"server shutting down",
-										)))
-										.await;
-									break;
-								},
-								result = rx.recv() => {
-									match result {
-										Ok(event) => {
-											let frame = encode_grpc_frame(&event.encode_to_vec());
-											if tx.send(Ok(frame)).await.is_err() {
-												break; // client disconnected
-											}
-										},
-										Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => {
-											continue; // skip missed events, keep streaming
-										},
-										Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-											let _ = tx
-												.send(Err(GrpcStatus::new(
-													GRPC_STATUS_UNAVAILABLE,
-													"server shutting down",
-											)))
-											.await;
-											break;
-										},
-									}
-								}
-							}
+						// The stream ends once the key it was opened with is revoked or rotated
+						// out, even if a key with the same name is still accepted.
+						let is_revoked =
+							|| api_key.as_deref().is_some_and(|key| !api_key_store.is_active(key));
+						let result = stream_events(
+							&event_log,
+							request.start_after_sequence,
+							&filter,
+							&mut shutdown_rx,
+							&tx,
+							&is_revoked,
+						)
+						.await;
+						if let Err(Some(status)) = result {
+							let _ = tx.send(Err(status)).await;
 						}
 					});
 					Ok(grpc_response(GrpcBody::Stream { rx: mpsc_rx, done: false }))
@@ -473,13 +798,106 @@ impl Service<Request<Incoming>> for NodeService {
 	}
 }
 
+impl Service<Request<Incoming>> for LockedService {
+	type Response = Response<GrpcBody>;
+	type Error = hyper::Error;
+	type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+	fn call(&self, req: Request<Incoming>) -> Self::Future {
+		if let Err(status) = validate_grpc_request(&req) {
+			return Box::pin(async move { Ok(grpc_error_response(status)) });
+		}
+
+		let path = req.uri().path().to_string();
+		let method = match path.strip_prefix(GRPC_SERVICE_PREFIX) {
+			Some(m) => m.to_string(),
+			None => {
+				let status =
+					GrpcStatus::new(GRPC_STATUS_UNIMPLEMENTED, format!("Unknown path: {path}"));
+				return Box::pin(async move { Ok(grpc_error_response(status)) });
+			},
+		};
+
+		let service = self.clone();
+		let (request_parts, request_body) = req.into_parts();
+		Box::pin(async move {
+			let content_length = match request_content_length(&request_parts.headers) {
+				Ok(content_length) => content_length,
+				Err(status) => return Ok(grpc_error_response(status)),
+			};
+			let body_bytes = match read_request_body(request_body, content_length).await {
+				Ok(bytes) => bytes,
+				Err(status) => return Ok(grpc_error_response(status)),
+			};
+
+			let auth_req = Request::from_parts(request_parts, ());
+			let (caller, _) = match authenticate_caller(
+				&auth_req,
+				&method,
+				service.connection_identity,
+				&service.api_key_store,
+				&service.nonce_cache,
+				&body_bytes,
+			) {
+				Ok(authenticated) => authenticated,
+				Err(status) => return Ok(grpc_error_response(status)),
+			};
+
+			let paginated_kv_store = service.paginated_kv_store;
+			match method.as_str() {
+				GET_NODE_INFO_PATH => {
+					let network = service.network;
+					handle_unary(paginated_kv_store, &method, &caller.name, body_bytes, |request| {
+						handle_locked_get_node_info_request(network, request)
+					})
+					.await
+				},
+				UNLOCK_PATH => {
+					let unlocker = service.unlocker;
+					handle_unary(paginated_kv_store, &method, &caller.name, body_bytes, |request| {
+						handle_unlock_request(unlocker, request)
+					})
+					.await
+				},
+				_ => {
+					let status = GrpcStatus::new(
+						GRPC_STATUS_UNAVAILABLE,
+						"Node is locked, unlock it via the Unlock RPC first",
+					);
+					Ok(grpc_error_response(status))
+				},
+			}
+		})
+	}
+}
+
 async fn handle_grpc_unary<
-	T: Message + Default,
+	T: Message + Default + Clone + Serialize + 'static,
 	R: Message,
 	Fut: Future<Output = Result<R, LdkServerError>> + Send,
 	F: Fn(Arc<Context>, T) -> Fut + Send,
 >(
 	context: Arc<Context>, body_bytes: bytes::Bytes, handler: F,
+) -> Result<Response<GrpcBody>, hyper::Error> {
+	let paginated_kv_store = Arc::clone(&context.paginated_kv_store);
+	let method = context.method.clone();
+	let caller = context.caller.name.clone();
+	handle_unary(paginated_kv_store, &method, &caller, body_bytes, |request| {
+		handler(context, request)
+	})
+	.await
+}
+
+/// Decodes a unary request and handles it, recording calls to state-changing methods in the audit
+/// log.
+async fn handle_unary<
+	T: Message + Default + Clone + Serialize + 'static,
+	R: Message,
+	Fut: Future<Output = Result<R, LdkServerError>> + Send,
+	F: FnOnce(T) -> Fut + Send,
+>(
+	paginated_kv_store: Arc<dyn PaginatedKVStore>, method: &str, caller: &str,
+	body_bytes: bytes::Bytes, handler: F,
 ) -> Result<Response<GrpcBody>, hyper::Error> {
 	// Decode gRPC framing then protobuf
 	let req_msg = decode_grpc_body(&body_bytes)
@@ -498,8 +916,29 @@ async fn handle_grpc_unary<
 	// future at yield points when a stream is reset.
 	tokio::task::yield_now().await;
 
+	// Record state-changing calls before handling them, so no call goes unaudited if the audit
+	// log can't be written.
+	let audit_entry = if is_audited(method) {
+		let request = sanitize_request(&req_msg);
+		match record_call(&*paginated_kv_store, method, caller, request) {
+			Ok(entry) => Some(entry),
+			Err(e) => return Ok(grpc_error_response(ldk_error_to_grpc_status(e))),
+		}
+	} else {
+		None
+	};
+
 	// Call handler
-	match handler(context, req_msg).await {
+	let result = handler(req_msg).await;
+
+	if let Some(entry) = audit_entry {
+		let method = entry.method.clone();
+		if let Err(e) = record_outcome(&*paginated_kv_store, entry, result.as_ref().err()) {
+			error!("Failed to record outcome of {method} call in audit log: {}", e.message);
+		}
+	}
+
+	match result {
 		Ok(response) => {
 			let encoded = encode_grpc_frame(&response.encode_to_vec());
 			Ok(grpc_response(GrpcBody::Unary { data: Some(encoded), trailers_sent: false }))
@@ -555,6 +994,99 @@ async fn read_request_body(
 	Ok(bytes)
 }
 
+type EventStreamSender = mpsc::Sender<Result<bytes::Bytes, GrpcStatus>>;
+
+/// Streams the events matching `filter` to a subscriber until the server shuts down or the
+/// subscriber disconnects or has its API key revoked.
+///
+/// If `start_after_sequence` is set, the persisted events after it are replayed before going live,
+/// failing the stream if some of them were already pruned. Events missed by a subscriber that lags
+/// behind are read back from the event log, so none are skipped.
+///
+/// Returns the status to end the stream with, or `None` if the subscriber disconnected.
+async fn stream_events(
+	event_log: &EventLog, start_after_sequence: Option<u64>, filter: &EventFilter,
+	shutdown_rx: &mut tokio::sync::watch::Receiver<bool>, tx: &EventStreamSender,
+	is_revoked: &impl Fn() -> bool,
+) -> Result<(), Option<GrpcStatus>> {
+	let shutting_down = || Some(GrpcStatus::new(GRPC_STATUS_UNAVAILABLE, "server shutting down"));
+	let (mut rx, live_sequence) = event_log.subscribe();
+	// The sequence number of the next event to send. `0` replays all events that weren't pruned.
+	let mut next_sequence = match start_after_sequence {
+		Some(0) => event_log.first_sequence(),
+		Some(sequence) => sequence.saturating_add(1),
+		None => live_sequence,
+	};
+	replay_events(event_log, &mut next_sequence, live_sequence, filter, tx, is_revoked).await?;
+	loop {
+		tokio::select! {
+			biased;
+			_ = shutdown_rx.changed() => return Err(shutting_down()),
+			result = rx.recv() => match result {
+				Ok(event) => {
+					if event.sequence < next_sequence {
+						continue; // the subscriber asked to start after this event
+					}
+					let to = event.sequence;
+					replay_events(event_log, &mut next_sequence, to, filter, tx, is_revoked).await?;
+					send_event(&event, filter, tx, is_revoked).await?;
+					next_sequence = event.sequence + 1;
+				},
+				Err(broadcast::error::RecvError::Lagged(_)) => {
+					continue; // missed events are replayed before the next one is sent
+				},
+				Err(broadcast::error::RecvError::Closed) => return Err(shutting_down()),
+			},
+		}
+	}
+}
+
+/// Sends the persisted events from `next_sequence` up to `to`, advancing `next_sequence` past them.
+///
+/// Fails if some of the events were pruned before they could be sent.
+async fn replay_events(
+	event_log: &EventLog, next_sequence: &mut u64, to: u64, filter: &EventFilter,
+	tx: &EventStreamSender, is_revoked: &impl Fn() -> bool,
+) -> Result<(), Option<GrpcStatus>> {
+	while *next_sequence < to {
+		let (events, continue_from) = event_log
+			.read_range(*next_sequence, to)
+			.map_err(|e| Some(ldk_error_to_grpc_status(e)))?;
+		// Checked after reading, as events are only removed after the first sequence is raised.
+		let first_sequence = event_log.first_sequence();
+		if *next_sequence < first_sequence {
+			return Err(Some(GrpcStatus::new(
+				GRPC_STATUS_FAILED_PRECONDITION,
+				format!(
+					"Event {} was pruned, the oldest event that can be replayed is {first_sequence}",
+					*next_sequence
+				),
+			)));
+		}
+		for event in &events {
+			send_event(event, filter, tx, is_revoked).await?;
+		}
+		*next_sequence = continue_from;
+	}
+	Ok(())
+}
+
+/// Sends `event` to the subscriber if it matches `filter`.
+async fn send_event(
+	event: &EventEnvelope, filter: &EventFilter, tx: &EventStreamSender,
+	is_revoked: &impl Fn() -> bool,
+) -> Result<(), Option<GrpcStatus>> {
+	if !filter.matches(event) {
+		return Ok(());
+	}
+	if is_revoked() {
+		return Err(Some(GrpcStatus::new(GRPC_STATUS_UNAUTHENTICATED, "API key was revoked")));
+	}
+	let frame = encode_grpc_frame(&event.encode_to_vec());
+	// The subscriber disconnected if its stream was dropped.
+	tx.send(Ok(frame)).await.map_err(|_| None)
+}
+
 /// Map an `LdkServerError` to a `GrpcStatus`.
 pub(crate) fn ldk_error_to_grpc_status(e: LdkServerError) -> GrpcStatus {
 	let code = match e.error_code {
@@ -562,16 +1094,28 @@ pub(crate) fn ldk_error_to_grpc_status(e: LdkServerError) -> GrpcStatus {
 		LdkServerErrorCode::AuthError => GRPC_STATUS_UNAUTHENTICATED,
 		LdkServerErrorCode::LightningError => GRPC_STATUS_FAILED_PRECONDITION,
 		LdkServerErrorCode::InternalServerError => GRPC_STATUS_INTERNAL,
+		LdkServerErrorCode::PermissionDeniedError => GRPC_STATUS_PERMISSION_DENIED,
+		LdkServerErrorCode::SpendLimitExceededError => GRPC_STATUS_RESOURCE_EXHAUSTED,
 	};
 	GrpcStatus { code, message: e.message }
 }
 
 #[cfg(test)]
 mod tests {
+	use hex::DisplayHex;
+
 	use super::*;
+	use crate::util::auth::Scope;
+
+	fn admin_keys(key: &str) -> Vec<ApiKey> {
+		vec![ApiKey { name: "admin".to_string(), key: key.to_string(), scope: Scope::Admin }]
+	}
 
-	fn compute_hmac(api_key: &str, timestamp: u64, body: &[u8]) -> String {
-		compute_auth_hmac(api_key, timestamp, body).to_string()
+	const TEST_NONCE: [u8; AUTH_NONCE_LEN] = [7; AUTH_NONCE_LEN];
+
+	fn auth_header(api_key: &str, timestamp: u64, body: &[u8]) -> String {
+		let hmac = compute_auth_hmac(api_key, timestamp, &TEST_NONCE, body);
+		format!("HMAC {timestamp}:{}:{hmac}", TEST_NONCE.as_hex())
 	}
 
 	fn create_test_request(auth_header: Option<String>) -> Request<()> {
@@ -589,17 +1133,16 @@ mod tests {
 		let body = b"test body";
 		let timestamp =
 			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
-		let hmac = compute_hmac(api_key, timestamp, body);
-		let auth_header = format!("HMAC {timestamp}:{hmac}");
-		let req = create_test_request(Some(auth_header));
+		let req = create_test_request(Some(auth_header(api_key, timestamp, body)));
 
-		assert!(validate_auth(&req, api_key, body).is_ok());
+		assert!(validate_auth(&req, &admin_keys(api_key), &NonceCache::new(16), body).is_ok());
 	}
 
 	#[test]
 	fn test_validate_auth_missing_header() {
 		let req = create_test_request(None);
-		let result = validate_auth(&req, "test_key", b"test body");
+		let result =
+			validate_auth(&req, &admin_keys("test_key"), &NonceCache::new(16), b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -607,7 +1150,8 @@ mod tests {
 	#[test]
 	fn test_validate_auth_invalid_format() {
 		let req = create_test_request(Some("12345:deadbeef".to_string()));
-		let result = validate_auth(&req, "test_key", b"test body");
+		let result =
+			validate_auth(&req, &admin_keys("test_key"), &NonceCache::new(16), b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -616,10 +1160,10 @@ mod tests {
 	fn test_validate_auth_wrong_key() {
 		let timestamp =
 			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
-		let hmac = compute_hmac("wrong_key", timestamp, b"test body");
-		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
+		let req = create_test_request(Some(auth_header("wrong_key", timestamp, b"test body")));
 
-		let result = validate_auth(&req, "test_api_key", b"test body");
+		let result =
+			validate_auth(&req, &admin_keys("test_api_key"), &NonceCache::new(16), b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -628,10 +1172,14 @@ mod tests {
 	fn test_validate_auth_wrong_body() {
 		let timestamp =
 			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
-		let hmac = compute_hmac("test_api_key", timestamp, b"signed body");
-		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
+		let req = create_test_request(Some(auth_header("test_api_key", timestamp, b"signed body")));
 
-		let result = validate_auth(&req, "test_api_key", b"modified body");
+		let result = validate_auth(
+			&req,
+			&admin_keys("test_api_key"),
+			&NonceCache::new(16),
+			b"modified body",
+		);
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
@@ -641,14 +1189,74 @@ mod tests {
 		let timestamp =
 			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs()
 				- 600;
-		let hmac = compute_hmac("test_api_key", timestamp, b"test body");
-		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
+		let req = create_test_request(Some(auth_header("test_api_key", timestamp, b"test body")));
 
-		let result = validate_auth(&req, "test_api_key", b"test body");
+		let result =
+			validate_auth(&req, &admin_keys("test_api_key"), &NonceCache::new(16), b"test body");
 		assert!(result.is_err());
 		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
 	}
 
+	#[test]
+	fn test_validate_auth_rejects_replay() {
+		let timestamp =
+			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+		let req = create_test_request(Some(auth_header("test_api_key", timestamp, b"test body")));
+		let nonce_cache = NonceCache::new(16);
+
+		assert!(
+			validate_auth(&req, &admin_keys("test_api_key"), &nonce_cache, b"test body").is_ok()
+		);
+		let result = validate_auth(&req, &admin_keys("test_api_key"), &nonce_cache, b"test body");
+		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
+	}
+
+	#[test]
+	fn test_validate_auth_requires_nonce() {
+		let timestamp =
+			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+		let mut hmac_engine: HmacEngine<sha256::Hash> = HmacEngine::new(b"test_api_key");
+		hmac_engine.input(&timestamp.to_be_bytes());
+		hmac_engine.input(b"test body");
+		let hmac = Hmac::<sha256::Hash>::from_engine(hmac_engine);
+		let req = create_test_request(Some(format!("HMAC {timestamp}:{hmac}")));
+
+		let result =
+			validate_auth(&req, &admin_keys("test_api_key"), &NonceCache::new(16), b"test body");
+		assert_eq!(result.unwrap_err().error_code, LdkServerErrorCode::AuthError);
+	}
+
+	#[test]
+	fn test_validate_auth_identifies_signing_key() {
+		let api_keys = vec![
+			ApiKey { name: "admin".to_string(), key: "admin_key".to_string(), scope: Scope::Admin },
+			ApiKey {
+				name: "pos".to_string(),
+				key: "pos_key".to_string(),
+				scope: Scope::InvoiceOnly,
+			},
+		];
+		let timestamp =
+			std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+		let req = create_test_request(Some(auth_header("pos_key", timestamp, b"test body")));
+
+		let api_key = validate_auth(&req, &api_keys, &NonceCache::new(16), b"test body").unwrap();
+		assert_eq!(api_key.key, "pos_key");
+		assert_eq!(
+			api_key.identity(),
+			Identity { name: "pos".to_string(), scope: Scope::InvoiceOnly }
+		);
+	}
+
+	#[test]
+	fn test_permission_denied_maps_to_grpc_status() {
+		let status = ldk_error_to_grpc_status(LdkServerError::new(
+			LdkServerErrorCode::PermissionDeniedError,
+			"denied",
+		));
+		assert_eq!(status.code, GRPC_STATUS_PERMISSION_DENIED);
+	}
+
 	#[test]
 	fn test_request_content_length_missing() {
 		let headers = HeaderMap::new();
@@ -0,0 +1,544 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::{Arc, Mutex};
+
+use hex::DisplayHex;
+use ldk_server_grpc::api::ListPendingActionsResponse;
+use ldk_server_grpc::events::{event_envelope, PendingActionCreated};
+use ldk_server_grpc::types::{ApiKeyScope, PageToken, PendingAction, PendingActionStatus};
+use prost::Message;
+use serde::Serialize;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{
+	InternalServerError, InvalidRequestError, PermissionDeniedError,
+};
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, PENDING_ACTIONS_PERSISTENCE_PRIMARY_NAMESPACE,
+	PENDING_ACTIONS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::audit::sanitize_request;
+use crate::util::auth::{Identity, Scope};
+use crate::util::current_time_secs;
+use crate::util::event_log::EventLog;
+use crate::util::spend_limits::SpendKind;
+
+/// For how long a pending action can be approved if no expiry is configured.
+pub(crate) const DEFAULT_APPROVAL_EXPIRY_SECS: u64 = 24 * 60 * 60;
+
+/// Determines which sends are parked until they are approved.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct ApprovalConfig {
+	/// Lightning payments above this amount require approval.
+	pub(crate) lightning_threshold_msat: Option<u64>,
+	/// On-chain payments above this amount require approval.
+	pub(crate) onchain_threshold_msat: Option<u64>,
+	/// Whether an action must be approved by a different identity than the one that requested it.
+	pub(crate) require_distinct_approver: bool,
+	/// For how long an action can be approved after it was requested.
+	pub(crate) expiry_secs: u64,
+}
+
+impl ApprovalConfig {
+	fn threshold_msat(&self, kind: SpendKind) -> Option<u64> {
+		match kind {
+			SpendKind::Lightning => self.lightning_threshold_msat,
+			SpendKind::Onchain => self.onchain_threshold_msat,
+		}
+	}
+}
+
+/// The persisted form of a pending action.
+#[derive(Clone, PartialEq, prost::Message)]
+struct PendingActionRecord {
+	#[prost(message, optional, tag = "1")]
+	action: Option<PendingAction>,
+	/// The encoded request, which is executed once the action is approved.
+	#[prost(bytes = "vec", tag = "2")]
+	request: Vec<u8>,
+	/// The scope of the requester at the time of the request, which the request is executed with.
+	#[prost(enumeration = "ApiKeyScope", tag = "3")]
+	requester_scope: i32,
+	#[prost(uint64, optional, tag = "4")]
+	requester_max_send_amount_msat: Option<u64>,
+	/// Whether the requester authenticated with an API key, whose current scope applies once the
+	/// action is approved.
+	#[prost(bool, tag = "5")]
+	requester_uses_api_key: bool,
+}
+
+/// An action that was marked as approved and is to be executed on behalf of its requester.
+pub(crate) struct ApprovedAction {
+	pub(crate) action: PendingAction,
+	/// The encoded request of the action.
+	pub(crate) request: Vec<u8>,
+	pub(crate) requester: Identity,
+	/// Whether the requester authenticated with an API key rather than the connection's identity.
+	pub(crate) requester_uses_api_key: bool,
+}
+
+/// Holds sends above the configured thresholds until they are approved or rejected.
+///
+/// Pending actions are persisted in the [`PaginatedKVStore`], so they survive restarts.
+pub(crate) struct ApprovalQueue {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	config: ApprovalConfig,
+	event_log: Arc<EventLog>,
+	/// Held while the status of an action changes, so that it is only approved or rejected once.
+	decision_lock: Mutex<()>,
+}
+
+impl ApprovalQueue {
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, config: ApprovalConfig,
+		event_log: Arc<EventLog>,
+	) -> Self {
+		Self { paginated_kv_store, config, event_log, decision_lock: Mutex::new(()) }
+	}
+
+	/// Returns whether a send of `amount_msat`, which may be made as any of `kinds`, requires
+	/// approval.
+	///
+	/// `amount_msat` is `None` if the amount cannot be determined before the payment is sent, in
+	/// which case approval is required if any threshold applies.
+	fn requires_approval(&self, kinds: &[SpendKind], amount_msat: Option<u64>) -> bool {
+		kinds.iter().filter_map(|kind| self.config.threshold_msat(*kind)).any(|threshold_msat| {
+			amount_msat.is_none_or(|amount_msat| amount_msat > threshold_msat)
+		})
+	}
+
+	/// Parks the `request` to `method` if it requires approval, returning the created action.
+	///
+	/// `uses_api_key` is whether the caller authenticated with an API key rather than the
+	/// connection's identity.
+	///
+	/// Returns `None` if the request is below the thresholds and can be executed right away.
+	pub(crate) fn submit<T: Message + Serialize>(
+		&self, caller: &Identity, uses_api_key: bool, method: &str, kinds: &[SpendKind],
+		amount_msat: Option<u64>, request: &T,
+	) -> Result<Option<PendingAction>, LdkServerError> {
+		if !self.requires_approval(kinds, amount_msat) {
+			return Ok(None);
+		}
+
+		let mut id = [0u8; 16];
+		getrandom::getrandom(&mut id).map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to generate pending action id: {e}"),
+			)
+		})?;
+		let created_at = current_time_secs();
+		let action = PendingAction {
+			id: id.to_lower_hex_string(),
+			method: method.to_string(),
+			requested_by: caller.name.clone(),
+			created_at,
+			amount_msat,
+			request: sanitize_request(request),
+			status: PendingActionStatus::AwaitingApproval as i32,
+			decided_by: None,
+			decided_at: None,
+			reason: None,
+			result: None,
+			expires_at: created_at.saturating_add(self.config.expiry_secs),
+		};
+		let (requester_scope, requester_max_send_amount_msat) = caller.scope.to_proto();
+		let record = PendingActionRecord {
+			action: Some(action.clone()),
+			request: request.encode_to_vec(),
+			requester_scope: requester_scope as i32,
+			requester_max_send_amount_msat,
+			requester_uses_api_key: uses_api_key,
+		};
+		write_action(&*self.paginated_kv_store, &action, &record)?;
+
+		let event = event_envelope::Event::PendingActionCreated(PendingActionCreated {
+			action: Some(action.clone()),
+		});
+		self.event_log.publish(event);
+		Ok(Some(action))
+	}
+
+	/// Marks the action with the given `id` as approved by `approver`, returning it along with
+	/// the request to execute.
+	///
+	/// The action is marked as approved before it is executed, so it is never executed twice. Its
+	/// outcome is to be recorded with [`ApprovalQueue::complete`].
+	pub(crate) fn approve(
+		&self, id: &str, approver: &str,
+	) -> Result<ApprovedAction, LdkServerError> {
+		let _guard = self.decision_lock.lock().unwrap();
+		let (mut action, record) = self.read_awaiting(id)?;
+		if self.config.require_distinct_approver && action.requested_by == approver {
+			return Err(LdkServerError::new(
+				PermissionDeniedError,
+				format!(
+					"Pending action '{id}' must be approved by a different API key than '{approver}', which requested it"
+				),
+			));
+		}
+
+		let scope = ApiKeyScope::from_i32(record.requester_scope)
+			.ok_or_else(|| LdkServerError::new(InternalServerError, "Unknown API key scope"))
+			.and_then(|scope| Scope::from_proto(scope, record.requester_max_send_amount_msat))
+			.map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Invalid requester scope of pending action '{id}': {}", e.message),
+				)
+			})?;
+
+		action.status = PendingActionStatus::Approved as i32;
+		action.decided_by = Some(approver.to_string());
+		action.decided_at = Some(current_time_secs());
+		write_action(&*self.paginated_kv_store, &action, &record)?;
+
+		let requester = Identity { name: action.requested_by.clone(), scope };
+		Ok(ApprovedAction {
+			action,
+			request: record.request,
+			requester,
+			requester_uses_api_key: record.requester_uses_api_key,
+		})
+	}
+
+	/// Records the outcome of executing an action returned by [`ApprovalQueue::approve`].
+	///
+	/// `result` is the payment ID or transaction ID of the executed send, or the error that
+	/// executing it failed with.
+	pub(crate) fn complete(
+		&self, mut action: PendingAction, result: Result<String, &LdkServerError>,
+	) -> Result<PendingAction, LdkServerError> {
+		let _guard = self.decision_lock.lock().unwrap();
+		let (_, record) = self.read(&action.id)?;
+		match result {
+			Ok(result) => action.result = Some(result),
+			Err(e) => {
+				action.status = PendingActionStatus::Failed as i32;
+				action.reason = Some(e.message.clone());
+			},
+		}
+		write_action(&*self.paginated_kv_store, &action, &record)?;
+		Ok(action)
+	}
+
+	/// Marks the action with the given `id` as rejected by `rejecter`.
+	pub(crate) fn reject(
+		&self, id: &str, rejecter: &str, reason: Option<String>,
+	) -> Result<PendingAction, LdkServerError> {
+		let _guard = self.decision_lock.lock().unwrap();
+		let (mut action, record) = self.read_awaiting(id)?;
+		action.status = PendingActionStatus::Rejected as i32;
+		action.decided_by = Some(rejecter.to_string());
+		action.decided_at = Some(current_time_secs());
+		action.reason = reason;
+		write_action(&*self.paginated_kv_store, &action, &record)?;
+		Ok(action)
+	}
+
+	fn read(&self, id: &str) -> Result<(PendingAction, PendingActionRecord), LdkServerError> {
+		read_action(&*self.paginated_kv_store, id)
+	}
+
+	/// Reads the action with the given `id`, failing if it is no longer awaiting approval.
+	///
+	/// An action found to be past its expiry is persisted as expired.
+	fn read_awaiting(
+		&self, id: &str,
+	) -> Result<(PendingAction, PendingActionRecord), LdkServerError> {
+		let (mut action, record) = self.read(id)?;
+		if expire_if_due(&mut action, current_time_secs()) {
+			write_action(&*self.paginated_kv_store, &action, &record)?;
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!("Pending action '{id}' has expired"),
+			));
+		}
+		if action.status != PendingActionStatus::AwaitingApproval as i32 {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!("Pending action '{id}' is no longer awaiting approval"),
+			));
+		}
+		Ok((action, record))
+	}
+}
+
+/// Lists the persisted pending actions, most recent first.
+pub(crate) fn list_pending_actions(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListPendingActionsResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			PENDING_ACTIONS_PERSISTENCE_PRIMARY_NAMESPACE,
+			PENDING_ACTIONS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list pending actions: {e}"))
+		})?;
+
+	let now = current_time_secs();
+	let mut actions = Vec::with_capacity(list_response.keys.len());
+	for id in list_response.keys {
+		let (mut action, _) = read_action(paginated_kv_store, &id)?;
+		expire_if_due(&mut action, now);
+		actions.push(action);
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListPendingActionsResponse { actions, next_page_token })
+}
+
+/// Marks `action` as expired if it is still awaiting approval at `now` but past its expiry,
+/// returning whether it was.
+fn expire_if_due(action: &mut PendingAction, now: u64) -> bool {
+	if action.status != PendingActionStatus::AwaitingApproval as i32 || now < action.expires_at {
+		return false;
+	}
+	action.status = PendingActionStatus::Expired as i32;
+	action.decided_at = Some(action.expires_at);
+	true
+}
+
+fn read_action(
+	paginated_kv_store: &dyn PaginatedKVStore, id: &str,
+) -> Result<(PendingAction, PendingActionRecord), LdkServerError> {
+	let record: PendingActionRecord = read_record(
+		paginated_kv_store,
+		PENDING_ACTIONS_PERSISTENCE_PRIMARY_NAMESPACE,
+		PENDING_ACTIONS_PERSISTENCE_SECONDARY_NAMESPACE,
+		id,
+	)?
+	.ok_or_else(|| {
+		LdkServerError::new(InvalidRequestError, format!("Unknown pending action '{id}'"))
+	})?;
+	let action = record.action.clone().ok_or_else(|| {
+		LdkServerError::new(
+			InternalServerError,
+			format!("Pending action record '{id}' is missing its action"),
+		)
+	})?;
+	Ok((action, record))
+}
+
+/// Persists `record` with its action replaced by `action`.
+fn write_action(
+	paginated_kv_store: &dyn PaginatedKVStore, action: &PendingAction, record: &PendingActionRecord,
+) -> Result<(), LdkServerError> {
+	let record = PendingActionRecord { action: Some(action.clone()), ..record.clone() };
+	write_record(
+		paginated_kv_store,
+		PENDING_ACTIONS_PERSISTENCE_PRIMARY_NAMESPACE,
+		PENDING_ACTIONS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&action.id,
+		action.created_at,
+		&record,
+	)
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::api::{Bolt11SendRequest, OnchainSendRequest};
+	use ldk_server_grpc::endpoints::{BOLT11_SEND_PATH, ONCHAIN_SEND_PATH};
+	use ldk_server_grpc::events::EventEnvelope;
+	use tokio::sync::broadcast;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+	use crate::util::event_log::EventLogConfig;
+
+	fn test_queue(
+		dir_name: &str, config: ApprovalConfig,
+	) -> (ApprovalQueue, broadcast::Receiver<EventEnvelope>) {
+		let store: Arc<dyn PaginatedKVStore> = Arc::new(test_store(dir_name));
+		let event_log =
+			Arc::new(EventLog::new(Arc::clone(&store), EventLogConfig::default()).unwrap());
+		let (event_receiver, _) = event_log.subscribe();
+		(ApprovalQueue::new(store, config, event_log), event_receiver)
+	}
+
+	fn test_config() -> ApprovalConfig {
+		ApprovalConfig {
+			lightning_threshold_msat: Some(1_000_000),
+			onchain_threshold_msat: None,
+			require_distinct_approver: true,
+			expiry_secs: DEFAULT_APPROVAL_EXPIRY_SECS,
+		}
+	}
+
+	fn identity(name: &str) -> Identity {
+		Identity { name: name.to_string(), scope: Scope::Send { max_amount_msat: Some(5_000_000) } }
+	}
+
+	#[test]
+	fn test_requires_approval() {
+		let (queue, _) = test_queue("test_requires_approval", test_config());
+		let lightning = [SpendKind::Lightning];
+		assert!(!queue.requires_approval(&lightning, Some(1_000_000)));
+		assert!(queue.requires_approval(&lightning, Some(1_000_001)));
+		assert!(queue.requires_approval(&lightning, None));
+
+		// No threshold is configured for on-chain sends.
+		let onchain = [SpendKind::Onchain];
+		assert!(!queue.requires_approval(&onchain, Some(u64::MAX)));
+		assert!(!queue.requires_approval(&onchain, None));
+
+		let both = [SpendKind::Lightning, SpendKind::Onchain];
+		assert!(queue.requires_approval(&both, Some(2_000_000)));
+	}
+
+	#[test]
+	fn test_submit_and_approve() {
+		let (queue, mut event_receiver) = test_queue("test_submit_and_approve", test_config());
+		let request = Bolt11SendRequest { invoice: "lnbcrt1".to_string(), ..Default::default() };
+
+		let below_threshold = queue.submit(
+			&identity("payouts"),
+			true,
+			BOLT11_SEND_PATH,
+			&[SpendKind::Lightning],
+			Some(1_000_000),
+			&request,
+		);
+		assert!(below_threshold.unwrap().is_none());
+		assert!(event_receiver.try_recv().is_err());
+
+		let action = queue
+			.submit(
+				&identity("payouts"),
+				true,
+				BOLT11_SEND_PATH,
+				&[SpendKind::Lightning],
+				Some(2_000_000),
+				&request,
+			)
+			.unwrap()
+			.unwrap();
+		assert_eq!(action.method, BOLT11_SEND_PATH);
+		assert_eq!(action.requested_by, "payouts");
+		assert_eq!(action.amount_msat, Some(2_000_000));
+		assert_eq!(action.status, PendingActionStatus::AwaitingApproval as i32);
+		assert!(action.request.contains("lnbcrt1"));
+		match event_receiver.try_recv().unwrap().event {
+			Some(event_envelope::Event::PendingActionCreated(event)) => {
+				assert_eq!(event.action, Some(action.clone()))
+			},
+			event => panic!("Unexpected event: {event:?}"),
+		}
+
+		// The requester can't approve its own action.
+		let err = queue.approve(&action.id, "payouts").err().unwrap();
+		assert_eq!(err.error_code, PermissionDeniedError);
+
+		let approved = queue.approve(&action.id, "treasurer").unwrap();
+		assert_eq!(approved.action.status, PendingActionStatus::Approved as i32);
+		assert_eq!(approved.action.decided_by.as_deref(), Some("treasurer"));
+		assert_eq!(approved.requester, identity("payouts"));
+		assert!(approved.requester_uses_api_key);
+		assert_eq!(Bolt11SendRequest::decode(approved.request.as_slice()).unwrap(), request);
+
+		// An approved action can't be approved or rejected again.
+		assert!(queue.approve(&action.id, "treasurer").is_err());
+		assert!(queue.reject(&action.id, "treasurer", None).is_err());
+
+		let completed = queue.complete(approved.action, Ok("payment-id".to_string())).unwrap();
+		assert_eq!(completed.status, PendingActionStatus::Approved as i32);
+		assert_eq!(completed.result.as_deref(), Some("payment-id"));
+
+		let response = list_pending_actions(&*queue.paginated_kv_store, None).unwrap();
+		assert_eq!(response.actions, vec![completed]);
+	}
+
+	#[test]
+	fn test_failed_execution() {
+		let (queue, _) = test_queue("test_failed_execution", test_config());
+		let action = queue
+			.submit(
+				&identity("payouts"),
+				true,
+				BOLT11_SEND_PATH,
+				&[SpendKind::Lightning],
+				None,
+				&Bolt11SendRequest::default(),
+			)
+			.unwrap()
+			.unwrap();
+		let approved = queue.approve(&action.id, "treasurer").unwrap();
+		let error = LdkServerError::new(InternalServerError, "no route");
+		let failed = queue.complete(approved.action, Err(&error)).unwrap();
+		assert_eq!(failed.status, PendingActionStatus::Failed as i32);
+		assert_eq!(failed.reason.as_deref(), Some("no route"));
+		assert!(failed.result.is_none());
+	}
+
+	#[test]
+	fn test_reject_and_expire() {
+		let config = ApprovalConfig {
+			lightning_threshold_msat: None,
+			onchain_threshold_msat: Some(0),
+			require_distinct_approver: false,
+			expiry_secs: 0,
+		};
+		let (queue, _) = test_queue("test_reject_and_expire", config);
+		let request = OnchainSendRequest::default();
+		let submit = || {
+			queue
+				.submit(
+					&identity("payouts"),
+					true,
+					ONCHAIN_SEND_PATH,
+					&[SpendKind::Onchain],
+					Some(1000),
+					&request,
+				)
+				.unwrap()
+				.unwrap()
+		};
+
+		// Actions expire right away without an expiry.
+		let expired = submit();
+		let err = queue.approve(&expired.id, "payouts").err().unwrap();
+		assert!(err.message.contains("expired"));
+		assert!(queue.reject(&expired.id, "payouts", None).is_err());
+
+		let err = queue.approve("unknown", "payouts").err().unwrap();
+		assert_eq!(err.error_code, InvalidRequestError);
+
+		let queue = ApprovalQueue { config: test_config(), ..queue };
+		let action = queue
+			.submit(
+				&identity("payouts"),
+				true,
+				BOLT11_SEND_PATH,
+				&[SpendKind::Lightning],
+				None,
+				&Bolt11SendRequest::default(),
+			)
+			.unwrap()
+			.unwrap();
+		let rejected =
+			queue.reject(&action.id, "payouts", Some("Wrong invoice".to_string())).unwrap();
+		assert_eq!(rejected.status, PendingActionStatus::Rejected as i32);
+		assert_eq!(rejected.decided_by.as_deref(), Some("payouts"));
+		assert_eq!(rejected.reason.as_deref(), Some("Wrong invoice"));
+		assert!(queue.approve(&action.id, "treasurer").is_err());
+
+		let response = list_pending_actions(&*queue.paginated_kv_store, None).unwrap();
+		let mut statuses: Vec<i32> = response.actions.iter().map(|action| action.status).collect();
+		statuses.sort();
+		assert_eq!(
+			statuses,
+			vec![PendingActionStatus::Rejected as i32, PendingActionStatus::Expired as i32]
+		);
+	}
+}
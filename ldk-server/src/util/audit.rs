@@ -0,0 +1,282 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hex::DisplayHex;
+use ldk_server_grpc::endpoints::{
+	LIST_API_KEYS_PATH, LIST_AUDIT_LOG_PATH, LIST_FAILED_WEBHOOK_DELIVERIES_PATH,
+	LIST_PENDING_ACTIONS_PATH,
+};
+use ldk_server_grpc::types::{AuditLogEntry, AuditOutcome};
+use prost::Message;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE, AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::auth::Scope;
+
+/// The maximum length of the request recorded in an audit log entry, in bytes.
+const MAX_AUDIT_REQUEST_LEN: usize = 4096;
+
+/// Replaces secrets in recorded requests.
+const REDACTED: &str = "<redacted>";
+
+/// Request fields whose name contains any of these hold secrets, e.g. `preimage`,
+/// `auto_claim_preimage` or `passphrase`, and are redacted in whichever request they appear.
+const SECRET_FIELD_MARKERS: [&str; 5] = ["preimage", "passphrase", "secret", "mnemonic", "seed"];
+
+/// Returns whether calls to the given RPC `method` are recorded in the audit log, i.e. whether
+/// they may change the state of the node.
+pub(crate) fn is_audited(method: &str) -> bool {
+	match method {
+		// Admin-only RPCs that don't change any state.
+		LIST_API_KEYS_PATH
+		| LIST_AUDIT_LOG_PATH
+		| LIST_PENDING_ACTIONS_PATH
+		| LIST_FAILED_WEBHOOK_DELIVERIES_PATH => false,
+		_ => !Scope::ReadOnly.permits(method),
+	}
+}
+
+/// Returns the JSON form of `request` recorded in the audit log, with secrets redacted and
+/// truncated to [`MAX_AUDIT_REQUEST_LEN`].
+///
+/// Secrets are redacted by field name, see [`SECRET_FIELD_MARKERS`], so that secrets in fields
+/// added to a request later are redacted too.
+pub(crate) fn sanitize_request<T: Serialize>(request: &T) -> String {
+	let mut request = match serde_json::to_value(request) {
+		Ok(request) => request,
+		Err(e) => return format!("<unserializable request: {e}>"),
+	};
+	redact_secrets(&mut request);
+
+	let mut sanitized = request.to_string();
+	if sanitized.len() > MAX_AUDIT_REQUEST_LEN {
+		let mut end = MAX_AUDIT_REQUEST_LEN;
+		while !sanitized.is_char_boundary(end) {
+			end -= 1;
+		}
+		sanitized.truncate(end);
+		sanitized.push_str("...");
+	}
+	sanitized
+}
+
+fn redact_secrets(value: &mut Value) {
+	match value {
+		Value::Object(fields) => {
+			for (name, field) in fields.iter_mut() {
+				if SECRET_FIELD_MARKERS.iter().any(|marker| name.contains(marker)) {
+					if !field.is_null() {
+						*field = Value::String(REDACTED.to_string());
+					}
+				} else {
+					redact_secrets(field);
+				}
+			}
+		},
+		Value::Array(values) => values.iter_mut().for_each(redact_secrets),
+		_ => {},
+	}
+}
+
+/// Records a call to `method` before it is handled.
+///
+/// The returned entry has an [`AuditOutcome::Incomplete`] outcome and is to be passed to
+/// [`record_outcome`] once the call was handled.
+pub(crate) fn record_call(
+	paginated_kv_store: &dyn PaginatedKVStore, method: &str, caller: &str, request: String,
+) -> Result<AuditLogEntry, LdkServerError> {
+	let mut id = [0u8; 16];
+	getrandom::getrandom(&mut id).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to generate audit log id: {e}"))
+	})?;
+	let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs();
+
+	let entry = AuditLogEntry {
+		id: id.to_lower_hex_string(),
+		method: method.to_string(),
+		caller: caller.to_string(),
+		timestamp,
+		request,
+		outcome: AuditOutcome::Incomplete as i32,
+		error_message: None,
+	};
+	write_entry(paginated_kv_store, &entry)?;
+	Ok(entry)
+}
+
+/// Completes an entry returned by [`record_call`] with the result of the call.
+pub(crate) fn record_outcome(
+	paginated_kv_store: &dyn PaginatedKVStore, mut entry: AuditLogEntry,
+	error: Option<&LdkServerError>,
+) -> Result<(), LdkServerError> {
+	match error {
+		Some(error) => {
+			entry.outcome = AuditOutcome::Failure as i32;
+			entry.error_message = Some(error.message.clone());
+		},
+		None => entry.outcome = AuditOutcome::Success as i32,
+	}
+	write_entry(paginated_kv_store, &entry)
+}
+
+fn write_entry(
+	paginated_kv_store: &dyn PaginatedKVStore, entry: &AuditLogEntry,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.write(
+			AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE,
+			AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+			&entry.id,
+			entry.timestamp as i64,
+			&entry.encode_to_vec(),
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to write audit log: {e}"))
+		})
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::api::{
+		find_payments_request, Bolt11ClaimForHashRequest, Bolt11ReceiveForHashRequest,
+		Bolt11SendRequest, EncryptMnemonicRequest, FindPaymentsRequest, OpenChannelRequest,
+		SpontaneousSendRequest, UnlockRequest,
+	};
+	use ldk_server_grpc::endpoints::{
+		BOLT11_SEND_PATH, CREATE_API_KEY_PATH, GET_NODE_INFO_PATH, LIST_PAYMENTS_PATH,
+		OPEN_CHANNEL_PATH, REDELIVER_WEBHOOK_PATH,
+	};
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	#[test]
+	fn test_is_audited() {
+		assert!(is_audited(BOLT11_SEND_PATH));
+		assert!(is_audited(OPEN_CHANNEL_PATH));
+		assert!(is_audited(CREATE_API_KEY_PATH));
+		assert!(!is_audited(GET_NODE_INFO_PATH));
+		assert!(!is_audited(LIST_PAYMENTS_PATH));
+		assert!(!is_audited(LIST_API_KEYS_PATH));
+		assert!(!is_audited(LIST_AUDIT_LOG_PATH));
+		assert!(!is_audited(LIST_PENDING_ACTIONS_PATH));
+		assert!(!is_audited(LIST_FAILED_WEBHOOK_DELIVERIES_PATH));
+		assert!(is_audited(REDELIVER_WEBHOOK_PATH));
+	}
+
+	#[test]
+	fn test_sanitize_request() {
+		let request = Bolt11ClaimForHashRequest {
+			payment_hash: None,
+			claimable_amount_msat: None,
+			preimage: "aa".repeat(32),
+		};
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(REDACTED));
+		assert!(!sanitized.contains(&"aa".repeat(32)));
+
+		let request = Bolt11ReceiveForHashRequest {
+			auto_claim_preimage: Some("bb".repeat(32)),
+			..Default::default()
+		};
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(REDACTED));
+		assert!(!sanitized.contains(&"bb".repeat(32)));
+
+		let request = Bolt11ReceiveForHashRequest::default();
+		assert!(!sanitize_request(&request).contains(REDACTED));
+
+		let request = SpontaneousSendRequest {
+			node_id: "03".repeat(33),
+			preimage: Some("cc".repeat(32)),
+			..Default::default()
+		};
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(REDACTED));
+		assert!(!sanitized.contains(&"cc".repeat(32)));
+		assert!(sanitized.contains(&"03".repeat(33)));
+
+		// Secrets in oneofs are redacted too.
+		let request = FindPaymentsRequest {
+			identifier: Some(find_payments_request::Identifier::Preimage("dd".repeat(32))),
+			..Default::default()
+		};
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(REDACTED));
+		assert!(!sanitized.contains(&"dd".repeat(32)));
+
+		let request = EncryptMnemonicRequest { passphrase: "correct horse".to_string() };
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(REDACTED));
+		assert!(!sanitized.contains("correct horse"));
+
+		let request = UnlockRequest { passphrase: "battery staple".to_string() };
+		assert!(!sanitize_request(&request).contains("battery staple"));
+
+		let request = OpenChannelRequest {
+			node_pubkey: "02".repeat(33),
+			address: "127.0.0.1:9735".to_string(),
+			..Default::default()
+		};
+		let sanitized = sanitize_request(&request);
+		assert!(sanitized.contains(&"02".repeat(33)));
+		assert!(sanitized.contains("127.0.0.1:9735"));
+
+		let request = Bolt11SendRequest {
+			invoice: "lnbc".repeat(MAX_AUDIT_REQUEST_LEN),
+			..Default::default()
+		};
+		let sanitized = sanitize_request(&request);
+		assert_eq!(sanitized.len(), MAX_AUDIT_REQUEST_LEN + 3);
+		assert!(sanitized.ends_with("..."));
+	}
+
+	#[test]
+	fn test_record_call_and_outcome() {
+		let store = test_store("test_record_call_and_outcome");
+		let read_entry = |id: &str| {
+			let bytes = store
+				.read(
+					AUDIT_LOG_PERSISTENCE_PRIMARY_NAMESPACE,
+					AUDIT_LOG_PERSISTENCE_SECONDARY_NAMESPACE,
+					id,
+				)
+				.unwrap();
+			AuditLogEntry::decode(&*bytes).unwrap()
+		};
+
+		let entry =
+			record_call(&store, BOLT11_SEND_PATH, "payouts", "request".to_string()).unwrap();
+		let id = entry.id.clone();
+		assert_eq!(read_entry(&id).outcome, AuditOutcome::Incomplete as i32);
+
+		let error = LdkServerError::new(InternalServerError, "no route");
+		record_outcome(&store, entry, Some(&error)).unwrap();
+		let entry = read_entry(&id);
+		assert_eq!(entry.method, BOLT11_SEND_PATH);
+		assert_eq!(entry.caller, "payouts");
+		assert_eq!(entry.request, "request");
+		assert_eq!(entry.outcome, AuditOutcome::Failure as i32);
+		assert_eq!(entry.error_message.as_deref(), Some("no route"));
+
+		let entry = record_call(&store, OPEN_CHANNEL_PATH, "admin", "request".to_string()).unwrap();
+		let id = entry.id.clone();
+		record_outcome(&store, entry, None).unwrap();
+		assert_eq!(read_entry(&id).outcome, AuditOutcome::Success as i32);
+		assert!(read_entry(&id).error_message.is_none());
+	}
+}
@@ -0,0 +1,861 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::{fmt, fs, io};
+
+use hex::DisplayHex;
+use ldk_server_grpc::endpoints::{
+	BOLT11_CLAIM_FOR_HASH_PATH, BOLT11_FAIL_FOR_HASH_PATH, BOLT11_RECEIVE_FOR_HASH_PATH,
+	BOLT11_RECEIVE_PATH, BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH,
+	BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH, BOLT11_SEND_PATH, BOLT11_SEND_UNDERPAYING_PATH,
+	BOLT12_RECEIVE_PATH, BOLT12_SEND_PATH, DECODE_INVOICE_PATH, DECODE_OFFER_PATH,
+	ESTIMATE_ROUTE_FEE_PATH, EXPORT_PATHFINDING_SCORES_PATH, FIND_PAYMENTS_PATH, GET_BALANCES_PATH,
+	GET_NODE_INFO_PATH, GET_PAYMENT_DETAILS_PATH, GRAPH_GET_CHANNEL_PATH, GRAPH_GET_NODE_PATH,
+	GRAPH_LIST_CHANNELS_PATH, GRAPH_LIST_NODES_PATH, LIST_CHANNELS_PATH,
+	LIST_CLAIMABLE_PAYMENTS_PATH, LIST_CLOSED_CHANNELS_PATH, LIST_FORWARDED_PAYMENTS_PATH,
+	LIST_INVOICES_PATH, LIST_OFFERS_PATH, LIST_ONCHAIN_TRANSACTIONS_PATH, LIST_PAYMENTS_PATH,
+	LIST_PEERS_PATH, ONCHAIN_RECEIVE_PATH, ONCHAIN_SEND_PATH, QUERY_ROUTES_PATH, SEND_PROBES_PATH,
+	SPONTANEOUS_SEND_PATH, SUBSCRIBE_EVENTS_PATH, UNIFIED_SEND_PATH, UPDATE_LABEL_PATH,
+	VERIFY_SIGNATURE_PATH,
+};
+use ldk_server_grpc::types::{ApiKeyInfo, ApiKeyScope};
+use log::warn;
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{
+	InternalServerError, InvalidRequestError, PermissionDeniedError,
+};
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE, API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::tls::{cert_fingerprint, cert_subject_common_name};
+use crate::util::{current_time_secs, write_new};
+
+/// The name of the identity authenticated by the `api_key` file in the network directory.
+pub(crate) const DEFAULT_API_KEY_NAME: &str = "admin";
+
+/// The maximum length of an API key name.
+const MAX_API_KEY_NAME_LEN: usize = 64;
+
+/// How long a rotated API key is still accepted if no grace period is given.
+pub(crate) const DEFAULT_ROTATION_GRACE_PERIOD_SECS: u64 = 3600;
+
+/// How often the last use of an API key is persisted at most, to avoid a write on every request.
+const LAST_USED_PERSIST_INTERVAL_SECS: u64 = 60;
+
+/// Determines which RPCs an authenticated caller may invoke.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Scope {
+	/// Grants access to every RPC.
+	Admin,
+	/// Grants access to RPCs that only read node state.
+	ReadOnly,
+	/// Grants read-only access, plus creating and settling invoices, offers and on-chain
+	/// addresses.
+	InvoiceOnly,
+	/// Grants invoice access, plus sending payments. If `max_amount_msat` is set, each outbound
+	/// payment is limited to that amount.
+	Send { max_amount_msat: Option<u64> },
+}
+
+/// The kind of access an RPC requires.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Access {
+	Read,
+	Receive,
+	Send,
+	Admin,
+}
+
+fn required_access(method: &str) -> Access {
+	match method {
+		GET_NODE_INFO_PATH
+		| GET_BALANCES_PATH
+		| LIST_CHANNELS_PATH
+		| LIST_CLOSED_CHANNELS_PATH
+		| GET_PAYMENT_DETAILS_PATH
+		| LIST_PAYMENTS_PATH
+		| FIND_PAYMENTS_PATH
+		| LIST_INVOICES_PATH
+		| LIST_OFFERS_PATH
+		| LIST_CLAIMABLE_PAYMENTS_PATH
+		| LIST_FORWARDED_PAYMENTS_PATH
+		| LIST_ONCHAIN_TRANSACTIONS_PATH
+		| LIST_PEERS_PATH
+		| VERIFY_SIGNATURE_PATH
+		| EXPORT_PATHFINDING_SCORES_PATH
+		| ESTIMATE_ROUTE_FEE_PATH
+		| QUERY_ROUTES_PATH
+		| GRAPH_LIST_CHANNELS_PATH
+		| GRAPH_GET_CHANNEL_PATH
+		| GRAPH_LIST_NODES_PATH
+		| GRAPH_GET_NODE_PATH
+		| DECODE_INVOICE_PATH
+		| DECODE_OFFER_PATH
+		| SUBSCRIBE_EVENTS_PATH => Access::Read,
+		ONCHAIN_RECEIVE_PATH
+		| BOLT11_RECEIVE_PATH
+		| BOLT11_RECEIVE_FOR_HASH_PATH
+		| BOLT11_CLAIM_FOR_HASH_PATH
+		| BOLT11_FAIL_FOR_HASH_PATH
+		| BOLT11_RECEIVE_VIA_JIT_CHANNEL_PATH
+		| BOLT11_RECEIVE_VARIABLE_AMOUNT_VIA_JIT_CHANNEL_PATH
+		| BOLT12_RECEIVE_PATH => Access::Receive,
+		ONCHAIN_SEND_PATH
+		| BOLT11_SEND_PATH
+		| BOLT11_SEND_UNDERPAYING_PATH
+		| BOLT12_SEND_PATH
+		| SPONTANEOUS_SEND_PATH
+		| UNIFIED_SEND_PATH
+		| SEND_PROBES_PATH
+		// Labels map payments and channels back to the caller's records, so they may only be
+		// changed by keys that may send.
+		| UPDATE_LABEL_PATH => Access::Send,
+		// Anything not explicitly listed above, e.g. channel and peer management, requires full
+		// access.
+		_ => Access::Admin,
+	}
+}
+
+impl Scope {
+	/// Returns whether a caller with this scope may invoke the given RPC `method`.
+	pub(crate) fn permits(&self, method: &str) -> bool {
+		let access = required_access(method);
+		match self {
+			Scope::Admin => true,
+			Scope::ReadOnly => access == Access::Read,
+			Scope::InvoiceOnly => matches!(access, Access::Read | Access::Receive),
+			Scope::Send { .. } => access != Access::Admin,
+		}
+	}
+
+	/// Checks that an outbound payment of `amount_msat` is within the limit of this scope.
+	///
+	/// `amount_msat` is `None` if the amount cannot be determined before the payment is sent, in
+	/// which case the payment is only permitted if this scope is not limited.
+	pub(crate) fn check_send_amount(&self, amount_msat: Option<u64>) -> Result<(), LdkServerError> {
+		let max_amount_msat = match self {
+			Scope::Send { max_amount_msat: Some(max_amount_msat) } => *max_amount_msat,
+			_ => return Ok(()),
+		};
+
+		match amount_msat {
+			Some(amount_msat) if amount_msat <= max_amount_msat => Ok(()),
+			Some(amount_msat) => Err(LdkServerError::new(
+				PermissionDeniedError,
+				format!(
+					"Payment amount of {amount_msat} msat exceeds the per-payment limit of {max_amount_msat} msat for this API key"
+				),
+			)),
+			None => Err(LdkServerError::new(
+				PermissionDeniedError,
+				"Payments without an explicit amount are not permitted for this API key",
+			)),
+		}
+	}
+}
+
+impl fmt::Display for Scope {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			Scope::Admin => write!(f, "admin"),
+			Scope::ReadOnly => write!(f, "read_only"),
+			Scope::InvoiceOnly => write!(f, "invoice_only"),
+			Scope::Send { .. } => write!(f, "send"),
+		}
+	}
+}
+
+/// An authenticated caller.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Identity {
+	pub(crate) name: String,
+	pub(crate) scope: Scope,
+}
+
+/// A named API key used for HMAC-based authentication.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ApiKey {
+	pub(crate) name: String,
+	/// The hex-encoded key the HMAC signature is computed with.
+	pub(crate) key: String,
+	pub(crate) scope: Scope,
+}
+
+impl ApiKey {
+	pub(crate) fn identity(&self) -> Identity {
+		Identity { name: self.name.clone(), scope: self.scope }
+	}
+}
+
+/// Determines which client certificates a [`ClientCertIdentity`] applies to.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum ClientCertMatcher {
+	/// Matches certificates whose subject has the given common name.
+	SubjectCommonName(String),
+	/// Matches the certificate with the given SHA-256 fingerprint of its DER encoding.
+	Fingerprint([u8; 32]),
+}
+
+/// An identity assigned to clients authenticating with a TLS client certificate.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct ClientCertIdentity {
+	pub(crate) name: String,
+	pub(crate) matcher: ClientCertMatcher,
+	pub(crate) scope: Scope,
+}
+
+/// Returns the identity of the first of `client_identities` matching the given DER-encoded client
+/// certificate, which must already have been verified during the TLS handshake.
+pub(crate) fn identity_for_client_cert(
+	client_identities: &[ClientCertIdentity], cert_der: &[u8],
+) -> Option<Identity> {
+	let common_name = cert_subject_common_name(cert_der);
+	let fingerprint = cert_fingerprint(cert_der);
+	client_identities
+		.iter()
+		.find(|identity| match &identity.matcher {
+			ClientCertMatcher::SubjectCommonName(name) => common_name.as_ref() == Some(name),
+			ClientCertMatcher::Fingerprint(expected) => *expected == fingerprint,
+		})
+		.map(|identity| Identity { name: identity.name.clone(), scope: identity.scope })
+}
+
+impl Scope {
+	pub(crate) fn from_proto(
+		scope: ApiKeyScope, max_send_amount_msat: Option<u64>,
+	) -> Result<Self, LdkServerError> {
+		let scope = match scope {
+			ApiKeyScope::Send => return Ok(Scope::Send { max_amount_msat: max_send_amount_msat }),
+			ApiKeyScope::ReadOnly => Scope::ReadOnly,
+			ApiKeyScope::InvoiceOnly => Scope::InvoiceOnly,
+			ApiKeyScope::Admin => Scope::Admin,
+		};
+		if max_send_amount_msat.is_some() {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				"`max_send_amount_msat` can only be set for API keys with the `SEND` scope",
+			));
+		}
+		Ok(scope)
+	}
+
+	pub(crate) fn to_proto(self) -> (ApiKeyScope, Option<u64>) {
+		match self {
+			Scope::Admin => (ApiKeyScope::Admin, None),
+			Scope::ReadOnly => (ApiKeyScope::ReadOnly, None),
+			Scope::InvoiceOnly => (ApiKeyScope::InvoiceOnly, None),
+			Scope::Send { max_amount_msat } => (ApiKeyScope::Send, max_amount_msat),
+		}
+	}
+}
+
+/// Returns whether `name` is a valid API key name, i.e. it is non-empty, at most
+/// [`MAX_API_KEY_NAME_LEN`] characters long and only consists of ASCII letters, digits, `-` and
+/// `_`.
+pub(crate) fn is_valid_api_key_name(name: &str) -> bool {
+	!name.is_empty()
+		&& name.len() <= MAX_API_KEY_NAME_LEN
+		&& name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The persisted form of an API key managed via the API.
+#[derive(Clone, PartialEq, prost::Message)]
+struct ApiKeyRecord {
+	#[prost(message, optional, tag = "1")]
+	info: Option<ApiKeyInfo>,
+	/// The hex-encoded key.
+	#[prost(string, tag = "2")]
+	key: String,
+	/// The hex-encoded key replaced by the last rotation, accepted until
+	/// `info.previous_key_expires_at`.
+	#[prost(string, optional, tag = "3")]
+	previous_key: Option<String>,
+}
+
+struct ManagedApiKey {
+	info: ApiKeyInfo,
+	scope: Scope,
+	key: String,
+	previous_key: Option<String>,
+	persisted_last_used_at: Option<u64>,
+}
+
+impl ManagedApiKey {
+	fn new(name: String, key: String, scope: Scope, created_at: u64) -> Self {
+		let (proto_scope, max_send_amount_msat) = scope.to_proto();
+		let info = ApiKeyInfo {
+			name,
+			scope: proto_scope as i32,
+			max_send_amount_msat,
+			created_at: Some(created_at),
+			last_used_at: None,
+			previous_key_expires_at: None,
+			from_config: false,
+		};
+		Self { info, scope, key, previous_key: None, persisted_last_used_at: None }
+	}
+
+	fn from_record(record: ApiKeyRecord) -> io::Result<Self> {
+		let ApiKeyRecord { info, key, previous_key } = record;
+		let info = info.ok_or_else(|| {
+			io::Error::new(io::ErrorKind::InvalidData, "API key record is missing its info")
+		})?;
+		let scope = ApiKeyScope::from_i32(info.scope)
+			.ok_or_else(|| LdkServerError::new(InvalidRequestError, "Unknown API key scope"))
+			.and_then(|scope| Scope::from_proto(scope, info.max_send_amount_msat))
+			.map_err(|e| {
+				io::Error::new(
+					io::ErrorKind::InvalidData,
+					format!("Invalid scope for API key '{}': {}", info.name, e.message),
+				)
+			})?;
+		let persisted_last_used_at = info.last_used_at;
+		Ok(Self { info, scope, key, previous_key, persisted_last_used_at })
+	}
+
+	fn to_record(&self) -> ApiKeyRecord {
+		ApiKeyRecord {
+			info: Some(self.info.clone()),
+			key: self.key.clone(),
+			previous_key: self.previous_key.clone(),
+		}
+	}
+
+	/// Returns the previous key if it is still accepted at `now`.
+	fn active_previous_key(&self, now: u64) -> Option<&String> {
+		match self.info.previous_key_expires_at {
+			Some(expires_at) if now < expires_at => self.previous_key.as_ref(),
+			_ => None,
+		}
+	}
+}
+
+struct ApiKeyStoreInner {
+	managed_keys: HashMap<String, ManagedApiKey>,
+	config_keys_last_used_at: HashMap<String, u64>,
+}
+
+/// Holds the API keys accepted by the server.
+///
+/// This includes the `admin` key from the `api_key` file, keys defined in the config file, and
+/// keys created via the API. The latter, as well as the `admin` key, can be rotated at runtime and
+/// are persisted in the [`PaginatedKVStore`].
+pub(crate) struct ApiKeyStore {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	api_key_path: PathBuf,
+	config_keys: Vec<ApiKey>,
+	inner: Mutex<ApiKeyStoreInner>,
+}
+
+impl ApiKeyStore {
+	/// Loads the persisted API keys.
+	///
+	/// `admin_key` is the hex-encoded key read from the `api_key` file at `api_key_path`. If it
+	/// doesn't match the persisted `admin` key, e.g. because the file was replaced, it takes
+	/// precedence.
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, api_key_path: PathBuf, admin_key: String,
+		config_keys: Vec<ApiKey>,
+	) -> io::Result<Self> {
+		let mut managed_keys = HashMap::new();
+		let mut page_token = None;
+		loop {
+			let list_response = paginated_kv_store.list(
+				API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE,
+				API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE,
+				page_token,
+			)?;
+			for name in list_response.keys {
+				let bytes = paginated_kv_store.read(
+					API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE,
+					API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE,
+					&name,
+				)?;
+				let record = ApiKeyRecord::decode(bytes.as_slice()).map_err(|e| {
+					io::Error::new(
+						io::ErrorKind::InvalidData,
+						format!("Failed to decode API key '{}': {}", name, e),
+					)
+				})?;
+				if config_keys.iter().any(|api_key| api_key.name == name) {
+					return Err(io::Error::new(
+						io::ErrorKind::InvalidInput,
+						format!(
+							"API key '{}' is defined in the config file, but was also created via the API",
+							name
+						),
+					));
+				}
+				managed_keys.insert(name, ManagedApiKey::from_record(record)?);
+			}
+			page_token = list_response.next_page_token;
+			if page_token.is_none() {
+				break;
+			}
+		}
+
+		let admin_key_is_current = managed_keys
+			.get(DEFAULT_API_KEY_NAME)
+			.is_some_and(|admin| admin.key == admin_key && admin.scope == Scope::Admin);
+		if !admin_key_is_current {
+			let admin = ManagedApiKey::new(
+				DEFAULT_API_KEY_NAME.to_string(),
+				admin_key,
+				Scope::Admin,
+				current_time_secs(),
+			);
+			persist_api_key(&*paginated_kv_store, &admin)?;
+			managed_keys.insert(DEFAULT_API_KEY_NAME.to_string(), admin);
+		}
+
+		let inner = ApiKeyStoreInner { managed_keys, config_keys_last_used_at: HashMap::new() };
+		Ok(Self { paginated_kv_store, api_key_path, config_keys, inner: Mutex::new(inner) })
+	}
+
+	/// Returns all keys that are currently accepted, including rotated keys that are still within
+	/// their grace period.
+	pub(crate) fn active_keys(&self) -> Vec<ApiKey> {
+		let now = current_time_secs();
+		let inner = self.inner.lock().unwrap();
+		let mut api_keys = self.config_keys.clone();
+		for managed in inner.managed_keys.values() {
+			let name = &managed.info.name;
+			api_keys.push(ApiKey {
+				name: name.clone(),
+				key: managed.key.clone(),
+				scope: managed.scope,
+			});
+			if let Some(previous_key) = managed.active_previous_key(now) {
+				api_keys.push(ApiKey {
+					name: name.clone(),
+					key: previous_key.clone(),
+					scope: managed.scope,
+				});
+			}
+		}
+		api_keys
+	}
+
+	/// Returns whether requests signed with the given hex-encoded key are currently accepted.
+	///
+	/// A key that was rotated out is no longer accepted once its grace period ends, even though the
+	/// name it was issued under still exists.
+	pub(crate) fn is_active(&self, key: &str) -> bool {
+		if self.config_keys.iter().any(|api_key| api_key.key == key) {
+			return true;
+		}
+		let now = current_time_secs();
+		self.inner.lock().unwrap().managed_keys.values().any(|managed| {
+			managed.key == key
+				|| managed.active_previous_key(now).is_some_and(|previous_key| previous_key == key)
+		})
+	}
+
+	/// Returns the current scope of the API key with the given name, or `None` if it doesn't
+	/// exist.
+	pub(crate) fn scope(&self, name: &str) -> Option<Scope> {
+		if let Some(api_key) = self.config_keys.iter().find(|api_key| api_key.name == name) {
+			return Some(api_key.scope);
+		}
+		self.inner.lock().unwrap().managed_keys.get(name).map(|managed| managed.scope)
+	}
+
+	/// Records that the API key with the given name was used to authenticate a request.
+	pub(crate) fn record_use(&self, name: &str) {
+		let now = current_time_secs();
+		let mut inner = self.inner.lock().unwrap();
+		let managed = match inner.managed_keys.get_mut(name) {
+			Some(managed) => managed,
+			None => {
+				inner.config_keys_last_used_at.insert(name.to_string(), now);
+				return;
+			},
+		};
+
+		managed.info.last_used_at = Some(now);
+		let persist_due = managed
+			.persisted_last_used_at
+			.is_none_or(|persisted| now >= persisted + LAST_USED_PERSIST_INTERVAL_SECS);
+		if persist_due {
+			match persist_api_key(&*self.paginated_kv_store, managed) {
+				Ok(()) => managed.persisted_last_used_at = Some(now),
+				Err(e) => warn!("Failed to persist last use of API key '{name}': {e}"),
+			}
+		}
+	}
+
+	/// Creates a new API key, returning the hex-encoded key.
+	pub(crate) fn create(&self, name: String, scope: Scope) -> Result<String, LdkServerError> {
+		if !is_valid_api_key_name(&name) {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!(
+					"Invalid API key name '{name}', it must be at most {MAX_API_KEY_NAME_LEN} characters long and only contain ASCII letters, digits, '-' and '_'"
+				),
+			));
+		}
+
+		let mut inner = self.inner.lock().unwrap();
+		if inner.managed_keys.contains_key(&name)
+			|| self.config_keys.iter().any(|api_key| api_key.name == name)
+		{
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!("An API key named '{name}' already exists"),
+			));
+		}
+
+		let key = generate_key_bytes()?.to_lower_hex_string();
+		let managed = ManagedApiKey::new(name.clone(), key.clone(), scope, current_time_secs());
+		persist_api_key(&*self.paginated_kv_store, &managed)
+			.map_err(|e| persist_error(&name, e))?;
+		inner.managed_keys.insert(name, managed);
+		Ok(key)
+	}
+
+	/// Returns the metadata of all API keys, sorted by name.
+	pub(crate) fn list(&self) -> Vec<ApiKeyInfo> {
+		let now = current_time_secs();
+		let inner = self.inner.lock().unwrap();
+		let mut api_keys: Vec<ApiKeyInfo> = inner
+			.managed_keys
+			.values()
+			.map(|managed| {
+				let mut info = managed.info.clone();
+				if managed.active_previous_key(now).is_none() {
+					info.previous_key_expires_at = None;
+				}
+				info
+			})
+			.collect();
+		api_keys.extend(self.config_keys.iter().map(|api_key| {
+			let (scope, max_send_amount_msat) = api_key.scope.to_proto();
+			ApiKeyInfo {
+				name: api_key.name.clone(),
+				scope: scope as i32,
+				max_send_amount_msat,
+				created_at: None,
+				last_used_at: inner.config_keys_last_used_at.get(&api_key.name).copied(),
+				previous_key_expires_at: None,
+				from_config: true,
+			}
+		}));
+		api_keys.sort_by(|a, b| a.name.cmp(&b.name));
+		api_keys
+	}
+
+	/// Replaces the API key with the given name with a newly generated one, returning the
+	/// hex-encoded key. The replaced key is still accepted for `grace_period_secs`.
+	///
+	/// Rotating the `admin` key also replaces the `api_key` file.
+	pub(crate) fn rotate(
+		&self, name: &str, grace_period_secs: u64,
+	) -> Result<String, LdkServerError> {
+		let mut inner = self.inner.lock().unwrap();
+		let managed = self.managed_key_mut(&mut inner, name)?;
+
+		let now = current_time_secs();
+		let key_bytes = generate_key_bytes()?;
+		let key = key_bytes.to_lower_hex_string();
+
+		let mut info = managed.info.clone();
+		info.created_at = Some(now);
+		let previous_key = if grace_period_secs > 0 {
+			info.previous_key_expires_at = Some(now.saturating_add(grace_period_secs));
+			Some(managed.key.clone())
+		} else {
+			info.previous_key_expires_at = None;
+			None
+		};
+		let rotated = ManagedApiKey {
+			info,
+			scope: managed.scope,
+			key: key.clone(),
+			previous_key,
+			persisted_last_used_at: managed.persisted_last_used_at,
+		};
+		persist_api_key(&*self.paginated_kv_store, &rotated).map_err(|e| persist_error(name, e))?;
+
+		if name == DEFAULT_API_KEY_NAME {
+			replace_api_key_file(&self.api_key_path, &key_bytes).map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Failed to write new API key to {}: {e}", self.api_key_path.display()),
+				)
+			})?;
+		}
+
+		*managed = rotated;
+		Ok(key)
+	}
+
+	/// Revokes the API key with the given name. Requests made with it are rejected immediately.
+	pub(crate) fn revoke(&self, name: &str) -> Result<(), LdkServerError> {
+		if name == DEFAULT_API_KEY_NAME {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				"The admin API key can't be revoked, rotate it instead",
+			));
+		}
+
+		let mut inner = self.inner.lock().unwrap();
+		self.managed_key_mut(&mut inner, name)?;
+		self.paginated_kv_store
+			.remove(
+				API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE,
+				API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE,
+				name,
+			)
+			.map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Failed to remove API key '{name}': {e}"),
+				)
+			})?;
+		inner.managed_keys.remove(name);
+		Ok(())
+	}
+
+	fn managed_key_mut<'a>(
+		&self, inner: &'a mut ApiKeyStoreInner, name: &str,
+	) -> Result<&'a mut ManagedApiKey, LdkServerError> {
+		if self.config_keys.iter().any(|api_key| api_key.name == name) {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!(
+					"API key '{name}' is defined in the config file and can only be changed there"
+				),
+			));
+		}
+		inner.managed_keys.get_mut(name).ok_or_else(|| {
+			LdkServerError::new(InvalidRequestError, format!("Unknown API key '{name}'"))
+		})
+	}
+}
+
+fn persist_api_key(
+	paginated_kv_store: &dyn PaginatedKVStore, managed: &ManagedApiKey,
+) -> io::Result<()> {
+	paginated_kv_store.write(
+		API_KEYS_PERSISTENCE_PRIMARY_NAMESPACE,
+		API_KEYS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&managed.info.name,
+		managed.info.created_at.unwrap_or_default() as i64,
+		&managed.to_record().encode_to_vec(),
+	)
+}
+
+fn persist_error(name: &str, e: io::Error) -> LdkServerError {
+	LdkServerError::new(InternalServerError, format!("Failed to persist API key '{name}': {e}"))
+}
+
+/// Atomically replaces the `api_key` file, keeping its 0400 permissions.
+fn replace_api_key_file(api_key_path: &Path, key_bytes: &[u8]) -> io::Result<()> {
+	let tmp_path = api_key_path.with_extension("tmp");
+	match fs::remove_file(&tmp_path) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+		Err(e) => return Err(e),
+	}
+	write_new(&tmp_path, key_bytes, 0o400)?;
+	fs::rename(&tmp_path, api_key_path)
+}
+
+fn generate_key_bytes() -> Result<[u8; 32], LdkServerError> {
+	let mut key_bytes = [0u8; 32];
+	getrandom::getrandom(&mut key_bytes).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to generate API key: {e}"))
+	})?;
+	Ok(key_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::endpoints::{
+		CLOSE_CHANNEL_PATH, FORCE_CLOSE_CHANNEL_PATH, OPEN_CHANNEL_PATH, SIGN_MESSAGE_PATH,
+	};
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::random_storage_path;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn test_api_key_store(dir_name: &str, config_keys: Vec<ApiKey>) -> (ApiKeyStore, PathBuf) {
+		let storage_dir = random_storage_path().join(dir_name);
+		let store = SqliteStore::new(storage_dir.clone(), None, None).unwrap();
+		let api_key_path = storage_dir.join("api_key");
+		let admin_key_bytes = [1u8; 32];
+		write_new(&api_key_path, &admin_key_bytes, 0o400).unwrap();
+		let api_key_store = ApiKeyStore::new(
+			Arc::new(store),
+			api_key_path,
+			admin_key_bytes.to_lower_hex_string(),
+			config_keys,
+		)
+		.unwrap();
+		(api_key_store, storage_dir)
+	}
+
+	#[test]
+	fn test_admin_scope_permits_everything() {
+		for method in
+			[GET_NODE_INFO_PATH, BOLT11_RECEIVE_PATH, ONCHAIN_SEND_PATH, OPEN_CHANNEL_PATH]
+		{
+			assert!(Scope::Admin.permits(method));
+		}
+		assert!(Scope::Admin.permits("SomeFutureMethod"));
+	}
+
+	#[test]
+	fn test_read_only_scope() {
+		assert!(Scope::ReadOnly.permits(GET_NODE_INFO_PATH));
+		assert!(Scope::ReadOnly.permits(LIST_PAYMENTS_PATH));
+		assert!(Scope::ReadOnly.permits(FIND_PAYMENTS_PATH));
+		assert!(Scope::ReadOnly.permits(LIST_INVOICES_PATH));
+		assert!(Scope::ReadOnly.permits(LIST_CLOSED_CHANNELS_PATH));
+		assert!(Scope::ReadOnly.permits(LIST_CLAIMABLE_PAYMENTS_PATH));
+		assert!(Scope::ReadOnly.permits(LIST_ONCHAIN_TRANSACTIONS_PATH));
+		assert!(Scope::ReadOnly.permits(QUERY_ROUTES_PATH));
+		assert!(!Scope::ReadOnly.permits(SEND_PROBES_PATH));
+		assert!(Scope::ReadOnly.permits(SUBSCRIBE_EVENTS_PATH));
+		assert!(!Scope::ReadOnly.permits(BOLT11_RECEIVE_PATH));
+		assert!(!Scope::ReadOnly.permits(BOLT11_SEND_PATH));
+		assert!(!Scope::ReadOnly.permits(SIGN_MESSAGE_PATH));
+		assert!(!Scope::ReadOnly.permits(UPDATE_LABEL_PATH));
+	}
+
+	#[test]
+	fn test_invoice_only_scope() {
+		assert!(Scope::InvoiceOnly.permits(GET_PAYMENT_DETAILS_PATH));
+		assert!(Scope::InvoiceOnly.permits(BOLT11_RECEIVE_PATH));
+		assert!(Scope::InvoiceOnly.permits(BOLT12_RECEIVE_PATH));
+		assert!(!Scope::InvoiceOnly.permits(UPDATE_LABEL_PATH));
+		assert!(!Scope::InvoiceOnly.permits(ONCHAIN_SEND_PATH));
+		assert!(!Scope::InvoiceOnly.permits(BOLT11_SEND_PATH));
+		assert!(!Scope::InvoiceOnly.permits(FORCE_CLOSE_CHANNEL_PATH));
+	}
+
+	#[test]
+	fn test_send_scope() {
+		let scope = Scope::Send { max_amount_msat: None };
+		assert!(scope.permits(BOLT11_RECEIVE_PATH));
+		assert!(scope.permits(BOLT11_SEND_PATH));
+		assert!(scope.permits(ONCHAIN_SEND_PATH));
+		assert!(!scope.permits(CLOSE_CHANNEL_PATH));
+		assert!(!scope.permits(OPEN_CHANNEL_PATH));
+		assert!(!scope.permits("SomeFutureMethod"));
+	}
+
+	#[test]
+	fn test_check_send_amount() {
+		assert!(Scope::Admin.check_send_amount(None).is_ok());
+		assert!(Scope::Send { max_amount_msat: None }.check_send_amount(None).is_ok());
+
+		let scope = Scope::Send { max_amount_msat: Some(1_000) };
+		assert!(scope.check_send_amount(Some(1_000)).is_ok());
+
+		let err = scope.check_send_amount(Some(1_001)).unwrap_err();
+		assert_eq!(err.error_code, PermissionDeniedError);
+
+		let err = scope.check_send_amount(None).unwrap_err();
+		assert_eq!(err.error_code, PermissionDeniedError);
+	}
+
+	#[test]
+	fn test_api_key_store_lifecycle() {
+		let config_key =
+			ApiKey { name: "monitoring".to_string(), key: "aa".repeat(32), scope: Scope::ReadOnly };
+		let (api_key_store, storage_dir) =
+			test_api_key_store("api_key_store_lifecycle", vec![config_key]);
+
+		let key = api_key_store.create("wallet".to_string(), Scope::InvoiceOnly).unwrap();
+		assert!(api_key_store.is_active(&key));
+		assert!(api_key_store.is_active(&"aa".repeat(32)));
+		assert_eq!(api_key_store.scope("wallet"), Some(Scope::InvoiceOnly));
+		assert_eq!(api_key_store.scope("monitoring"), Some(Scope::ReadOnly));
+		assert_eq!(api_key_store.scope("unknown"), None);
+		assert!(api_key_store
+			.active_keys()
+			.iter()
+			.any(|api_key| api_key.key == key && api_key.scope == Scope::InvoiceOnly));
+
+		// Names must be unique and well-formed.
+		for name in ["wallet", "monitoring", DEFAULT_API_KEY_NAME, "", "not valid"] {
+			let err = api_key_store.create(name.to_string(), Scope::ReadOnly).unwrap_err();
+			assert_eq!(err.error_code, InvalidRequestError);
+		}
+
+		let names: Vec<_> = api_key_store.list().into_iter().map(|info| info.name).collect();
+		assert_eq!(names, vec!["admin", "monitoring", "wallet"]);
+
+		// The previous key is accepted during the grace period.
+		let rotated_key = api_key_store.rotate("wallet", 60).unwrap();
+		let active_keys: Vec<_> =
+			api_key_store.active_keys().into_iter().map(|api_key| api_key.key).collect();
+		assert!(active_keys.contains(&key));
+		assert!(active_keys.contains(&rotated_key));
+		assert!(api_key_store.is_active(&key));
+
+		// Without a grace period, the previous key is rejected immediately.
+		let rotated_again_key = api_key_store.rotate("wallet", 0).unwrap();
+		let active_keys: Vec<_> =
+			api_key_store.active_keys().into_iter().map(|api_key| api_key.key).collect();
+		assert!(!active_keys.contains(&rotated_key));
+		assert!(active_keys.contains(&rotated_again_key));
+		assert!(!api_key_store.is_active(&rotated_key));
+		assert!(api_key_store.is_active(&rotated_again_key));
+
+		// Keys from the config file can't be managed via the API.
+		assert!(api_key_store.rotate("monitoring", 0).is_err());
+		assert!(api_key_store.revoke("monitoring").is_err());
+		assert!(api_key_store.revoke(DEFAULT_API_KEY_NAME).is_err());
+
+		api_key_store.revoke("wallet").unwrap();
+		assert!(!api_key_store.is_active(&rotated_again_key));
+		assert_eq!(api_key_store.scope("wallet"), None);
+		assert!(api_key_store.revoke("wallet").is_err());
+
+		// Rotating the admin key replaces the `api_key` file.
+		let admin_key = api_key_store.rotate(DEFAULT_API_KEY_NAME, 0).unwrap();
+		let file_key = fs::read(storage_dir.join("api_key")).unwrap().to_lower_hex_string();
+		assert_eq!(admin_key, file_key);
+	}
+
+	#[test]
+	fn test_api_key_store_reload() {
+		let (api_key_store, storage_dir) = test_api_key_store("api_key_store_reload", Vec::new());
+		let key = api_key_store
+			.create("payer".to_string(), Scope::Send { max_amount_msat: Some(5_000) })
+			.unwrap();
+		let admin_key = api_key_store.rotate(DEFAULT_API_KEY_NAME, 60).unwrap();
+		drop(api_key_store);
+
+		let store = SqliteStore::new(storage_dir.clone(), None, None).unwrap();
+		let reloaded = ApiKeyStore::new(
+			Arc::new(store),
+			storage_dir.join("api_key"),
+			admin_key.clone(),
+			Vec::new(),
+		)
+		.unwrap();
+		let active_keys = reloaded.active_keys();
+		assert!(active_keys.iter().any(|api_key| api_key.name == "payer"
+			&& api_key.key == key
+			&& api_key.scope == Scope::Send { max_amount_msat: Some(5_000) }));
+		assert!(active_keys
+			.iter()
+			.any(|api_key| api_key.name == DEFAULT_API_KEY_NAME && api_key.key == admin_key));
+		assert_eq!(active_keys.iter().filter(|k| k.name == DEFAULT_API_KEY_NAME).count(), 2);
+	}
+}
@@ -0,0 +1,362 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ldk_server_grpc::api::ListClaimablePaymentsResponse;
+use ldk_server_grpc::types::{ClaimablePayment, PageToken};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, AUTO_CLAIM_PREIMAGES_PERSISTENCE_PRIMARY_NAMESPACE,
+	AUTO_CLAIM_PREIMAGES_PERSISTENCE_SECONDARY_NAMESPACE,
+	CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+	CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+
+/// How many blocks before their claim deadline claimable payments are resolved if no safety margin
+/// is configured.
+pub(crate) const DEFAULT_SAFETY_MARGIN_BLOCKS: u32 = 2;
+
+/// How often the claim deadlines of claimable payments are checked against the best block.
+pub(crate) const CLAIM_DEADLINE_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The number of blocks before a claimable payment is resolved at which a
+/// `PaymentClaimDeadlineApproaching` event is emitted.
+const CLAIM_DEADLINE_WARNING_BLOCKS: u32 = 6;
+
+/// Determines when claimable payments that weren't claimed or failed are resolved by the server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HodlInvoiceConfig {
+	/// Claimable payments are claimed or failed back this many blocks before their claim deadline.
+	pub(crate) safety_margin_blocks: u32,
+}
+
+impl Default for HodlInvoiceConfig {
+	fn default() -> Self {
+		Self { safety_margin_blocks: DEFAULT_SAFETY_MARGIN_BLOCKS }
+	}
+}
+
+/// The persisted form of a preimage registered via `Bolt11ReceiveForHash`.
+#[derive(Clone, PartialEq, prost::Message)]
+struct AutoClaimPreimageRecord {
+	#[prost(bytes = "vec", tag = "1")]
+	preimage: Vec<u8>,
+}
+
+/// What is due for a claimable payment at the current block height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum DeadlineAction {
+	/// The payment will be resolved within [`CLAIM_DEADLINE_WARNING_BLOCKS`].
+	Warn,
+	/// The payment is to be failed back.
+	Fail,
+	/// The payment is to be claimed with the given preimage.
+	Claim([u8; 32]),
+}
+
+/// Registers the preimage a payment for the invoice with the given payment hash is claimed with if
+/// it is still claimable at its auto-resolve height.
+pub(crate) fn register_auto_claim_preimage(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str, preimage: [u8; 32],
+) -> Result<(), LdkServerError> {
+	write_record(
+		paginated_kv_store,
+		AUTO_CLAIM_PREIMAGES_PERSISTENCE_PRIMARY_NAMESPACE,
+		AUTO_CLAIM_PREIMAGES_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_hash,
+		current_time_secs(),
+		&AutoClaimPreimageRecord { preimage: preimage.to_vec() },
+	)
+}
+
+fn read_auto_claim_preimage(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<Option<[u8; 32]>, LdkServerError> {
+	let record = read_record::<AutoClaimPreimageRecord>(
+		paginated_kv_store,
+		AUTO_CLAIM_PREIMAGES_PERSISTENCE_PRIMARY_NAMESPACE,
+		AUTO_CLAIM_PREIMAGES_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_hash,
+	)?;
+	record
+		.map(|record| {
+			<[u8; 32]>::try_from(record.preimage.as_slice()).map_err(|_| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Invalid auto-claim preimage for payment hash '{payment_hash}'"),
+				)
+			})
+		})
+		.transpose()
+}
+
+/// Records a payment that became claimable, to be resolved `config.safety_margin_blocks` before
+/// its claim deadline unless it is claimed or failed before.
+///
+/// A payment that is recorded again keeps the time it first became claimable.
+pub(crate) fn record_claimable_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, config: &HodlInvoiceConfig, payment_hash: String,
+	payment_id: String, claimable_amount_msat: u64, claim_deadline: Option<u32>,
+) -> Result<ClaimablePayment, LdkServerError> {
+	let created_at = read_claimable_payment(paginated_kv_store, &payment_hash)?
+		.map(|claimable| claimable.created_at)
+		.unwrap_or_else(current_time_secs);
+	let claimable = ClaimablePayment {
+		auto_claim: read_auto_claim_preimage(paginated_kv_store, &payment_hash)?.is_some(),
+		payment_hash,
+		payment_id,
+		claimable_amount_msat,
+		claim_deadline,
+		auto_resolve_height: claim_deadline
+			.map(|deadline| deadline.saturating_sub(config.safety_margin_blocks)),
+		created_at,
+	};
+	write_record(
+		paginated_kv_store,
+		CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+		CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&claimable.payment_hash,
+		claimable.created_at,
+		&claimable,
+	)?;
+	Ok(claimable)
+}
+
+/// Forgets the payment with the given payment hash once it was claimed or failed back.
+///
+/// Its auto-claim preimage is deleted separately with [`remove_auto_claim_preimage`].
+pub(crate) fn remove_claimable_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.remove(
+			CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			payment_hash,
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to remove claimable payment '{payment_hash}': {e}"),
+			)
+		})
+}
+
+/// Deletes the auto-claim preimage registered for the given payment hash once the payment settled
+/// or was failed back, so the preimage isn't kept around after it can no longer be used.
+pub(crate) fn remove_auto_claim_preimage(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.remove(
+			AUTO_CLAIM_PREIMAGES_PERSISTENCE_PRIMARY_NAMESPACE,
+			AUTO_CLAIM_PREIMAGES_PERSISTENCE_SECONDARY_NAMESPACE,
+			payment_hash,
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to remove auto-claim preimage '{payment_hash}': {e}"),
+			)
+		})
+}
+
+fn read_claimable_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<Option<ClaimablePayment>, LdkServerError> {
+	read_record(
+		paginated_kv_store,
+		CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+		CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_hash,
+	)
+}
+
+pub(crate) fn list_claimable_payments(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListClaimablePaymentsResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			CLAIMABLE_PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			CLAIMABLE_PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to list claimable payments: {e}"),
+			)
+		})?;
+
+	let mut claimable_payments = Vec::with_capacity(list_response.keys.len());
+	for payment_hash in list_response.keys {
+		if let Some(claimable) = read_claimable_payment(paginated_kv_store, &payment_hash)? {
+			claimable_payments.push(claimable);
+		}
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListClaimablePaymentsResponse { claimable_payments, next_page_token })
+}
+
+/// Determines which claimable payments are to be warned about or resolved as blocks are mined.
+pub(crate) struct ClaimDeadlineEnforcer {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	/// The payment hashes of the claimable payments that were warned about.
+	warned: HashSet<String>,
+}
+
+impl ClaimDeadlineEnforcer {
+	pub(crate) fn new(paginated_kv_store: Arc<dyn PaginatedKVStore>) -> Self {
+		Self { paginated_kv_store, warned: HashSet::new() }
+	}
+
+	/// Returns the claimable payments that something is due for at `current_height`.
+	///
+	/// Each payment is warned about once, and returned for resolution until it is removed.
+	pub(crate) fn due_actions(
+		&mut self, current_height: u32,
+	) -> Result<Vec<(ClaimablePayment, DeadlineAction)>, LdkServerError> {
+		let mut claimable_payments = Vec::new();
+		let mut page_token = None;
+		loop {
+			let response = list_claimable_payments(&*self.paginated_kv_store, page_token)?;
+			claimable_payments.extend(response.claimable_payments);
+			page_token = response.next_page_token;
+			if page_token.is_none() {
+				break;
+			}
+		}
+		let payment_hashes: HashSet<&str> =
+			claimable_payments.iter().map(|claimable| claimable.payment_hash.as_str()).collect();
+		self.warned.retain(|payment_hash| payment_hashes.contains(payment_hash.as_str()));
+
+		let mut actions = Vec::new();
+		for claimable in claimable_payments {
+			let Some(auto_resolve_height) = claimable.auto_resolve_height else {
+				continue;
+			};
+			if current_height >= auto_resolve_height {
+				let action = match read_auto_claim_preimage(
+					&*self.paginated_kv_store,
+					&claimable.payment_hash,
+				)? {
+					Some(preimage) => DeadlineAction::Claim(preimage),
+					None => DeadlineAction::Fail,
+				};
+				actions.push((claimable, action));
+			} else if current_height.saturating_add(CLAIM_DEADLINE_WARNING_BLOCKS)
+				>= auto_resolve_height
+				&& self.warned.insert(claimable.payment_hash.clone())
+			{
+				actions.push((claimable, DeadlineAction::Warn));
+			}
+		}
+		Ok(actions)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn record(store: &dyn PaginatedKVStore, payment_hash: &str, claim_deadline: Option<u32>) {
+		let config = HodlInvoiceConfig { safety_margin_blocks: 10 };
+		record_claimable_payment(
+			store,
+			&config,
+			payment_hash.to_string(),
+			format!("id-{payment_hash}"),
+			1_000,
+			claim_deadline,
+		)
+		.unwrap();
+	}
+
+	fn actions(
+		enforcer: &mut ClaimDeadlineEnforcer, current_height: u32,
+	) -> Vec<(String, DeadlineAction)> {
+		let mut actions: Vec<_> = enforcer
+			.due_actions(current_height)
+			.unwrap()
+			.into_iter()
+			.map(|(claimable, action)| (claimable.payment_hash, action))
+			.collect();
+		actions.sort_by(|a, b| a.0.cmp(&b.0));
+		actions
+	}
+
+	#[test]
+	fn test_record_and_list_claimable_payments() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_record_and_list_claimable_payments"));
+		register_auto_claim_preimage(&*store, "bb", [7; 32]).unwrap();
+		record(&*store, "aa", Some(150));
+		record(&*store, "bb", None);
+
+		let mut claimable_payments =
+			list_claimable_payments(&*store, None).unwrap().claimable_payments;
+		claimable_payments.sort_by(|a, b| a.payment_hash.cmp(&b.payment_hash));
+		assert_eq!(claimable_payments.len(), 2);
+		assert_eq!(claimable_payments[0].payment_id, "id-aa");
+		assert_eq!(claimable_payments[0].auto_resolve_height, Some(140));
+		assert!(!claimable_payments[0].auto_claim);
+		assert_eq!(claimable_payments[1].auto_resolve_height, None);
+		assert!(claimable_payments[1].auto_claim);
+
+		remove_claimable_payment(&*store, "bb").unwrap();
+		let claimable_payments = list_claimable_payments(&*store, None).unwrap().claimable_payments;
+		assert_eq!(claimable_payments.len(), 1);
+		assert!(read_auto_claim_preimage(&*store, "bb").unwrap().is_some());
+		remove_auto_claim_preimage(&*store, "bb").unwrap();
+		assert!(read_auto_claim_preimage(&*store, "bb").unwrap().is_none());
+		// Removing a payment or preimage that isn't recorded succeeds.
+		remove_claimable_payment(&*store, "bb").unwrap();
+		remove_auto_claim_preimage(&*store, "bb").unwrap();
+	}
+
+	#[test]
+	fn test_due_actions() {
+		let store: Arc<dyn PaginatedKVStore> = Arc::new(test_store("test_due_actions"));
+		register_auto_claim_preimage(&*store, "bb", [7; 32]).unwrap();
+		// Resolved at heights 140, 150 and never.
+		record(&*store, "aa", Some(150));
+		record(&*store, "bb", Some(160));
+		record(&*store, "cc", None);
+
+		let mut enforcer = ClaimDeadlineEnforcer::new(Arc::clone(&store));
+		assert!(actions(&mut enforcer, 100).is_empty());
+		assert_eq!(actions(&mut enforcer, 134), vec![("aa".to_string(), DeadlineAction::Warn)]);
+		// Warnings are only emitted once.
+		assert!(actions(&mut enforcer, 135).is_empty());
+
+		assert_eq!(
+			actions(&mut enforcer, 145),
+			vec![
+				("aa".to_string(), DeadlineAction::Fail),
+				("bb".to_string(), DeadlineAction::Warn)
+			]
+		);
+		// Payments are due until they're removed.
+		remove_claimable_payment(&*store, "aa").unwrap();
+		assert_eq!(
+			actions(&mut enforcer, 150),
+			vec![("bb".to_string(), DeadlineAction::Claim([7; 32]))]
+		);
+	}
+}
@@ -0,0 +1,170 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use ldk_server_grpc::api::ListClosedChannelsResponse;
+use ldk_server_grpc::types::{Channel, ClosedChannel, PageToken};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, CLOSED_CHANNELS_PERSISTENCE_PRIMARY_NAMESPACE,
+	CLOSED_CHANNELS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::labels::{read_label, LabelTarget};
+
+/// Builds the record of a closed channel from the channel's details as last observed while it was
+/// open, if any.
+///
+/// The closure itself, i.e. its state, reason, initiator and time, is left for the caller to fill
+/// in.
+pub(crate) fn closed_channel_from_snapshot(
+	channel_id: String, user_channel_id: String, counterparty_node_id: String,
+	snapshot: Option<&Channel>,
+) -> ClosedChannel {
+	let mut closed_channel =
+		ClosedChannel { channel_id, user_channel_id, counterparty_node_id, ..Default::default() };
+	if let Some(channel) = snapshot {
+		closed_channel.funding_txo = channel.funding_txo.clone();
+		closed_channel.channel_value_sats = Some(channel.channel_value_sats);
+		closed_channel.last_local_balance_msat = Some(
+			channel.outbound_capacity_msat
+				+ channel.unspendable_punishment_reserve.unwrap_or(0) * 1000,
+		);
+		closed_channel.is_outbound = Some(channel.is_outbound);
+	}
+	closed_channel
+}
+
+/// Records a closed channel, keyed by its channel ID.
+pub(crate) fn record_closed_channel(
+	paginated_kv_store: &dyn PaginatedKVStore, closed_channel: &ClosedChannel,
+) -> Result<(), LdkServerError> {
+	write_record(
+		paginated_kv_store,
+		CLOSED_CHANNELS_PERSISTENCE_PRIMARY_NAMESPACE,
+		CLOSED_CHANNELS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&closed_channel.channel_id,
+		closed_channel.closed_at,
+		closed_channel,
+	)
+}
+
+pub(crate) fn list_closed_channels(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListClosedChannelsResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			CLOSED_CHANNELS_PERSISTENCE_PRIMARY_NAMESPACE,
+			CLOSED_CHANNELS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list closed channels: {e}"))
+		})?;
+
+	let mut closed_channels = Vec::with_capacity(list_response.keys.len());
+	for channel_id in list_response.keys {
+		let Some(mut closed_channel) = read_record::<ClosedChannel>(
+			paginated_kv_store,
+			CLOSED_CHANNELS_PERSISTENCE_PRIMARY_NAMESPACE,
+			CLOSED_CHANNELS_PERSISTENCE_SECONDARY_NAMESPACE,
+			&channel_id,
+		)?
+		else {
+			continue;
+		};
+		let user_label =
+			read_label(paginated_kv_store, LabelTarget::Channel, &closed_channel.user_channel_id)?
+				.unwrap_or_default();
+		closed_channel.label = user_label.label;
+		closed_channel.metadata = user_label.metadata;
+		closed_channels.push(closed_channel);
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListClosedChannelsResponse { closed_channels, next_page_token })
+}
+
+/// Returns whether a channel with the given user channel ID was recorded as closed.
+pub(crate) fn is_closed_channel(
+	paginated_kv_store: &dyn PaginatedKVStore, user_channel_id: &str,
+) -> Result<bool, LdkServerError> {
+	let mut page_token = None;
+	loop {
+		let response = list_closed_channels(paginated_kv_store, page_token)?;
+		if response.closed_channels.iter().any(|channel| channel.user_channel_id == user_channel_id)
+		{
+			return Ok(true);
+		}
+		match response.next_page_token {
+			Some(next_page_token) => page_token = Some(next_page_token),
+			None => return Ok(false),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use std::collections::BTreeMap;
+
+	use ldk_server_grpc::types::{ChannelState, OutPoint};
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+	use crate::util::labels::{new_label, set_label};
+
+	#[test]
+	fn test_list_closed_channels() {
+		let store = test_store("test_list_closed_channels");
+
+		let snapshot = Channel {
+			funding_txo: Some(OutPoint { txid: "ab".repeat(32), vout: 1 }),
+			channel_value_sats: 100_000,
+			outbound_capacity_msat: 40_000_000,
+			unspendable_punishment_reserve: Some(1_000),
+			is_outbound: true,
+			..Default::default()
+		};
+		let mut closed = closed_channel_from_snapshot(
+			"aa".repeat(32),
+			"1".to_string(),
+			"02".repeat(33),
+			Some(&snapshot),
+		);
+		closed.state = ChannelState::Closed as i32;
+		closed.closed_at = 1_700_000_000;
+		assert_eq!(closed.channel_value_sats, Some(100_000));
+		assert_eq!(closed.last_local_balance_msat, Some(41_000_000));
+		assert_eq!(closed.is_outbound, Some(true));
+		record_closed_channel(&store, &closed).unwrap();
+
+		// Channels that were never observed open are recorded without their details.
+		let mut open_failed =
+			closed_channel_from_snapshot("bb".repeat(32), "2".to_string(), "03".repeat(33), None);
+		open_failed.state = ChannelState::OpenFailed as i32;
+		open_failed.closed_at = 1_700_000_100;
+		assert_eq!(open_failed.funding_txo, None);
+		assert_eq!(open_failed.last_local_balance_msat, None);
+		record_closed_channel(&store, &open_failed).unwrap();
+
+		let user_label = new_label(Some("lsp".to_string()), BTreeMap::new()).unwrap();
+		set_label(&store, LabelTarget::Channel, "1", &user_label);
+
+		let response = list_closed_channels(&store, None).unwrap();
+		let channel_ids: Vec<&str> =
+			response.closed_channels.iter().map(|c| c.channel_id.as_str()).collect();
+		assert_eq!(channel_ids, vec!["bb".repeat(32), "aa".repeat(32)]);
+		assert_eq!(response.closed_channels[1].label.as_deref(), Some("lsp"));
+		assert_eq!(response.closed_channels[0].label, None);
+
+		assert!(is_closed_channel(&store, "2").unwrap());
+		assert!(!is_closed_channel(&store, "3").unwrap());
+	}
+}
@@ -7,6 +7,7 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+use std::collections::HashMap;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -14,6 +15,7 @@ use std::time::Duration;
 use std::{fs, io};
 
 use clap::Parser;
+use hex::FromHex;
 use ldk_node::bitcoin::secp256k1::PublicKey;
 use ldk_node::bitcoin::Network;
 use ldk_node::config::{AsyncPaymentsRole, HRNResolverConfig, HumanReadableNamesConfig};
@@ -21,15 +23,30 @@ use ldk_node::lightning::ln::msgs::SocketAddress;
 use ldk_node::lightning::routing::gossip::NodeAlias;
 use ldk_node::liquidity::LSPS2ServiceConfig;
 use ldk_node::probing::{ProbingConfig, ProbingConfigBuilder};
+use ldk_server_grpc::events::EventType;
 use log::LevelFilter;
 use serde::{Deserialize, Serialize};
 
+use crate::util::approvals::{ApprovalConfig, DEFAULT_APPROVAL_EXPIRY_SECS};
+use crate::util::auth::{
+	is_valid_api_key_name, ApiKey, ClientCertIdentity, ClientCertMatcher, Identity, Scope,
+	DEFAULT_API_KEY_NAME,
+};
+use crate::util::claimable_payments::{HodlInvoiceConfig, DEFAULT_SAFETY_MARGIN_BLOCKS};
+use crate::util::event_log::{EventLogConfig, DEFAULT_EVENT_RETENTION_DAYS};
+use crate::util::spend_limits::{SpendBudget, SpendLimits};
+use crate::util::webhooks::{WebhookConfig, DEFAULT_WEBHOOK_MAX_ATTEMPTS};
+
 const DEFAULT_GRPC_SERVICE_ADDRESS: &str = "127.0.0.1:3536";
 const DEFAULT_PATHFINDING_SCORES_SOURCE_URL: &str =
 	"https://rapidsync.lightningdevkit.org/scoring/scorer.bin";
 const DEFAULT_LOG_MAX_SIZE_MB: u64 = 50;
 const DEFAULT_LOG_ROTATION_INTERVAL_HOURS: u64 = 24;
 const DEFAULT_LOG_MAX_FILES: usize = 5;
+const DEFAULT_UNIX_SOCKET_MODE: u32 = 0o600;
+/// The name of the identity requests on the Unix socket are authenticated as, unless they are
+/// signed with an API key.
+pub(crate) const UNIX_SOCKET_IDENTITY_NAME: &str = "unix_socket";
 
 #[cfg(not(test))]
 const DEFAULT_CONFIG_FILE: &str = "config.toml";
@@ -77,6 +94,15 @@ pub struct Config {
 	pub metrics_password: Option<String>,
 	pub tor_config: Option<TorConfig>,
 	pub hrn_config: HumanReadableNamesConfig,
+	pub api_keys: Vec<ApiKey>,
+	pub spend_limits: SpendLimits,
+	/// The spending limits of API keys and client identities, by name.
+	pub key_spend_limits: HashMap<String, SpendLimits>,
+	pub approval_config: Option<ApprovalConfig>,
+	pub unix_socket_config: Option<UnixSocketConfig>,
+	pub webhooks: Vec<WebhookConfig>,
+	pub hodl_invoice_config: HodlInvoiceConfig,
+	pub event_log_config: EventLogConfig,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -91,6 +117,24 @@ pub struct TlsConfig {
 	pub cert_path: Option<String>,
 	pub key_path: Option<String>,
 	pub hosts: Vec<String>,
+	pub client_ca_path: Option<String>,
+	pub client_identities: Vec<ClientCertIdentity>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnixSocketConfig {
+	pub path: PathBuf,
+	/// The permissions of the socket file, e.g. `0o660`.
+	pub mode: u32,
+	/// The user owning the socket file, by name or uid.
+	pub owner: Option<String>,
+	/// The group owning the socket file, by name or gid.
+	pub group: Option<String>,
+	/// Whether connections on the socket use TLS, with the same certificate as the TCP listener.
+	pub tls: bool,
+	/// The identity requests on the socket that aren't signed with an API key are authenticated
+	/// as.
+	pub identity: Identity,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -125,6 +169,7 @@ struct ConfigBuilder {
 	alias: Option<String>,
 	network: Option<Network>,
 	tls_config: Option<TlsConfig>,
+	tls_client_identities: Option<Vec<ClientIdentityTomlConfig>>,
 	grpc_service_address: Option<String>,
 	storage_dir_path: Option<String>,
 	electrum_url: Option<String>,
@@ -152,6 +197,13 @@ struct ConfigBuilder {
 	metrics_password: Option<String>,
 	tor_proxy_address: Option<String>,
 	hrn: Option<HrnTomlConfig>,
+	api_keys: Option<Vec<ApiKeyTomlConfig>>,
+	spend_limits: Option<SpendLimitsTomlConfig>,
+	approvals: Option<ApprovalsTomlConfig>,
+	unix_socket: Option<UnixSocketTomlConfig>,
+	webhooks: Option<Vec<WebhookTomlConfig>>,
+	hodl_invoices: Option<HodlInvoicesTomlConfig>,
+	events: Option<EventsTomlConfig>,
 }
 
 impl ConfigBuilder {
@@ -213,7 +265,10 @@ impl ConfigBuilder {
 				cert_path: tls.cert_path,
 				key_path: tls.key_path,
 				hosts: tls.hosts.unwrap_or_default(),
+				client_ca_path: tls.client_ca_path,
+				client_identities: Vec::new(),
 			});
+			self.tls_client_identities = tls.client_identities;
 		}
 
 		if let Some(metrics) = toml.metrics {
@@ -235,6 +290,34 @@ impl ConfigBuilder {
 		if let Some(hrn) = toml.hrn {
 			self.hrn = Some(hrn);
 		}
+
+		if let Some(api_keys) = toml.api_keys {
+			self.api_keys = Some(api_keys);
+		}
+
+		if let Some(spend_limits) = toml.spend_limits {
+			self.spend_limits = Some(spend_limits);
+		}
+
+		if let Some(approvals) = toml.approvals {
+			self.approvals = Some(approvals);
+		}
+
+		if let Some(unix_socket) = toml.unix_socket {
+			self.unix_socket = Some(unix_socket);
+		}
+
+		if let Some(webhooks) = toml.webhooks {
+			self.webhooks = Some(webhooks);
+		}
+
+		if let Some(hodl_invoices) = toml.hodl_invoices {
+			self.hodl_invoices = Some(hodl_invoices);
+		}
+
+		if let Some(events) = toml.events {
+			self.events = Some(events);
+		}
 	}
 
 	fn merge_args(&mut self, args: &ArgsConfig) {
@@ -582,12 +665,61 @@ impl ConfigBuilder {
 			None => HumanReadableNamesConfig::default(),
 		};
 
+		let mut key_spend_limits = HashMap::new();
+		let api_keys = build_api_keys(self.api_keys.unwrap_or_default(), &mut key_spend_limits)?;
+
+		let tls_config = match self.tls_config {
+			Some(mut tls_config) => {
+				tls_config.client_identities = build_client_identities(
+					self.tls_client_identities.unwrap_or_default(),
+					tls_config.client_ca_path.is_some(),
+					&api_keys,
+					&mut key_spend_limits,
+				)?;
+				Some(tls_config)
+			},
+			None => None,
+		};
+
+		let spend_limits = match self.spend_limits {
+			Some(spend_limits) => SpendLimits::try_from(spend_limits)?,
+			None => SpendLimits::default(),
+		};
+
+		let approval_config = self.approvals.map(ApprovalConfig::try_from).transpose()?;
+
+		let unix_socket_config = self
+			.unix_socket
+			.map(|unix_socket| {
+				let client_identities =
+					tls_config.as_ref().map(|tls| tls.client_identities.as_slice()).unwrap_or(&[]);
+				build_unix_socket_config(
+					unix_socket,
+					&api_keys,
+					client_identities,
+					&mut key_spend_limits,
+				)
+			})
+			.transpose()?;
+
+		let webhooks = build_webhooks(self.webhooks.unwrap_or_default())?;
+
+		let hodl_invoice_config = match self.hodl_invoices {
+			Some(hodl_invoices) => HodlInvoiceConfig::try_from(hodl_invoices)?,
+			None => HodlInvoiceConfig::default(),
+		};
+
+		let event_log_config = match self.events {
+			Some(events) => EventLogConfig::try_from(events)?,
+			None => EventLogConfig::default(),
+		};
+
 		Ok(Config {
 			network,
 			listening_addrs,
 			announcement_addrs,
 			alias,
-			tls_config: self.tls_config,
+			tls_config,
 			grpc_service_addr,
 			storage_dir_path: self.storage_dir_path,
 			chain_source,
@@ -610,6 +742,14 @@ impl ConfigBuilder {
 			metrics_password,
 			tor_config: tor_proxy_address.map(|proxy_address| TorConfig { proxy_address }),
 			hrn_config,
+			api_keys,
+			spend_limits,
+			key_spend_limits,
+			approval_config,
+			unix_socket_config,
+			webhooks,
+			hodl_invoice_config,
+			event_log_config,
 		})
 	}
 }
@@ -630,6 +770,13 @@ pub struct TomlConfig {
 	probing: Option<ProbingTomlConfig>,
 	tor: Option<TomlTorConfig>,
 	hrn: Option<HrnTomlConfig>,
+	api_keys: Option<Vec<ApiKeyTomlConfig>>,
+	spend_limits: Option<SpendLimitsTomlConfig>,
+	approvals: Option<ApprovalsTomlConfig>,
+	unix_socket: Option<UnixSocketTomlConfig>,
+	webhooks: Option<Vec<WebhookTomlConfig>>,
+	hodl_invoices: Option<HodlInvoicesTomlConfig>,
+	events: Option<EventsTomlConfig>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -695,6 +842,19 @@ struct TomlTlsConfig {
 	cert_path: Option<String>,
 	key_path: Option<String>,
 	hosts: Option<Vec<String>>,
+	client_ca_path: Option<String>,
+	client_identities: Option<Vec<ClientIdentityTomlConfig>>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ClientIdentityTomlConfig {
+	name: String,
+	subject: Option<String>,
+	fingerprint: Option<String>,
+	scope: String,
+	max_send_amount_msat: Option<u64>,
+	spend_limits: Option<SpendLimitsTomlConfig>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -732,6 +892,165 @@ struct HrnTomlConfig {
 	enable_resolution_service: Option<bool>,
 }
 
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ApiKeyTomlConfig {
+	name: String,
+	key: String,
+	scope: String,
+	max_send_amount_msat: Option<u64>,
+	spend_limits: Option<SpendLimitsTomlConfig>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct SpendLimitsTomlConfig {
+	lightning_max_payment_msat: Option<u64>,
+	lightning_max_hourly_msat: Option<u64>,
+	lightning_max_daily_msat: Option<u64>,
+	onchain_max_payment_sats: Option<u64>,
+	onchain_max_hourly_sats: Option<u64>,
+	onchain_max_daily_sats: Option<u64>,
+}
+
+impl TryFrom<SpendLimitsTomlConfig> for SpendLimits {
+	type Error = io::Error;
+
+	fn try_from(value: SpendLimitsTomlConfig) -> Result<Self, Self::Error> {
+		let sats_to_msat = |sats: Option<u64>| {
+			sats.map(|sats| {
+				sats.checked_mul(1000).ok_or_else(|| {
+					io::Error::new(
+						io::ErrorKind::InvalidInput,
+						format!("On-chain spending limit of {sats} sats is too large"),
+					)
+				})
+			})
+			.transpose()
+		};
+
+		Ok(SpendLimits {
+			lightning: SpendBudget {
+				max_payment_msat: value.lightning_max_payment_msat,
+				max_hourly_msat: value.lightning_max_hourly_msat,
+				max_daily_msat: value.lightning_max_daily_msat,
+			},
+			onchain: SpendBudget {
+				max_payment_msat: sats_to_msat(value.onchain_max_payment_sats)?,
+				max_hourly_msat: sats_to_msat(value.onchain_max_hourly_sats)?,
+				max_daily_msat: sats_to_msat(value.onchain_max_daily_sats)?,
+			},
+		})
+	}
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct ApprovalsTomlConfig {
+	lightning_threshold_msat: Option<u64>,
+	onchain_threshold_sats: Option<u64>,
+	require_distinct_approver: Option<bool>,
+	expiry_secs: Option<u64>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct WebhookTomlConfig {
+	name: String,
+	url: String,
+	secret: String,
+	event_types: Option<Vec<String>>,
+	max_attempts: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct HodlInvoicesTomlConfig {
+	safety_margin_blocks: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct EventsTomlConfig {
+	retention_days: Option<u32>,
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct UnixSocketTomlConfig {
+	path: String,
+	mode: Option<String>,
+	owner: Option<String>,
+	group: Option<String>,
+	tls: Option<bool>,
+	scope: Option<String>,
+	max_send_amount_msat: Option<u64>,
+	spend_limits: Option<SpendLimitsTomlConfig>,
+}
+
+impl TryFrom<ApprovalsTomlConfig> for ApprovalConfig {
+	type Error = io::Error;
+
+	fn try_from(value: ApprovalsTomlConfig) -> Result<Self, Self::Error> {
+		if value.lightning_threshold_msat.is_none() && value.onchain_threshold_sats.is_none() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"At least one of `lightning_threshold_msat` and `onchain_threshold_sats` must be set in [approvals]",
+			));
+		}
+
+		let onchain_threshold_msat = value
+			.onchain_threshold_sats
+			.map(|sats| {
+				sats.checked_mul(1000).ok_or_else(|| {
+					io::Error::new(
+						io::ErrorKind::InvalidInput,
+						format!("On-chain approval threshold of {sats} sats is too large"),
+					)
+				})
+			})
+			.transpose()?;
+
+		Ok(ApprovalConfig {
+			lightning_threshold_msat: value.lightning_threshold_msat,
+			onchain_threshold_msat,
+			require_distinct_approver: value.require_distinct_approver.unwrap_or(false),
+			expiry_secs: value.expiry_secs.unwrap_or(DEFAULT_APPROVAL_EXPIRY_SECS),
+		})
+	}
+}
+
+impl TryFrom<HodlInvoicesTomlConfig> for HodlInvoiceConfig {
+	type Error = io::Error;
+
+	fn try_from(value: HodlInvoicesTomlConfig) -> Result<Self, Self::Error> {
+		let safety_margin_blocks =
+			value.safety_margin_blocks.unwrap_or(DEFAULT_SAFETY_MARGIN_BLOCKS);
+		if safety_margin_blocks == 0 {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`safety_margin_blocks` in [hodl_invoices] must be at least 1",
+			));
+		}
+		Ok(HodlInvoiceConfig { safety_margin_blocks })
+	}
+}
+
+impl TryFrom<EventsTomlConfig> for EventLogConfig {
+	type Error = io::Error;
+
+	fn try_from(value: EventsTomlConfig) -> Result<Self, Self::Error> {
+		let retention_days = value.retention_days.unwrap_or(DEFAULT_EVENT_RETENTION_DAYS);
+		if retention_days == 0 {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				"`retention_days` in [events] must be at least 1",
+			));
+		}
+		Ok(EventLogConfig { retention_days })
+	}
+}
+
 impl TryFrom<HrnTomlConfig> for HumanReadableNamesConfig {
 	type Error = io::Error;
 
@@ -830,6 +1149,263 @@ fn parse_async_payments_role(role: &str) -> io::Result<AsyncPaymentsRole> {
 	}
 }
 
+fn parse_api_key_scope(scope: &str, max_send_amount_msat: Option<u64>) -> io::Result<Scope> {
+	let scope = match scope.trim().to_ascii_lowercase().as_str() {
+		"admin" => Scope::Admin,
+		"read_only" | "read-only" => Scope::ReadOnly,
+		"invoice_only" | "invoice-only" => Scope::InvoiceOnly,
+		"send" => return Ok(Scope::Send { max_amount_msat: max_send_amount_msat }),
+		other => {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!(
+					"Invalid API key scope '{}' configured; expected 'admin', 'read_only', 'invoice_only' or 'send'",
+					other
+				),
+			))
+		},
+	};
+
+	if max_send_amount_msat.is_some() {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			"`max_send_amount_msat` only applies to the `send` scope",
+		));
+	}
+
+	Ok(scope)
+}
+
+fn build_api_keys(
+	configs: Vec<ApiKeyTomlConfig>, key_spend_limits: &mut HashMap<String, SpendLimits>,
+) -> io::Result<Vec<ApiKey>> {
+	let mut api_keys: Vec<ApiKey> = Vec::with_capacity(configs.len());
+	for ApiKeyTomlConfig { name, key, scope, max_send_amount_msat, spend_limits } in configs {
+		if !is_valid_api_key_name(&name) || name == DEFAULT_API_KEY_NAME {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Invalid API key name '{}' configured", name),
+			));
+		}
+		if <[u8; 32]>::from_hex(&key).is_err() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("API key '{}' must be a 32-byte hex string", name),
+			));
+		}
+		if api_keys.iter().any(|api_key| api_key.name == name) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Duplicate API key name '{}' configured", name),
+			));
+		}
+		if api_keys.iter().any(|api_key| api_key.key == key) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("API key '{}' reuses the key of another API key", name),
+			));
+		}
+
+		let scope = parse_api_key_scope(&scope, max_send_amount_msat)?;
+		if let Some(spend_limits) = spend_limits {
+			key_spend_limits.insert(name.clone(), SpendLimits::try_from(spend_limits)?);
+		}
+		api_keys.push(ApiKey { name, key, scope });
+	}
+	Ok(api_keys)
+}
+
+fn build_webhooks(configs: Vec<WebhookTomlConfig>) -> io::Result<Vec<WebhookConfig>> {
+	let mut webhooks: Vec<WebhookConfig> = Vec::with_capacity(configs.len());
+	for WebhookTomlConfig { name, url, secret, event_types, max_attempts } in configs {
+		// Webhook names are part of the keys deliveries are persisted under, so they follow the
+		// same rules as API key names.
+		if !is_valid_api_key_name(&name) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Invalid webhook name '{}' configured", name),
+			));
+		}
+		if webhooks.iter().any(|webhook| webhook.name == name) {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Duplicate webhook name '{}' configured", name),
+			));
+		}
+		let url = url
+			.parse::<hyper::Uri>()
+			.ok()
+			.filter(|url| {
+				matches!(url.scheme_str(), Some("http") | Some("https")) && url.host().is_some()
+			})
+			.ok_or_else(|| {
+				io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!("Invalid URL '{}' configured for webhook '{}'", url, name),
+				)
+			})?;
+		if secret.is_empty() {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Webhook '{}' must have a non-empty secret", name),
+			));
+		}
+		let event_types = event_types
+			.unwrap_or_default()
+			.iter()
+			.map(|event_type| parse_event_type(event_type))
+			.collect::<io::Result<_>>()?;
+		let max_attempts = max_attempts.unwrap_or(DEFAULT_WEBHOOK_MAX_ATTEMPTS);
+		if max_attempts == 0 {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("`max_attempts` of webhook '{}' must be at least 1", name),
+			));
+		}
+		webhooks.push(WebhookConfig { name, url, secret, event_types, max_attempts });
+	}
+	Ok(webhooks)
+}
+
+fn parse_event_type(event_type: &str) -> io::Result<EventType> {
+	let name = format!("EVENT_TYPE_{}", event_type.trim().to_ascii_uppercase());
+	EventType::from_str_name(&name).filter(|t| *t != EventType::Unspecified).ok_or_else(|| {
+		io::Error::new(
+			io::ErrorKind::InvalidInput,
+			format!("Invalid webhook event type '{}' configured", event_type),
+		)
+	})
+}
+
+fn build_client_identities(
+	configs: Vec<ClientIdentityTomlConfig>, client_ca_configured: bool, api_keys: &[ApiKey],
+	key_spend_limits: &mut HashMap<String, SpendLimits>,
+) -> io::Result<Vec<ClientCertIdentity>> {
+	if !configs.is_empty() && !client_ca_configured {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			"`tls.client_identities` requires `tls.client_ca_path` to be set",
+		));
+	}
+
+	let mut client_identities: Vec<ClientCertIdentity> = Vec::with_capacity(configs.len());
+	for ClientIdentityTomlConfig {
+		name,
+		subject,
+		fingerprint,
+		scope,
+		max_send_amount_msat,
+		spend_limits,
+	} in configs
+	{
+		if !is_valid_api_key_name(&name) || name == DEFAULT_API_KEY_NAME {
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Invalid client identity name '{}' configured", name),
+			));
+		}
+		if client_identities.iter().any(|identity| identity.name == name)
+			|| api_keys.iter().any(|api_key| api_key.name == name)
+		{
+			return Err(io::Error::new(
+				io::ErrorKind::InvalidInput,
+				format!("Duplicate client identity name '{}' configured", name),
+			));
+		}
+
+		let matcher = match (subject, fingerprint) {
+			(Some(subject), None) => ClientCertMatcher::SubjectCommonName(subject),
+			(None, Some(fingerprint)) => {
+				let fingerprint =
+					<[u8; 32]>::from_hex(&fingerprint.replace(':', "")).map_err(|_| {
+						io::Error::new(
+							io::ErrorKind::InvalidInput,
+							format!(
+								"Client identity '{}' must have a SHA-256 fingerprint in hex",
+								name
+							),
+						)
+					})?;
+				ClientCertMatcher::Fingerprint(fingerprint)
+			},
+			_ => {
+				return Err(io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!(
+						"Client identity '{}' must set exactly one of `subject` or `fingerprint`",
+						name
+					),
+				))
+			},
+		};
+
+		let scope = parse_api_key_scope(&scope, max_send_amount_msat)?;
+		if let Some(spend_limits) = spend_limits {
+			key_spend_limits.insert(name.clone(), SpendLimits::try_from(spend_limits)?);
+		}
+		client_identities.push(ClientCertIdentity { name, matcher, scope });
+	}
+	Ok(client_identities)
+}
+
+fn build_unix_socket_config(
+	config: UnixSocketTomlConfig, api_keys: &[ApiKey], client_identities: &[ClientCertIdentity],
+	key_spend_limits: &mut HashMap<String, SpendLimits>,
+) -> io::Result<UnixSocketConfig> {
+	let UnixSocketTomlConfig {
+		path,
+		mode,
+		owner,
+		group,
+		tls,
+		scope,
+		max_send_amount_msat,
+		spend_limits,
+	} = config;
+
+	if path.is_empty() {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, "`unix_socket.path` must be set"));
+	}
+
+	let mode = match mode {
+		Some(mode) => u32::from_str_radix(mode.trim_start_matches("0o"), 8)
+			.ok()
+			.filter(|mode| *mode <= 0o777)
+			.ok_or_else(|| {
+				io::Error::new(
+					io::ErrorKind::InvalidInput,
+					format!("Invalid Unix socket mode '{}' configured; expected e.g. '0660'", mode),
+				)
+			})?,
+		None => DEFAULT_UNIX_SOCKET_MODE,
+	};
+
+	// Spending limits and audit log entries are tracked by name, so it must not be shared.
+	if api_keys.iter().any(|api_key| api_key.name == UNIX_SOCKET_IDENTITY_NAME)
+		|| client_identities.iter().any(|identity| identity.name == UNIX_SOCKET_IDENTITY_NAME)
+	{
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			format!("The name '{UNIX_SOCKET_IDENTITY_NAME}' is reserved for the Unix socket"),
+		));
+	}
+
+	let scope = parse_api_key_scope(scope.as_deref().unwrap_or("admin"), max_send_amount_msat)?;
+	if let Some(spend_limits) = spend_limits {
+		key_spend_limits
+			.insert(UNIX_SOCKET_IDENTITY_NAME.to_string(), SpendLimits::try_from(spend_limits)?);
+	}
+
+	Ok(UnixSocketConfig {
+		path: PathBuf::from(path),
+		mode,
+		owner,
+		group,
+		tls: tls.unwrap_or(false),
+		identity: Identity { name: UNIX_SOCKET_IDENTITY_NAME.to_string(), scope },
+	})
+}
+
 fn build_probing_config(config: Option<ProbingTomlConfig>) -> io::Result<Option<ProbingConfig>> {
 	let Some(config) = config else {
 		return Ok(None);
@@ -1478,6 +2054,8 @@ mod tests {
 				cert_path: Some("/path/to/tls.crt".to_string()),
 				key_path: Some("/path/to/tls.key".to_string()),
 				hosts: vec!["example.com".to_string(), "ldk-server.local".to_string()],
+				client_ca_path: None,
+				client_identities: Vec::new(),
 			}),
 			chain_source: ChainSource::Rpc {
 				rpc_host: "127.0.0.1".to_string(),
@@ -1526,6 +2104,14 @@ mod tests {
 				proxy_address: SocketAddress::from_str("127.0.0.1:9050").unwrap(),
 			}),
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: Vec::new(),
+			spend_limits: SpendLimits::default(),
+			key_spend_limits: HashMap::new(),
+			approval_config: None,
+			unix_socket_config: None,
+			webhooks: Vec::new(),
+			hodl_invoice_config: HodlInvoiceConfig::default(),
+			event_log_config: EventLogConfig::default(),
 		};
 
 		assert_eq!(config.listening_addrs, expected.listening_addrs);
@@ -1546,6 +2132,7 @@ mod tests {
 		assert_eq!(config.enable_zero_fee_commitments, expected.enable_zero_fee_commitments);
 		assert_eq!(config.metrics_enabled, expected.metrics_enabled);
 		assert_eq!(config.tor_config, expected.tor_config);
+		assert_eq!(config.api_keys, expected.api_keys);
 
 		// Test case where only electrum is set
 
@@ -1968,6 +2555,14 @@ mod tests {
 			metrics_password: None,
 			tor_config: None,
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: Vec::new(),
+			spend_limits: SpendLimits::default(),
+			key_spend_limits: HashMap::new(),
+			approval_config: None,
+			unix_socket_config: None,
+			webhooks: Vec::new(),
+			hodl_invoice_config: HodlInvoiceConfig::default(),
+			event_log_config: EventLogConfig::default(),
 			log_max_size_bytes: 50 * 1024 * 1024,
 			log_rotation_interval_secs: 24 * 60 * 60,
 			log_max_files: 5,
@@ -2048,6 +2643,8 @@ mod tests {
 				cert_path: Some("/path/to/tls.crt".to_string()),
 				key_path: Some("/path/to/tls.key".to_string()),
 				hosts: vec!["example.com".to_string(), "ldk-server.local".to_string()],
+				client_ca_path: None,
+				client_identities: Vec::new(),
 			}),
 			chain_source: ChainSource::Rpc {
 				rpc_host: host,
@@ -2092,6 +2689,14 @@ mod tests {
 				proxy_address: SocketAddress::from_str("127.0.0.1:9050").unwrap(),
 			}),
 			hrn_config: HumanReadableNamesConfig::default(),
+			api_keys: Vec::new(),
+			spend_limits: SpendLimits::default(),
+			key_spend_limits: HashMap::new(),
+			approval_config: None,
+			unix_socket_config: None,
+			webhooks: Vec::new(),
+			hodl_invoice_config: HodlInvoiceConfig::default(),
+			event_log_config: EventLogConfig::default(),
 			log_max_size_bytes: 50 * 1024 * 1024,
 			log_rotation_interval_secs: 24 * 60 * 60,
 			log_max_files: 5,
@@ -2349,6 +2954,564 @@ mod tests {
 		assert!(err.to_string().contains("enable_resolution_service"));
 	}
 
+	#[test]
+	fn test_api_keys_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_api_keys_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+		let pos_key = "11".repeat(32);
+		let payouts_key = "22".repeat(32);
+		let api_key_entry = |name: &str, key: &str, scope: &str| {
+			format!("\n[[api_keys]]\nname = \"{name}\"\nkey = \"{key}\"\nscope = \"{scope}\"\n")
+		};
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		// No `[[api_keys]]` entries -> only the `api_key` file is accepted.
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert!(config.api_keys.is_empty());
+
+		let toml_config = format!(
+			"{}{}{}max_send_amount_msat = 100000\n",
+			base_config,
+			api_key_entry("pos", &pos_key, "invoice_only"),
+			api_key_entry("payouts", &payouts_key, "send"),
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(
+			config.api_keys,
+			vec![
+				ApiKey { name: "pos".to_string(), key: pos_key.clone(), scope: Scope::InvoiceOnly },
+				ApiKey {
+					name: "payouts".to_string(),
+					key: payouts_key.clone(),
+					scope: Scope::Send { max_amount_msat: Some(100_000) },
+				},
+			]
+		);
+
+		let invalid_configs = [
+			// Unknown scope.
+			api_key_entry("pos", &pos_key, "bogus"),
+			// Send limit on a scope that can't send.
+			format!("{}max_send_amount_msat = 1\n", api_key_entry("pos", &pos_key, "read_only")),
+			// Key that is not 32 bytes of hex.
+			api_key_entry("pos", "not-hex", "read_only"),
+			// Name reserved for the `api_key` file.
+			api_key_entry("admin", &pos_key, "admin"),
+			// Name with characters other than ASCII letters, digits, '-' and '_'.
+			api_key_entry("point of sale", &pos_key, "read_only"),
+			// Duplicate name.
+			format!(
+				"{}{}",
+				api_key_entry("pos", &pos_key, "read_only"),
+				api_key_entry("pos", &payouts_key, "read_only")
+			),
+			// Reused key.
+			format!(
+				"{}{}",
+				api_key_entry("pos", &pos_key, "read_only"),
+				api_key_entry("payouts", &pos_key, "read_only")
+			),
+		];
+		for api_keys_config in invalid_configs {
+			let toml_config = format!("{}{}", base_config, api_keys_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			let err = load_config(&args_config).unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		}
+	}
+
+	#[test]
+	fn test_tls_client_identities_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_tls_client_identities_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+
+				[tls]
+				"#;
+		let fingerprint = "ab:".repeat(31) + "ab";
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		let toml_config = format!(
+			r#"{base_config}client_ca_path = "/path/to/client_ca.crt"
+
+				[[tls.client_identities]]
+				name = "pos"
+				subject = "pos-terminal"
+				scope = "invoice_only"
+
+				[[tls.client_identities]]
+				name = "payouts"
+				fingerprint = "{fingerprint}"
+				scope = "send"
+				max_send_amount_msat = 100000
+				"#
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let tls_config = load_config(&args_config).unwrap().tls_config.unwrap();
+		assert_eq!(tls_config.client_ca_path.as_deref(), Some("/path/to/client_ca.crt"));
+		assert_eq!(
+			tls_config.client_identities,
+			vec![
+				ClientCertIdentity {
+					name: "pos".to_string(),
+					matcher: ClientCertMatcher::SubjectCommonName("pos-terminal".to_string()),
+					scope: Scope::InvoiceOnly,
+				},
+				ClientCertIdentity {
+					name: "payouts".to_string(),
+					matcher: ClientCertMatcher::Fingerprint([0xab; 32]),
+					scope: Scope::Send { max_amount_msat: Some(100_000) },
+				},
+			]
+		);
+
+		let identity_entry = |name: &str, matcher: &str| {
+			format!("\n[[tls.client_identities]]\nname = \"{name}\"\n{matcher}\nscope = \"read_only\"\n")
+		};
+		let client_ca_path = "client_ca_path = \"/path/to/client_ca.crt\"\n";
+		let invalid_configs = [
+			// Identities without a CA to verify client certificates against.
+			identity_entry("pos", "subject = \"pos-terminal\""),
+			// Neither a subject nor a fingerprint.
+			format!("{}{}", client_ca_path, identity_entry("pos", "")),
+			// Both a subject and a fingerprint.
+			format!(
+				"{}{}",
+				client_ca_path,
+				identity_entry(
+					"pos",
+					&format!("subject = \"pos\"\nfingerprint = \"{fingerprint}\"")
+				)
+			),
+			// Fingerprint that is not 32 bytes of hex.
+			format!("{}{}", client_ca_path, identity_entry("pos", "fingerprint = \"abcd\"")),
+			// Name reserved for the `api_key` file.
+			format!("{}{}", client_ca_path, identity_entry("admin", "subject = \"admin\"")),
+			// Duplicate name.
+			format!(
+				"{}{}{}",
+				client_ca_path,
+				identity_entry("pos", "subject = \"pos-1\""),
+				identity_entry("pos", "subject = \"pos-2\"")
+			),
+		];
+		for tls_config in invalid_configs {
+			let toml_config = format!("{}{}", base_config, tls_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			let err = load_config(&args_config).unwrap_err();
+			assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+		}
+	}
+
+	#[test]
+	fn test_spend_limits_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_spend_limits_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+		let payouts_key = "22".repeat(32);
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		// No limits are configured by default.
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(config.spend_limits, SpendLimits::default());
+		assert!(config.key_spend_limits.is_empty());
+
+		let toml_config = format!(
+			"{base_config}
+			[spend_limits]
+			lightning_max_payment_msat = 1000000
+			lightning_max_daily_msat = 10000000
+			onchain_max_hourly_sats = 50000
+
+			[[api_keys]]
+			name = \"payouts\"
+			key = \"{payouts_key}\"
+			scope = \"send\"
+			spend_limits = {{ lightning_max_hourly_msat = 500000 }}
+			"
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(
+			config.spend_limits,
+			SpendLimits {
+				lightning: SpendBudget {
+					max_payment_msat: Some(1_000_000),
+					max_hourly_msat: None,
+					max_daily_msat: Some(10_000_000),
+				},
+				onchain: SpendBudget {
+					max_payment_msat: None,
+					max_hourly_msat: Some(50_000_000),
+					max_daily_msat: None,
+				},
+			}
+		);
+		assert_eq!(
+			config.key_spend_limits,
+			HashMap::from([(
+				"payouts".to_string(),
+				SpendLimits {
+					lightning: SpendBudget { max_hourly_msat: Some(500_000), ..Default::default() },
+					onchain: SpendBudget::default(),
+				}
+			)])
+		);
+
+		let invalid_configs = [
+			"[spend_limits]\nonchain_max_daily_sats = 18446744073709552\n",
+			"[spend_limits]\nlightning_max_weekly_msat = 1000\n",
+		];
+		for spend_limits_config in invalid_configs {
+			let toml_config = format!("{}\n{}", base_config, spend_limits_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			assert!(load_config(&args_config).is_err(), "{spend_limits_config}");
+		}
+	}
+
+	#[test]
+	fn test_approvals_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_approvals_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		assert!(load_config(&args_config).unwrap().approval_config.is_none());
+
+		let toml_config = format!(
+			"{base_config}
+			[approvals]
+			onchain_threshold_sats = 1000000
+			require_distinct_approver = true
+			"
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(
+			config.approval_config,
+			Some(ApprovalConfig {
+				lightning_threshold_msat: None,
+				onchain_threshold_msat: Some(1_000_000_000),
+				require_distinct_approver: true,
+				expiry_secs: DEFAULT_APPROVAL_EXPIRY_SECS,
+			})
+		);
+
+		let invalid_configs = [
+			"[approvals]\nexpiry_secs = 3600\n",
+			"[approvals]\nonchain_threshold_sats = 18446744073709552\n",
+			"[approvals]\nlightning_threshold_msat = 1000\napprovers = 2\n",
+		];
+		for approvals_config in invalid_configs {
+			let toml_config = format!("{}\n{}", base_config, approvals_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			assert!(load_config(&args_config).is_err(), "{approvals_config}");
+		}
+	}
+
+	#[test]
+	fn test_webhooks_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_webhooks_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		assert!(load_config(&args_config).unwrap().webhooks.is_empty());
+
+		let toml_config = format!(
+			r#"{base_config}
+			[[webhooks]]
+			name = "payments"
+			url = "https://example.com/ldk"
+			secret = "payments-secret"
+			event_types = ["payment_received", "payment_successful"]
+			max_attempts = 3
+
+			[[webhooks]]
+			name = "all"
+			url = "http://127.0.0.1:8080"
+			secret = "all-secret"
+			"#
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(
+			config.webhooks,
+			vec![
+				WebhookConfig {
+					name: "payments".to_string(),
+					url: "https://example.com/ldk".parse().unwrap(),
+					secret: "payments-secret".to_string(),
+					event_types: vec![EventType::PaymentReceived, EventType::PaymentSuccessful],
+					max_attempts: 3,
+				},
+				WebhookConfig {
+					name: "all".to_string(),
+					url: "http://127.0.0.1:8080".parse().unwrap(),
+					secret: "all-secret".to_string(),
+					event_types: vec![],
+					max_attempts: DEFAULT_WEBHOOK_MAX_ATTEMPTS,
+				},
+			]
+		);
+
+		let webhook = |name: &str, url: &str, secret: &str, fields: &str| {
+			format!(
+				"[[webhooks]]\nname = \"{name}\"\nurl = \"{url}\"\n\
+				secret = \"{secret}\"\n{fields}"
+			)
+		};
+		let url = "https://example.com";
+		let invalid_configs = [
+			webhook("hook", "ftp://example.com", "s", ""),
+			webhook("hook", "/path", "s", ""),
+			webhook("hook hook", url, "s", ""),
+			webhook("hook", url, "", ""),
+			webhook("hook", url, "s", "event_types = [\"unspecified\"]"),
+			webhook("hook", url, "s", "event_types = [\"payment_sent\"]"),
+			webhook("hook", url, "s", "max_attempts = 0"),
+			format!("{}\n{}", webhook("hook", url, "s", ""), webhook("hook", url, "t", "")),
+		];
+		for webhooks_config in invalid_configs {
+			let toml_config = format!("{}\n{}", base_config, webhooks_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			assert!(load_config(&args_config).is_err(), "{webhooks_config}");
+		}
+	}
+
+	#[test]
+	fn test_hodl_invoices_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_hodl_invoices_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		assert_eq!(
+			load_config(&args_config).unwrap().hodl_invoice_config,
+			HodlInvoiceConfig { safety_margin_blocks: DEFAULT_SAFETY_MARGIN_BLOCKS }
+		);
+
+		let toml_config = format!("{base_config}\n[hodl_invoices]\nsafety_margin_blocks = 12\n");
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		assert_eq!(
+			load_config(&args_config).unwrap().hodl_invoice_config,
+			HodlInvoiceConfig { safety_margin_blocks: 12 }
+		);
+
+		let toml_config = format!("{base_config}\n[hodl_invoices]\nsafety_margin_blocks = 0\n");
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		assert!(load_config(&args_config).is_err());
+	}
+
+	#[test]
+	fn test_events_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_events_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		assert_eq!(
+			load_config(&args_config).unwrap().event_log_config,
+			EventLogConfig { retention_days: DEFAULT_EVENT_RETENTION_DAYS }
+		);
+
+		let toml_config = format!("{base_config}\n[events]\nretention_days = 7\n");
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		assert_eq!(
+			load_config(&args_config).unwrap().event_log_config,
+			EventLogConfig { retention_days: 7 }
+		);
+
+		let toml_config = format!("{base_config}\n[events]\nretention_days = 0\n");
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		assert!(load_config(&args_config).is_err());
+	}
+
+	#[test]
+	fn test_unix_socket_config() {
+		let storage_path = std::env::temp_dir();
+		let config_file_name = "test_unix_socket_config.toml";
+
+		let base_config = r#"
+				[node]
+				network = "regtest"
+
+				[bitcoind]
+				rpc_address = "127.0.0.1:8332"
+				rpc_user = "bitcoind-testuser"
+				rpc_password = "bitcoind-testpassword"
+				"#;
+
+		let mut args_config = empty_args_config();
+		args_config.config_file =
+			Some(storage_path.join(config_file_name).to_string_lossy().to_string());
+
+		fs::write(storage_path.join(config_file_name), base_config).unwrap();
+		assert!(load_config(&args_config).unwrap().unix_socket_config.is_none());
+
+		let toml_config = format!(
+			"{base_config}
+			[unix_socket]
+			path = \"/run/ldk-server/grpc.sock\"
+			"
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		assert_eq!(
+			load_config(&args_config).unwrap().unix_socket_config,
+			Some(UnixSocketConfig {
+				path: PathBuf::from("/run/ldk-server/grpc.sock"),
+				mode: 0o600,
+				owner: None,
+				group: None,
+				tls: false,
+				identity: Identity {
+					name: UNIX_SOCKET_IDENTITY_NAME.to_string(),
+					scope: Scope::Admin,
+				},
+			})
+		);
+
+		let toml_config = format!(
+			"{base_config}
+			[unix_socket]
+			path = \"/run/ldk-server/grpc.sock\"
+			mode = \"0660\"
+			owner = \"ldk\"
+			group = \"1001\"
+			tls = true
+			scope = \"send\"
+			max_send_amount_msat = 100000
+			spend_limits = {{ lightning_max_daily_msat = 500000 }}
+			"
+		);
+		fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+		let config = load_config(&args_config).unwrap();
+		assert_eq!(
+			config.unix_socket_config,
+			Some(UnixSocketConfig {
+				path: PathBuf::from("/run/ldk-server/grpc.sock"),
+				mode: 0o660,
+				owner: Some("ldk".to_string()),
+				group: Some("1001".to_string()),
+				tls: true,
+				identity: Identity {
+					name: UNIX_SOCKET_IDENTITY_NAME.to_string(),
+					scope: Scope::Send { max_amount_msat: Some(100_000) },
+				},
+			})
+		);
+		assert!(config.key_spend_limits.contains_key(UNIX_SOCKET_IDENTITY_NAME));
+
+		let invalid_configs = [
+			"[unix_socket]\nmode = \"0600\"\n".to_string(),
+			"[unix_socket]\npath = \"\"\n".to_string(),
+			"[unix_socket]\npath = \"/tmp/grpc.sock\"\nmode = \"0999\"\n".to_string(),
+			"[unix_socket]\npath = \"/tmp/grpc.sock\"\nmode = \"01777\"\n".to_string(),
+			"[unix_socket]\npath = \"/tmp/grpc.sock\"\nscope = \"root\"\n".to_string(),
+			format!(
+				"[unix_socket]\npath = \"/tmp/grpc.sock\"\n\n[[api_keys]]\nname = \"{}\"\nkey = \"{}\"\nscope = \"admin\"\n",
+				UNIX_SOCKET_IDENTITY_NAME,
+				"ab".repeat(32)
+			),
+		];
+		for unix_socket_config in invalid_configs {
+			let toml_config = format!("{}\n{}", base_config, unix_socket_config);
+			fs::write(storage_path.join(config_file_name), &toml_config).unwrap();
+			assert!(load_config(&args_config).is_err(), "{unix_socket_config}");
+		}
+	}
+
 	#[test]
 	fn test_parse_dns_server_address() {
 		assert_eq!(
@@ -7,29 +7,47 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
-use std::path::Path;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::sync::Mutex;
 use std::{fs, io};
 
+use hex::{DisplayHex, FromHex};
 use ldk_node::bip39::Mnemonic;
 use ldk_node::entropy::{generate_entropy_mnemonic, NodeEntropy};
 use log::info;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, CHACHA20_POLY1305, NONCE_LEN};
+use ring::pbkdf2;
+use tokio::sync::oneshot;
 
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
 use crate::util::write_new;
 
 const DEFAULT_MNEMONIC_FILE: &str = "keys_mnemonic";
 
+/// The first field of an encrypted mnemonic file, which is followed by the PBKDF2 iterations, salt,
+/// nonce and ciphertext, separated by colons.
+const ENCRYPTED_MNEMONIC_PREFIX: &str = "ldk-server-encrypted-mnemonic-v1";
+const PBKDF2_ITERATIONS: u32 = 600_000;
+const SALT_LEN: usize = 16;
+
 pub(crate) fn load_or_generate_node_entropy(storage_dir: &Path) -> io::Result<NodeEntropy> {
 	let mnemonic_path = storage_dir.join(DEFAULT_MNEMONIC_FILE);
 
 	let mnemonic = if mnemonic_path.exists() {
 		let raw = fs::read_to_string(&mnemonic_path)?;
-		Mnemonic::from_str(raw.trim()).map_err(|e| {
-			io::Error::new(
-				io::ErrorKind::InvalidData,
-				format!("Invalid BIP39 mnemonic in {}: {}", mnemonic_path.display(), e),
-			)
-		})?
+		if is_encrypted(&raw) {
+			return Err(io::Error::new(
+				io::ErrorKind::PermissionDenied,
+				format!(
+					"Mnemonic in {} is encrypted and must be unlocked",
+					mnemonic_path.display()
+				),
+			));
+		}
+		parse_mnemonic(&raw, &mnemonic_path)?
 	} else {
 		if let Some(parent) = mnemonic_path.parent() {
 			fs::create_dir_all(parent)?;
@@ -46,6 +64,181 @@ pub(crate) fn load_or_generate_node_entropy(storage_dir: &Path) -> io::Result<No
 	Ok(NodeEntropy::from_bip39_mnemonic(mnemonic, None))
 }
 
+/// Returns whether the mnemonic in the storage directory is encrypted with a passphrase.
+pub(crate) fn is_mnemonic_encrypted(storage_dir: &Path) -> io::Result<bool> {
+	match fs::read_to_string(storage_dir.join(DEFAULT_MNEMONIC_FILE)) {
+		Ok(raw) => Ok(is_encrypted(&raw)),
+		Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(false),
+		Err(e) => Err(e),
+	}
+}
+
+/// Decrypts the encrypted mnemonic in the storage directory with the given passphrase.
+///
+/// Fails with [`io::ErrorKind::PermissionDenied`] if the passphrase is wrong.
+pub(crate) fn unlock_node_entropy(storage_dir: &Path, passphrase: &str) -> io::Result<NodeEntropy> {
+	let mnemonic_path = storage_dir.join(DEFAULT_MNEMONIC_FILE);
+	let raw = fs::read_to_string(&mnemonic_path)?;
+	let plaintext = decrypt(raw.trim(), passphrase).map_err(|e| {
+		io::Error::new(e.kind(), format!("Failed to decrypt {}: {}", mnemonic_path.display(), e))
+	})?;
+	let mnemonic = parse_mnemonic(&plaintext, &mnemonic_path)?;
+	Ok(NodeEntropy::from_bip39_mnemonic(mnemonic, None))
+}
+
+/// Encrypts the plaintext mnemonic in the storage directory in place with the given passphrase.
+///
+/// The file is replaced atomically, so it holds either the plaintext or the encrypted mnemonic at
+/// any time.
+pub(crate) fn encrypt_mnemonic_file(storage_dir: &Path, passphrase: &str) -> io::Result<()> {
+	if passphrase.is_empty() {
+		return Err(io::Error::new(io::ErrorKind::InvalidInput, "Passphrase must not be empty"));
+	}
+
+	let mnemonic_path = storage_dir.join(DEFAULT_MNEMONIC_FILE);
+	let raw = fs::read_to_string(&mnemonic_path)?;
+	if is_encrypted(&raw) {
+		return Err(io::Error::new(
+			io::ErrorKind::InvalidInput,
+			format!("Mnemonic in {} is already encrypted", mnemonic_path.display()),
+		));
+	}
+	let mnemonic = parse_mnemonic(&raw, &mnemonic_path)?;
+	let encrypted = encrypt(&mnemonic.to_string(), passphrase)?;
+
+	let tmp_path = mnemonic_path.with_extension("tmp");
+	match fs::remove_file(&tmp_path) {
+		Ok(()) => {},
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+		Err(e) => return Err(e),
+	}
+	write_new(&tmp_path, format!("{}\n", encrypted).as_bytes(), 0o600)?;
+	fs::rename(&tmp_path, &mnemonic_path)?;
+	info!("Encrypted BIP39 mnemonic at {}.", mnemonic_path.display());
+	Ok(())
+}
+
+/// Unlocks the encrypted mnemonic of a server that was started in the locked state, handing the
+/// node entropy to the receiver returned by [`NodeUnlocker::new`].
+pub(crate) struct NodeUnlocker {
+	storage_dir: PathBuf,
+	entropy_sender: Mutex<Option<oneshot::Sender<NodeEntropy>>>,
+}
+
+impl NodeUnlocker {
+	pub(crate) fn new(storage_dir: PathBuf) -> (Self, oneshot::Receiver<NodeEntropy>) {
+		let (entropy_sender, entropy_receiver) = oneshot::channel();
+		(Self { storage_dir, entropy_sender: Mutex::new(Some(entropy_sender)) }, entropy_receiver)
+	}
+
+	/// Decrypts the mnemonic with the given passphrase. Unlocking again after the node was
+	/// unlocked has no effect.
+	pub(crate) fn unlock(&self, passphrase: &str) -> Result<(), LdkServerError> {
+		let node_entropy = unlock_node_entropy(&self.storage_dir, passphrase).map_err(|e| {
+			if e.kind() == io::ErrorKind::PermissionDenied {
+				LdkServerError::new(InvalidRequestError, "Invalid passphrase")
+			} else {
+				LdkServerError::new(InternalServerError, format!("Failed to unlock mnemonic: {e}"))
+			}
+		})?;
+
+		if let Some(entropy_sender) = self.entropy_sender.lock().unwrap().take() {
+			// The receiver is only dropped once the server shuts down.
+			let _ = entropy_sender.send(node_entropy);
+		}
+		Ok(())
+	}
+}
+
+fn parse_mnemonic(raw: &str, mnemonic_path: &Path) -> io::Result<Mnemonic> {
+	Mnemonic::from_str(raw.trim()).map_err(|e| {
+		io::Error::new(
+			io::ErrorKind::InvalidData,
+			format!("Invalid BIP39 mnemonic in {}: {}", mnemonic_path.display(), e),
+		)
+	})
+}
+
+fn is_encrypted(raw: &str) -> bool {
+	raw.trim_start().starts_with(ENCRYPTED_MNEMONIC_PREFIX)
+}
+
+fn derive_key(passphrase: &str, iterations: NonZeroU32, salt: &[u8]) -> LessSafeKey {
+	let mut key_bytes = [0u8; 32];
+	pbkdf2::derive(
+		pbkdf2::PBKDF2_HMAC_SHA256,
+		iterations,
+		salt,
+		passphrase.as_bytes(),
+		&mut key_bytes,
+	);
+	let key = UnboundKey::new(&CHACHA20_POLY1305, &key_bytes).expect("Key has the right length");
+	LessSafeKey::new(key)
+}
+
+fn encrypt(plaintext: &str, passphrase: &str) -> io::Result<String> {
+	let mut salt = [0u8; SALT_LEN];
+	let mut nonce = [0u8; NONCE_LEN];
+	getrandom::getrandom(&mut salt)
+		.and_then(|()| getrandom::getrandom(&mut nonce))
+		.map_err(|e| io::Error::other(format!("Failed to generate random bytes: {e}")))?;
+
+	let iterations = NonZeroU32::new(PBKDF2_ITERATIONS).expect("Iterations are non-zero");
+	let key = derive_key(passphrase, iterations, &salt);
+	let mut ciphertext = plaintext.as_bytes().to_vec();
+	key.seal_in_place_append_tag(
+		Nonce::assume_unique_for_key(nonce),
+		Aad::from(ENCRYPTED_MNEMONIC_PREFIX.as_bytes()),
+		&mut ciphertext,
+	)
+	.map_err(|_| io::Error::other("Failed to encrypt mnemonic"))?;
+
+	Ok(format!(
+		"{}:{}:{}:{}:{}",
+		ENCRYPTED_MNEMONIC_PREFIX,
+		PBKDF2_ITERATIONS,
+		salt.to_lower_hex_string(),
+		nonce.to_lower_hex_string(),
+		ciphertext.to_lower_hex_string()
+	))
+}
+
+fn decrypt(encrypted: &str, passphrase: &str) -> io::Result<String> {
+	let invalid = |msg: &str| io::Error::new(io::ErrorKind::InvalidData, msg.to_string());
+
+	let mut fields = encrypted.split(':');
+	let (iterations, salt, nonce, ciphertext) =
+		match (fields.next(), fields.next(), fields.next(), fields.next(), fields.next()) {
+			(
+				Some(ENCRYPTED_MNEMONIC_PREFIX),
+				Some(iterations),
+				Some(salt),
+				Some(nonce),
+				Some(ciphertext),
+			) if fields.next().is_none() => (iterations, salt, nonce, ciphertext),
+			_ => return Err(invalid("Unsupported encrypted mnemonic format")),
+		};
+	let iterations = iterations
+		.parse::<u32>()
+		.ok()
+		.and_then(NonZeroU32::new)
+		.ok_or_else(|| invalid("Invalid PBKDF2 iterations"))?;
+	let salt = Vec::<u8>::from_hex(salt).map_err(|_| invalid("Invalid salt"))?;
+	let nonce = <[u8; NONCE_LEN]>::from_hex(nonce).map_err(|_| invalid("Invalid nonce"))?;
+	let mut ciphertext =
+		Vec::<u8>::from_hex(ciphertext).map_err(|_| invalid("Invalid ciphertext"))?;
+
+	let key = derive_key(passphrase, iterations, &salt);
+	let plaintext = key
+		.open_in_place(
+			Nonce::assume_unique_for_key(nonce),
+			Aad::from(ENCRYPTED_MNEMONIC_PREFIX.as_bytes()),
+			&mut ciphertext,
+		)
+		.map_err(|_| io::Error::new(io::ErrorKind::PermissionDenied, "Invalid passphrase"))?;
+	String::from_utf8(plaintext.to_vec()).map_err(|_| invalid("Decrypted mnemonic is not UTF-8"))
+}
+
 #[cfg(test)]
 mod tests {
 	use std::os::unix::fs::{MetadataExt, PermissionsExt};
@@ -80,7 +273,7 @@ mod tests {
 		assert_eq!(perms.mode() & 0o777, 0o600, "expected 0600 permissions");
 
 		let content = fs::read_to_string(&mnemonic_path).unwrap();
-		let word_count = content.trim().split_whitespace().count();
+		let word_count = content.split_whitespace().count();
 		assert_eq!(word_count, 24, "expected 24-word mnemonic, got {}", word_count);
 
 		let mtime_before = fs::metadata(&mnemonic_path).unwrap().mtime();
@@ -114,6 +307,55 @@ mod tests {
 		assert!(stale_seed_path.exists(), "stale keys_seed was removed");
 	}
 
+	#[test]
+	fn encrypts_mnemonic_in_place() {
+		let dir = tempdir("encrypt");
+		let mnemonic_path = dir.join(DEFAULT_MNEMONIC_FILE);
+		fs::write(&mnemonic_path, format!("{}\n", KNOWN_MNEMONIC)).unwrap();
+
+		encrypt_mnemonic_file(&dir, "correct horse").unwrap();
+
+		let content = fs::read_to_string(&mnemonic_path).unwrap();
+		assert!(!content.contains("abandon"), "mnemonic was stored in plaintext");
+		let perms = fs::metadata(&mnemonic_path).unwrap().permissions();
+		assert_eq!(perms.mode() & 0o777, 0o600, "expected 0600 permissions");
+		assert!(is_mnemonic_encrypted(&dir).unwrap());
+		assert!(!dir.join(DEFAULT_MNEMONIC_FILE).with_extension("tmp").exists());
+
+		assert_eq!(decrypt(content.trim(), "correct horse").unwrap(), KNOWN_MNEMONIC);
+		unlock_node_entropy(&dir, "correct horse").unwrap();
+	}
+
+	#[test]
+	fn rejects_wrong_passphrase() {
+		let dir = tempdir("wrong-passphrase");
+		fs::write(dir.join(DEFAULT_MNEMONIC_FILE), format!("{}\n", KNOWN_MNEMONIC)).unwrap();
+		encrypt_mnemonic_file(&dir, "correct horse").unwrap();
+
+		let err = unlock_node_entropy(&dir, "battery staple").unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+	}
+
+	#[test]
+	fn encrypted_mnemonic_is_never_replaced() {
+		let dir = tempdir("encrypted-load");
+		let mnemonic_path = dir.join(DEFAULT_MNEMONIC_FILE);
+		fs::write(&mnemonic_path, format!("{}\n", KNOWN_MNEMONIC)).unwrap();
+		encrypt_mnemonic_file(&dir, "correct horse").unwrap();
+		let bytes_before = fs::read(&mnemonic_path).unwrap();
+
+		let err = load_or_generate_node_entropy(&dir).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::PermissionDenied);
+		let err = encrypt_mnemonic_file(&dir, "battery staple").unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+
+		assert_eq!(
+			fs::read(&mnemonic_path).unwrap(),
+			bytes_before,
+			"mnemonic file content changed"
+		);
+	}
+
 	#[test]
 	fn rejects_invalid_mnemonic_file() {
 		let dir = tempdir("invalid");
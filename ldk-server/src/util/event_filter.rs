@@ -0,0 +1,313 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashSet;
+
+use ldk_server_grpc::api::SubscribeEventsRequest;
+use ldk_server_grpc::events::event_envelope::Event;
+use ldk_server_grpc::events::{EventEnvelope, EventType};
+use ldk_server_grpc::types::{Payment, PaymentDirection};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InvalidRequestError;
+
+/// Selects the events streamed to a subscriber, as requested in its [`SubscribeEventsRequest`].
+///
+/// An event matches if it matches all of the set filters. An event that doesn't carry what a filter
+/// applies to doesn't match it.
+#[derive(Debug)]
+pub(crate) struct EventFilter {
+	event_types: HashSet<EventType>,
+	channel_ids: HashSet<String>,
+	counterparty_node_ids: HashSet<String>,
+	payment_direction: Option<PaymentDirection>,
+	min_amount_msat: Option<u64>,
+}
+
+impl EventFilter {
+	pub(crate) fn new(request: &SubscribeEventsRequest) -> Result<Self, LdkServerError> {
+		let invalid = |field: &str| {
+			LdkServerError::new(
+				InvalidRequestError,
+				format!("Invalid {field} in SubscribeEventsRequest"),
+			)
+		};
+
+		let event_types = request
+			.event_types
+			.iter()
+			.map(|t| {
+				EventType::from_i32(*t)
+					.filter(|t| *t != EventType::Unspecified)
+					.ok_or_else(|| invalid("event_types"))
+			})
+			.collect::<Result<_, _>>()?;
+		let payment_direction = request
+			.payment_direction
+			.map(|d| PaymentDirection::from_i32(d).ok_or_else(|| invalid("payment_direction")))
+			.transpose()?;
+
+		Ok(Self {
+			event_types,
+			channel_ids: request.channel_ids.iter().cloned().collect(),
+			counterparty_node_ids: request.counterparty_node_ids.iter().cloned().collect(),
+			payment_direction,
+			min_amount_msat: request.min_amount_msat,
+		})
+	}
+
+	/// Returns whether `envelope` should be streamed to the subscriber.
+	pub(crate) fn matches(&self, envelope: &EventEnvelope) -> bool {
+		let Some(event) = &envelope.event else {
+			return false;
+		};
+		if !self.event_types.is_empty() && !self.event_types.contains(&event_type(event)) {
+			return false;
+		}
+		if !self.channel_ids.is_empty()
+			&& !channel_ids(event).iter().any(|channel_id| self.channel_ids.contains(*channel_id))
+		{
+			return false;
+		}
+		if !self.counterparty_node_ids.is_empty()
+			&& !counterparty_node_ids(event)
+				.iter()
+				.any(|node_id| self.counterparty_node_ids.contains(*node_id))
+		{
+			return false;
+		}
+		if let Some(direction) = self.payment_direction {
+			if payment(event).map(|payment| payment.direction) != Some(direction as i32) {
+				return false;
+			}
+		}
+		if let Some(min_amount_msat) = self.min_amount_msat {
+			if amount_msat(event).is_none_or(|amount_msat| amount_msat < min_amount_msat) {
+				return false;
+			}
+		}
+		true
+	}
+}
+
+pub(crate) fn event_type(event: &Event) -> EventType {
+	match event {
+		Event::PaymentReceived(_) => EventType::PaymentReceived,
+		Event::PaymentSuccessful(_) => EventType::PaymentSuccessful,
+		Event::PaymentFailed(_) => EventType::PaymentFailed,
+		Event::PaymentForwarded(_) => EventType::PaymentForwarded,
+		Event::PaymentClaimable(_) => EventType::PaymentClaimable,
+		Event::ChannelStateChanged(_) => EventType::ChannelStateChanged,
+		Event::PendingActionCreated(_) => EventType::PendingActionCreated,
+		Event::SplicePending(_) => EventType::SplicePending,
+		Event::SpliceCompleted(_) => EventType::SpliceCompleted,
+		Event::SpliceFailed(_) => EventType::SpliceFailed,
+		Event::PeerConnected(_) => EventType::PeerConnected,
+		Event::PeerDisconnected(_) => EventType::PeerDisconnected,
+		Event::OnchainFundsReceived(_) => EventType::OnchainFundsReceived,
+		Event::OnchainFundsConfirmed(_) => EventType::OnchainFundsConfirmed,
+		Event::WalletSyncCompleted(_) => EventType::WalletSyncCompleted,
+		Event::PaymentClaimDeadlineApproaching(_) => EventType::PaymentClaimDeadlineApproaching,
+	}
+}
+
+fn channel_ids(event: &Event) -> Vec<&str> {
+	match event {
+		Event::ChannelStateChanged(event) => vec![event.channel_id.as_str()],
+		Event::SplicePending(event) => vec![event.channel_id.as_str()],
+		Event::SpliceCompleted(event) => vec![event.channel_id.as_str()],
+		Event::SpliceFailed(event) => vec![event.channel_id.as_str()],
+		Event::PaymentForwarded(event) => event
+			.forwarded_payment
+			.iter()
+			.flat_map(|forwarded| forwarded.prev_htlcs.iter().chain(&forwarded.next_htlcs))
+			.map(|htlc| htlc.channel_id.as_str())
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+fn counterparty_node_ids(event: &Event) -> Vec<&str> {
+	match event {
+		Event::ChannelStateChanged(event) => {
+			event.counterparty_node_id.as_deref().into_iter().collect()
+		},
+		Event::SplicePending(event) => vec![event.counterparty_node_id.as_str()],
+		Event::SpliceCompleted(event) => {
+			event.counterparty_node_id.as_deref().into_iter().collect()
+		},
+		Event::SpliceFailed(event) => vec![event.counterparty_node_id.as_str()],
+		Event::PeerConnected(event) => vec![event.node_id.as_str()],
+		Event::PeerDisconnected(event) => vec![event.node_id.as_str()],
+		Event::PaymentForwarded(event) => event
+			.forwarded_payment
+			.iter()
+			.flat_map(|forwarded| forwarded.prev_htlcs.iter().chain(&forwarded.next_htlcs))
+			.filter_map(|htlc| htlc.node_id.as_deref())
+			.collect(),
+		_ => Vec::new(),
+	}
+}
+
+fn payment(event: &Event) -> Option<&Payment> {
+	match event {
+		Event::PaymentReceived(event) => event.payment.as_ref(),
+		Event::PaymentSuccessful(event) => event.payment.as_ref(),
+		Event::PaymentFailed(event) => event.payment.as_ref(),
+		Event::PaymentClaimable(event) => event.payment.as_ref(),
+		Event::OnchainFundsReceived(event) => event.payment.as_ref(),
+		Event::OnchainFundsConfirmed(event) => event.payment.as_ref(),
+		Event::PaymentClaimDeadlineApproaching(event) => event.payment.as_ref(),
+		_ => None,
+	}
+}
+
+fn amount_msat(event: &Event) -> Option<u64> {
+	match event {
+		Event::PaymentForwarded(event) => {
+			event.forwarded_payment.as_ref()?.outbound_amount_forwarded_msat
+		},
+		Event::PendingActionCreated(event) => event.action.as_ref()?.amount_msat,
+		_ => payment(event)?.amount_msat,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::events::{
+		ChannelStateChanged, PaymentForwarded, PaymentReceived, PeerConnected, SplicePending,
+	};
+	use ldk_server_grpc::types::{ForwardedPayment, HtlcLocator};
+
+	use super::*;
+
+	fn envelope(event: Event) -> EventEnvelope {
+		EventEnvelope { event: Some(event), ..Default::default() }
+	}
+
+	fn payment_received(direction: PaymentDirection, amount_msat: Option<u64>) -> EventEnvelope {
+		envelope(Event::PaymentReceived(PaymentReceived {
+			payment: Some(Payment {
+				direction: direction as i32,
+				amount_msat,
+				..Default::default()
+			}),
+			..Default::default()
+		}))
+	}
+
+	fn channel_state_changed(channel_id: &str, counterparty_node_id: &str) -> EventEnvelope {
+		envelope(Event::ChannelStateChanged(ChannelStateChanged {
+			channel_id: channel_id.to_string(),
+			counterparty_node_id: Some(counterparty_node_id.to_string()),
+			..Default::default()
+		}))
+	}
+
+	fn payment_forwarded(next_channel_id: &str, amount_msat: u64) -> EventEnvelope {
+		envelope(Event::PaymentForwarded(PaymentForwarded {
+			forwarded_payment: Some(ForwardedPayment {
+				outbound_amount_forwarded_msat: Some(amount_msat),
+				prev_htlcs: vec![HtlcLocator {
+					channel_id: "aa".to_string(),
+					node_id: Some("02aa".to_string()),
+					..Default::default()
+				}],
+				next_htlcs: vec![HtlcLocator {
+					channel_id: next_channel_id.to_string(),
+					..Default::default()
+				}],
+				..Default::default()
+			}),
+		}))
+	}
+
+	#[test]
+	fn test_event_filter() {
+		let inbound = payment_received(PaymentDirection::Inbound, Some(5_000));
+		let unknown_amount = payment_received(PaymentDirection::Inbound, None);
+		let channel = channel_state_changed("bb", "02bb");
+		let forwarded = payment_forwarded("bb", 20_000);
+
+		let filter = EventFilter::new(&SubscribeEventsRequest::default()).unwrap();
+		assert!([&inbound, &unknown_amount, &channel, &forwarded]
+			.iter()
+			.all(|event| filter.matches(event)));
+
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			event_types: vec![EventType::PaymentReceived as i32],
+			..Default::default()
+		})
+		.unwrap();
+		assert!(filter.matches(&inbound));
+		assert!(!filter.matches(&channel));
+
+		// Forwards match channel filters on either side.
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			channel_ids: vec!["bb".to_string()],
+			..Default::default()
+		})
+		.unwrap();
+		assert!(filter.matches(&channel));
+		assert!(filter.matches(&forwarded));
+		assert!(!filter.matches(&inbound));
+
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			counterparty_node_ids: vec!["02aa".to_string()],
+			..Default::default()
+		})
+		.unwrap();
+		assert!(filter.matches(&forwarded));
+		assert!(!filter.matches(&channel));
+
+		// Peer and splice events match the counterparty they concern.
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			channel_ids: vec!["cc".to_string()],
+			counterparty_node_ids: vec!["02cc".to_string()],
+			..Default::default()
+		})
+		.unwrap();
+		assert!(filter.matches(&envelope(Event::SplicePending(SplicePending {
+			channel_id: "cc".to_string(),
+			counterparty_node_id: "02cc".to_string(),
+			..Default::default()
+		}))));
+		// Peer events don't concern a channel.
+		assert!(!filter.matches(&envelope(Event::PeerConnected(PeerConnected {
+			node_id: "02cc".to_string(),
+			..Default::default()
+		}))));
+
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			payment_direction: Some(PaymentDirection::Outbound as i32),
+			..Default::default()
+		})
+		.unwrap();
+		assert!(!filter.matches(&inbound));
+		assert!(!filter.matches(&forwarded));
+
+		// Filters are combined, and events of unknown amount don't match a minimum amount.
+		let filter = EventFilter::new(&SubscribeEventsRequest {
+			payment_direction: Some(PaymentDirection::Inbound as i32),
+			min_amount_msat: Some(1_000),
+			..Default::default()
+		})
+		.unwrap();
+		assert!(filter.matches(&inbound));
+		assert!(!filter.matches(&unknown_amount));
+		assert!(!filter.matches(&channel));
+
+		let err = EventFilter::new(&SubscribeEventsRequest {
+			event_types: vec![EventType::Unspecified as i32],
+			..Default::default()
+		})
+		.unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+	}
+}
@@ -0,0 +1,350 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use ldk_server_grpc::events::{event_envelope, EventEnvelope};
+use log::{debug, error};
+use tokio::sync::broadcast;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+	EVENTS_PERSISTENCE_SECONDARY_NAMESPACE, EVENT_LOG_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+	EVENT_LOG_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+use crate::util::webhooks::WebhookDispatcher;
+
+/// The number of events buffered for each live subscriber before it lags behind.
+const EVENT_BROADCAST_CAPACITY: usize = 1024;
+
+/// The number of events read from the store at a time when replaying events.
+const EVENT_REPLAY_BATCH_SIZE: u64 = 100;
+
+/// The default number of days persisted events are retained for.
+pub(crate) const DEFAULT_EVENT_RETENTION_DAYS: u32 = 30;
+
+/// How often events that are older than the retention window are pruned.
+pub(crate) const EVENT_PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+const EVENT_LOG_STATE_KEY: &str = "state";
+
+/// Determines how long persisted events can be replayed for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct EventLogConfig {
+	/// Events are pruned once they are older than this many days.
+	pub(crate) retention_days: u32,
+}
+
+impl Default for EventLogConfig {
+	fn default() -> Self {
+		Self { retention_days: DEFAULT_EVENT_RETENTION_DAYS }
+	}
+}
+
+/// The persisted state of the event log.
+#[derive(Clone, PartialEq, prost::Message)]
+struct EventLogStateRecord {
+	/// The sequence number of the oldest event that wasn't pruned.
+	#[prost(uint64, tag = "1")]
+	first_sequence: u64,
+}
+
+/// Publishes server events to subscribers and webhooks, after persisting them with a sequence
+/// number.
+///
+/// Sequence numbers start at 1 and increase by one with every event, also across restarts, so that
+/// subscribers can resume from the last event they processed. Events are pruned once they are older
+/// than the configured retention window.
+pub(crate) struct EventLog {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	event_sender: broadcast::Sender<EventEnvelope>,
+	/// The sequence number of the next event. Held while an event is published, so that events are
+	/// broadcast in the order of their sequence numbers.
+	next_sequence: Mutex<u64>,
+	/// The sequence number of the oldest event that wasn't pruned. Raised before events are
+	/// removed, so that events below it can no longer be replayed.
+	first_sequence: Mutex<u64>,
+	retention_secs: u64,
+	webhooks: Option<Arc<WebhookDispatcher>>,
+}
+
+impl EventLog {
+	/// Opens the event log, continuing after the last event that was persisted.
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, config: EventLogConfig,
+	) -> Result<Self, LdkServerError> {
+		let list_response = paginated_kv_store
+			.list(
+				EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				None,
+			)
+			.map_err(|e| {
+				LdkServerError::new(InternalServerError, format!("Failed to list events: {e}"))
+			})?;
+		// Events are listed in descending order of their sequence number.
+		let last_sequence = match list_response.keys.first() {
+			Some(key) => key.parse::<u64>().map_err(|e| {
+				LdkServerError::new(InternalServerError, format!("Invalid event key '{key}': {e}"))
+			})?,
+			None => 0,
+		};
+		let first_sequence = read_record::<EventLogStateRecord>(
+			&*paginated_kv_store,
+			EVENT_LOG_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+			EVENT_LOG_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+			EVENT_LOG_STATE_KEY,
+		)?
+		.map_or(1, |state| state.first_sequence);
+		let (event_sender, _) = broadcast::channel(EVENT_BROADCAST_CAPACITY);
+		Ok(Self {
+			paginated_kv_store,
+			event_sender,
+			// All events may have been pruned.
+			next_sequence: Mutex::new((last_sequence + 1).max(first_sequence)),
+			first_sequence: Mutex::new(first_sequence),
+			retention_secs: u64::from(config.retention_days) * 24 * 60 * 60,
+			webhooks: None,
+		})
+	}
+
+	/// Queues every published event for delivery to the configured webhooks.
+	pub(crate) fn with_webhooks(mut self, webhooks: Option<Arc<WebhookDispatcher>>) -> Self {
+		self.webhooks = webhooks;
+		self
+	}
+
+	/// Persists `event` under the next sequence number and broadcasts it to live subscribers.
+	///
+	/// If the event can't be persisted, it is still broadcast, but can't be replayed.
+	pub(crate) fn publish(&self, event: event_envelope::Event) {
+		let mut next_sequence = self.next_sequence.lock().unwrap();
+		let envelope = EventEnvelope {
+			sequence: *next_sequence,
+			timestamp: current_time_secs(),
+			event: Some(event),
+		};
+		if let Err(e) = write_record(
+			&*self.paginated_kv_store,
+			EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			&event_key(envelope.sequence),
+			envelope.sequence,
+			&envelope,
+		) {
+			error!("Failed to persist event {}: {e}", envelope.sequence);
+		}
+		if let Some(webhooks) = &self.webhooks {
+			webhooks.enqueue(&envelope);
+		}
+		*next_sequence += 1;
+		if let Err(e) = self.event_sender.send(envelope) {
+			debug!("No event subscribers connected, skipping event: {e}");
+		}
+	}
+
+	/// Subscribes to the events published from now on.
+	///
+	/// Returns the receiver along with the sequence number of the first event it will receive.
+	/// Earlier events can be read with [`EventLog::read_range`].
+	pub(crate) fn subscribe(&self) -> (broadcast::Receiver<EventEnvelope>, u64) {
+		let next_sequence = self.next_sequence.lock().unwrap();
+		(self.event_sender.subscribe(), *next_sequence)
+	}
+
+	/// Returns the sequence number of the oldest event that can still be replayed.
+	///
+	/// Events read with [`EventLog::read_range`] are complete if their range starts at or after the
+	/// sequence number returned afterwards.
+	pub(crate) fn first_sequence(&self) -> u64 {
+		*self.first_sequence.lock().unwrap()
+	}
+
+	/// Removes the persisted events that are older than the retention window.
+	pub(crate) fn prune(&self) -> Result<(), LdkServerError> {
+		let cutoff = current_time_secs().saturating_sub(self.retention_secs);
+		let next_sequence = *self.next_sequence.lock().unwrap();
+		let first_sequence = self.first_sequence();
+		let mut pruned_until = first_sequence;
+		while pruned_until < next_sequence {
+			let envelope = read_record::<EventEnvelope>(
+				&*self.paginated_kv_store,
+				EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&event_key(pruned_until),
+			)?;
+			// Events that couldn't be persisted have nothing to prune.
+			if envelope.is_some_and(|envelope| envelope.timestamp >= cutoff) {
+				break;
+			}
+			pruned_until += 1;
+		}
+		if pruned_until == first_sequence {
+			return Ok(());
+		}
+
+		{
+			let mut first_sequence = self.first_sequence.lock().unwrap();
+			if pruned_until <= *first_sequence {
+				return Ok(()); // pruned concurrently
+			}
+			write_record(
+				&*self.paginated_kv_store,
+				EVENT_LOG_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENT_LOG_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+				EVENT_LOG_STATE_KEY,
+				0,
+				&EventLogStateRecord { first_sequence: pruned_until },
+			)?;
+			*first_sequence = pruned_until;
+		}
+		for sequence in first_sequence..pruned_until {
+			if let Err(e) = self.paginated_kv_store.remove(
+				EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&event_key(sequence),
+			) {
+				error!("Failed to remove pruned event {sequence}: {e}");
+			}
+		}
+		debug!("Pruned events {first_sequence} to {}", pruned_until - 1);
+		Ok(())
+	}
+
+	/// Returns the persisted events with a sequence number of at least `from` and below `to`, up to
+	/// `EVENT_REPLAY_BATCH_SIZE` of them, along with the sequence number to continue reading from.
+	///
+	/// Events that couldn't be persisted or were pruned are skipped.
+	pub(crate) fn read_range(
+		&self, from: u64, to: u64,
+	) -> Result<(Vec<EventEnvelope>, u64), LdkServerError> {
+		let to = to.min(from.saturating_add(EVENT_REPLAY_BATCH_SIZE));
+		let mut events = Vec::new();
+		for sequence in from..to {
+			if let Some(envelope) = read_record::<EventEnvelope>(
+				&*self.paginated_kv_store,
+				EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&event_key(sequence),
+			)? {
+				events.push(envelope);
+			}
+		}
+		Ok((events, to.max(from)))
+	}
+}
+
+fn event_key(sequence: u64) -> String {
+	sequence.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::events::PaymentSuccessful;
+	use ldk_server_grpc::types::Payment;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn payment_successful(id: &str) -> event_envelope::Event {
+		event_envelope::Event::PaymentSuccessful(PaymentSuccessful {
+			payment: Some(Payment { id: id.to_string(), ..Default::default() }),
+		})
+	}
+
+	fn payment_id(envelope: &EventEnvelope) -> &str {
+		match &envelope.event {
+			Some(event_envelope::Event::PaymentSuccessful(event)) => {
+				&event.payment.as_ref().unwrap().id
+			},
+			other => panic!("Unexpected event {other:?}"),
+		}
+	}
+
+	#[test]
+	fn test_publish_and_replay() {
+		let store: Arc<dyn PaginatedKVStore> = Arc::new(test_store("test_publish_and_replay"));
+
+		let event_log = EventLog::new(Arc::clone(&store), EventLogConfig::default()).unwrap();
+		// Events are persisted without any subscribers.
+		event_log.publish(payment_successful("a"));
+		let (mut receiver, next_sequence) = event_log.subscribe();
+		assert_eq!(next_sequence, 2);
+		event_log.publish(payment_successful("b"));
+
+		let live = receiver.try_recv().unwrap();
+		assert_eq!((live.sequence, payment_id(&live)), (2, "b"));
+		assert!(live.timestamp > 0);
+
+		// Sequence numbers continue after a restart.
+		drop(event_log);
+		let event_log = EventLog::new(store, EventLogConfig::default()).unwrap();
+		event_log.publish(payment_successful("c"));
+		let (_, next_sequence) = event_log.subscribe();
+		assert_eq!(next_sequence, 4);
+
+		let (replayed, continue_from) = event_log.read_range(2, next_sequence).unwrap();
+		let replayed: Vec<(u64, &str)> =
+			replayed.iter().map(|envelope| (envelope.sequence, payment_id(envelope))).collect();
+		assert_eq!(replayed, vec![(2, "b"), (3, "c")]);
+		assert_eq!(continue_from, next_sequence);
+		assert!(event_log.read_range(4, next_sequence).unwrap().0.is_empty());
+
+		// Large ranges are read in batches.
+		let (batch, continue_from) = event_log.read_range(1, u64::MAX).unwrap();
+		assert_eq!(batch.len(), 3);
+		assert_eq!(continue_from, 1 + EVENT_REPLAY_BATCH_SIZE);
+	}
+
+	#[test]
+	fn test_prune() {
+		let store: Arc<dyn PaginatedKVStore> = Arc::new(test_store("test_prune"));
+
+		// Events published long ago, outside of the retention window.
+		for (sequence, id) in [(1, "a"), (2, "b")] {
+			let envelope =
+				EventEnvelope { sequence, timestamp: 1, event: Some(payment_successful(id)) };
+			write_record(
+				&*store,
+				EVENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+				EVENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&event_key(sequence),
+				sequence,
+				&envelope,
+			)
+			.unwrap();
+		}
+		let event_log = EventLog::new(Arc::clone(&store), EventLogConfig::default()).unwrap();
+		event_log.publish(payment_successful("c"));
+		assert_eq!(event_log.first_sequence(), 1);
+		assert_eq!(event_log.read_range(1, 4).unwrap().0.len(), 3);
+
+		event_log.prune().unwrap();
+		assert_eq!(event_log.first_sequence(), 3);
+		let (retained, _) = event_log.read_range(1, 4).unwrap();
+		let retained: Vec<u64> = retained.iter().map(|envelope| envelope.sequence).collect();
+		assert_eq!(retained, vec![3]);
+
+		// Events within the retention window are kept.
+		event_log.prune().unwrap();
+		assert_eq!(event_log.first_sequence(), 3);
+
+		// The oldest retained event is remembered across restarts.
+		drop(event_log);
+		let event_log = EventLog::new(store, EventLogConfig::default()).unwrap();
+		assert_eq!(event_log.first_sequence(), 3);
+		event_log.publish(payment_successful("d"));
+		assert_eq!(event_log.subscribe().1, 5);
+	}
+}
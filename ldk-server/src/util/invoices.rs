@@ -0,0 +1,326 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use ldk_server_grpc::api::{ListInvoicesResponse, ListOffersResponse};
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{
+	Invoice, InvoiceStatus, Offer, PageToken, Payment, PaymentDirection, PaymentStatus,
+};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+	INVOICES_PERSISTENCE_SECONDARY_NAMESPACE, OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+	OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+use crate::util::labels::apply_invoice_label;
+
+/// Records an invoice issued by the server, keyed by its payment hash.
+pub(crate) fn record_invoice(
+	paginated_kv_store: &dyn PaginatedKVStore, invoice: &Invoice,
+) -> Result<(), LdkServerError> {
+	write_record(
+		paginated_kv_store,
+		INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+		INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+		&invoice.payment_hash,
+		invoice.created_at,
+		invoice,
+	)
+}
+
+/// Records an offer issued by the server, keyed by its offer ID.
+pub(crate) fn record_offer(
+	paginated_kv_store: &dyn PaginatedKVStore, offer: &Offer,
+) -> Result<(), LdkServerError> {
+	write_record(
+		paginated_kv_store,
+		OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+		OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&offer.offer_id,
+		offer.created_at,
+		offer,
+	)
+}
+
+/// Marks the invoice or offer that `payment` was made for as paid, once the payment was received.
+///
+/// Payments for invoices and offers that weren't issued by the server are ignored.
+pub(crate) fn record_received_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, payment: &Payment,
+) -> Result<(), LdkServerError> {
+	if payment.direction() != PaymentDirection::Inbound
+		|| payment.status() != PaymentStatus::Succeeded
+	{
+		return Ok(());
+	}
+
+	match payment.kind.as_ref().and_then(|kind| kind.kind.as_ref()) {
+		Some(Kind::Bolt11(bolt11)) => {
+			let payment_hash = bolt11.hash.to_lowercase();
+			let Some(mut invoice) = read_invoice(paginated_kv_store, &payment_hash)? else {
+				return Ok(());
+			};
+			if invoice.status() == InvoiceStatus::Paid {
+				return Ok(());
+			}
+			invoice.status = InvoiceStatus::Paid as i32;
+			invoice.payment_id = Some(payment.id.clone());
+			invoice.amount_received_msat = payment.amount_msat;
+			invoice.settled_at = Some(payment.latest_update_timestamp);
+			record_invoice(paginated_kv_store, &invoice)
+		},
+		Some(Kind::Bolt12Offer(bolt12)) => {
+			let offer_id = bolt12.offer_id.to_lowercase();
+			let Some(mut offer) = read_offer(paginated_kv_store, &offer_id)? else {
+				return Ok(());
+			};
+			// The payment was already counted if the event it was received with is replayed.
+			if offer.last_payment_id.as_ref() == Some(&payment.id) {
+				return Ok(());
+			}
+			offer.status = InvoiceStatus::Paid as i32;
+			offer.payment_count += 1;
+			offer.amount_received_msat += payment.amount_msat.unwrap_or(0);
+			offer.last_payment_id = Some(payment.id.clone());
+			offer.last_paid_at = Some(payment.latest_update_timestamp);
+			record_offer(paginated_kv_store, &offer)
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Marks the open invoice with the given payment hash as cancelled, after its payment was failed
+/// back.
+pub(crate) fn cancel_invoice(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<(), LdkServerError> {
+	let Some(mut invoice) = read_invoice(paginated_kv_store, payment_hash)? else {
+		return Ok(());
+	};
+	if invoice.status() != InvoiceStatus::Open {
+		return Ok(());
+	}
+	invoice.status = InvoiceStatus::Cancelled as i32;
+	invoice.settled_at = Some(current_time_secs());
+	record_invoice(paginated_kv_store, &invoice)
+}
+
+pub(crate) fn list_invoices(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListInvoicesResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+			INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list invoices: {e}"))
+		})?;
+
+	let now = current_time_secs();
+	let mut invoices = Vec::with_capacity(list_response.keys.len());
+	for payment_hash in list_response.keys {
+		let Some(mut invoice) = read_invoice(paginated_kv_store, &payment_hash)? else {
+			continue;
+		};
+		if invoice.status() == InvoiceStatus::Open && now >= invoice.expires_at {
+			invoice.status = InvoiceStatus::Expired as i32;
+		}
+		apply_invoice_label(paginated_kv_store, &mut invoice)?;
+		invoices.push(invoice);
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListInvoicesResponse { invoices, next_page_token })
+}
+
+pub(crate) fn list_offers(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListOffersResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+			OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(InternalServerError, format!("Failed to list offers: {e}"))
+		})?;
+
+	let now = current_time_secs();
+	let mut offers = Vec::with_capacity(list_response.keys.len());
+	for offer_id in list_response.keys {
+		let Some(mut offer) = read_offer(paginated_kv_store, &offer_id)? else {
+			continue;
+		};
+		if offer.status() == InvoiceStatus::Open
+			&& offer.expires_at.is_some_and(|expires_at| now >= expires_at)
+		{
+			offer.status = InvoiceStatus::Expired as i32;
+		}
+		offers.push(offer);
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListOffersResponse { offers, next_page_token })
+}
+
+fn read_invoice(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<Option<Invoice>, LdkServerError> {
+	read_record(
+		paginated_kv_store,
+		INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+		INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_hash,
+	)
+}
+
+fn read_offer(
+	paginated_kv_store: &dyn PaginatedKVStore, offer_id: &str,
+) -> Result<Option<Offer>, LdkServerError> {
+	read_record(
+		paginated_kv_store,
+		OFFERS_PERSISTENCE_PRIMARY_NAMESPACE,
+		OFFERS_PERSISTENCE_SECONDARY_NAMESPACE,
+		offer_id,
+	)
+}
+
+/// Returns whether an invoice with the given payment hash was issued by the server.
+pub(crate) fn is_issued_invoice(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_hash: &str,
+) -> Result<bool, LdkServerError> {
+	let invoice = read_record::<Invoice>(
+		paginated_kv_store,
+		INVOICES_PERSISTENCE_PRIMARY_NAMESPACE,
+		INVOICES_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_hash,
+	)?;
+	Ok(invoice.is_some())
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::{Bolt11, Bolt12Offer, PaymentKind};
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+	use crate::io::persist::sqlite_store::SqliteStore;
+
+	fn received_payment(id: &str, kind: Kind, amount_msat: u64) -> Payment {
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind { kind: Some(kind) }),
+			amount_msat: Some(amount_msat),
+			fee_paid_msat: None,
+			direction: PaymentDirection::Inbound as i32,
+			status: PaymentStatus::Succeeded as i32,
+			latest_update_timestamp: 1_000,
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_invoice_status() {
+		let store = test_store("test_invoice_status");
+		let now = current_time_secs();
+		let invoice = |payment_hash: &str, expires_at: u64| Invoice {
+			payment_hash: payment_hash.to_string(),
+			amount_msat: Some(5_000),
+			label: Some("order-1".to_string()),
+			created_at: now,
+			expires_at,
+			..Default::default()
+		};
+		let (paid, cancelled, expired) = ("aa".repeat(32), "bb".repeat(32), "cc".repeat(32));
+		record_invoice(&store, &invoice(&paid, now + 3600)).unwrap();
+		record_invoice(&store, &invoice(&cancelled, now + 3600)).unwrap();
+		record_invoice(&store, &invoice(&expired, now)).unwrap();
+		assert!(is_issued_invoice(&store, &paid).unwrap());
+		assert!(!is_issued_invoice(&store, &"dd".repeat(32)).unwrap());
+
+		let statuses = |store: &SqliteStore| -> Vec<(String, InvoiceStatus)> {
+			let mut invoices = list_invoices(store, None).unwrap().invoices;
+			invoices.sort_by(|a, b| a.payment_hash.cmp(&b.payment_hash));
+			invoices.iter().map(|i| (i.payment_hash.clone(), i.status())).collect()
+		};
+		assert_eq!(
+			statuses(&store),
+			[
+				(paid.clone(), InvoiceStatus::Open),
+				(cancelled.clone(), InvoiceStatus::Open),
+				(expired.clone(), InvoiceStatus::Expired)
+			]
+		);
+
+		// Payments are correlated by their payment hash, whatever its case.
+		let bolt11 = Kind::Bolt11(Bolt11 { hash: paid.to_uppercase(), ..Default::default() });
+		record_received_payment(&store, &received_payment("p1", bolt11, 5_000)).unwrap();
+		cancel_invoice(&store, &cancelled).unwrap();
+		// An invoice that was paid can't be cancelled anymore.
+		cancel_invoice(&store, &paid).unwrap();
+		assert_eq!(
+			statuses(&store),
+			[
+				(paid.clone(), InvoiceStatus::Paid),
+				(cancelled, InvoiceStatus::Cancelled),
+				(expired, InvoiceStatus::Expired)
+			]
+		);
+
+		let paid = read_invoice(&store, &paid).unwrap().unwrap();
+		assert_eq!(paid.payment_id.as_deref(), Some("p1"));
+		assert_eq!(paid.amount_received_msat, Some(5_000));
+		assert_eq!(paid.settled_at, Some(1_000));
+		assert_eq!(paid.label.as_deref(), Some("order-1"));
+
+		// Payments for invoices the server didn't record are ignored.
+		let unknown = Kind::Bolt11(Bolt11 { hash: "dd".repeat(32), ..Default::default() });
+		record_received_payment(&store, &received_payment("p2", unknown, 1_000)).unwrap();
+		assert_eq!(list_invoices(&store, None).unwrap().invoices.len(), 3);
+	}
+
+	#[test]
+	fn test_offer_payments() {
+		let store = test_store("test_offer_payments");
+		let offer_id = "aa".repeat(32);
+		let offer = Offer {
+			offer_id: offer_id.clone(),
+			description: "coffee".to_string(),
+			created_at: current_time_secs(),
+			..Default::default()
+		};
+		record_offer(&store, &offer).unwrap();
+		assert_eq!(list_offers(&store, None).unwrap().offers[0].status(), InvoiceStatus::Open);
+
+		let bolt12 =
+			|| Kind::Bolt12Offer(Bolt12Offer { offer_id: offer_id.clone(), ..Default::default() });
+		let first = received_payment("p1", bolt12(), 2_000);
+		record_received_payment(&store, &first).unwrap();
+		// Replaying the same payment doesn't count it again.
+		record_received_payment(&store, &first).unwrap();
+		record_received_payment(&store, &received_payment("p2", bolt12(), 3_000)).unwrap();
+		// Payments that weren't received yet aren't counted.
+		let mut pending = received_payment("p3", bolt12(), 4_000);
+		pending.status = PaymentStatus::Pending as i32;
+		record_received_payment(&store, &pending).unwrap();
+
+		let offer = list_offers(&store, None).unwrap().offers.remove(0);
+		assert_eq!(offer.status(), InvoiceStatus::Paid);
+		assert_eq!(offer.payment_count, 2);
+		assert_eq!(offer.amount_received_msat, 5_000);
+		assert_eq!(offer.last_payment_id.as_deref(), Some("p2"));
+	}
+}
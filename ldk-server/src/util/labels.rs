@@ -0,0 +1,290 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::BTreeMap;
+
+use ldk_server_grpc::types::{Channel, Invoice, Payment, UserLabel};
+use log::error;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, CHANNEL_LABELS_PERSISTENCE_PRIMARY_NAMESPACE,
+	CHANNEL_LABELS_PERSISTENCE_SECONDARY_NAMESPACE, PAYMENT_LABELS_PERSISTENCE_PRIMARY_NAMESPACE,
+	PAYMENT_LABELS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+
+const MAX_LABEL_LENGTH: usize = 256;
+const MAX_METADATA_ENTRIES: usize = 32;
+const MAX_METADATA_KEY_LENGTH: usize = 64;
+const MAX_METADATA_VALUE_LENGTH: usize = 1024;
+
+/// What a label is attached to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum LabelTarget {
+	/// A payment, keyed by its hex-encoded payment ID.
+	Payment,
+	/// A channel, keyed by its `user_channel_id`.
+	Channel,
+}
+
+impl LabelTarget {
+	fn namespaces(&self) -> (&'static str, &'static str) {
+		match self {
+			LabelTarget::Payment => (
+				PAYMENT_LABELS_PERSISTENCE_PRIMARY_NAMESPACE,
+				PAYMENT_LABELS_PERSISTENCE_SECONDARY_NAMESPACE,
+			),
+			LabelTarget::Channel => (
+				CHANNEL_LABELS_PERSISTENCE_PRIMARY_NAMESPACE,
+				CHANNEL_LABELS_PERSISTENCE_SECONDARY_NAMESPACE,
+			),
+		}
+	}
+}
+
+/// Validates the label and metadata of a request that creates a payment, invoice or channel, before
+/// it is created.
+pub(crate) fn new_label(
+	label: Option<String>, metadata: BTreeMap<String, String>,
+) -> Result<UserLabel, LdkServerError> {
+	let user_label = UserLabel { label: label.filter(|label| !label.is_empty()), metadata };
+	validate_label(&user_label)?;
+	Ok(user_label)
+}
+
+/// Attaches `user_label` to a payment or channel that was just created.
+///
+/// Nothing is stored for an empty label. As the payment or channel exists by then, failures are
+/// logged rather than returned.
+pub(crate) fn set_label(
+	paginated_kv_store: &dyn PaginatedKVStore, target: LabelTarget, key: &str,
+	user_label: &UserLabel,
+) {
+	if user_label.label.is_none() && user_label.metadata.is_empty() {
+		return;
+	}
+	let (primary_namespace, secondary_namespace) = target.namespaces();
+	if let Err(e) = write_record(
+		paginated_kv_store,
+		primary_namespace,
+		secondary_namespace,
+		key,
+		current_time_secs(),
+		user_label,
+	) {
+		error!("Failed to attach label to {target:?} {key}: {e}");
+	}
+}
+
+/// Changes the label attached to a payment or channel.
+///
+/// The label is replaced if `label` is set, and removed if it is empty. Metadata entries are
+/// merged into the current metadata, and removed if their value is empty.
+pub(crate) fn update_label(
+	paginated_kv_store: &dyn PaginatedKVStore, target: LabelTarget, key: &str,
+	label: Option<String>, metadata: BTreeMap<String, String>,
+) -> Result<UserLabel, LdkServerError> {
+	let mut user_label = read_label(paginated_kv_store, target, key)?.unwrap_or_default();
+	if let Some(label) = label {
+		user_label.label = Some(label).filter(|label| !label.is_empty());
+	}
+	for (key, value) in metadata {
+		if value.is_empty() {
+			user_label.metadata.remove(&key);
+		} else {
+			user_label.metadata.insert(key, value);
+		}
+	}
+	validate_label(&user_label)?;
+
+	let (primary_namespace, secondary_namespace) = target.namespaces();
+	if user_label.label.is_none() && user_label.metadata.is_empty() {
+		paginated_kv_store.remove(primary_namespace, secondary_namespace, key).map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to remove {primary_namespace} record '{key}': {e}"),
+			)
+		})?;
+	} else {
+		write_record(
+			paginated_kv_store,
+			primary_namespace,
+			secondary_namespace,
+			key,
+			current_time_secs(),
+			&user_label,
+		)?;
+	}
+	Ok(user_label)
+}
+
+/// Returns the label attached to a payment or channel, if any.
+pub(crate) fn read_label(
+	paginated_kv_store: &dyn PaginatedKVStore, target: LabelTarget, key: &str,
+) -> Result<Option<UserLabel>, LdkServerError> {
+	let (primary_namespace, secondary_namespace) = target.namespaces();
+	read_record(paginated_kv_store, primary_namespace, secondary_namespace, key)
+}
+
+/// Fills in the label and metadata attached to `payment`.
+pub(crate) fn apply_payment_label(
+	paginated_kv_store: &dyn PaginatedKVStore, payment: &mut Payment,
+) -> Result<(), LdkServerError> {
+	let user_label =
+		read_label(paginated_kv_store, LabelTarget::Payment, &payment.id)?.unwrap_or_default();
+	payment.label = user_label.label;
+	payment.metadata = user_label.metadata;
+	Ok(())
+}
+
+/// Fills in the label and metadata attached to `channel`.
+pub(crate) fn apply_channel_label(
+	paginated_kv_store: &dyn PaginatedKVStore, channel: &mut Channel,
+) -> Result<(), LdkServerError> {
+	let user_label =
+		read_label(paginated_kv_store, LabelTarget::Channel, &channel.user_channel_id)?
+			.unwrap_or_default();
+	channel.label = user_label.label;
+	channel.metadata = user_label.metadata;
+	Ok(())
+}
+
+/// Fills in the label and metadata attached to the payment for `invoice`, which has the invoice's
+/// payment hash as its ID.
+pub(crate) fn apply_invoice_label(
+	paginated_kv_store: &dyn PaginatedKVStore, invoice: &mut Invoice,
+) -> Result<(), LdkServerError> {
+	if let Some(user_label) =
+		read_label(paginated_kv_store, LabelTarget::Payment, &invoice.payment_hash)?
+	{
+		invoice.label = user_label.label;
+		invoice.metadata = user_label.metadata;
+	}
+	Ok(())
+}
+
+fn validate_label(user_label: &UserLabel) -> Result<(), LdkServerError> {
+	let invalid = |message: String| Err(LdkServerError::new(InvalidRequestError, message));
+	if user_label.label.as_ref().is_some_and(|label| label.len() > MAX_LABEL_LENGTH) {
+		return invalid(format!("Label must be at most {MAX_LABEL_LENGTH} bytes long"));
+	}
+	if user_label.metadata.len() > MAX_METADATA_ENTRIES {
+		return invalid(format!("Metadata must have at most {MAX_METADATA_ENTRIES} entries"));
+	}
+	for (key, value) in &user_label.metadata {
+		if key.is_empty() || key.len() > MAX_METADATA_KEY_LENGTH {
+			return invalid(format!(
+				"Metadata keys must be between 1 and {MAX_METADATA_KEY_LENGTH} bytes long"
+			));
+		}
+		if value.len() > MAX_METADATA_VALUE_LENGTH {
+			return invalid(format!(
+				"Metadata value of '{key}' must be at most {MAX_METADATA_VALUE_LENGTH} bytes long"
+			));
+		}
+	}
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn metadata(entries: &[(&str, &str)]) -> BTreeMap<String, String> {
+		entries.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+	}
+
+	#[test]
+	fn test_update_label() {
+		let store = test_store("test_update_label");
+		let payment_id = "aa".repeat(32);
+
+		// Nothing is stored for payments created without a label.
+		let user_label = new_label(Some(String::new()), BTreeMap::new()).unwrap();
+		set_label(&store, LabelTarget::Payment, &payment_id, &user_label);
+		assert_eq!(read_label(&store, LabelTarget::Payment, &payment_id).unwrap(), None);
+
+		let user_label = new_label(
+			Some("order-42".to_string()),
+			metadata(&[("customer", "alice"), ("sku", "coffee")]),
+		)
+		.unwrap();
+		set_label(&store, LabelTarget::Payment, &payment_id, &user_label);
+		// Labels of payments and channels are kept apart.
+		assert_eq!(read_label(&store, LabelTarget::Channel, &payment_id).unwrap(), None);
+
+		let user_label = update_label(
+			&store,
+			LabelTarget::Payment,
+			&payment_id,
+			None,
+			metadata(&[("sku", ""), ("shipped", "true")]),
+		)
+		.unwrap();
+		assert_eq!(user_label.label.as_deref(), Some("order-42"));
+		assert_eq!(user_label.metadata, metadata(&[("customer", "alice"), ("shipped", "true")]));
+
+		let mut payment = Payment { id: payment_id.clone(), ..Default::default() };
+		apply_payment_label(&store, &mut payment).unwrap();
+		assert_eq!(payment.label, user_label.label);
+		assert_eq!(payment.metadata, user_label.metadata);
+
+		// Removing the label and all metadata removes the record.
+		let user_label = update_label(
+			&store,
+			LabelTarget::Payment,
+			&payment_id,
+			Some(String::new()),
+			metadata(&[("customer", ""), ("shipped", "")]),
+		)
+		.unwrap();
+		assert_eq!(user_label, UserLabel::default());
+		assert_eq!(read_label(&store, LabelTarget::Payment, &payment_id).unwrap(), None);
+	}
+
+	#[test]
+	fn test_label_limits() {
+		let store = test_store("test_label_limits");
+		let user_channel_id = "42";
+
+		let err = new_label(Some("a".repeat(MAX_LABEL_LENGTH + 1)), BTreeMap::new()).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+
+		let too_many = (0..=MAX_METADATA_ENTRIES).map(|i| (i.to_string(), "v".to_string()));
+		let err = new_label(None, too_many.collect()).unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+
+		let err =
+			new_label(None, metadata(&[("order", &"a".repeat(MAX_METADATA_VALUE_LENGTH + 1))]))
+				.unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+
+		// Updates that would exceed the limits aren't applied.
+		let user_label = new_label(Some("channel-1".to_string()), BTreeMap::new()).unwrap();
+		set_label(&store, LabelTarget::Channel, user_channel_id, &user_label);
+		let err = update_label(
+			&store,
+			LabelTarget::Channel,
+			user_channel_id,
+			None,
+			metadata(&[("", "v")]),
+		)
+		.unwrap_err();
+		assert_eq!(err.error_code, InvalidRequestError);
+		assert_eq!(
+			read_label(&store, LabelTarget::Channel, user_channel_id).unwrap(),
+			Some(user_label)
+		);
+	}
+}
@@ -7,18 +7,36 @@
 // You may not use this file except in accordance with one or both of these
 // licenses.
 
+pub(crate) mod approvals;
+pub(crate) mod audit;
+pub(crate) mod auth;
+pub(crate) mod claimable_payments;
+pub(crate) mod closed_channels;
 pub(crate) mod config;
 pub(crate) mod entropy;
+pub(crate) mod event_filter;
+pub(crate) mod event_log;
+pub(crate) mod invoices;
+pub(crate) mod labels;
 pub(crate) mod logger;
 pub(crate) mod metrics;
+pub(crate) mod node_monitor;
+pub(crate) mod nonce_cache;
+pub(crate) mod onchain_payments;
+pub(crate) mod probes;
 pub(crate) mod proto_adapter;
+pub(crate) mod routing;
+pub(crate) mod spend_limits;
 pub(crate) mod systemd;
 pub(crate) mod tls;
+pub(crate) mod unix_socket;
+pub(crate) mod webhooks;
 
 use std::fs::{self, OpenOptions};
 use std::io::{self, Write};
 use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
 use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub(crate) fn write_new(path: &Path, contents: &[u8], mode: u32) -> io::Result<()> {
 	let mut file = OpenOptions::new().create_new(true).write(true).mode(mode).open(path)?;
@@ -28,6 +46,11 @@ pub(crate) fn write_new(path: &Path, contents: &[u8], mode: u32) -> io::Result<(
 	Ok(())
 }
 
+/// Returns the current time in seconds since the UNIX epoch.
+pub(crate) fn current_time_secs() -> u64 {
+	SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
 #[cfg(test)]
 mod tests {
 	use std::path::PathBuf;
@@ -0,0 +1,469 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use ldk_server_grpc::events::event_envelope::Event;
+use ldk_server_grpc::events::{
+	OnchainFundsConfirmed, OnchainFundsReceived, PeerConnected, PeerDisconnected,
+	WalletSyncCompleted,
+};
+use ldk_server_grpc::types::confirmation_status::Status;
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{BestBlock, Payment, PaymentDirection, Peer};
+use log::error;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, NODE_MONITOR_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+	NODE_MONITOR_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+	ONCHAIN_RECEIPTS_PERSISTENCE_PRIMARY_NAMESPACE,
+	ONCHAIN_RECEIPTS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+
+/// How often the node is polled for changes that aren't surfaced as LDK Node events.
+pub(crate) const NODE_MONITOR_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+const NODE_MONITOR_STATE_KEY: &str = "state";
+
+/// Whether an inbound on-chain payment was reported as confirmed.
+#[derive(Clone, PartialEq, prost::Message)]
+struct OnchainReceiptRecord {
+	#[prost(bool, tag = "1")]
+	confirmed: bool,
+}
+
+/// The persisted state of the [`NodeMonitor`].
+#[derive(Clone, PartialEq, prost::Message)]
+struct NodeMonitorStateRecord {
+	/// When the on-chain receipts found on the first start were taken as the baseline.
+	#[prost(uint64, tag = "1")]
+	baseline_taken_at: u64,
+}
+
+/// The state of the node relevant to [`NodeMonitor`], as observed in one poll.
+#[derive(Debug, Default)]
+pub(crate) struct NodeSnapshot {
+	pub(crate) peers: Vec<Peer>,
+	/// The on-chain payments known to the wallet, if they were listed for this poll, see
+	/// [`NodeMonitor::should_list_onchain_payments`]. The inbound ones are the on-chain receipts.
+	pub(crate) onchain_payments: Option<Vec<Payment>>,
+	pub(crate) latest_onchain_wallet_sync_timestamp: Option<u64>,
+	pub(crate) latest_lightning_wallet_sync_timestamp: Option<u64>,
+	pub(crate) current_best_block: BestBlock,
+}
+
+/// Derives events from changes of the node's state that LDK Node doesn't emit events for, by
+/// comparing consecutive [`NodeSnapshot`]s.
+///
+/// The first snapshot is only taken as the baseline for peers and wallet syncs, so that the state
+/// found at startup isn't reported as changes. The on-chain receipts that were reported are
+/// persisted instead, so that those received or confirmed while the server wasn't running are
+/// reported after it starts. Only on the very first start are the receipts found taken as the
+/// baseline, so that existing deposits aren't reported.
+pub(crate) struct NodeMonitor {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	initialized: bool,
+	/// Whether the on-chain receipts were taken as the baseline, on the very first start.
+	onchain_receipts_baseline_taken: bool,
+	connected_peers: HashMap<String, Peer>,
+	/// The IDs of the reported inbound on-chain payments, mapped to whether they were reported as
+	/// confirmed.
+	onchain_receipts: HashMap<String, bool>,
+	latest_onchain_wallet_sync_timestamp: Option<u64>,
+	latest_lightning_wallet_sync_timestamp: Option<u64>,
+}
+
+impl NodeMonitor {
+	/// Creates the monitor, loading the on-chain receipts that were already reported.
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	) -> Result<Self, LdkServerError> {
+		let onchain_receipts_baseline_taken = read_record::<NodeMonitorStateRecord>(
+			&*paginated_kv_store,
+			NODE_MONITOR_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+			NODE_MONITOR_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+			NODE_MONITOR_STATE_KEY,
+		)?
+		.is_some();
+
+		let mut onchain_receipts = HashMap::new();
+		let mut page_token = None;
+		loop {
+			let list_response = paginated_kv_store
+				.list(
+					ONCHAIN_RECEIPTS_PERSISTENCE_PRIMARY_NAMESPACE,
+					ONCHAIN_RECEIPTS_PERSISTENCE_SECONDARY_NAMESPACE,
+					page_token,
+				)
+				.map_err(|e| {
+					LdkServerError::new(
+						InternalServerError,
+						format!("Failed to list on-chain receipts: {e}"),
+					)
+				})?;
+			for payment_id in list_response.keys {
+				if let Some(record) = read_record::<OnchainReceiptRecord>(
+					&*paginated_kv_store,
+					ONCHAIN_RECEIPTS_PERSISTENCE_PRIMARY_NAMESPACE,
+					ONCHAIN_RECEIPTS_PERSISTENCE_SECONDARY_NAMESPACE,
+					&payment_id,
+				)? {
+					onchain_receipts.insert(payment_id, record.confirmed);
+				}
+			}
+			page_token = list_response.next_page_token;
+			if page_token.is_none() {
+				break;
+			}
+		}
+
+		Ok(Self {
+			paginated_kv_store,
+			initialized: false,
+			onchain_receipts_baseline_taken,
+			connected_peers: HashMap::new(),
+			onchain_receipts,
+			latest_onchain_wallet_sync_timestamp: None,
+			latest_lightning_wallet_sync_timestamp: None,
+		})
+	}
+
+	/// Returns whether the on-chain payments need to be listed for the next snapshot.
+	///
+	/// They only change when the on-chain wallet syncs, so listing them, which goes through all
+	/// payments, is skipped otherwise.
+	pub(crate) fn should_list_onchain_payments(
+		&self, latest_onchain_wallet_sync_timestamp: Option<u64>,
+	) -> bool {
+		!self.initialized
+			|| latest_onchain_wallet_sync_timestamp != self.latest_onchain_wallet_sync_timestamp
+	}
+
+	/// Returns the events for the changes since the previous snapshot.
+	pub(crate) fn poll(&mut self, snapshot: NodeSnapshot) -> Vec<Event> {
+		let mut events = Vec::new();
+		let initialized = std::mem::replace(&mut self.initialized, true);
+
+		let connected_peers: HashMap<String, Peer> = snapshot
+			.peers
+			.into_iter()
+			.filter(|peer| peer.is_connected)
+			.map(|peer| (peer.node_id.clone(), peer))
+			.collect();
+		if initialized {
+			for (node_id, peer) in &connected_peers {
+				if !self.connected_peers.contains_key(node_id) {
+					events.push(Event::PeerConnected(PeerConnected {
+						node_id: node_id.clone(),
+						address: peer.address.clone(),
+					}));
+				}
+			}
+			for (node_id, peer) in &self.connected_peers {
+				if !connected_peers.contains_key(node_id) {
+					events.push(Event::PeerDisconnected(PeerDisconnected {
+						node_id: node_id.clone(),
+						address: peer.address.clone(),
+					}));
+				}
+			}
+		}
+		self.connected_peers = connected_peers;
+
+		let take_baseline = !self.onchain_receipts_baseline_taken;
+		let onchain_receipts = snapshot
+			.onchain_payments
+			.into_iter()
+			.flatten()
+			.filter(|payment| payment.direction() == PaymentDirection::Inbound);
+		for payment in onchain_receipts {
+			let confirmed = is_confirmed(&payment);
+			let reported = self.onchain_receipts.get(&payment.id).copied();
+			if reported == Some(confirmed) {
+				continue;
+			}
+			self.record_onchain_receipt(&payment.id, confirmed);
+			match reported {
+				_ if take_baseline => {},
+				None => {
+					events.push(Event::OnchainFundsReceived(OnchainFundsReceived {
+						payment: Some(payment.clone()),
+					}));
+					if confirmed {
+						events.push(Event::OnchainFundsConfirmed(OnchainFundsConfirmed {
+							payment: Some(payment),
+						}));
+					}
+				},
+				Some(false) if confirmed => {
+					events.push(Event::OnchainFundsConfirmed(OnchainFundsConfirmed {
+						payment: Some(payment),
+					}));
+				},
+				// The confirmation was reorged out, it is reported again once it reconfirms.
+				Some(_) => {},
+			}
+		}
+		if take_baseline {
+			let state = NodeMonitorStateRecord { baseline_taken_at: current_time_secs() };
+			match write_record(
+				&*self.paginated_kv_store,
+				NODE_MONITOR_STATE_PERSISTENCE_PRIMARY_NAMESPACE,
+				NODE_MONITOR_STATE_PERSISTENCE_SECONDARY_NAMESPACE,
+				NODE_MONITOR_STATE_KEY,
+				state.baseline_taken_at,
+				&state,
+			) {
+				Ok(()) => self.onchain_receipts_baseline_taken = true,
+				Err(e) => error!("Failed to persist node monitor state: {e}"),
+			}
+		}
+
+		let onchain_synced = snapshot.latest_onchain_wallet_sync_timestamp
+			!= self.latest_onchain_wallet_sync_timestamp;
+		let lightning_synced = snapshot.latest_lightning_wallet_sync_timestamp
+			!= self.latest_lightning_wallet_sync_timestamp;
+		if initialized && (onchain_synced || lightning_synced) {
+			events.push(Event::WalletSyncCompleted(WalletSyncCompleted {
+				latest_onchain_wallet_sync_timestamp: snapshot.latest_onchain_wallet_sync_timestamp,
+				latest_lightning_wallet_sync_timestamp: snapshot
+					.latest_lightning_wallet_sync_timestamp,
+				current_best_block: Some(snapshot.current_best_block),
+			}));
+		}
+		self.latest_onchain_wallet_sync_timestamp = snapshot.latest_onchain_wallet_sync_timestamp;
+		self.latest_lightning_wallet_sync_timestamp =
+			snapshot.latest_lightning_wallet_sync_timestamp;
+
+		events
+	}
+
+	/// Remembers that `payment_id` was reported, so that it isn't reported again after a restart.
+	fn record_onchain_receipt(&mut self, payment_id: &str, confirmed: bool) {
+		self.onchain_receipts.insert(payment_id.to_string(), confirmed);
+		if let Err(e) = write_record(
+			&*self.paginated_kv_store,
+			ONCHAIN_RECEIPTS_PERSISTENCE_PRIMARY_NAMESPACE,
+			ONCHAIN_RECEIPTS_PERSISTENCE_SECONDARY_NAMESPACE,
+			payment_id,
+			current_time_secs(),
+			&OnchainReceiptRecord { confirmed },
+		) {
+			error!("Failed to persist on-chain receipt {payment_id}: {e}");
+		}
+	}
+}
+
+fn is_confirmed(payment: &Payment) -> bool {
+	match payment.kind.as_ref().and_then(|kind| kind.kind.as_ref()) {
+		Some(Kind::Onchain(onchain)) => {
+			matches!(
+				onchain.status.as_ref().and_then(|status| status.status.as_ref()),
+				Some(Status::Confirmed(_))
+			)
+		},
+		_ => false,
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::{
+		ConfirmationStatus, Confirmed, Onchain, PaymentKind, Unconfirmed,
+	};
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn peer(node_id: &str, is_connected: bool) -> Peer {
+		Peer {
+			node_id: node_id.to_string(),
+			address: "127.0.0.1:9735".to_string(),
+			is_persisted: true,
+			is_connected,
+		}
+	}
+
+	fn onchain_receipt(id: &str, confirmed: bool) -> Payment {
+		let status = if confirmed {
+			Status::Confirmed(Confirmed { height: 100, ..Default::default() })
+		} else {
+			Status::Unconfirmed(Unconfirmed {})
+		};
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind {
+				kind: Some(Kind::Onchain(Onchain {
+					txid: id.to_string(),
+					status: Some(ConfirmationStatus { status: Some(status) }),
+				})),
+			}),
+			..Default::default()
+		}
+	}
+
+	fn snapshot(peers: Vec<Peer>, onchain_payments: Vec<Payment>, height: u32) -> NodeSnapshot {
+		NodeSnapshot {
+			peers,
+			onchain_payments: Some(onchain_payments),
+			current_best_block: BestBlock { height, ..Default::default() },
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn test_peer_and_onchain_events() {
+		let mut monitor =
+			NodeMonitor::new(Arc::new(test_store("test_peer_and_onchain_events"))).unwrap();
+		// The state found at startup isn't reported.
+		let events = monitor.poll(snapshot(
+			vec![peer("02aa", true), peer("02bb", false)],
+			vec![onchain_receipt("a", false)],
+			100,
+		));
+		assert!(events.is_empty());
+
+		// Outbound on-chain payments aren't receipts.
+		let mut sent = onchain_receipt("c", false);
+		sent.set_direction(PaymentDirection::Outbound);
+		let events = monitor.poll(snapshot(
+			vec![peer("02aa", false), peer("02bb", true)],
+			vec![onchain_receipt("a", true), onchain_receipt("b", false), sent],
+			101,
+		));
+		assert_eq!(events.len(), 4);
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::PeerConnected(PeerConnected { node_id, .. }) if node_id == "02bb"
+		)));
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::PeerDisconnected(PeerDisconnected { node_id, .. }) if node_id == "02aa"
+		)));
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::OnchainFundsConfirmed(OnchainFundsConfirmed { payment: Some(payment) })
+				if payment.id == "a"
+		)));
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::OnchainFundsReceived(OnchainFundsReceived { payment: Some(payment) })
+				if payment.id == "b"
+		)));
+
+		// Nothing changed.
+		let events = monitor.poll(snapshot(
+			vec![peer("02aa", false), peer("02bb", true)],
+			vec![onchain_receipt("a", true), onchain_receipt("b", false)],
+			102,
+		));
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn test_onchain_events_after_restart() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_onchain_events_after_restart"));
+		let mut monitor = NodeMonitor::new(Arc::clone(&store)).unwrap();
+		// The receipts found on the very first start aren't reported.
+		let events = monitor.poll(snapshot(Vec::new(), vec![onchain_receipt("a", false)], 100));
+		assert!(events.is_empty());
+		let events = monitor.poll(snapshot(
+			Vec::new(),
+			vec![onchain_receipt("a", false), onchain_receipt("b", false)],
+			101,
+		));
+		assert_eq!(events.len(), 1);
+		drop(monitor);
+
+		// Receipts received or confirmed while the server wasn't running are reported once it
+		// starts, but the connected peers are only taken as the baseline.
+		let mut monitor = NodeMonitor::new(Arc::clone(&store)).unwrap();
+		let events = monitor.poll(snapshot(
+			vec![peer("02aa", true)],
+			vec![
+				onchain_receipt("a", true),
+				onchain_receipt("b", false),
+				onchain_receipt("c", false),
+			],
+			105,
+		));
+		assert_eq!(events.len(), 2);
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::OnchainFundsConfirmed(OnchainFundsConfirmed { payment: Some(payment) })
+				if payment.id == "a"
+		)));
+		assert!(events.iter().any(|event| matches!(
+			event,
+			Event::OnchainFundsReceived(OnchainFundsReceived { payment: Some(payment) })
+				if payment.id == "c"
+		)));
+		drop(monitor);
+
+		let mut monitor = NodeMonitor::new(store).unwrap();
+		let events = monitor.poll(snapshot(
+			Vec::new(),
+			vec![
+				onchain_receipt("a", true),
+				onchain_receipt("b", false),
+				onchain_receipt("c", false),
+			],
+			106,
+		));
+		assert!(events.is_empty());
+	}
+
+	#[test]
+	fn test_onchain_payments_are_listed_after_wallet_syncs() {
+		let mut monitor = NodeMonitor::new(Arc::new(test_store(
+			"test_onchain_payments_are_listed_after_wallet_syncs",
+		)))
+		.unwrap();
+		assert!(monitor.should_list_onchain_payments(None));
+
+		let mut synced = snapshot(Vec::new(), Vec::new(), 100);
+		synced.latest_onchain_wallet_sync_timestamp = Some(1_000);
+		monitor.poll(synced);
+		assert!(!monitor.should_list_onchain_payments(Some(1_000)));
+		assert!(monitor.should_list_onchain_payments(Some(1_030)));
+
+		// Receipts that weren't listed aren't considered gone.
+		let mut unlisted = snapshot(Vec::new(), Vec::new(), 100);
+		unlisted.onchain_payments = None;
+		unlisted.latest_onchain_wallet_sync_timestamp = Some(1_000);
+		assert!(monitor.poll(unlisted).is_empty());
+	}
+
+	#[test]
+	fn test_wallet_sync_events() {
+		let mut monitor =
+			NodeMonitor::new(Arc::new(test_store("test_wallet_sync_events"))).unwrap();
+		let mut synced = snapshot(Vec::new(), Vec::new(), 100);
+		synced.latest_onchain_wallet_sync_timestamp = Some(1_000);
+		assert!(monitor.poll(synced).is_empty());
+
+		let mut synced = snapshot(Vec::new(), Vec::new(), 101);
+		synced.latest_onchain_wallet_sync_timestamp = Some(1_000);
+		synced.latest_lightning_wallet_sync_timestamp = Some(1_010);
+		match &monitor.poll(synced)[..] {
+			[Event::WalletSyncCompleted(event)] => {
+				assert_eq!(event.latest_lightning_wallet_sync_timestamp, Some(1_010));
+				assert_eq!(event.current_best_block.as_ref().unwrap().height, 101);
+			},
+			events => panic!("Unexpected events {events:?}"),
+		}
+	}
+}
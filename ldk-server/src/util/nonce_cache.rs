@@ -0,0 +1,111 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+/// The length of the nonce included in the signed material of each request, in bytes.
+pub(crate) const AUTH_NONCE_LEN: usize = 16;
+
+/// The maximum number of nonces kept by default, about 1,000 requests per second for a minute.
+pub(crate) const DEFAULT_NONCE_CACHE_CAPACITY: usize = 65_536;
+
+/// Keeps track of the nonces of recently authenticated requests, so a captured request can't be
+/// replayed while its timestamp is still within the accepted tolerance.
+///
+/// Nonces are remembered together with the timestamp of their request and forgotten once that
+/// timestamp is no longer accepted anyway. If the cache is full, the entry with the oldest
+/// timestamp is evicted and requests with a timestamp up to and including the evicted one are
+/// rejected from then on, so the cache stays bounded without ever accepting a replay.
+pub(crate) struct NonceCache {
+	capacity: usize,
+	inner: Mutex<NonceCacheInner>,
+}
+
+struct NonceCacheInner {
+	seen: BTreeSet<(u64, [u8; AUTH_NONCE_LEN])>,
+	/// Requests with a timestamp at or below this are rejected, as their nonces may have been
+	/// evicted.
+	evicted_up_to: Option<u64>,
+}
+
+impl NonceCache {
+	pub(crate) fn new(capacity: usize) -> Self {
+		assert!(capacity > 0, "Nonce cache capacity must be positive");
+		let inner = NonceCacheInner { seen: BTreeSet::new(), evicted_up_to: None };
+		Self { capacity, inner: Mutex::new(inner) }
+	}
+
+	/// Records the nonce of a request with the given timestamp, returning `false` if it can't be
+	/// told apart from a replay.
+	///
+	/// `min_valid_timestamp` is the oldest timestamp that is currently accepted. Entries older
+	/// than that are pruned, as their requests are rejected regardless of the nonce.
+	pub(crate) fn insert(
+		&self, timestamp: u64, nonce: [u8; AUTH_NONCE_LEN], min_valid_timestamp: u64,
+	) -> bool {
+		let mut inner = self.inner.lock().unwrap();
+
+		if inner.evicted_up_to.is_some_and(|evicted_up_to| timestamp <= evicted_up_to) {
+			return false;
+		}
+
+		while inner.seen.first().is_some_and(|(oldest, _)| *oldest < min_valid_timestamp) {
+			inner.seen.pop_first();
+		}
+
+		if !inner.seen.insert((timestamp, nonce)) {
+			return false;
+		}
+
+		if inner.seen.len() > self.capacity {
+			if let Some((evicted, _)) = inner.seen.pop_first() {
+				inner.evicted_up_to = Some(evicted);
+				if evicted >= timestamp {
+					// We just evicted the nonce of this very request.
+					return false;
+				}
+			}
+		}
+		true
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn test_nonce_cache_rejects_replays() {
+		let cache = NonceCache::new(10);
+		assert!(cache.insert(100, [1; AUTH_NONCE_LEN], 40));
+		assert!(!cache.insert(100, [1; AUTH_NONCE_LEN], 40));
+		assert!(cache.insert(100, [2; AUTH_NONCE_LEN], 40));
+		assert!(cache.insert(101, [1; AUTH_NONCE_LEN], 40));
+
+		// Pruned entries would be rejected for their timestamp anyway.
+		assert!(cache.insert(200, [3; AUTH_NONCE_LEN], 140));
+		assert_eq!(cache.inner.lock().unwrap().seen.len(), 1);
+	}
+
+	#[test]
+	fn test_nonce_cache_is_bounded() {
+		let cache = NonceCache::new(2);
+		assert!(cache.insert(100, [1; AUTH_NONCE_LEN], 40));
+		assert!(cache.insert(101, [2; AUTH_NONCE_LEN], 40));
+		assert!(cache.insert(102, [3; AUTH_NONCE_LEN], 40));
+		assert_eq!(cache.inner.lock().unwrap().seen.len(), 2);
+
+		// The evicted nonce can't be replayed, and neither can any other request from that second.
+		assert!(!cache.insert(100, [1; AUTH_NONCE_LEN], 40));
+		assert!(!cache.insert(100, [4; AUTH_NONCE_LEN], 40));
+		assert!(!cache.insert(102, [3; AUTH_NONCE_LEN], 40));
+		assert!(cache.insert(103, [4; AUTH_NONCE_LEN], 40));
+	}
+}
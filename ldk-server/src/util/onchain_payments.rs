@@ -0,0 +1,133 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use ldk_server_grpc::types::Payment;
+use log::error;
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::InternalServerError;
+use crate::io::persist::paginated_kv_store::{PaginatedKVStore, PaymentIndex};
+
+/// Persists an on-chain payment with the other payments, so that it's listed by
+/// `ListOnchainTransactions` and `ListPayments`.
+///
+/// The payment is written with its latest update time, which the store keeps as its creation time
+/// when it's written again. Pages are thus ordered by when the transaction was first seen, which
+/// doesn't change when it confirms.
+pub(crate) fn persist_onchain_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, payment: &Payment,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.write_payment(
+			&payment.id,
+			payment.latest_update_timestamp as i64,
+			&payment.encode_to_vec(),
+			&PaymentIndex::from(payment),
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to persist on-chain payment {}: {e}", payment.id),
+			)
+		})
+}
+
+/// Keeps the on-chain payments known to LDK Node's wallet persisted with the other payments.
+///
+/// LDK Node doesn't emit events for on-chain payments, so they're persisted from the payments
+/// listed whenever the wallet synced, see
+/// [`crate::util::node_monitor::NodeMonitor::should_list_onchain_payments`].
+pub(crate) struct OnchainPaymentRecorder {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	/// The latest update time of each persisted payment, by payment ID. After a restart, all
+	/// payments are persisted once more.
+	recorded: HashMap<String, u64>,
+}
+
+impl OnchainPaymentRecorder {
+	pub(crate) fn new(paginated_kv_store: Arc<dyn PaginatedKVStore>) -> Self {
+		Self { paginated_kv_store, recorded: HashMap::new() }
+	}
+
+	/// Persists the given on-chain payments that changed since they were last persisted.
+	pub(crate) fn record(&mut self, payments: &[Payment]) {
+		for payment in payments {
+			if self.recorded.get(&payment.id) == Some(&payment.latest_update_timestamp) {
+				continue;
+			}
+			match persist_onchain_payment(&*self.paginated_kv_store, payment) {
+				Ok(()) => {
+					self.recorded.insert(payment.id.clone(), payment.latest_update_timestamp);
+				},
+				Err(e) => error!("{}", e.message),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::confirmation_status::Status;
+	use ldk_server_grpc::types::payment_kind::Kind;
+	use ldk_server_grpc::types::{
+		ConfirmationStatus, Confirmed, Onchain, PaymentKind, PaymentKindType, Unconfirmed,
+	};
+
+	use super::*;
+	use crate::io::persist::paginated_kv_store::PaymentFilter;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn onchain_payment(id: &str, confirmed: bool, latest_update_timestamp: u64) -> Payment {
+		let status = if confirmed {
+			Status::Confirmed(Confirmed { height: 100, ..Default::default() })
+		} else {
+			Status::Unconfirmed(Unconfirmed {})
+		};
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind {
+				kind: Some(Kind::Onchain(Onchain {
+					txid: id.to_string(),
+					status: Some(ConfirmationStatus { status: Some(status) }),
+				})),
+			}),
+			latest_update_timestamp,
+			..Default::default()
+		}
+	}
+
+	fn list_onchain_payments(store: &dyn PaginatedKVStore) -> Vec<String> {
+		let filter = PaymentFilter { kind: Some(PaymentKindType::Onchain), ..Default::default() };
+		store.list_payments(&filter, 100, None).unwrap().keys
+	}
+
+	#[test]
+	fn test_pages_keep_their_order_when_transactions_confirm() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_pages_keep_their_order_when_transactions_confirm"));
+		let mut recorder = OnchainPaymentRecorder::new(Arc::clone(&store));
+		recorder.record(&[onchain_payment("a", false, 1_000), onchain_payment("b", false, 2_000)]);
+		assert_eq!(list_onchain_payments(&*store), vec!["b", "a"]);
+
+		// The older transaction confirming doesn't move it ahead of the newer one.
+		recorder.record(&[onchain_payment("a", true, 3_000), onchain_payment("b", false, 2_000)]);
+		assert_eq!(list_onchain_payments(&*store), vec!["b", "a"]);
+		let filter = PaymentFilter { updated_since: Some(3_000), ..Default::default() };
+		assert_eq!(store.list_payments(&filter, 100, None).unwrap().keys, vec!["a"]);
+
+		// After a restart, the payments are persisted again without changing their order.
+		let mut recorder = OnchainPaymentRecorder::new(Arc::clone(&store));
+		recorder.record(&[onchain_payment("a", true, 3_000), onchain_payment("b", true, 4_000)]);
+		assert_eq!(list_onchain_payments(&*store), vec!["b", "a"]);
+	}
+}
@@ -0,0 +1,310 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use ldk_server_grpc::types::{ProbeResult, ProbeStatus, RoutePath};
+
+/// How often the pathfinding scores are checked for the outcomes of pending probes.
+///
+/// The scores are read once per interval for all pending probes, however many requests wait for
+/// their outcomes.
+pub(crate) const PROBE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How long a probe without an observed outcome is assumed to lock liquidity.
+///
+/// Probes resolve within seconds, unless a node along their path holds on to the HTLC. Their
+/// outcome may also never be observed, e.g. if a concurrent payment updated the same scores.
+const PROBE_EXPIRY: Duration = Duration::from_secs(60 * 60);
+
+/// The liquidity of a channel in one direction as estimated by the scorer, as lower and upper
+/// bound in millisatoshis, or `None` if the scorer has no estimate for the channel.
+pub(crate) type LiquidityRange = Option<(u64, u64)>;
+
+/// The estimated status of a probe and the short channel ID of the channel it failed at, if any.
+type ProbeOutcome = (ProbeStatus, Option<u64>);
+
+struct Probe {
+	path: RoutePath,
+	/// The liquidity estimated for each hop of the path before the probe was sent.
+	baseline: Vec<LiquidityRange>,
+	/// The liquidity estimated for each hop of the path when the scores were last checked.
+	liquidity: Vec<LiquidityRange>,
+	sent_at: Instant,
+	outcome: Option<ProbeOutcome>,
+	/// Whether the request that sent the probe returned, so nothing waits for its outcome anymore.
+	returned: bool,
+}
+
+#[derive(Default)]
+struct ProbeTrackerState {
+	next_id: u64,
+	probes: HashMap<u64, Probe>,
+	/// When a request last claimed checking the scores, see [`ProbeTracker::claim_poll`].
+	polled_at: Option<Instant>,
+}
+
+/// Tracks the probes sent via `SendProbes` and estimates their outcomes.
+///
+/// LDK Node doesn't report the outcomes of probes, but LDK's scorer learns from them: a probe
+/// failing at a channel lowers the upper bound of the channel's liquidity, and a probe reaching the
+/// end of its path raises the lower bounds of the liquidity of all channels along it. The outcomes
+/// are thus estimated by comparing the liquidity estimated for the hops of a path to the estimates
+/// from before the probe was sent. This is only an estimate: LDK Node picks the paths of the probes
+/// with its own router, which may send them along other paths than the tracked ones, and other
+/// payments update the same scores.
+#[derive(Default)]
+pub(crate) struct ProbeTracker {
+	state: Mutex<ProbeTrackerState>,
+}
+
+impl ProbeTracker {
+	pub(crate) fn new() -> Self {
+		Self::default()
+	}
+
+	/// Tracks a probe sent along `path`, returning its ID.
+	///
+	/// The `baseline` is the liquidity estimated for each hop of the path before the probe was sent.
+	pub(crate) fn track(&self, path: RoutePath, baseline: Vec<LiquidityRange>) -> u64 {
+		let mut state = self.state.lock().unwrap();
+		let id = state.next_id;
+		state.next_id += 1;
+		let liquidity = baseline.clone();
+		let probe = Probe {
+			path,
+			baseline,
+			liquidity,
+			sent_at: Instant::now(),
+			outcome: None,
+			returned: false,
+		};
+		state.probes.insert(id, probe);
+		id
+	}
+
+	/// Returns whether the caller should check the scores for the outcomes of the pending probes, via
+	/// [`Self::pending_paths`] and [`Self::update`].
+	///
+	/// Only one caller is told to per [`PROBE_POLL_INTERVAL`], so concurrent requests share a single
+	/// read of the scores and otherwise only look at the outcomes it observed.
+	pub(crate) fn claim_poll(&self) -> bool {
+		let mut state = self.state.lock().unwrap();
+		let now = Instant::now();
+		if state.polled_at.is_some_and(|polled_at| now - polled_at < PROBE_POLL_INTERVAL) {
+			return false;
+		}
+		state.polled_at = Some(now);
+		true
+	}
+
+	/// Returns the paths of the probes without an observed outcome, by ID.
+	pub(crate) fn pending_paths(&self) -> Vec<(u64, RoutePath)> {
+		let state = self.state.lock().unwrap();
+		state
+			.probes
+			.iter()
+			.filter(|(_, probe)| probe.outcome.is_none())
+			.map(|(id, probe)| (*id, probe.path.clone()))
+			.collect()
+	}
+
+	/// Updates the outcomes of the pending probes from the liquidity currently estimated for the
+	/// hops of their paths, as looked up for [`Self::pending_paths`].
+	pub(crate) fn update(&self, liquidity: &HashMap<u64, Vec<LiquidityRange>>) {
+		let mut state = self.state.lock().unwrap();
+		for (id, probe) in state.probes.iter_mut() {
+			if probe.outcome.is_some() {
+				continue;
+			}
+			if let Some(current) = liquidity.get(id) {
+				probe.outcome = observed_outcome(&probe.path, &probe.baseline, current);
+				probe.liquidity = current.clone();
+			}
+		}
+		state.probes.retain(|_, probe| {
+			!(probe.returned && probe.outcome.is_some()) && probe.sent_at.elapsed() < PROBE_EXPIRY
+		});
+	}
+
+	/// Returns whether the outcomes of all the given probes were observed.
+	pub(crate) fn is_resolved(&self, ids: &[u64]) -> bool {
+		let state = self.state.lock().unwrap();
+		ids.iter().all(|id| state.probes.get(id).is_none_or(|probe| probe.outcome.is_some()))
+	}
+
+	/// Returns the results of the given probes, once nothing waits for their outcomes anymore.
+	///
+	/// A probe without an observed outcome is still returned as succeeded if the liquidity
+	/// estimated for its path when the scores were last checked suffices, as a successful probe
+	/// then doesn't change the estimates. Otherwise it stays pending and keeps being tracked until
+	/// its outcome is observed.
+	pub(crate) fn finish(&self, ids: &[u64]) -> Vec<ProbeResult> {
+		let mut state = self.state.lock().unwrap();
+		let results = ids
+			.iter()
+			.filter_map(|id| {
+				let probe = state.probes.get_mut(id)?;
+				probe.returned = true;
+				if probe.outcome.is_none() {
+					let has_liquidity = probe.liquidity.len() == probe.path.hops.len()
+						&& probe.liquidity.iter().all(|range| {
+							range.is_some_and(|(lower, _)| lower >= probe.path.amount_msat)
+						});
+					if has_liquidity {
+						probe.outcome = Some((ProbeStatus::Succeeded, None));
+					}
+				}
+				let (estimated_status, estimated_failed_short_channel_id) =
+					probe.outcome.unwrap_or((ProbeStatus::Pending, None));
+				Some(ProbeResult {
+					path: Some(probe.path.clone()),
+					estimated_status: estimated_status.into(),
+					estimated_failed_short_channel_id,
+				})
+			})
+			.collect();
+		state.probes.retain(|_, probe| !(probe.returned && probe.outcome.is_some()));
+		results
+	}
+
+	/// Returns the liquidity locked by the probes without an observed outcome, in millisatoshis.
+	pub(crate) fn locked_msat(&self) -> u64 {
+		let state = self.state.lock().unwrap();
+		state
+			.probes
+			.values()
+			.filter(|probe| probe.outcome.is_none() && probe.sent_at.elapsed() < PROBE_EXPIRY)
+			.map(|probe| probe.path.amount_msat + probe.path.fee_msat)
+			.sum()
+	}
+}
+
+/// Infers the outcome of a probe from how the liquidity estimated for the hops of its path changed
+/// since it was sent, if it changed the way the scorer updates it for a failed or successful probe.
+fn observed_outcome(
+	path: &RoutePath, baseline: &[LiquidityRange], current: &[LiquidityRange],
+) -> Option<ProbeOutcome> {
+	if baseline.len() != path.hops.len() || current.len() != path.hops.len() {
+		return None;
+	}
+	for ((hop, before), after) in path.hops.iter().zip(baseline).zip(current) {
+		let upper_bound_lowered = match (before, after) {
+			(Some((_, before)), Some((_, after))) => after < before,
+			(None, Some((_, after))) => *after <= path.amount_msat,
+			(_, None) => false,
+		};
+		if upper_bound_lowered {
+			return Some((ProbeStatus::Failed, Some(hop.short_channel_id)));
+		}
+	}
+	// The last hop's lower bound is only raised if the probe reached the end of the path, while a
+	// failure downstream of a channel raises the lower bound of the channel too.
+	let lower_bound_raised = match (baseline.last()?, current.last()?) {
+		(Some((before, _)), Some((after, _))) => after > before,
+		(None, Some((after, _))) => *after > 0,
+		(_, None) => false,
+	};
+	lower_bound_raised.then_some((ProbeStatus::Succeeded, None))
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::RouteHop;
+
+	use super::*;
+
+	fn path(short_channel_ids: &[u64], amount_msat: u64) -> RoutePath {
+		let hops = short_channel_ids
+			.iter()
+			.map(|short_channel_id| RouteHop {
+				node_id: format!("02{short_channel_id:064x}"),
+				short_channel_id: *short_channel_id,
+				..Default::default()
+			})
+			.collect();
+		RoutePath { hops, amount_msat, fee_msat: 1_000, ..Default::default() }
+	}
+
+	#[test]
+	fn test_probe_outcomes() {
+		let tracker = ProbeTracker::new();
+		let baseline = vec![Some((0, 500_000)), None, Some((10_000, 800_000))];
+		let failed = tracker.track(path(&[1, 2, 3], 100_000), baseline.clone());
+		let succeeded = tracker.track(path(&[1, 2, 3], 100_000), baseline.clone());
+		let pending = tracker.track(path(&[4, 5], 100_000), vec![None, None]);
+		assert_eq!(tracker.locked_msat(), 3 * 101_000);
+		assert!(!tracker.is_resolved(&[failed, succeeded]));
+
+		// Decay moves the bounds apart, which isn't an outcome.
+		let decayed = vec![Some((0, 600_000)), None, Some((5_000, 900_000))];
+		let liquidity = HashMap::from([
+			(failed, decayed.clone()),
+			(succeeded, decayed),
+			(pending, vec![None, None]),
+		]);
+		tracker.update(&liquidity);
+		assert_eq!(tracker.pending_paths().len(), 3);
+
+		// The failed probe lowered the upper bound of the second hop, after raising the lower bound
+		// of the first one.
+		let failed_liquidity = vec![Some((100_000, 500_000)), Some((0, 100_000)), None];
+		let succeeded_liquidity =
+			vec![Some((100_000, 500_000)), Some((100_000, 700_000)), Some((100_000, 800_000))];
+		let liquidity = HashMap::from([
+			(failed, failed_liquidity),
+			(succeeded, succeeded_liquidity),
+			(pending, vec![None, None]),
+		]);
+		tracker.update(&liquidity);
+		assert!(tracker.is_resolved(&[failed, succeeded]));
+		assert_eq!(tracker.locked_msat(), 101_000);
+
+		let results = tracker.finish(&[failed, succeeded, pending]);
+		assert_eq!(results[0].estimated_status(), ProbeStatus::Failed);
+		assert_eq!(results[0].estimated_failed_short_channel_id, Some(2));
+		assert_eq!(results[1].estimated_status(), ProbeStatus::Succeeded);
+		assert_eq!(results[1].estimated_failed_short_channel_id, None);
+		assert_eq!(results[2].estimated_status(), ProbeStatus::Pending);
+
+		// The pending probe is still tracked after its request returned.
+		assert_eq!(tracker.pending_paths().len(), 1);
+		assert_eq!(tracker.locked_msat(), 101_000);
+		tracker.update(&HashMap::from([(pending, vec![Some((0, 50_000)), None])]));
+		assert!(tracker.pending_paths().is_empty());
+		assert_eq!(tracker.locked_msat(), 0);
+	}
+
+	#[test]
+	fn test_claim_poll() {
+		let tracker = ProbeTracker::new();
+		assert!(tracker.claim_poll());
+		// A concurrent request doesn't read the scores again within the poll interval.
+		assert!(!tracker.claim_poll());
+		tracker.state.lock().unwrap().polled_at = Some(Instant::now() - PROBE_POLL_INTERVAL);
+		assert!(tracker.claim_poll());
+	}
+
+	#[test]
+	fn test_probe_with_known_liquidity() {
+		let tracker = ProbeTracker::new();
+		// The liquidity of the path was already known to suffice, e.g. from an earlier probe.
+		let liquidity = vec![Some((100_000, 500_000)), Some((200_000, 300_000))];
+		let id = tracker.track(path(&[1, 2], 100_000), liquidity.clone());
+		let liquidity = HashMap::from([(id, liquidity)]);
+		tracker.update(&liquidity);
+		assert!(!tracker.is_resolved(&[id]));
+
+		let results = tracker.finish(&[id]);
+		assert_eq!(results[0].estimated_status(), ProbeStatus::Succeeded);
+		assert_eq!(tracker.locked_msat(), 0);
+	}
+}
@@ -18,6 +18,7 @@ use ldk_node::lightning::chain::channelmonitor::BalanceSource;
 use ldk_node::lightning::routing::gossip::{
 	ChannelInfo, ChannelUpdateInfo, NodeAnnouncementInfo, NodeInfo, RoutingFees,
 };
+use ldk_node::lightning::routing::router::{Path, Route};
 use ldk_node::lightning_invoice::{Bolt11InvoiceDescription, Description, Sha256};
 use ldk_node::lightning_types::features::NodeFeatures;
 use ldk_node::payment::{
@@ -92,6 +93,8 @@ pub(crate) fn channel_to_proto(channel: ChannelDetails) -> Channel {
 			.forwarding_info
 			.as_ref()
 			.map(|info| info.cltv_expiry_delta as u32),
+		label: None,
+		metadata: BTreeMap::new(),
 	}
 }
 
@@ -149,6 +152,8 @@ pub(crate) fn payment_to_proto(payment: PaymentDetails) -> Payment {
 			PaymentStatus::Failed => ldk_server_grpc::types::PaymentStatus::Failed.into(),
 		},
 		latest_update_timestamp,
+		label: None,
+		metadata: BTreeMap::new(),
 	}
 }
 
@@ -499,6 +504,36 @@ pub(crate) fn graph_node_to_proto(node: NodeInfo) -> ldk_server_grpc::types::Gra
 	}
 }
 
+pub(crate) fn route_to_proto(route: &Route) -> ldk_server_grpc::types::Route {
+	ldk_server_grpc::types::Route {
+		paths: route.paths.iter().map(route_path_to_proto).collect(),
+		total_fee_msat: route.get_total_fees(),
+		total_amount_msat: route.get_total_amount(),
+	}
+}
+
+pub(crate) fn route_path_to_proto(path: &Path) -> ldk_server_grpc::types::RoutePath {
+	let last_hop = path.hops.len().saturating_sub(1);
+	let hops = path
+		.hops
+		.iter()
+		.enumerate()
+		.map(|(idx, hop)| ldk_server_grpc::types::RouteHop {
+			node_id: hop.pubkey.to_string(),
+			short_channel_id: hop.short_channel_id,
+			// The fee of the last hop is the amount delivered to the destination.
+			fee_msat: if idx == last_hop { 0 } else { hop.fee_msat },
+			cltv_expiry_delta: hop.cltv_expiry_delta,
+		})
+		.collect();
+	ldk_server_grpc::types::RoutePath {
+		hops,
+		amount_msat: path.final_value_msat(),
+		fee_msat: path.fee_msat(),
+		total_cltv_expiry_delta: path.hops.iter().map(|hop| hop.cltv_expiry_delta).sum(),
+	}
+}
+
 /// Converts LDK feature flags into proto features keyed by the signaled bit.
 ///
 /// Feature names are derived from LDK's `Features::Display` impl, so they stay
@@ -0,0 +1,334 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use ldk_node::bitcoin::constants::ChainHash;
+use ldk_node::bitcoin::secp256k1::PublicKey;
+use ldk_node::lightning::ln::msgs::UnsignedChannelUpdate;
+use ldk_node::lightning::routing::gossip::{ChannelUpdateInfo, NetworkGraph, NodeId};
+use ldk_node::lightning::routing::router::{find_route, Path, Route, RouteParameters};
+use ldk_node::lightning::routing::scoring::{
+	ProbabilisticScorer, ProbabilisticScoringDecayParameters, ProbabilisticScoringFeeParameters,
+};
+use ldk_node::lightning::util::logger::{Logger, Record};
+use ldk_node::lightning::util::ser::ReadableArgs;
+use ldk_node::lightning_types::features::ChannelFeatures;
+use ldk_node::Node;
+use ldk_server_grpc::types::RoutePath;
+use log::{debug, error, trace};
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, LightningError};
+use crate::util::current_time_secs;
+use crate::util::probes::LiquidityRange;
+
+/// Forwards the logs of LDK's router and scorer to the server's log at trace level.
+struct RouterLogger;
+
+impl Logger for RouterLogger {
+	fn log(&self, record: Record) {
+		trace!("{}", record.args);
+	}
+}
+
+static ROUTER_LOGGER: RouterLogger = RouterLogger;
+
+pub(crate) type RoutingGraph = NetworkGraph<&'static RouterLogger>;
+
+pub(crate) type RoutingScorer = ProbabilisticScorer<Arc<RoutingGraph>, &'static RouterLogger>;
+
+/// How long a [`RoutingSnapshot`] is reused for before it is taken again.
+const ROUTING_SNAPSHOT_TTL: Duration = Duration::from_secs(10);
+
+/// A copy of the network graph of the node along with its pathfinding scores, see
+/// [`routing_graph`] and [`routing_scorer`].
+pub(crate) struct RoutingSnapshot {
+	pub(crate) graph: Arc<RoutingGraph>,
+	pub(crate) scorer: RoutingScorer,
+}
+
+/// Caches the [`RoutingSnapshot`] for [`ROUTING_SNAPSHOT_TTL`], so that routing requests in quick
+/// succession don't each copy the whole graph and read the whole scorer.
+pub(crate) struct RoutingSnapshotCache {
+	snapshot: Mutex<Option<(Instant, Arc<RoutingSnapshot>)>>,
+}
+
+impl RoutingSnapshotCache {
+	pub(crate) fn new() -> Self {
+		Self { snapshot: Mutex::new(None) }
+	}
+
+	/// Returns the snapshot of `node`, taking a new one if the cached one expired.
+	///
+	/// Concurrent callers wait for a single new snapshot rather than each taking one. This blocks,
+	/// see [`run_blocking`].
+	pub(crate) fn get(&self, node: &Node) -> Arc<RoutingSnapshot> {
+		let mut cached = self.snapshot.lock().unwrap();
+		if let Some((taken_at, snapshot)) = &*cached {
+			if taken_at.elapsed() < ROUTING_SNAPSHOT_TTL {
+				return Arc::clone(snapshot);
+			}
+		}
+		let graph = Arc::new(routing_graph(node));
+		let scorer = routing_scorer(node, Arc::clone(&graph));
+		let snapshot = Arc::new(RoutingSnapshot { graph, scorer });
+		*cached = Some((Instant::now(), Arc::clone(&snapshot)));
+		snapshot
+	}
+}
+
+/// Returns the parameters the node's router scores channels with.
+///
+/// LDK Node doesn't expose them and uses LDK's defaults, so routes are found with the same ones.
+fn node_scoring_fee_params() -> ProbabilisticScoringFeeParameters {
+	ProbabilisticScoringFeeParameters::default()
+}
+
+/// Returns the parameters the node's scorer decays its liquidity estimates with.
+///
+/// LDK Node doesn't expose them and uses LDK's defaults, so the scores are read with the same ones.
+fn node_scoring_decay_params() -> ProbabilisticScoringDecayParameters {
+	ProbabilisticScoringDecayParameters::default()
+}
+
+/// Runs `f` on a blocking thread, as copying the network graph and reading the pathfinding scores
+/// can take a while on a large graph.
+pub(crate) async fn run_blocking<T, F>(f: F) -> Result<T, LdkServerError>
+where
+	T: Send + 'static,
+	F: FnOnce() -> Result<T, LdkServerError> + Send + 'static,
+{
+	tokio::task::spawn_blocking(f).await.map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Routing task failed: {e}"))
+	})?
+}
+
+/// Runs LDK's router for `route_params` from `node`, without sending anything.
+///
+/// The route is found against the network graph and the pathfinding scores of `snapshot`, with the
+/// node's scoring parameters, see [`node_scoring_fee_params`].
+pub(crate) fn query_route(
+	node: &Node, snapshot: &RoutingSnapshot, route_params: &RouteParameters,
+) -> Result<Route, LdkServerError> {
+	let graph: &RoutingGraph = &snapshot.graph;
+	let mut random_seed_bytes = [0u8; 32];
+	getrandom::getrandom(&mut random_seed_bytes).map_err(|e| {
+		LdkServerError::new(InternalServerError, format!("Failed to generate random bytes: {e}"))
+	})?;
+	find_route(
+		&node.node_id(),
+		route_params,
+		graph,
+		None,
+		&ROUTER_LOGGER,
+		&snapshot.scorer,
+		&node_scoring_fee_params(),
+		&random_seed_bytes,
+	)
+	.map_err(|e| LdkServerError::new(LightningError, format!("Failed to find a route: {e:?}")))
+}
+
+/// Copies the network graph of `node`.
+///
+/// LDK Node doesn't expose its router, so routes are found against a copy of its graph. This
+/// node's channels are taken from its channel list rather than from gossip: the usable ones are
+/// added, also if they aren't announced, with their next outbound HTLC limits, and the others are
+/// left without an update in this node's direction, so that the router doesn't use them.
+pub(crate) fn routing_graph(node: &Node) -> RoutingGraph {
+	let network = node.config().network;
+	let chain_hash = ChainHash::using_genesis_block(network);
+	let graph = NetworkGraph::new(network, &ROUTER_LOGGER);
+	let our_node_id = NodeId::from_pubkey(&node.node_id());
+
+	let node_graph = node.network_graph();
+	for short_channel_id in node_graph.list_channels() {
+		let Some(channel) = node_graph.channel(short_channel_id) else {
+			continue;
+		};
+		let (Ok(node_one), Ok(node_two)) =
+			(channel.node_one.as_pubkey(), channel.node_two.as_pubkey())
+		else {
+			continue;
+		};
+		if let Err(e) = graph.add_channel_from_partial_announcement(
+			short_channel_id,
+			channel.announcement_received_time,
+			channel.features.clone(),
+			node_one,
+			node_two,
+		) {
+			debug!("Failed to copy channel {short_channel_id} of the network graph: {}", e.err);
+			continue;
+		}
+		let updates = [
+			(channel.node_one, false, channel.one_to_two),
+			(channel.node_two, true, channel.two_to_one),
+		];
+		for (from, direction, update) in updates {
+			let Some(update) = update.filter(|_| from != our_node_id) else {
+				continue;
+			};
+			let msg = channel_update(chain_hash, short_channel_id, direction, &update);
+			if let Err(e) = graph.update_channel_unsigned(&msg) {
+				debug!("Failed to copy update of channel {short_channel_id}: {}", e.err);
+			}
+		}
+	}
+
+	for channel in node.list_channels().into_iter().filter(|channel| channel.is_usable) {
+		let Some(short_channel_id) = channel.short_channel_id.or(channel.outbound_scid_alias)
+		else {
+			continue;
+		};
+		let is_ours = graph
+			.read_only()
+			.channel(short_channel_id)
+			.map(|info| info.node_one == our_node_id || info.node_two == our_node_id);
+		match is_ours {
+			Some(true) => {},
+			Some(false) => continue,
+			None => {
+				if let Err(e) = graph.add_channel_from_partial_announcement(
+					short_channel_id,
+					current_time_secs(),
+					ChannelFeatures::empty(),
+					node.node_id(),
+					channel.counterparty_node_id,
+				) {
+					debug!(
+						"Failed to add channel {short_channel_id} to the network graph: {}",
+						e.err
+					);
+					continue;
+				}
+			},
+		}
+		let direction = graph
+			.read_only()
+			.channel(short_channel_id)
+			.is_some_and(|info| info.node_two == our_node_id);
+		// Routing fees and the CLTV expiry delta of this node's channels aren't charged.
+		let msg = UnsignedChannelUpdate {
+			chain_hash,
+			short_channel_id,
+			timestamp: current_time_secs() as u32,
+			message_flags: 1,
+			channel_flags: direction as u8,
+			cltv_expiry_delta: 0,
+			htlc_minimum_msat: channel.next_outbound_htlc_minimum_msat,
+			htlc_maximum_msat: channel.next_outbound_htlc_limit_msat,
+			fee_base_msat: 0,
+			fee_proportional_millionths: 0,
+			excess_data: Vec::new(),
+		};
+		if let Err(e) = graph.update_channel_unsigned(&msg) {
+			debug!("Failed to update channel {short_channel_id} in the network graph: {}", e.err);
+		}
+	}
+	graph
+}
+
+/// Returns the scorer with the pathfinding scores learned by `node`, or an empty one if there are
+/// none yet.
+pub(crate) fn routing_scorer(node: &Node, graph: Arc<RoutingGraph>) -> RoutingScorer {
+	let decay_params = node_scoring_decay_params();
+	match node.export_pathfinding_scores() {
+		Ok(scores) => {
+			let args = (decay_params, Arc::clone(&graph), &ROUTER_LOGGER);
+			match ProbabilisticScorer::read(&mut &scores[..], args) {
+				Ok(scorer) => return scorer,
+				Err(e) => error!("Failed to read the pathfinding scores: {e}"),
+			}
+		},
+		Err(e) => debug!("No pathfinding scores available: {e}"),
+	}
+	ProbabilisticScorer::new(decay_params, graph, &ROUTER_LOGGER)
+}
+
+/// Returns the paths of `route` the way LDK sends probes along them, each with whether it's probed.
+///
+/// This mirrors LDK's preflight probing: hops over likely unannounced channels at the end of a path
+/// are dropped, and a path isn't probed if it has less than two hops left or if the probes would
+/// use more than 1/`liquidity_limit_multiplier` of the liquidity of its first channel.
+pub(crate) fn preflight_probe_paths(
+	node: &Node, route: &Route, liquidity_limit_multiplier: u64,
+) -> Vec<(Path, bool)> {
+	let channels = node.list_channels();
+	let mut used_liquidity_msat = HashMap::new();
+	let mut probe_paths = Vec::with_capacity(route.paths.len());
+	for path in &route.paths {
+		let mut path = path.clone();
+		while path.hops.last().is_some_and(|hop| !hop.maybe_announced_channel) {
+			let final_value_msat = path.final_value_msat();
+			path.hops.pop();
+			if let Some(last_hop) = path.hops.last_mut() {
+				last_hop.fee_msat += final_value_msat;
+			}
+		}
+		if path.hops.len() < 2 {
+			probe_paths.push((path, false));
+			continue;
+		}
+
+		let first_hop_short_channel_id = path.hops[0].short_channel_id;
+		let first_channel = channels.iter().find(|channel| {
+			channel.short_channel_id.or(channel.outbound_scid_alias)
+				== Some(first_hop_short_channel_id)
+		});
+		if let Some(channel) = first_channel {
+			let path_value_msat = path.final_value_msat() + path.fee_msat();
+			let used_msat = used_liquidity_msat.entry(first_hop_short_channel_id).or_insert(0);
+			if channel.next_outbound_htlc_limit_msat
+				< (*used_msat + path_value_msat) * liquidity_limit_multiplier
+			{
+				probe_paths.push((path, false));
+				continue;
+			}
+			*used_msat += path_value_msat;
+		}
+		probe_paths.push((path, true));
+	}
+	probe_paths
+}
+
+/// Returns the liquidity `scorer` estimates for each hop of `path`, in the direction of the hop.
+pub(crate) fn estimated_liquidity(scorer: &RoutingScorer, path: &RoutePath) -> Vec<LiquidityRange> {
+	path.hops
+		.iter()
+		.map(|hop| {
+			let target = PublicKey::from_str(&hop.node_id).ok()?;
+			scorer.estimated_channel_liquidity_range(
+				hop.short_channel_id,
+				&NodeId::from_pubkey(&target),
+			)
+		})
+		.collect()
+}
+
+fn channel_update(
+	chain_hash: ChainHash, short_channel_id: u64, direction: bool, update: &ChannelUpdateInfo,
+) -> UnsignedChannelUpdate {
+	UnsignedChannelUpdate {
+		chain_hash,
+		short_channel_id,
+		timestamp: update.last_update,
+		message_flags: 1,
+		channel_flags: direction as u8 | if update.enabled { 0 } else { 1 << 1 },
+		cltv_expiry_delta: update.cltv_expiry_delta,
+		htlc_minimum_msat: update.htlc_minimum_msat,
+		htlc_maximum_msat: update.htlc_maximum_msat,
+		fee_base_msat: update.fees.base_msat,
+		fee_proportional_millionths: update.fees.proportional_millionths,
+		excess_data: Vec::new(),
+	}
+}
@@ -0,0 +1,640 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::{fmt, io};
+
+use ldk_server_grpc::types::payment_kind::Kind;
+use ldk_server_grpc::types::{Payment, PaymentStatus};
+use log::error;
+use prost::Message;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::SpendLimitExceededError;
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+	SPENDS_PERSISTENCE_PRIMARY_NAMESPACE, SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+
+const HOUR_SECS: u64 = 60 * 60;
+const DAY_SECS: u64 = 24 * HOUR_SECS;
+
+/// Whether an outbound payment is sent over Lightning or on-chain, which have separate budgets.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SpendKind {
+	Lightning,
+	Onchain,
+}
+
+impl fmt::Display for SpendKind {
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match self {
+			SpendKind::Lightning => write!(f, "Lightning"),
+			SpendKind::Onchain => write!(f, "on-chain"),
+		}
+	}
+}
+
+/// Limits on the amount sent per payment, per rolling hour and per rolling day, in millisatoshis.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SpendBudget {
+	pub(crate) max_payment_msat: Option<u64>,
+	pub(crate) max_hourly_msat: Option<u64>,
+	pub(crate) max_daily_msat: Option<u64>,
+}
+
+impl SpendBudget {
+	fn is_unlimited(&self) -> bool {
+		self.max_payment_msat.is_none()
+			&& self.max_hourly_msat.is_none()
+			&& self.max_daily_msat.is_none()
+	}
+}
+
+/// The outbound budgets for Lightning and on-chain payments.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct SpendLimits {
+	pub(crate) lightning: SpendBudget,
+	pub(crate) onchain: SpendBudget,
+}
+
+impl SpendLimits {
+	fn budget(&self, kind: SpendKind) -> &SpendBudget {
+		match kind {
+			SpendKind::Lightning => &self.lightning,
+			SpendKind::Onchain => &self.onchain,
+		}
+	}
+}
+
+struct Spend {
+	payment_id: String,
+	/// The name of the API key or client identity that sent the payment, if known.
+	caller: Option<String>,
+	kind: SpendKind,
+	amount_msat: u64,
+	/// The fee paid for the payment, once known.
+	fee_msat: u64,
+	timestamp: u64,
+}
+
+impl Spend {
+	fn total_msat(&self) -> u64 {
+		self.amount_msat.saturating_add(self.fee_msat)
+	}
+}
+
+/// The persisted form of a spend, stored under the ID of its payment.
+#[derive(Clone, PartialEq, prost::Message)]
+struct SpendRecord {
+	#[prost(string, optional, tag = "1")]
+	caller: Option<String>,
+	#[prost(bool, tag = "2")]
+	is_onchain: bool,
+	#[prost(uint64, tag = "3")]
+	amount_msat: u64,
+	#[prost(uint64, tag = "4")]
+	fee_msat: u64,
+	#[prost(uint64, tag = "5")]
+	timestamp: u64,
+}
+
+impl From<&Spend> for SpendRecord {
+	fn from(spend: &Spend) -> Self {
+		SpendRecord {
+			caller: spend.caller.clone(),
+			is_onchain: spend.kind == SpendKind::Onchain,
+			amount_msat: spend.amount_msat,
+			fee_msat: spend.fee_msat,
+			timestamp: spend.timestamp,
+		}
+	}
+}
+
+/// Enforces the server-wide and per-key outbound spending limits.
+///
+/// Spends of the last day are kept in memory and persisted in their own namespace, from which they
+/// are rebuilt at startup, so restarts don't reset the server-wide or per-key totals. Spends are
+/// removed from the namespace once they fall out of the daily window.
+pub(crate) struct SpendTracker {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	server_limits: SpendLimits,
+	key_limits: HashMap<String, SpendLimits>,
+	state: Mutex<SpendState>,
+}
+
+#[derive(Default)]
+struct SpendState {
+	spends: Vec<Spend>,
+	/// The sends that are within the limits but weren't made yet, by reservation ID. They count
+	/// against the limits, so concurrent sends can't together exceed a limit that each of them is
+	/// within.
+	reserved: HashMap<u64, Vec<Spend>>,
+	next_reservation_id: u64,
+}
+
+impl SpendState {
+	/// Drops the spends that fell out of the daily window, returning their payment IDs.
+	fn remove_expired(&mut self, now: u64) -> Vec<String> {
+		let (expired, spends): (Vec<Spend>, Vec<Spend>) = std::mem::take(&mut self.spends)
+			.into_iter()
+			.partition(|spend| spend.timestamp + DAY_SECS <= now);
+		self.spends = spends;
+		expired.into_iter().map(|spend| spend.payment_id).collect()
+	}
+}
+
+/// Returned by [`SpendTracker::reserve`] for a send that is within the limits.
+///
+/// The reserved amount counts against the limits until the send is recorded with
+/// [`SpendReservation::record`], or until the reservation is dropped, e.g. because the send
+/// failed.
+pub(crate) struct SpendReservation<'a> {
+	tracker: &'a SpendTracker,
+	id: u64,
+	caller: String,
+	amount_msat: Option<u64>,
+}
+
+impl SpendTracker {
+	/// Rebuilds the spends of the last day from the spends namespace.
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, server_limits: SpendLimits,
+		key_limits: HashMap<String, SpendLimits>,
+	) -> io::Result<Self> {
+		let spends =
+			load_spends(paginated_kv_store.as_ref(), current_time_secs().saturating_sub(DAY_SECS))?;
+		Ok(Self {
+			paginated_kv_store,
+			server_limits,
+			key_limits,
+			state: Mutex::new(SpendState { spends, ..Default::default() }),
+		})
+	}
+
+	/// Checks that sending `amount_msat` as `caller` stays within the server-wide limits and those
+	/// of the caller's key for each of `kinds`, and reserves the amount against them.
+	///
+	/// `amount_msat` is `None` if the amount cannot be determined before the payment is sent, in
+	/// which case the payment is only permitted if no limit applies to it.
+	pub(crate) fn reserve(
+		&self, caller: &str, kinds: &[SpendKind], amount_msat: Option<u64>,
+	) -> Result<SpendReservation<'_>, LdkServerError> {
+		let now = current_time_secs();
+		let (result, expired) = {
+			let mut state = self.state.lock().unwrap();
+			let expired = state.remove_expired(now);
+			let result = kinds
+				.iter()
+				.try_for_each(|&kind| self.check(&state, caller, kind, amount_msat, now))
+				.map(|()| {
+					let id = state.next_reservation_id;
+					state.next_reservation_id += 1;
+					let reserved = kinds
+						.iter()
+						.map(|&kind| Spend {
+							payment_id: String::new(),
+							caller: Some(caller.to_string()),
+							kind,
+							amount_msat: amount_msat.unwrap_or(0),
+							fee_msat: 0,
+							timestamp: now,
+						})
+						.collect();
+					state.reserved.insert(id, reserved);
+					id
+				});
+			(result, expired)
+		};
+		for payment_id in expired {
+			remove_spend(self.paginated_kv_store.as_ref(), &payment_id);
+		}
+		let id = result?;
+		Ok(SpendReservation { tracker: self, id, caller: caller.to_string(), amount_msat })
+	}
+
+	fn check(
+		&self, state: &SpendState, caller: &str, kind: SpendKind, amount_msat: Option<u64>,
+		now: u64,
+	) -> Result<(), LdkServerError> {
+		let spends: Vec<&Spend> =
+			state.spends.iter().chain(state.reserved.values().flatten()).collect();
+
+		let server_budget = self.server_limits.budget(kind);
+		check_budget(server_budget, "server-wide", kind, amount_msat, &spends, now, |_| true)?;
+
+		if let Some(key_limits) = self.key_limits.get(caller) {
+			let key_budget = key_limits.budget(kind);
+			check_budget(
+				key_budget,
+				&format!("'{caller}'"),
+				kind,
+				amount_msat,
+				&spends,
+				now,
+				|spend| spend.caller.as_deref() == Some(caller),
+			)?;
+		}
+		Ok(())
+	}
+
+	/// Stops counting a payment that failed against the limits.
+	pub(crate) fn payment_failed(&self, payment_id: &str) {
+		self.state.lock().unwrap().spends.retain(|spend| spend.payment_id != payment_id);
+		remove_spend(self.paginated_kv_store.as_ref(), payment_id);
+	}
+
+	/// Counts the fee paid for a payment against the limits, once it is known, e.g. when a
+	/// Lightning payment succeeded.
+	pub(crate) fn payment_fee_paid(&self, payment_id: &str, fee_msat: u64) {
+		let mut state = self.state.lock().unwrap();
+		let Some(spend) = state.spends.iter_mut().find(|spend| spend.payment_id == payment_id)
+		else {
+			return;
+		};
+		if spend.fee_msat == fee_msat {
+			return;
+		}
+		spend.fee_msat = fee_msat;
+		persist_spend(self.paginated_kv_store.as_ref(), spend);
+	}
+}
+
+impl SpendReservation<'_> {
+	/// Records the payment made for the reservation, which then counts against the limits in place
+	/// of the reserved amount.
+	///
+	/// The spend is persisted, so it is accounted for after a restart even if the payment is still
+	/// pending by then. The reserved amount is counted, if any, and otherwise the amount of
+	/// `payment`, along with its fee if already known.
+	pub(crate) fn record(self, payment: Payment) {
+		let spend = Spend {
+			kind: spend_kind(&payment),
+			amount_msat: self.amount_msat.or(payment.amount_msat).unwrap_or(0),
+			fee_msat: payment.fee_paid_msat.unwrap_or(0),
+			timestamp: current_time_secs(),
+			payment_id: payment.id,
+			caller: Some(self.caller.clone()),
+		};
+		persist_spend(self.tracker.paginated_kv_store.as_ref(), &spend);
+		let mut state = self.tracker.state.lock().unwrap();
+		state.reserved.remove(&self.id);
+		state.spends.push(spend);
+	}
+}
+
+impl Drop for SpendReservation<'_> {
+	fn drop(&mut self) {
+		self.tracker.state.lock().unwrap().reserved.remove(&self.id);
+	}
+}
+
+fn persist_spend(paginated_kv_store: &dyn PaginatedKVStore, spend: &Spend) {
+	if let Err(e) = paginated_kv_store.write(
+		SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+		SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+		&spend.payment_id,
+		spend.timestamp as i64,
+		&SpendRecord::from(spend).encode_to_vec(),
+	) {
+		error!("Failed to persist spend of payment {}: {e}", spend.payment_id);
+	}
+}
+
+fn remove_spend(paginated_kv_store: &dyn PaginatedKVStore, payment_id: &str) {
+	if let Err(e) = paginated_kv_store.remove(
+		SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+		SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_id,
+	) {
+		error!("Failed to remove spend of payment {payment_id} from persistence: {e}");
+	}
+}
+
+fn check_budget(
+	budget: &SpendBudget, limit_owner: &str, kind: SpendKind, amount_msat: Option<u64>,
+	spends: &[&Spend], now: u64, counts: impl Fn(&Spend) -> bool,
+) -> Result<(), LdkServerError> {
+	if budget.is_unlimited() {
+		return Ok(());
+	}
+	let amount_msat = amount_msat.ok_or_else(|| {
+		LdkServerError::new(
+			SpendLimitExceededError,
+			format!(
+				"The amount of this {kind} payment can't be determined before sending it, which is not permitted under the {limit_owner} spending limits"
+			),
+		)
+	})?;
+
+	if let Some(max_payment_msat) = budget.max_payment_msat {
+		if amount_msat > max_payment_msat {
+			return Err(LdkServerError::new(
+				SpendLimitExceededError,
+				format!(
+					"Payment of {amount_msat} msat exceeds the {limit_owner} {kind} per-payment limit of {max_payment_msat} msat"
+				),
+			));
+		}
+	}
+
+	let windows =
+		[("hourly", HOUR_SECS, budget.max_hourly_msat), ("daily", DAY_SECS, budget.max_daily_msat)];
+	for (window_name, window_secs, max_msat) in windows {
+		let Some(max_msat) = max_msat else { continue };
+		let spent_msat: u64 = spends
+			.iter()
+			.filter(|spend| spend.kind == kind && spend.timestamp + window_secs > now)
+			.filter(|spend| counts(spend))
+			.fold(0u64, |total, spend| total.saturating_add(spend.total_msat()));
+		if spent_msat.saturating_add(amount_msat) > max_msat {
+			return Err(LdkServerError::new(
+				SpendLimitExceededError,
+				format!(
+					"Payment of {amount_msat} msat exceeds the {limit_owner} {kind} {window_name} limit of {max_msat} msat, of which {spent_msat} msat were already spent"
+				),
+			));
+		}
+	}
+	Ok(())
+}
+
+fn spend_kind(payment: &Payment) -> SpendKind {
+	match payment.kind.as_ref().and_then(|kind| kind.kind.as_ref()) {
+		Some(Kind::Onchain(_)) => SpendKind::Onchain,
+		_ => SpendKind::Lightning,
+	}
+}
+
+/// Reads the spends recorded since `since`, i.e. of the payments sent since then that haven't
+/// failed.
+///
+/// Older spends are removed, as are those of payments persisted as failed, e.g. because the server
+/// stopped before it removed their spend.
+fn load_spends(paginated_kv_store: &dyn PaginatedKVStore, since: u64) -> io::Result<Vec<Spend>> {
+	let mut spends = Vec::new();
+	let mut stale_keys = Vec::new();
+	let mut page_token = None;
+	loop {
+		let list_response = paginated_kv_store.list(
+			SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+			SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token,
+		)?;
+		for key in list_response.keys {
+			let record_bytes = paginated_kv_store.read(
+				SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+				SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+				&key,
+			)?;
+			let record = SpendRecord::decode(&*record_bytes)
+				.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+			if record.timestamp < since || is_failed_payment(paginated_kv_store, &key)? {
+				stale_keys.push(key);
+				continue;
+			}
+			spends.push(Spend {
+				payment_id: key,
+				caller: record.caller,
+				kind: if record.is_onchain { SpendKind::Onchain } else { SpendKind::Lightning },
+				amount_msat: record.amount_msat,
+				fee_msat: record.fee_msat,
+				timestamp: record.timestamp,
+			});
+		}
+
+		match list_response.next_page_token {
+			Some(next_page_token) => page_token = Some(next_page_token),
+			None => break,
+		}
+	}
+	for key in stale_keys {
+		paginated_kv_store.remove(
+			SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+			SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+			&key,
+		)?;
+	}
+	Ok(spends)
+}
+
+fn is_failed_payment(
+	paginated_kv_store: &dyn PaginatedKVStore, payment_id: &str,
+) -> io::Result<bool> {
+	let payment_bytes = match paginated_kv_store.read(
+		PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE,
+		PAYMENTS_PERSISTENCE_SECONDARY_NAMESPACE,
+		payment_id,
+	) {
+		Ok(payment_bytes) => payment_bytes,
+		Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(false),
+		Err(e) => return Err(e),
+	};
+	let payment = Payment::decode(&*payment_bytes)
+		.map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+	Ok(payment.status == PaymentStatus::Failed as i32)
+}
+
+#[cfg(test)]
+mod tests {
+	use ldk_server_grpc::types::{Bolt11, Onchain, PaymentDirection, PaymentKind};
+
+	use super::*;
+	use crate::io::persist::paginated_kv_store::PaymentIndex;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn outbound_payment(id: &str, kind: Kind, amount_msat: u64) -> Payment {
+		Payment {
+			id: id.to_string(),
+			kind: Some(PaymentKind { kind: Some(kind) }),
+			amount_msat: Some(amount_msat),
+			fee_paid_msat: None,
+			direction: PaymentDirection::Outbound as i32,
+			status: PaymentStatus::Pending as i32,
+			latest_update_timestamp: current_time_secs(),
+			..Default::default()
+		}
+	}
+
+	fn bolt11(id: &str, amount_msat: u64) -> Payment {
+		outbound_payment(id, Kind::Bolt11(Bolt11::default()), amount_msat)
+	}
+
+	fn onchain(id: &str, amount_msat: u64) -> Payment {
+		outbound_payment(id, Kind::Onchain(Onchain::default()), amount_msat)
+	}
+
+	#[test]
+	fn test_spend_limits_are_enforced() {
+		let server_limits = SpendLimits {
+			lightning: SpendBudget {
+				max_payment_msat: Some(50_000),
+				max_hourly_msat: Some(100_000),
+				max_daily_msat: None,
+			},
+			onchain: SpendBudget::default(),
+		};
+		let key_limits = HashMap::from([(
+			"payouts".to_string(),
+			SpendLimits {
+				lightning: SpendBudget::default(),
+				onchain: SpendBudget {
+					max_payment_msat: None,
+					max_hourly_msat: None,
+					max_daily_msat: Some(1_000_000),
+				},
+			},
+		)]);
+		let tracker = SpendTracker::new(
+			Arc::new(test_store("test_spend_limits_are_enforced")),
+			server_limits,
+			key_limits,
+		)
+		.unwrap();
+
+		let lightning = &[SpendKind::Lightning];
+		let onchain_kind = &[SpendKind::Onchain];
+		let err = tracker.reserve("admin", lightning, Some(50_001)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+		let err = tracker.reserve("admin", lightning, None).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+
+		tracker.reserve("admin", lightning, Some(50_000)).unwrap().record(bolt11("aa", 50_000));
+		// A send that wasn't made yet counts against the limits until it's released.
+		let reservation = tracker.reserve("payouts", lightning, Some(50_000)).unwrap();
+		let err = tracker.reserve("admin", lightning, Some(1)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+		drop(reservation);
+		tracker.reserve("payouts", lightning, Some(50_000)).unwrap().record(bolt11("bb", 50_000));
+		let err = tracker.reserve("admin", lightning, Some(1)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+
+		// Failed payments no longer count against the limits.
+		tracker.payment_failed("aa");
+		tracker.reserve("admin", lightning, Some(50_000)).unwrap();
+
+		// On-chain sends are only limited for `payouts`, and only by its own sends.
+		tracker.reserve("admin", onchain_kind, None).unwrap();
+		let reservation = tracker.reserve("admin", onchain_kind, Some(2_000_000)).unwrap();
+		reservation.record(onchain("cc", 2_000_000));
+		let reservation = tracker.reserve("payouts", onchain_kind, Some(600_000)).unwrap();
+		reservation.record(onchain("dd", 600_000));
+		let err = tracker.reserve("payouts", onchain_kind, Some(400_001)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+		tracker.reserve("payouts", onchain_kind, Some(400_000)).unwrap();
+	}
+
+	#[test]
+	fn test_fees_count_against_limits() {
+		let limits = SpendLimits {
+			lightning: SpendBudget {
+				max_payment_msat: None,
+				max_hourly_msat: Some(100_000),
+				max_daily_msat: None,
+			},
+			onchain: SpendBudget::default(),
+		};
+		let tracker = SpendTracker::new(
+			Arc::new(test_store("test_fees_count_against_limits")),
+			limits,
+			HashMap::new(),
+		)
+		.unwrap();
+		let lightning = &[SpendKind::Lightning];
+		let mut payment = bolt11("aa", 50_000);
+		payment.fee_paid_msat = Some(1_000);
+		tracker.reserve("admin", lightning, Some(50_000)).unwrap().record(payment);
+		tracker.reserve("admin", lightning, Some(40_000)).unwrap().record(bolt11("bb", 40_000));
+		tracker.reserve("admin", lightning, Some(9_000)).unwrap();
+
+		tracker.payment_fee_paid("bb", 500);
+		let err = tracker.reserve("admin", lightning, Some(9_000)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+		tracker.reserve("admin", lightning, Some(8_500)).unwrap();
+	}
+
+	#[test]
+	fn test_spends_are_rebuilt_after_restart() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_spends_are_rebuilt_after_restart"));
+		let limits = SpendLimits {
+			lightning: SpendBudget {
+				max_payment_msat: None,
+				max_hourly_msat: None,
+				max_daily_msat: Some(100_000),
+			},
+			onchain: SpendBudget::default(),
+		};
+		let key_limits = HashMap::from([("payouts".to_string(), limits)]);
+
+		let tracker =
+			SpendTracker::new(Arc::clone(&store), SpendLimits::default(), HashMap::new()).unwrap();
+		for (caller, payment) in [
+			("payouts", bolt11("aa", 50_000)),
+			("payouts", bolt11("bb", 10_000)),
+			("admin", bolt11("cc", 60_000)),
+			("payouts", bolt11("dd", 60_000)),
+			("payouts", bolt11("ff", 60_000)),
+		] {
+			tracker.reserve(caller, &[SpendKind::Lightning], None).unwrap().record(payment);
+		}
+		// Failed payments don't count, also if the server stopped before it removed their spend.
+		tracker.payment_failed("dd");
+		let mut failed = bolt11("ff", 60_000);
+		failed.status = PaymentStatus::Failed as i32;
+		store
+			.write_payment("ff", 0, &failed.encode_to_vec(), &PaymentIndex::from(&failed))
+			.unwrap();
+		tracker.payment_fee_paid("bb", 5_000);
+
+		// Old spends don't count.
+		let old = SpendRecord {
+			caller: Some("payouts".to_string()),
+			is_onchain: false,
+			amount_msat: 60_000,
+			fee_msat: 0,
+			timestamp: current_time_secs() - DAY_SECS - HOUR_SECS,
+		};
+		store
+			.write(
+				SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+				SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+				"ee",
+				old.timestamp as i64,
+				&old.encode_to_vec(),
+			)
+			.unwrap();
+
+		let tracker =
+			SpendTracker::new(Arc::clone(&store), SpendLimits::default(), key_limits).unwrap();
+		assert_eq!(tracker.state.lock().unwrap().spends.len(), 3);
+		tracker.reserve("payouts", &[SpendKind::Lightning], Some(35_000)).unwrap();
+		let err = tracker.reserve("payouts", &[SpendKind::Lightning], Some(35_001)).err().unwrap();
+		assert_eq!(err.error_code, SpendLimitExceededError);
+
+		// The old spend and that of the failed payment were pruned.
+		let spends = store
+			.list(
+				SPENDS_PERSISTENCE_PRIMARY_NAMESPACE,
+				SPENDS_PERSISTENCE_SECONDARY_NAMESPACE,
+				None,
+			)
+			.unwrap();
+		let mut spend_keys = spends.keys;
+		spend_keys.sort();
+		assert_eq!(spend_keys, ["aa", "bb", "cc"]);
+
+		// Spends aren't listed as payments.
+		let payments = store.list(PAYMENTS_PERSISTENCE_PRIMARY_NAMESPACE, "", None).unwrap();
+		assert_eq!(payments.keys, ["ff"]);
+	}
+}
@@ -10,13 +10,18 @@
 use std::fs;
 use std::net::IpAddr;
 use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::SystemTime;
 
 use base64::Engine;
+use ring::digest;
 use ring::rand::SystemRandom;
 use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING};
 use tokio_rustls::rustls::crypto::ring::default_provider;
 use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
-use tokio_rustls::rustls::ServerConfig;
+use tokio_rustls::rustls::server::WebPkiClientVerifier;
+use tokio_rustls::rustls::{RootCertStore, ServerConfig};
+use tokio_rustls::TlsAcceptor;
 
 use crate::util::config::TlsConfig;
 use crate::util::write_new;
@@ -43,6 +48,8 @@ const TAG_BIT_STRING: u8 = 0x03;
 const TAG_OCTET_STRING: u8 = 0x04;
 const TAG_OID: u8 = 0x06;
 const TAG_UTF8_STRING: u8 = 0x0C;
+const TAG_PRINTABLE_STRING: u8 = 0x13;
+const TAG_IA5_STRING: u8 = 0x16;
 const TAG_UTC_TIME: u8 = 0x17;
 const TAG_SEQUENCE: u8 = 0x30;
 const TAG_SET: u8 = 0x31;
@@ -52,25 +59,97 @@ const TAG_SET: u8 = 0x31;
 pub fn get_or_generate_tls_config(
 	tls_config: Option<TlsConfig>, storage_dir: &str,
 ) -> Result<ServerConfig, String> {
+	let (cert_path, key_path) = tls_file_paths(tls_config.as_ref(), storage_dir);
 	if let Some(config) = tls_config {
-		let cert_path = config.cert_path.unwrap_or(format!("{storage_dir}/tls.crt"));
-		let key_path = config.key_path.unwrap_or(format!("{storage_dir}/tls.key"));
 		if !fs::exists(&cert_path).unwrap_or(false) || !fs::exists(&key_path).unwrap_or(false) {
 			generate_self_signed_cert(&cert_path, &key_path, &config.hosts)?;
 		}
-		load_tls_config(&cert_path, &key_path)
+		load_tls_config(&cert_path, &key_path, config.client_ca_path.as_deref())
 	} else {
 		// Check if we already have generated certs, if we don't, generate new ones
-		let cert_path = format!("{storage_dir}/tls.crt");
-		let key_path = format!("{storage_dir}/tls.key");
 		if !fs::exists(&cert_path).unwrap_or(false) || !fs::exists(&key_path).unwrap_or(false) {
 			generate_self_signed_cert(&cert_path, &key_path, &[])?;
 		}
 
-		load_tls_config(&cert_path, &key_path)
+		load_tls_config(&cert_path, &key_path, None)
 	}
 }
 
+/// Returns the paths of the certificate and key files, defaulting to the storage directory.
+fn tls_file_paths(tls_config: Option<&TlsConfig>, storage_dir: &str) -> (String, String) {
+	let cert_path = tls_config.and_then(|config| config.cert_path.clone());
+	let key_path = tls_config.and_then(|config| config.key_path.clone());
+	(
+		cert_path.unwrap_or(format!("{storage_dir}/tls.crt")),
+		key_path.unwrap_or(format!("{storage_dir}/tls.key")),
+	)
+}
+
+/// A TLS acceptor whose certificate and key can be reloaded from disk, e.g. after a renewal.
+///
+/// Reloading only affects new handshakes, established connections are left untouched.
+pub(crate) struct ReloadableTlsAcceptor {
+	cert_path: String,
+	key_path: String,
+	client_ca_path: Option<String>,
+	acceptor: RwLock<TlsAcceptor>,
+	/// The modification times of the certificate and key files when they were last loaded.
+	modified: Mutex<Option<(SystemTime, SystemTime)>>,
+}
+
+impl ReloadableTlsAcceptor {
+	/// Gets or generates the TLS configuration, see [`get_or_generate_tls_config`].
+	pub(crate) fn new(tls_config: Option<TlsConfig>, storage_dir: &str) -> Result<Self, String> {
+		let (cert_path, key_path) = tls_file_paths(tls_config.as_ref(), storage_dir);
+		let client_ca_path = tls_config.as_ref().and_then(|config| config.client_ca_path.clone());
+		let server_config = get_or_generate_tls_config(tls_config, storage_dir)?;
+		let modified = modified_times(&cert_path, &key_path);
+		Ok(Self {
+			cert_path,
+			key_path,
+			client_ca_path,
+			acceptor: RwLock::new(TlsAcceptor::from(Arc::new(server_config))),
+			modified: Mutex::new(modified),
+		})
+	}
+
+	/// Returns the acceptor for the next handshake.
+	pub(crate) fn acceptor(&self) -> TlsAcceptor {
+		self.acceptor.read().unwrap().clone()
+	}
+
+	/// Reloads the certificate and key. If they can't be loaded, the previous ones stay in use.
+	pub(crate) fn reload(&self) -> Result<(), String> {
+		let modified = modified_times(&self.cert_path, &self.key_path);
+		let server_config =
+			load_tls_config(&self.cert_path, &self.key_path, self.client_ca_path.as_deref())?;
+		*self.acceptor.write().unwrap() = TlsAcceptor::from(Arc::new(server_config));
+		*self.modified.lock().unwrap() = modified;
+		Ok(())
+	}
+
+	/// Reloads the certificate and key if either file was modified since they were last loaded,
+	/// returning whether they were reloaded.
+	pub(crate) fn reload_if_changed(&self) -> Result<bool, String> {
+		let modified = modified_times(&self.cert_path, &self.key_path);
+		{
+			let mut last_modified = self.modified.lock().unwrap();
+			// Either file may be missing while it is being replaced.
+			if modified.is_none() || modified == *last_modified {
+				return Ok(false);
+			}
+			// Only retry files that failed to load once they change again.
+			*last_modified = modified;
+		}
+		self.reload().map(|()| true)
+	}
+}
+
+fn modified_times(cert_path: &str, key_path: &str) -> Option<(SystemTime, SystemTime)> {
+	let modified = |path: &str| fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+	Some((modified(cert_path)?, modified(key_path)?))
+}
+
 /// Parses a PEM-encoded certificate file and returns the DER-encoded certificates.
 fn parse_pem_certs(pem_data: &str) -> Result<Vec<CertificateDer<'static>>, String> {
 	let mut certs = Vec::new();
@@ -396,7 +475,12 @@ fn der_context_implicit(tag_num: u8, content: &[u8]) -> Vec<u8> {
 }
 
 /// Loads TLS configuration from provided paths.
-fn load_tls_config(cert_path: &str, key_path: &str) -> Result<ServerConfig, String> {
+///
+/// If `client_ca_path` is set, clients are required to present a certificate issued by one of the
+/// CAs in that file.
+fn load_tls_config(
+	cert_path: &str, key_path: &str, client_ca_path: Option<&str>,
+) -> Result<ServerConfig, String> {
 	let cert_pem = fs::read_to_string(cert_path)
 		.map_err(|e| format!("Failed to read TLS certificate file '{cert_path}': {e}"))?;
 	let key_pem = fs::read_to_string(key_path)
@@ -410,16 +494,117 @@ fn load_tls_config(cert_path: &str, key_path: &str) -> Result<ServerConfig, Stri
 
 	let key = parse_pem_private_key(&key_pem)?;
 
-	let mut config = ServerConfig::builder_with_provider(default_provider().into())
+	let provider = Arc::new(default_provider());
+	let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
 		.with_safe_default_protocol_versions()
-		.map_err(|e| format!("Failed to set TLS protocol versions: {e}"))?
-		.with_no_client_auth()
+		.map_err(|e| format!("Failed to set TLS protocol versions: {e}"))?;
+	let builder = match client_ca_path {
+		Some(client_ca_path) => {
+			let client_ca_pem = fs::read_to_string(client_ca_path).map_err(|e| {
+				format!("Failed to read TLS client CA file '{client_ca_path}': {e}")
+			})?;
+			let mut roots = RootCertStore::empty();
+			for ca_cert in parse_pem_certs(&client_ca_pem)? {
+				roots
+					.add(ca_cert)
+					.map_err(|e| format!("Invalid certificate in '{client_ca_path}': {e}"))?;
+			}
+			if roots.is_empty() {
+				return Err(format!(
+					"No certificates found in TLS client CA file '{client_ca_path}'"
+				));
+			}
+			let verifier = WebPkiClientVerifier::builder_with_provider(Arc::new(roots), provider)
+				.build()
+				.map_err(|e| format!("Failed to build TLS client certificate verifier: {e}"))?;
+			builder.with_client_cert_verifier(verifier)
+		},
+		None => builder.with_no_client_auth(),
+	};
+	let mut config = builder
 		.with_single_cert(certs, key)
 		.map_err(|e| format!("Failed to build TLS server config: {e}"))?;
 	config.alpn_protocols = vec![b"h2".to_vec()];
 	Ok(config)
 }
 
+/// Returns the common name of the subject of the given DER-encoded X.509 certificate, if any.
+pub(crate) fn cert_subject_common_name(cert_der: &[u8]) -> Option<String> {
+	// Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signatureValue }
+	let (_, cert, _) = der_read(cert_der, Some(TAG_SEQUENCE))?;
+	let (_, tbs_cert, _) = der_read(cert, Some(TAG_SEQUENCE))?;
+
+	// Skip the optional version, serialNumber, signature, issuer and validity to get to the
+	// subject.
+	let (tag, _, mut rest) = der_read(tbs_cert, None)?;
+	if tag == 0xA0 {
+		(_, _, rest) = der_read(rest, Some(TAG_INTEGER))?;
+	}
+	for _ in 0..3 {
+		(_, _, rest) = der_read(rest, Some(TAG_SEQUENCE))?;
+	}
+	let (_, mut rdns, _) = der_read(rest, Some(TAG_SEQUENCE))?;
+
+	// Name ::= SEQUENCE OF RelativeDistinguishedName
+	// RDN ::= SET OF AttributeTypeAndValue
+	// AttributeTypeAndValue ::= SEQUENCE { type OID, value ANY }
+	while !rdns.is_empty() {
+		let (_, mut attributes, next_rdns) = der_read(rdns, Some(TAG_SET))?;
+		while !attributes.is_empty() {
+			let (_, attribute, next_attributes) = der_read(attributes, Some(TAG_SEQUENCE))?;
+			let (_, oid, value) = der_read(attribute, Some(TAG_OID))?;
+			if oid == OID_COMMON_NAME {
+				let (tag, value, _) = der_read(value, None)?;
+				return match tag {
+					TAG_UTF8_STRING | TAG_PRINTABLE_STRING | TAG_IA5_STRING => {
+						String::from_utf8(value.to_vec()).ok()
+					},
+					_ => None,
+				};
+			}
+			attributes = next_attributes;
+		}
+		rdns = next_rdns;
+	}
+	None
+}
+
+/// Returns the SHA-256 fingerprint of the given DER-encoded X.509 certificate.
+pub(crate) fn cert_fingerprint(cert_der: &[u8]) -> [u8; 32] {
+	let digest = digest::digest(&digest::SHA256, cert_der);
+	let mut fingerprint = [0u8; 32];
+	fingerprint.copy_from_slice(digest.as_ref());
+	fingerprint
+}
+
+/// Reads a single DER element from the start of `input`, returning its tag, content and the
+/// remaining input. If `expected_tag` is set, elements with a different tag are rejected.
+fn der_read(input: &[u8], expected_tag: Option<u8>) -> Option<(u8, &[u8], &[u8])> {
+	let (&tag, input) = input.split_first()?;
+	if expected_tag.is_some_and(|expected_tag| expected_tag != tag) {
+		return None;
+	}
+
+	let (&first_len_byte, input) = input.split_first()?;
+	let (len, input) = if first_len_byte < 0x80 {
+		(first_len_byte as usize, input)
+	} else {
+		let num_len_bytes = (first_len_byte & 0x7F) as usize;
+		if num_len_bytes == 0 || num_len_bytes > 4 || input.len() < num_len_bytes {
+			return None;
+		}
+		let (len_bytes, input) = input.split_at(num_len_bytes);
+		let len = len_bytes.iter().fold(0usize, |len, b| (len << 8) | *b as usize);
+		(len, input)
+	};
+
+	if input.len() < len {
+		return None;
+	}
+	let (content, rest) = input.split_at(len);
+	Some((tag, content, rest))
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -467,6 +652,39 @@ mod tests {
 		assert!(result.is_err());
 	}
 
+	#[test]
+	fn test_reload_if_changed() {
+		let mut suffix_bytes = [0u8; 8];
+		getrandom::getrandom(&mut suffix_bytes).unwrap();
+		let storage_dir = std::env::temp_dir()
+			.join(format!("test_tls_reload_{}", u64::from_ne_bytes(suffix_bytes)));
+		fs::create_dir_all(&storage_dir).unwrap();
+		let storage_dir = storage_dir.to_str().unwrap();
+
+		let acceptor = ReloadableTlsAcceptor::new(None, storage_dir).unwrap();
+		assert!(!acceptor.reload_if_changed().unwrap());
+
+		// Replace the certificate and key, as a renewal would.
+		let cert_path = format!("{storage_dir}/tls.crt");
+		let key_path = format!("{storage_dir}/tls.key");
+		fs::remove_file(&key_path).unwrap();
+		generate_self_signed_cert(&cert_path, &key_path, &[]).unwrap();
+		let later = SystemTime::now() + std::time::Duration::from_secs(60);
+		fs::File::options().write(true).open(&cert_path).unwrap().set_modified(later).unwrap();
+		assert!(acceptor.reload_if_changed().unwrap());
+		assert!(!acceptor.reload_if_changed().unwrap());
+
+		// A broken certificate is not retried until it changes again.
+		fs::write(&cert_path, "not a cert").unwrap();
+		let later = later + std::time::Duration::from_secs(60);
+		fs::File::options().write(true).open(&cert_path).unwrap().set_modified(later).unwrap();
+		assert!(acceptor.reload_if_changed().is_err());
+		assert!(!acceptor.reload_if_changed().unwrap());
+		assert!(acceptor.reload().is_err());
+
+		fs::remove_dir_all(storage_dir).unwrap();
+	}
+
 	#[test]
 	fn test_generate_and_load_roundtrip() {
 		let temp_dir = std::env::temp_dir();
@@ -489,9 +707,23 @@ mod tests {
 		assert!(key_path.exists());
 
 		// Load config
-		let res = load_tls_config(cert_path.to_str().unwrap(), key_path.to_str().unwrap());
+		let res = load_tls_config(cert_path.to_str().unwrap(), key_path.to_str().unwrap(), None);
 		assert!(res.is_ok());
 
+		// Require client certificates issued by the generated certificate
+		let res = load_tls_config(
+			cert_path.to_str().unwrap(),
+			key_path.to_str().unwrap(),
+			Some(cert_path.to_str().unwrap()),
+		);
+		assert!(res.is_ok());
+
+		// The generated certificate can be matched by its subject
+		let cert_pem = fs::read_to_string(&cert_path).unwrap();
+		let certs = parse_pem_certs(&cert_pem).unwrap();
+		assert_eq!(cert_subject_common_name(&certs[0]).as_deref(), Some(ISSUER_NAME));
+		assert_ne!(cert_fingerprint(&certs[0]), [0u8; 32]);
+
 		// Clean up
 		let _ = fs::remove_file(&cert_path);
 		let _ = fs::remove_file(&key_path);
@@ -0,0 +1,140 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::os::unix::fs::{chown, FileTypeExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::{fs, io};
+
+use crate::util::config::UnixSocketConfig;
+
+const PASSWD_FILE: &str = "/etc/passwd";
+const GROUP_FILE: &str = "/etc/group";
+
+/// Binds a non-blocking listener to the configured Unix socket and applies the configured
+/// permissions and ownership to the socket file.
+///
+/// A socket file left behind by a previous run is replaced, unless another process is still
+/// listening on it.
+pub(crate) fn bind_unix_socket(config: &UnixSocketConfig) -> io::Result<UnixListener> {
+	match fs::symlink_metadata(&config.path) {
+		Ok(metadata) if metadata.file_type().is_socket() => {
+			if UnixStream::connect(&config.path).is_ok() {
+				return Err(io::Error::new(
+					io::ErrorKind::AddrInUse,
+					format!("Another process is listening on {}", config.path.display()),
+				));
+			}
+			fs::remove_file(&config.path)?;
+		},
+		Ok(_) => {
+			return Err(io::Error::new(
+				io::ErrorKind::AlreadyExists,
+				format!("{} exists and is not a socket", config.path.display()),
+			));
+		},
+		Err(e) if e.kind() == io::ErrorKind::NotFound => {},
+		Err(e) => return Err(e),
+	}
+
+	let listener = UnixListener::bind(&config.path)?;
+	fs::set_permissions(&config.path, fs::Permissions::from_mode(config.mode))?;
+
+	let uid = config.owner.as_deref().map(|owner| resolve_id(owner, PASSWD_FILE)).transpose()?;
+	let gid = config.group.as_deref().map(|group| resolve_id(group, GROUP_FILE)).transpose()?;
+	if uid.is_some() || gid.is_some() {
+		chown(&config.path, uid, gid)?;
+	}
+
+	listener.set_nonblocking(true)?;
+	Ok(listener)
+}
+
+/// Resolves a user or group given by name or numeric id, looking names up in the given
+/// `/etc/passwd` or `/etc/group` file.
+fn resolve_id(name: &str, database: &str) -> io::Result<u32> {
+	if let Ok(id) = name.parse::<u32>() {
+		return Ok(id);
+	}
+	let entries = fs::read_to_string(database)?;
+	find_id(&entries, name).ok_or_else(|| {
+		io::Error::new(io::ErrorKind::NotFound, format!("'{name}' was not found in {database}"))
+	})
+}
+
+/// Finds the id of `name` in the entries of a `/etc/passwd` or `/etc/group` file, whose lines
+/// both start with `name:password:id`.
+fn find_id(entries: &str, name: &str) -> Option<u32> {
+	entries.lines().find_map(|line| {
+		let mut fields = line.split(':');
+		if fields.next() != Some(name) {
+			return None;
+		}
+		fields.nth(1)?.parse().ok()
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::util::auth::{Identity, Scope};
+
+	#[test]
+	fn test_find_id() {
+		let passwd = "root:x:0:0:root:/root:/bin/bash\n\
+			# comment\n\
+			ldk:x:1001:1001::/home/ldk:/bin/sh\n";
+		assert_eq!(find_id(passwd, "root"), Some(0));
+		assert_eq!(find_id(passwd, "ldk"), Some(1001));
+		assert_eq!(find_id(passwd, "ld"), None);
+
+		let group = "wheel:x:10:root,ldk\nldk-clients:x:1002:\n";
+		assert_eq!(find_id(group, "ldk-clients"), Some(1002));
+		assert_eq!(find_id(group, "ldk"), None);
+	}
+
+	#[test]
+	fn test_bind_unix_socket() {
+		let dir = std::env::temp_dir()
+			.join(format!("ldk-server-unix-socket-test-{}", std::process::id()));
+		let _ = fs::remove_dir_all(&dir);
+		fs::create_dir(&dir).unwrap();
+		let config = UnixSocketConfig {
+			path: dir.join("grpc.sock"),
+			mode: 0o660,
+			owner: None,
+			group: None,
+			tls: false,
+			identity: Identity { name: "unix_socket".to_string(), scope: Scope::Admin },
+		};
+
+		let listener = bind_unix_socket(&config).unwrap();
+		let metadata = fs::symlink_metadata(&config.path).unwrap();
+		assert!(metadata.file_type().is_socket());
+		assert_eq!(metadata.permissions().mode() & 0o777, 0o660);
+
+		// The socket is still in use.
+		let err = bind_unix_socket(&config).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::AddrInUse);
+
+		// A stale socket is replaced.
+		drop(listener);
+		let listener = bind_unix_socket(&config).unwrap();
+		drop(listener);
+
+		// Other files are never replaced.
+		let file_path = dir.join("not-a-socket");
+		fs::write(&file_path, b"data").unwrap();
+		let err =
+			bind_unix_socket(&UnixSocketConfig { path: file_path.clone(), ..config }).unwrap_err();
+		assert_eq!(err.kind(), io::ErrorKind::AlreadyExists);
+		assert_eq!(fs::read(&file_path).unwrap(), b"data");
+
+		fs::remove_dir_all(dir).unwrap();
+	}
+}
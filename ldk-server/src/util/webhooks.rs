@@ -0,0 +1,779 @@
+// This file is Copyright its original authors, visible in version control
+// history.
+//
+// This file is licensed under the Apache License, Version 2.0 <LICENSE-APACHE
+// or http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your option.
+// You may not use this file except in accordance with one or both of these
+// licenses.
+
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use bytes::Bytes;
+use hex::DisplayHex;
+use http_body_util::Full;
+use hyper::header::{CONTENT_TYPE, HOST};
+use hyper::{Request, StatusCode, Uri};
+use hyper_util::rt::TokioIo;
+use ldk_server_grpc::api::ListFailedWebhookDeliveriesResponse;
+use ldk_server_grpc::events::{EventEnvelope, EventType, WebhookDelivery, WebhookDeliveryStatus};
+use ldk_server_grpc::types::PageToken;
+use log::{debug, error, warn};
+use ring::hmac;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpStream;
+use tokio::sync::{watch, Notify};
+use tokio_rustls::rustls::crypto::ring::default_provider;
+use tokio_rustls::rustls::pki_types::ServerName;
+use tokio_rustls::rustls::{ClientConfig, RootCertStore};
+use tokio_rustls::TlsConnector;
+
+use crate::api::error::LdkServerError;
+use crate::api::error::LdkServerErrorCode::{InternalServerError, InvalidRequestError};
+use crate::io::persist::paginated_kv_store::PaginatedKVStore;
+use crate::io::persist::{
+	read_record, write_record, WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE,
+	WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+	WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+};
+use crate::util::current_time_secs;
+use crate::util::event_filter::event_type;
+
+/// The number of times a delivery is attempted before it fails, unless configured otherwise.
+pub(crate) const DEFAULT_WEBHOOK_MAX_ATTEMPTS: u32 = 10;
+
+/// The delay before the first retry of a delivery, which doubles with every further attempt.
+const INITIAL_RETRY_DELAY_SECS: u64 = 5;
+
+/// The maximum delay between two attempts of a delivery.
+const MAX_RETRY_DELAY_SECS: u64 = 60 * 60;
+
+/// For how long a webhook may take to respond before the attempt is considered failed.
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// For how long a delivery task sleeps at most before checking its queue again.
+const MAX_IDLE_SECS: u64 = 60;
+
+/// The header carrying the ID of the delivery, which stays the same across its attempts.
+pub(crate) const DELIVERY_ID_HEADER: &str = "x-ldk-server-delivery-id";
+
+/// The header carrying the time of the attempt, in seconds since the UNIX epoch.
+pub(crate) const TIMESTAMP_HEADER: &str = "x-ldk-server-timestamp";
+
+/// The header carrying the hex-encoded HMAC-SHA256 of `{timestamp}.{body}`, keyed with the
+/// webhook's secret.
+pub(crate) const SIGNATURE_HEADER: &str = "x-ldk-server-signature";
+
+/// An HTTP endpoint that events are POSTed to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WebhookConfig {
+	/// Identifies the webhook in its deliveries.
+	pub(crate) name: String,
+	pub(crate) url: Uri,
+	/// The key deliveries are signed with.
+	pub(crate) secret: String,
+	/// The types of events delivered to the webhook. All events are delivered if empty.
+	pub(crate) event_types: Vec<EventType>,
+	/// The number of times a delivery is attempted before it fails.
+	pub(crate) max_attempts: u32,
+}
+
+impl WebhookConfig {
+	fn subscribes_to(&self, envelope: &EventEnvelope) -> bool {
+		match &envelope.event {
+			Some(event) => {
+				self.event_types.is_empty() || self.event_types.contains(&event_type(event))
+			},
+			None => false,
+		}
+	}
+}
+
+/// The deliveries queued for a webhook, which are attempted by a task of its own.
+struct WebhookQueue {
+	config: WebhookConfig,
+	/// The `(next_attempt_at, sequence, delivery_id)` of every queued delivery, so that only the
+	/// deliveries that are due are read from the store.
+	due: Mutex<BTreeSet<(u64, u64, String)>>,
+	/// Wakes up the webhook's delivery task when a delivery was queued.
+	wakeup: Notify,
+}
+
+impl WebhookQueue {
+	fn push(&self, delivery: &WebhookDelivery) {
+		let at = delivery.next_attempt_at.unwrap_or(0);
+		let sequence = delivery.event.as_ref().map_or(0, |event| event.sequence);
+		self.due.lock().unwrap().insert((at, sequence, delivery.id.clone()));
+	}
+
+	/// Removes the deliveries that are due at `now` from the queue, returning the sequence numbers
+	/// of their events and their IDs in the order of their events.
+	fn take_due(&self, now: u64) -> Vec<(u64, String)> {
+		let mut due = self.due.lock().unwrap();
+		let not_due = due.split_off(&(now.saturating_add(1), 0, String::new()));
+		let mut taken: Vec<(u64, String)> = std::mem::replace(&mut *due, not_due)
+			.into_iter()
+			.map(|(_, sequence, delivery_id)| (sequence, delivery_id))
+			.collect();
+		taken.sort();
+		taken
+	}
+
+	/// Returns when the next queued delivery is due.
+	fn next_attempt_at(&self) -> Option<u64> {
+		self.due.lock().unwrap().first().map(|(at, _, _)| *at)
+	}
+}
+
+/// Delivers events to the configured webhooks.
+///
+/// Deliveries are queued in the [`PaginatedKVStore`] as events are published, so that they survive
+/// restarts. Every webhook is delivered to by a task of its own, so that a webhook that is slow to
+/// respond doesn't hold up the others. Failed attempts are retried with exponential backoff, until
+/// the webhook's `max_attempts` are used up and the delivery is moved to the failed deliveries,
+/// from where it can be redelivered.
+///
+/// Deliveries aren't guaranteed to arrive in the order of their events, as a failed delivery can be
+/// retried after deliveries of later events, and redelivered deliveries are attempted right away. Receivers that
+/// depend on the order are to sort by the event's `sequence`.
+pub(crate) struct WebhookDispatcher {
+	paginated_kv_store: Arc<dyn PaginatedKVStore>,
+	queues: Vec<WebhookQueue>,
+	tls_connector: TlsConnector,
+}
+
+impl WebhookDispatcher {
+	/// Creates the dispatcher, queueing the deliveries that were pending when the server stopped.
+	pub(crate) fn new(
+		paginated_kv_store: Arc<dyn PaginatedKVStore>, webhooks: Vec<WebhookConfig>,
+	) -> Result<Self, String> {
+		let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+		let tls_config = ClientConfig::builder_with_provider(Arc::new(default_provider()))
+			.with_safe_default_protocol_versions()
+			.map_err(|e| format!("Failed to set TLS protocol versions: {e}"))?
+			.with_root_certificates(roots)
+			.with_no_client_auth();
+		let queues = webhooks
+			.into_iter()
+			.map(|config| WebhookQueue {
+				config,
+				due: Mutex::new(BTreeSet::new()),
+				wakeup: Notify::new(),
+			})
+			.collect();
+		let dispatcher = Self {
+			paginated_kv_store,
+			queues,
+			tls_connector: TlsConnector::from(Arc::new(tls_config)),
+		};
+
+		let deliveries = pending_deliveries(&*dispatcher.paginated_kv_store)
+			.map_err(|e| format!("Failed to read webhook deliveries: {e}"))?;
+		for mut delivery in deliveries {
+			match dispatcher.queue(&delivery.webhook) {
+				Some(queue) => queue.push(&delivery),
+				None => {
+					delivery.last_error = Some("Webhook is no longer configured".to_string());
+					dispatcher.fail_delivery(delivery);
+				},
+			}
+		}
+		Ok(dispatcher)
+	}
+
+	fn queue(&self, webhook: &str) -> Option<&WebhookQueue> {
+		self.queues.iter().find(|queue| queue.config.name == webhook)
+	}
+
+	/// Queues the delivery of `envelope` to every webhook subscribed to its type.
+	///
+	/// As the event was already published, failures to queue a delivery are only logged.
+	pub(crate) fn enqueue(&self, envelope: &EventEnvelope) {
+		let now = current_time_secs();
+		for queue in self.queues.iter().filter(|queue| queue.config.subscribes_to(envelope)) {
+			let delivery = WebhookDelivery {
+				id: format!("{}-{}", envelope.sequence, queue.config.name),
+				webhook: queue.config.name.clone(),
+				event: Some(envelope.clone()),
+				status: WebhookDeliveryStatus::Pending as i32,
+				attempts: 0,
+				last_error: None,
+				last_attempt_at: None,
+				next_attempt_at: Some(now),
+			};
+			match write_delivery(&*self.paginated_kv_store, &delivery) {
+				Ok(()) => {
+					queue.push(&delivery);
+					queue.wakeup.notify_one();
+				},
+				Err(e) => error!("Failed to queue webhook delivery {}: {e}", delivery.id),
+			}
+		}
+	}
+
+	/// Moves a failed delivery back to the queue, to be attempted again right away with a fresh
+	/// number of attempts.
+	pub(crate) fn redeliver(&self, delivery_id: &str) -> Result<WebhookDelivery, LdkServerError> {
+		let Some(mut delivery) = read_record::<WebhookDelivery>(
+			&*self.paginated_kv_store,
+			WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+			WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE,
+			delivery_id,
+		)?
+		else {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!("No failed webhook delivery with ID '{delivery_id}'"),
+			));
+		};
+		let Some(queue) = self.queue(&delivery.webhook) else {
+			return Err(LdkServerError::new(
+				InvalidRequestError,
+				format!("Webhook '{}' is not configured", delivery.webhook),
+			));
+		};
+
+		delivery.status = WebhookDeliveryStatus::Pending as i32;
+		delivery.attempts = 0;
+		delivery.next_attempt_at = Some(current_time_secs());
+		write_delivery(&*self.paginated_kv_store, &delivery)?;
+		remove_delivery(
+			&*self.paginated_kv_store,
+			WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE,
+			&delivery.id,
+		)?;
+		queue.push(&delivery);
+		queue.wakeup.notify_one();
+		Ok(delivery)
+	}
+
+	/// Delivers queued events until the server shuts down, running a task for every webhook.
+	pub(crate) async fn run(self: Arc<Self>, shutdown_rx: watch::Receiver<bool>) {
+		let workers: Vec<_> = (0..self.queues.len())
+			.map(|index| tokio::spawn(Arc::clone(&self).run_worker(index, shutdown_rx.clone())))
+			.collect();
+		for worker in workers {
+			if let Err(e) = worker.await {
+				error!("Webhook delivery task failed: {e}");
+			}
+		}
+	}
+
+	/// Delivers the events queued for the webhook at `index` until the server shuts down.
+	async fn run_worker(self: Arc<Self>, index: usize, mut shutdown_rx: watch::Receiver<bool>) {
+		let queue = &self.queues[index];
+		loop {
+			let next_attempt_at = self.attempt_due_deliveries(queue).await;
+			let idle_secs = next_attempt_at
+				.map_or(MAX_IDLE_SECS, |at| at.saturating_sub(current_time_secs()))
+				.min(MAX_IDLE_SECS);
+			tokio::select! {
+				_ = shutdown_rx.changed() => return,
+				_ = queue.wakeup.notified() => {},
+				_ = tokio::time::sleep(Duration::from_secs(idle_secs)) => {},
+			}
+		}
+	}
+
+	/// Attempts the deliveries queued for a webhook that are due, returning when the next one will
+	/// be.
+	async fn attempt_due_deliveries(&self, queue: &WebhookQueue) -> Option<u64> {
+		let webhook = &queue.config;
+		for (sequence, delivery_id) in queue.take_due(current_time_secs()) {
+			let now = current_time_secs();
+			let mut delivery = match read_record::<WebhookDelivery>(
+				&*self.paginated_kv_store,
+				WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+				WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+				&delivery_id,
+			) {
+				Ok(Some(delivery)) => delivery,
+				Ok(None) => continue,
+				Err(e) => {
+					error!("Failed to read webhook delivery {delivery_id}: {e}");
+					// Tried again later, rather than dropped.
+					let retry_at = now + INITIAL_RETRY_DELAY_SECS;
+					queue.due.lock().unwrap().insert((retry_at, sequence, delivery_id));
+					continue;
+				},
+			};
+
+			let result = self.attempt(webhook, &delivery).await;
+			delivery.attempts += 1;
+			delivery.last_attempt_at = Some(now);
+			match result {
+				Ok(()) => {
+					debug!("Delivered webhook delivery {}", delivery.id);
+					if let Err(e) = remove_delivery(
+						&*self.paginated_kv_store,
+						WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+						&delivery.id,
+					) {
+						error!("Failed to remove webhook delivery {}: {e}", delivery.id);
+					}
+				},
+				Err(e) => {
+					warn!(
+						"Webhook delivery {} failed on attempt {}: {e}",
+						delivery.id, delivery.attempts
+					);
+					delivery.last_error = Some(e);
+					if delivery.attempts >= webhook.max_attempts {
+						self.fail_delivery(delivery);
+						continue;
+					}
+					delivery.next_attempt_at = Some(now + retry_delay_secs(delivery.attempts));
+					if let Err(e) = write_delivery(&*self.paginated_kv_store, &delivery) {
+						error!("Failed to update webhook delivery {}: {e}", delivery.id);
+					}
+					queue.push(&delivery);
+				},
+			}
+		}
+		queue.next_attempt_at()
+	}
+
+	/// Moves a delivery that ran out of attempts to the failed deliveries.
+	fn fail_delivery(&self, mut delivery: WebhookDelivery) {
+		error!(
+			"Webhook delivery {} failed after {} attempts, giving up",
+			delivery.id, delivery.attempts
+		);
+		delivery.status = WebhookDeliveryStatus::Failed as i32;
+		delivery.next_attempt_at = None;
+		// The failed record is written first, so that the delivery isn't lost if removing the
+		// queued one fails.
+		let result = write_delivery(&*self.paginated_kv_store, &delivery).and_then(|()| {
+			remove_delivery(
+				&*self.paginated_kv_store,
+				WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+				&delivery.id,
+			)
+		});
+		if let Err(e) = result {
+			error!("Failed to record failed webhook delivery {}: {e}", delivery.id);
+		}
+	}
+
+	/// POSTs the delivery's event to the webhook, succeeding if it responds with a 2xx status.
+	async fn attempt(
+		&self, webhook: &WebhookConfig, delivery: &WebhookDelivery,
+	) -> Result<(), String> {
+		let body = serde_json::to_vec(&delivery.event)
+			.map_err(|e| format!("Failed to serialize event: {e}"))?;
+		let timestamp = current_time_secs();
+		let signature = sign(&webhook.secret, timestamp, &body);
+		let authority = webhook.url.authority().ok_or("Webhook URL has no host")?;
+		let path = webhook.url.path_and_query().map_or("/", |path| path.as_str());
+		let request = Request::post(path)
+			.header(HOST, authority.as_str())
+			.header(CONTENT_TYPE, "application/json")
+			.header(DELIVERY_ID_HEADER, &delivery.id)
+			.header(TIMESTAMP_HEADER, timestamp.to_string())
+			.header(SIGNATURE_HEADER, signature)
+			.body(Full::new(Bytes::from(body)))
+			.map_err(|e| format!("Failed to build request: {e}"))?;
+
+		let status = tokio::time::timeout(ATTEMPT_TIMEOUT, self.send(&webhook.url, request))
+			.await
+			.map_err(|_| format!("Timed out after {} seconds", ATTEMPT_TIMEOUT.as_secs()))??;
+		if status.is_success() {
+			Ok(())
+		} else {
+			Err(format!("Webhook responded with status {status}"))
+		}
+	}
+
+	async fn send(&self, url: &Uri, request: Request<Full<Bytes>>) -> Result<StatusCode, String> {
+		let host = url.host().ok_or("Webhook URL has no host")?;
+		// IPv6 hosts are enclosed in brackets in URLs.
+		let host = host.trim_start_matches('[').trim_end_matches(']');
+		let is_https = url.scheme_str() == Some("https");
+		let port = url.port_u16().unwrap_or(if is_https { 443 } else { 80 });
+		let stream = TcpStream::connect((host, port))
+			.await
+			.map_err(|e| format!("Failed to connect to {host}:{port}: {e}"))?;
+		if is_https {
+			let server_name = ServerName::try_from(host.to_string())
+				.map_err(|e| format!("Invalid webhook host '{host}': {e}"))?;
+			let stream = self
+				.tls_connector
+				.connect(server_name, stream)
+				.await
+				.map_err(|e| format!("TLS handshake failed: {e}"))?;
+			send_request(stream, request).await
+		} else {
+			send_request(stream, request).await
+		}
+	}
+}
+
+async fn send_request<S>(stream: S, request: Request<Full<Bytes>>) -> Result<StatusCode, String>
+where
+	S: AsyncRead + AsyncWrite + Send + Unpin + 'static,
+{
+	let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+		.await
+		.map_err(|e| format!("HTTP handshake failed: {e}"))?;
+	tokio::spawn(async move {
+		if let Err(e) = connection.await {
+			debug!("Webhook connection closed with error: {e}");
+		}
+	});
+	let response =
+		sender.send_request(request).await.map_err(|e| format!("Failed to send request: {e}"))?;
+	Ok(response.status())
+}
+
+/// Returns the hex-encoded HMAC-SHA256 of `{timestamp}.{body}`, keyed with `secret`.
+fn sign(secret: &str, timestamp: u64, body: &[u8]) -> String {
+	let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+	let mut context = hmac::Context::with_key(&key);
+	context.update(timestamp.to_string().as_bytes());
+	context.update(b".");
+	context.update(body);
+	context.sign().as_ref().to_lower_hex_string()
+}
+
+/// Returns the queued deliveries, in the order of their events.
+fn pending_deliveries(
+	paginated_kv_store: &dyn PaginatedKVStore,
+) -> Result<Vec<WebhookDelivery>, LdkServerError> {
+	let mut keys = Vec::new();
+	let mut page_token = None;
+	loop {
+		let list_response = paginated_kv_store
+			.list(
+				WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+				WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+				page_token,
+			)
+			.map_err(|e| {
+				LdkServerError::new(
+					InternalServerError,
+					format!("Failed to list webhook deliveries: {e}"),
+				)
+			})?;
+		keys.extend(list_response.keys);
+		page_token = list_response.next_page_token;
+		if page_token.is_none() {
+			break;
+		}
+	}
+
+	// Deliveries are listed in descending order of their event's sequence number.
+	let mut deliveries = Vec::with_capacity(keys.len());
+	for key in keys.iter().rev() {
+		if let Some(delivery) = read_record::<WebhookDelivery>(
+			paginated_kv_store,
+			WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+			WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE,
+			key,
+		)? {
+			deliveries.push(delivery);
+		}
+	}
+	Ok(deliveries)
+}
+
+/// Returns the delay before the next attempt of a delivery that failed `attempts` times.
+fn retry_delay_secs(attempts: u32) -> u64 {
+	let exponent = attempts.saturating_sub(1).min(u64::BITS - 1);
+	INITIAL_RETRY_DELAY_SECS.saturating_mul(1 << exponent).min(MAX_RETRY_DELAY_SECS)
+}
+
+/// Writes a delivery to the queue or the failed deliveries, depending on its status.
+///
+/// Deliveries are keyed by their ID and ordered by the sequence number of their event.
+fn write_delivery(
+	paginated_kv_store: &dyn PaginatedKVStore, delivery: &WebhookDelivery,
+) -> Result<(), LdkServerError> {
+	let secondary_namespace = if delivery.status == WebhookDeliveryStatus::Failed as i32 {
+		WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE
+	} else {
+		WEBHOOK_DELIVERIES_PENDING_PERSISTENCE_SECONDARY_NAMESPACE
+	};
+	write_record(
+		paginated_kv_store,
+		WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+		secondary_namespace,
+		&delivery.id,
+		delivery.event.as_ref().map_or(0, |event| event.sequence),
+		delivery,
+	)
+}
+
+fn remove_delivery(
+	paginated_kv_store: &dyn PaginatedKVStore, secondary_namespace: &str, delivery_id: &str,
+) -> Result<(), LdkServerError> {
+	paginated_kv_store
+		.remove(WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE, secondary_namespace, delivery_id)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to remove webhook delivery '{delivery_id}': {e}"),
+			)
+		})
+}
+
+pub(crate) fn list_failed_webhook_deliveries(
+	paginated_kv_store: &dyn PaginatedKVStore, page_token: Option<PageToken>,
+) -> Result<ListFailedWebhookDeliveriesResponse, LdkServerError> {
+	let list_response = paginated_kv_store
+		.list(
+			WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+			WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE,
+			page_token.map(|p| (p.token, p.index)),
+		)
+		.map_err(|e| {
+			LdkServerError::new(
+				InternalServerError,
+				format!("Failed to list failed webhook deliveries: {e}"),
+			)
+		})?;
+
+	let mut deliveries = Vec::with_capacity(list_response.keys.len());
+	for delivery_id in list_response.keys {
+		if let Some(delivery) = read_record::<WebhookDelivery>(
+			paginated_kv_store,
+			WEBHOOK_DELIVERIES_PERSISTENCE_PRIMARY_NAMESPACE,
+			WEBHOOK_DELIVERIES_FAILED_PERSISTENCE_SECONDARY_NAMESPACE,
+			&delivery_id,
+		)? {
+			deliveries.push(delivery);
+		}
+	}
+	let next_page_token =
+		list_response.next_page_token.map(|(token, index)| PageToken { token, index });
+	Ok(ListFailedWebhookDeliveriesResponse { deliveries, next_page_token })
+}
+
+#[cfg(test)]
+mod tests {
+	use std::convert::Infallible;
+	use std::sync::Mutex;
+
+	use http_body_util::BodyExt;
+	use hyper::body::Incoming;
+	use hyper::service::service_fn;
+	use hyper::{HeaderMap, Response};
+	use ldk_server_grpc::events::event_envelope::Event;
+	use ldk_server_grpc::events::{ChannelStateChanged, PaymentReceived};
+	use tokio::net::TcpListener;
+
+	use super::*;
+	use crate::io::persist::sqlite_store::tests::test_store;
+
+	fn webhook(name: &str, url: &str, event_types: Vec<EventType>) -> WebhookConfig {
+		WebhookConfig {
+			name: name.to_string(),
+			url: url.parse().unwrap(),
+			secret: "secret".to_string(),
+			event_types,
+			max_attempts: 1,
+		}
+	}
+
+	fn envelope(sequence: u64, event: Event) -> EventEnvelope {
+		EventEnvelope { sequence, timestamp: 1_700_000_000, event: Some(event) }
+	}
+
+	#[test]
+	fn test_retry_delay() {
+		assert_eq!(retry_delay_secs(1), 5);
+		assert_eq!(retry_delay_secs(2), 10);
+		assert_eq!(retry_delay_secs(5), 80);
+		assert_eq!(retry_delay_secs(20), MAX_RETRY_DELAY_SECS);
+		assert_eq!(retry_delay_secs(u32::MAX), MAX_RETRY_DELAY_SECS);
+	}
+
+	/// Spawns a stand-in webhook that records the requests it receives, fails the first one if
+	/// `fail_first` is set and accepts all others.
+	async fn serve_webhook(fail_first: bool) -> (String, Arc<Mutex<Vec<(HeaderMap, Bytes)>>>) {
+		let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let port = listener.local_addr().unwrap().port();
+		let requests: Arc<Mutex<Vec<(HeaderMap, Bytes)>>> = Arc::new(Mutex::new(Vec::new()));
+		let server_requests = Arc::clone(&requests);
+		tokio::spawn(async move {
+			loop {
+				let (stream, _) = listener.accept().await.unwrap();
+				let requests = Arc::clone(&server_requests);
+				let service = service_fn(move |request: Request<Incoming>| {
+					let requests = Arc::clone(&requests);
+					async move {
+						let headers = request.headers().clone();
+						let body = request.into_body().collect().await.unwrap().to_bytes();
+						let mut requests = requests.lock().unwrap();
+						requests.push((headers, body));
+						let status = if fail_first && requests.len() == 1 { 500 } else { 200 };
+						Ok::<_, Infallible>(
+							Response::builder()
+								.status(status)
+								.body(Full::new(Bytes::new()))
+								.unwrap(),
+						)
+					}
+				});
+				tokio::spawn(
+					hyper::server::conn::http1::Builder::new()
+						.serve_connection(TokioIo::new(stream), service),
+				);
+			}
+		});
+		(format!("http://127.0.0.1:{port}/hook"), requests)
+	}
+
+	#[test]
+	fn test_enqueue_filters_by_event_type() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_enqueue_filters_by_event_type"));
+		let dispatcher = WebhookDispatcher::new(
+			Arc::clone(&store),
+			vec![
+				webhook("all", "http://localhost/all", vec![]),
+				webhook(
+					"channels",
+					"http://localhost/channels",
+					vec![EventType::ChannelStateChanged],
+				),
+			],
+		)
+		.unwrap();
+
+		dispatcher.enqueue(&envelope(1, Event::PaymentReceived(PaymentReceived::default())));
+		dispatcher
+			.enqueue(&envelope(2, Event::ChannelStateChanged(ChannelStateChanged::default())));
+
+		// Deliveries are ordered by event, but not by webhook within an event.
+		let mut ids: Vec<String> =
+			pending_deliveries(&*store).unwrap().into_iter().map(|d| d.id).collect();
+		assert_eq!(ids[0], "1-all");
+		ids.sort();
+		assert_eq!(ids, vec!["1-all", "2-all", "2-channels"]);
+
+		// Each webhook's queue only holds its own deliveries.
+		let due = |webhook: &str| dispatcher.queue(webhook).unwrap().take_due(u64::MAX);
+		assert_eq!(due("all"), vec![(1, "1-all".to_string()), (2, "2-all".to_string())]);
+		assert_eq!(due("channels"), vec![(2, "2-channels".to_string())]);
+		assert!(due("all").is_empty());
+	}
+
+	#[test]
+	fn test_pending_deliveries_are_queued_after_restart() {
+		let store: Arc<dyn PaginatedKVStore> =
+			Arc::new(test_store("test_pending_deliveries_are_queued_after_restart"));
+		let webhooks = vec![
+			webhook("kept", "http://localhost/kept", vec![]),
+			webhook("removed", "http://localhost/removed", vec![]),
+		];
+		let dispatcher = WebhookDispatcher::new(Arc::clone(&store), webhooks).unwrap();
+		dispatcher.enqueue(&envelope(1, Event::PaymentReceived(PaymentReceived::default())));
+		drop(dispatcher);
+
+		let webhooks = vec![webhook("kept", "http://localhost/kept", vec![])];
+		let dispatcher = WebhookDispatcher::new(Arc::clone(&store), webhooks).unwrap();
+		let queue = dispatcher.queue("kept").unwrap();
+		assert!(queue.next_attempt_at().is_some());
+		assert_eq!(queue.take_due(u64::MAX), vec![(1, "1-kept".to_string())]);
+
+		// Deliveries to webhooks that are no longer configured fail.
+		let failed = list_failed_webhook_deliveries(&*store, None).unwrap().deliveries;
+		assert_eq!(failed.len(), 1);
+		assert_eq!(failed[0].id, "1-removed");
+		assert_eq!(failed[0].last_error.as_deref(), Some("Webhook is no longer configured"));
+	}
+
+	#[test]
+	fn test_deliver_and_redeliver() {
+		let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+		runtime.block_on(async {
+			let (url, requests) = serve_webhook(true).await;
+			let store: Arc<dyn PaginatedKVStore> =
+				Arc::new(test_store("test_deliver_and_redeliver"));
+			let dispatcher =
+				WebhookDispatcher::new(Arc::clone(&store), vec![webhook("hook", &url, vec![])])
+					.unwrap();
+			let queue = dispatcher.queue("hook").unwrap();
+			let event = envelope(7, Event::PaymentReceived(PaymentReceived::default()));
+			dispatcher.enqueue(&event);
+
+			// The only attempt fails, so the delivery is moved to the failed deliveries.
+			assert_eq!(dispatcher.attempt_due_deliveries(queue).await, None);
+			assert!(pending_deliveries(&*store).unwrap().is_empty());
+			let failed = list_failed_webhook_deliveries(&*store, None).unwrap().deliveries;
+			assert_eq!(failed.len(), 1);
+			assert_eq!(failed[0].id, "7-hook");
+			assert_eq!(failed[0].status, WebhookDeliveryStatus::Failed as i32);
+			assert_eq!(failed[0].attempts, 1);
+			assert!(failed[0].last_error.as_ref().unwrap().contains("500"));
+
+			let err = dispatcher.redeliver("8-hook").unwrap_err();
+			assert_eq!(err.error_code, InvalidRequestError);
+			let redelivered = dispatcher.redeliver("7-hook").unwrap();
+			assert_eq!(redelivered.status, WebhookDeliveryStatus::Pending as i32);
+			assert_eq!(redelivered.attempts, 0);
+			assert!(list_failed_webhook_deliveries(&*store, None).unwrap().deliveries.is_empty());
+
+			assert_eq!(dispatcher.attempt_due_deliveries(queue).await, None);
+			assert!(pending_deliveries(&*store).unwrap().is_empty());
+			assert!(list_failed_webhook_deliveries(&*store, None).unwrap().deliveries.is_empty());
+
+			// Both attempts carry the same delivery ID and a valid signature of the event.
+			let requests = requests.lock().unwrap();
+			assert_eq!(requests.len(), 2);
+			for (headers, body) in requests.iter() {
+				assert_eq!(headers[DELIVERY_ID_HEADER], "7-hook");
+				assert_eq!(headers[CONTENT_TYPE], "application/json");
+				let timestamp: u64 = headers[TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+				assert_eq!(headers[SIGNATURE_HEADER], sign("secret", timestamp, body));
+				assert_eq!(&body[..], &serde_json::to_vec(&event).unwrap()[..]);
+			}
+		});
+	}
+
+	#[test]
+	fn test_unresponsive_webhook_does_not_block_others() {
+		let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap();
+		runtime.block_on(async {
+			// A webhook that accepts connections but never responds.
+			let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+			let hung_url = format!("http://{}/hook", listener.local_addr().unwrap());
+			tokio::spawn(async move {
+				let mut streams = Vec::new();
+				loop {
+					let (stream, _) = listener.accept().await.unwrap();
+					streams.push(stream);
+				}
+			});
+			let (url, requests) = serve_webhook(false).await;
+
+			let store: Arc<dyn PaginatedKVStore> =
+				Arc::new(test_store("test_unresponsive_webhook_does_not_block_others"));
+			let dispatcher = Arc::new(
+				WebhookDispatcher::new(
+					Arc::clone(&store),
+					vec![webhook("hung", &hung_url, vec![]), webhook("hook", &url, vec![])],
+				)
+				.unwrap(),
+			);
+			let (shutdown_tx, shutdown_rx) = watch::channel(false);
+			let dispatcher_task = tokio::spawn(Arc::clone(&dispatcher).run(shutdown_rx));
+			dispatcher.enqueue(&envelope(1, Event::PaymentReceived(PaymentReceived::default())));
+
+			// The delivery to the responsive webhook completes well before the other one times out.
+			tokio::time::timeout(ATTEMPT_TIMEOUT / 2, async {
+				while requests.lock().unwrap().is_empty() {
+					tokio::time::sleep(Duration::from_millis(10)).await;
+				}
+			})
+			.await
+			.unwrap();
+			assert_eq!(requests.lock().unwrap()[0].0[DELIVERY_ID_HEADER], "1-hook");
+
+			shutdown_tx.send(true).unwrap();
+			dispatcher_task.await.unwrap();
+		});
+	}
+}